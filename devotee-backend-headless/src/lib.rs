@@ -0,0 +1,519 @@
+#![deny(missing_docs)]
+
+//! Headless offscreen backend for the devotee project.
+//!
+//! Steps the application for a fixed number of frames without any window system,
+//! rendering into an in-memory framebuffer.
+
+use std::convert::Infallible;
+use std::time::Duration;
+
+use devotee_backend::{
+    Application, Context, Converter, EventContext, Middleware, RenderSurface, RenderTarget,
+    ResizeRenderSurface,
+};
+
+/// Maximal amount of fixed timestep updates performed to catch up before a single frame.
+const MAX_FIXED_STEPS: u32 = 8;
+
+/// Backend stepping the application offscreen.
+pub struct HeadlessBackend<Event> {
+    frames: usize,
+    dimensions: Option<(usize, usize)>,
+    events: Vec<(usize, Event)>,
+    frame_delay: Option<Duration>,
+}
+
+impl<Event> HeadlessBackend<Event> {
+    /// Create new backend instance stepping a single frame.
+    pub fn new() -> Self {
+        let frames = 1;
+        let dimensions = None;
+        let events = Vec::new();
+        let frame_delay = None;
+        Self {
+            frames,
+            dimensions,
+            events,
+            frame_delay,
+        }
+    }
+
+    /// Set number of frames to step.
+    pub fn with_frames(self, frames: usize) -> Self {
+        Self { frames, ..self }
+    }
+
+    /// Set framebuffer dimensions.
+    /// By default the framebuffer matches the render surface dimensions.
+    pub fn with_dimensions(self, width: usize, height: usize) -> Self {
+        Self {
+            dimensions: Some((width, height)),
+            ..self
+        }
+    }
+
+    /// Set simulated time between the stepped frames used by the fixed timestep updates.
+    /// By default frames are apart by the update delay.
+    pub fn with_frame_delay(self, frame_delay: Duration) -> Self {
+        Self {
+            frame_delay: Some(frame_delay),
+            ..self
+        }
+    }
+
+    /// Schedule the `event` to be handled before the update of the `frame`.
+    pub fn with_event(self, frame: usize, event: Event) -> Self {
+        let mut events = self.events;
+        events.push((frame, event));
+        Self { events, ..self }
+    }
+}
+
+impl<Event> Default for HeadlessBackend<Event> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Event> HeadlessBackend<Event> {
+    /// Run this backend for the configured number of frames or until shutdown.
+    /// Return the framebuffer with the last presented frame.
+    pub fn run<App, Mid, Rend, Data, Conv>(
+        self,
+        app: App,
+        middleware: Mid,
+        update_delay: Duration,
+    ) -> HeadlessSurface
+    where
+        App: for<'a> Application<
+            'a,
+            <Mid as Middleware<'a, HeadlessControl>>::Init,
+            <Mid as Middleware<'a, HeadlessControl>>::Context,
+            Rend,
+            Conv,
+        >,
+        Mid: for<'a> Middleware<
+            'a,
+            HeadlessControl,
+            Event = Event,
+            EventContext = &'a HeadlessSurface,
+            Surface = &'a mut HeadlessSurface,
+            RenderTarget = HeadlessRenderTarget<'a, Rend>,
+        >,
+        Rend: RenderSurface<Data = Data>,
+        Conv: Converter<Data = Data>,
+    {
+        let mut app = app;
+        let mut middleware = middleware;
+
+        let mut control = HeadlessControl {
+            should_quit: false,
+            frame: 0,
+            fixed_timestep: false,
+        };
+        let init = middleware.init(&mut control);
+        app.init(init);
+        let fixed_timestep = control.fixed_timestep;
+        let frame_delay = self.frame_delay.unwrap_or(update_delay);
+        let mut now = Duration::ZERO;
+        let mut next_update = update_delay;
+
+        let mut surface = HeadlessSurface::new(self.dimensions);
+        let mut events = self.events;
+        events.sort_by_key(|(frame, _)| *frame);
+        let mut events = events.into_iter().peekable();
+
+        for frame in 0..self.frames {
+            control.frame = frame;
+            while let Some((_, event)) = events.next_if(|(at, _)| *at <= frame) {
+                middleware.handle_event(event, &surface, &mut control);
+            }
+            if control.should_quit {
+                break;
+            }
+
+            now += frame_delay;
+            let mut steps = 0;
+            while !fixed_timestep || now >= next_update {
+                let context = middleware.update(&mut control, update_delay);
+                app.update(context);
+                next_update += update_delay;
+                steps += 1;
+                if !fixed_timestep || control.should_quit {
+                    break;
+                }
+                if steps >= MAX_FIXED_STEPS {
+                    // Drop the backlog instead of falling further behind.
+                    next_update = now + update_delay;
+                    break;
+                }
+            }
+
+            let mut render_target = middleware.render(&mut surface);
+            let render_surface =
+                <HeadlessRenderTarget<'_, Rend> as RenderTarget<Conv>>::render_surface_mut(
+                    &mut render_target,
+                );
+            if fixed_timestep {
+                app.interpolate(interpolation(next_update.saturating_sub(now), update_delay));
+            }
+            app.render(render_surface);
+            let _ = devotee_backend::RenderTarget::present(render_target, app.converter());
+
+            if control.should_quit {
+                break;
+            }
+        }
+
+        surface
+    }
+}
+
+/// Default Middleware for the headless backend.
+pub struct HeadlessMiddleware<RenderSurface, Input> {
+    background_color: u32,
+    render_surface: RenderSurface,
+    input: Input,
+}
+
+impl<RenderSurface, Input> HeadlessMiddleware<RenderSurface, Input>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+{
+    /// Create new middleware instance with desired render surface and input handler.
+    pub fn new(render_surface: RenderSurface, input: Input) -> Self {
+        let background_color = 0;
+        Self {
+            background_color,
+            render_surface,
+            input,
+        }
+    }
+
+    /// Set background color for the unoccupied space.
+    pub fn with_background_color(self, background_color: u32) -> Self {
+        Self {
+            background_color,
+            ..self
+        }
+    }
+}
+
+impl<'a, RenderSurface, Input> Middleware<'a, HeadlessControl>
+    for HeadlessMiddleware<RenderSurface, Input>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+    RenderSurface: 'a,
+    Input: 'a + devotee_backend::Input<'a, HeadlessEventContext<'a>>,
+{
+    type Event = Input::Event;
+    type EventContext = &'a HeadlessSurface;
+    type Surface = &'a mut HeadlessSurface;
+    type Init = HeadlessInit<'a>;
+    type Context = HeadlessContext<'a, Input>;
+    type RenderTarget = HeadlessRenderTarget<'a, RenderSurface>;
+
+    fn init(&'a mut self, control: &'a mut HeadlessControl) -> Self::Init {
+        HeadlessInit { control }
+    }
+
+    fn update(&'a mut self, control: &'a mut HeadlessControl, delta: Duration) -> Self::Context {
+        let input = &mut self.input;
+        let render_surface = &mut self.render_surface;
+        HeadlessContext {
+            control,
+            delta,
+            input,
+            render_surface,
+        }
+    }
+
+    fn handle_event(
+        &mut self,
+        event: Self::Event,
+        event_context: Self::EventContext,
+        _control: &mut HeadlessControl,
+    ) -> Option<Self::Event> {
+        let context = HeadlessEventContext {
+            surface: event_context,
+            resolution: (self.render_surface.width(), self.render_surface.height()),
+        };
+        self.input.handle_event(event, &context)
+    }
+
+    fn render(&'a mut self, surface: Self::Surface) -> Self::RenderTarget {
+        let dimensions =
+            surface.target((self.render_surface.width(), self.render_surface.height()));
+        if surface.dimensions != dimensions {
+            surface.resize(dimensions);
+        }
+        let background_color = self.background_color;
+        let render_surface = &mut self.render_surface;
+        HeadlessRenderTarget {
+            background_color,
+            render_surface,
+            surface,
+        }
+    }
+}
+
+/// Default Init for the headless backend.
+pub struct HeadlessInit<'a> {
+    control: &'a mut HeadlessControl,
+}
+
+impl<'a> HeadlessInit<'a> {
+    /// Get reference to `HeadlessControl`.
+    pub fn control(&self) -> &HeadlessControl {
+        self.control
+    }
+
+    /// Get mutable reference to `HeadlessControl`.
+    pub fn control_mut(&mut self) -> &mut HeadlessControl {
+        self.control
+    }
+
+    /// Enable or disable fixed timestep updates.
+    /// When enabled, the backend performs as many updates with the fixed delta as needed to catch up
+    /// with the simulated clock of the stepped frames and passes the interpolation factor to the application before rendering.
+    pub fn set_fixed_timestep(&mut self, fixed_timestep: bool) {
+        self.control.fixed_timestep = fixed_timestep;
+    }
+}
+
+/// Default Context for the headless backend.
+pub struct HeadlessContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, HeadlessEventContext<'a>>,
+{
+    control: &'a mut HeadlessControl,
+    input: &'a mut Input,
+    delta: Duration,
+    render_surface: &'a mut dyn ResizeRenderSurface,
+}
+
+impl<'a, Input> HeadlessContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, HeadlessEventContext<'a>>,
+{
+    /// Get reference to `HeadlessControl`.
+    pub fn control(&self) -> &HeadlessControl {
+        self.control
+    }
+
+    /// Get mutable reference to `HeadlessControl`.
+    pub fn control_mut(&mut self) -> &mut HeadlessControl {
+        self.control
+    }
+
+    /// Resize the render surface, keeping its contents in the overlapping area if `preserve` is set.
+    /// Return `false` if the render surface does not support resizing.
+    pub fn resize_render_target(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        self.render_surface.resize(width, height, preserve)
+    }
+
+    /// Get mutable reference to the input system, e.g. to simulate input in tests.
+    pub fn input_mut(&mut self) -> &mut Input {
+        self.input
+    }
+}
+
+impl<'a, Input> Context<'a, Input> for HeadlessContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, HeadlessEventContext<'a>>,
+{
+    fn input(&self) -> &Input {
+        self.input
+    }
+
+    fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    fn shutdown(&mut self) {
+        self.control.shutdown();
+    }
+}
+
+impl<'a, Input> Drop for HeadlessContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, HeadlessEventContext<'a>>,
+{
+    fn drop(&mut self) {
+        self.input.tick();
+    }
+}
+
+/// In-memory framebuffer of `0xff_rr_gg_bb` pixels.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeadlessSurface {
+    dimensions: (usize, usize),
+    pixels: Vec<u32>,
+    fixed: bool,
+}
+
+impl HeadlessSurface {
+    /// Create framebuffer of fixed `dimensions` or following the render surface dimensions.
+    fn new(dimensions: Option<(usize, usize)>) -> Self {
+        let mut surface = Self {
+            fixed: dimensions.is_some(),
+            ..Default::default()
+        };
+        surface.resize(dimensions.unwrap_or_default());
+        surface
+    }
+
+    /// Get framebuffer dimensions to place the render surface of `resolution` on.
+    fn target(&self, resolution: (usize, usize)) -> (usize, usize) {
+        if self.fixed {
+            self.dimensions
+        } else {
+            resolution
+        }
+    }
+
+    fn resize(&mut self, dimensions: (usize, usize)) {
+        self.dimensions = dimensions;
+        self.pixels = vec![0; dimensions.0 * dimensions.1];
+    }
+
+    /// Get dimensions of the framebuffer in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.dimensions
+    }
+
+    /// Get framebuffer pixels row by row.
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// Get specific pixel value.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<u32> {
+        (x < self.dimensions.0 && y < self.dimensions.1)
+            .then(|| self.pixels[x + y * self.dimensions.0])
+    }
+
+    /// Consume the framebuffer and get its pixels row by row.
+    pub fn into_pixels(self) -> Vec<u32> {
+        self.pixels
+    }
+}
+
+/// Default Render Target for the headless backend.
+pub struct HeadlessRenderTarget<'a, RenderSurface> {
+    background_color: u32,
+    render_surface: &'a mut RenderSurface,
+    surface: &'a mut HeadlessSurface,
+}
+
+impl<'a, RenderSurface, Converter> RenderTarget<Converter>
+    for HeadlessRenderTarget<'a, RenderSurface>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+    Converter: devotee_backend::Converter<Data = RenderSurface::Data>,
+{
+    type RenderSurface = RenderSurface;
+    type PresentError = Infallible;
+
+    fn render_surface(&self) -> &Self::RenderSurface {
+        self.render_surface
+    }
+
+    fn render_surface_mut(&mut self) -> &mut Self::RenderSurface {
+        self.render_surface
+    }
+
+    fn present(self, converter: Converter) -> Result<(), Self::PresentError> {
+        let resolution = (self.render_surface.width(), self.render_surface.height());
+        let dimensions = self.surface.dimensions;
+
+        self.surface.pixels.fill(self.background_color);
+        if let Some(((start_x, start_y), scale)) = placement(dimensions, resolution) {
+            for y in 0..resolution.1 {
+                for x in 0..resolution.0 {
+                    let pixel_color = self.render_surface.data(x, y);
+                    let pixel_value = converter.convert(x, y, pixel_color);
+                    for iy in 0..scale {
+                        let index =
+                            (start_x + x * scale) + (iy + start_y + y * scale) * dimensions.0;
+                        self.surface.pixels[index..index + scale].fill(pixel_value);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Default Control instance for the headless backend.
+pub struct HeadlessControl {
+    should_quit: bool,
+    frame: usize,
+    fixed_timestep: bool,
+}
+
+impl HeadlessControl {
+    /// Tell backend to stop stepping frames.
+    pub fn shutdown(&mut self) -> &mut Self {
+        self.should_quit = true;
+        self
+    }
+
+    /// Get index of the current frame.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+}
+
+/// Default Event Context for the headless backend.
+pub struct HeadlessEventContext<'a> {
+    surface: &'a HeadlessSurface,
+    resolution: (usize, usize),
+}
+
+impl<'a> EventContext for HeadlessEventContext<'a> {
+    fn position_into_render_surface_space(
+        &self,
+        position: (f32, f32),
+    ) -> Result<(i32, i32), (i32, i32)> {
+        let dimensions = self.surface.target(self.resolution);
+        if let Some(((start_x, start_y), scale)) = placement(dimensions, self.resolution) {
+            let position = (
+                (position.0 as i32 - start_x as i32) / scale as i32,
+                (position.1 as i32 - start_y as i32) / scale as i32,
+            );
+
+            if position.0 < 0
+                || position.0 >= self.resolution.0 as i32
+                || position.1 < 0
+                || position.1 >= self.resolution.1 as i32
+            {
+                Err(position)
+            } else {
+                Ok(position)
+            }
+        } else {
+            Err((0, 0))
+        }
+    }
+}
+
+fn interpolation(remaining: Duration, update_delay: Duration) -> f32 {
+    (1.0 - remaining.as_secs_f32() / update_delay.as_secs_f32()).clamp(0.0, 1.0)
+}
+
+fn placement(
+    surface: (usize, usize),
+    resolution: (usize, usize),
+) -> Option<((usize, usize), usize)> {
+    if resolution.0 == 0 || resolution.1 == 0 {
+        return None;
+    }
+    let scale = (surface.0 / resolution.0).min(surface.1 / resolution.1);
+    (scale >= 1).then(|| {
+        let start_x = (surface.0 - resolution.0 * scale) / 2;
+        let start_y = (surface.1 - resolution.1 * scale) / 2;
+        ((start_x, start_y), scale)
+    })
+}
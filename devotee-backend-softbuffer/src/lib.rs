@@ -2,41 +2,166 @@
 
 //! [Softbuffer](https://crates.io/crates/softbuffer)-based backend for the devotee project.
 
-use std::num::TryFromIntError;
+use std::num::{NonZeroU32, TryFromIntError};
 use std::rc::Rc;
-use std::time::{Duration, Instant};
-
+use std::time::Duration;
+
+use devotee_backend::adjustment::ColorAdjustment;
+use devotee_backend::capture::Frame;
+#[cfg(feature = "screenshot")]
+use devotee_backend::capture::ScreenshotError;
+use devotee_backend::config::BackendConfig;
+use devotee_backend::convert::{convert_region, MaybeSync};
+use devotee_backend::cursor::CursorImage;
+use devotee_backend::damage::Damage;
+use devotee_backend::diagnostics::{LatencyRecorder, LatencyReport, PresentStats};
+use devotee_backend::magnifier::{Magnifier, MagnifierControls};
+use devotee_backend::motion::CursorMotion;
+use devotee_backend::platform::{Instant, MaybeStatic};
+use devotee_backend::pointer::PointerTracker;
+use devotee_backend::savestate::{Savestate, SavestateRequest, Savestates};
+use devotee_backend::window::{WindowConfig, WindowToken};
+use devotee_backend::winit_event::{event_category, is_press};
 use devotee_backend::{
-    Application, Context, Converter, EventContext, Middleware, RenderSurface, RenderTarget,
+    Application, BackendRequest, Context, Converter, EventCategories, EventContext, Middleware,
+    PresentMode, RenderSurface, RenderTarget, ResizeRenderSurface, SafeArea, ScaleMode, Viewport,
+};
+use softbuffer::{Buffer, Rect, SoftBufferError, Surface};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::error::{EventLoopError, ExternalError, OsError};
+use winit::event::{DeviceEvent, ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event_loop::{
+    ControlFlow, DeviceEvents, EventLoop, EventLoopBuilder, EventLoopProxy, EventLoopWindowTarget,
+};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+use winit::raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use winit::window::{
+    BadIcon, CursorGrabMode, CursorIcon, Fullscreen, Icon, ResizeDirection, Window, WindowBuilder,
+    WindowLevel,
 };
-use softbuffer::{Buffer, SoftBufferError, Surface};
-use winit::dpi::PhysicalSize;
-use winit::error::{EventLoopError, OsError};
-use winit::event::{Event, StartCause, WindowEvent};
-use winit::event_loop::{ControlFlow, EventLoop};
-use winit::window::{Window, WindowBuilder};
 
 pub use winit;
 
 type Buf<'a> = Buffer<'a, Rc<Window>, Rc<Window>>;
 
+/// Maximal amount of fixed timestep updates performed to catch up before a single frame.
+const MAX_FIXED_STEPS: u32 = 8;
+
 /// Backend based on the [Softbuffer](https://crates.io/crates/softbuffer) project.
 pub struct SoftBackend {
     window: Rc<Window>,
-    event_loop: EventLoop<()>,
+    event_loop: EventLoop<BackendRequest>,
+    present_mode: PresentMode,
+    device_events: Option<DeviceEvents>,
+    render_on_demand: bool,
+    coalesce_cursor: bool,
+    max_fps: Option<u32>,
+    window_sized: bool,
 }
 
 impl SoftBackend {
     /// Create new backend instance with desired window title.
     pub fn try_new(title: &str) -> Result<Self, Error> {
-        let event_loop = EventLoop::new()?;
-        let window = Rc::new(WindowBuilder::new().with_title(title).build(&event_loop)?);
-        Ok(Self { window, event_loop })
+        Self::try_with_window_config(&WindowConfig::new(title))
+    }
+
+    /// Create new backend instance with the window created according to the `config`.
+    /// The window is shown once the middleware and the application are initialized.
+    pub fn try_with_window_config(config: &WindowConfig) -> Result<Self, Error> {
+        let event_loop = EventLoopBuilder::with_user_event().build()?;
+        let builder = window_builder(config)?.with_visible(false);
+        let window = Rc::new(builder.build(&event_loop)?);
+        let window_sized = config.size().is_some();
+        let present_mode = PresentMode::default();
+        let device_events = None;
+        let render_on_demand = false;
+        let coalesce_cursor = true;
+        Ok(Self {
+            window,
+            event_loop,
+            present_mode,
+            device_events,
+            render_on_demand,
+            coalesce_cursor,
+            max_fps: None,
+            window_sized,
+        })
+    }
+
+    /// Set frame presentation mode.
+    ///
+    /// Softbuffer has no vertical synchronization, so `PresentMode::Sync` follows the frame callbacks
+    /// of the compositor on Wayland and is approximated with a timer at the monitor refresh rate elsewhere.
+    /// The timer is also used on Wayland when the frame rate is limited.
+    pub fn with_present_mode(self, present_mode: PresentMode) -> Self {
+        Self {
+            present_mode,
+            ..self
+        }
+    }
+
+    /// Apply backend-level settings of the `config`.
+    /// Update rate is applied by passing `config.update_delay()` to `run`.
+    pub fn with_config(self, config: &BackendConfig) -> Self {
+        self.with_present_mode(config.present_mode)
+            .with_max_fps(config.max_fps)
+    }
+
+    /// Set initial render frame rate cap independent of the update rate.
+    /// `None` or zero removes the cap leaving redraws to the present mode.
+    pub fn with_max_fps(self, max_fps: Option<u32>) -> Self {
+        Self {
+            max_fps: max_fps.filter(|&fps| fps > 0),
+            ..self
+        }
+    }
+
+    /// Set device events listening policy.
+    /// By default device events are listened to only if the middleware is interested in device motion.
+    pub fn with_device_events(self, device_events: DeviceEvents) -> Self {
+        Self {
+            device_events: Some(device_events),
+            ..self
+        }
+    }
+
+    /// Enable or disable render-on-demand mode.
+    /// In this mode redraws happen only when requested with `SoftControl::request_redraw`,
+    /// and the backend stops waking up for updates until the next event
+    /// if an update tick neither received events nor requested a redraw.
+    pub fn with_render_on_demand(self, render_on_demand: bool) -> Self {
+        Self {
+            render_on_demand,
+            ..self
+        }
+    }
+
+    /// Enable or disable coalescing of cursor movement events.
+    /// When enabled, only the latest cursor movement is passed to the middleware
+    /// before any other event or the next update.
+    /// Every movement is still registered in the control `CursorMotion`.
+    /// Enabled by default.
+    pub fn with_cursor_coalescing(self, coalesce_cursor: bool) -> Self {
+        Self {
+            coalesce_cursor,
+            ..self
+        }
+    }
+
+    /// Get proxy sending requests to the event loop from other threads,
+    /// e.g. to activate the window on launching another application instance.
+    pub fn event_proxy(&self) -> EventLoopProxy<BackendRequest> {
+        self.event_loop.create_proxy()
     }
 }
 
 impl SoftBackend {
     /// Run this backend to completion.
+    ///
+    /// On `wasm32` the event loop is spawned in the browser and this call returns immediately.
+    ///
+    /// Converters targeting texel formats other than `0xff_rr_gg_bb`, e.g. `Rgb565` or `Gray8`,
+    /// are to be wrapped with `devotee_backend::texel::Packed`.
     pub fn run<App, Mid, Rend, Data, Conv>(
         self,
         app: App,
@@ -59,8 +184,11 @@ impl SoftBackend {
             Surface = Buf<'a>,
             RenderTarget = SoftRenderTarget<'a, Rend>,
         >,
-        Rend: RenderSurface<Data = Data>,
-        Conv: Converter<Data = Data>,
+        App: MaybeStatic,
+        Mid: MaybeStatic,
+        Rend: RenderSurface<Data = Data> + MaybeStatic + MaybeSync,
+        Data: Clone + MaybeStatic,
+        Conv: Converter<Data = Data> + MaybeStatic + MaybeSync,
     {
         let mut app = app;
         let mut middleware = middleware;
@@ -72,75 +200,518 @@ impl SoftBackend {
 
         let mut control = SoftControl {
             should_quit: false,
+            paused: None,
+            redraw_requested: false,
+            pointer_captured: false,
+            motion: CursorMotion::new(),
+            present_stats: PresentStats::new(),
+            tick: 0,
+            savestate: None,
+            savestates: Savestates::new(),
+            fixed_timestep: false,
+            max_fps: self.max_fps,
+            next_window: 0,
+            windows: Vec::new(),
+            window_requests: Vec::new(),
+            window_sized: self.window_sized,
             window: window.clone(),
         };
         let init = middleware.init(&mut control);
         app.init(init);
+        window.set_visible(true);
+        let window_sized = control.window_sized;
+        let fixed_timestep = control.fixed_timestep;
+        let mut max_fps = control.max_fps;
 
         surface.resize(
             window.inner_size().width.try_into()?,
             window.inner_size().height.try_into()?,
         )?;
 
+        let present_mode = self.present_mode;
+        let mut next_update = Instant::now() + update_delay;
+        let event_interest = middleware.event_interest();
+        let device_events = self.device_events.unwrap_or(
+            if event_interest.contains(EventCategories::DEVICE_MOTION) {
+                DeviceEvents::WhenFocused
+            } else {
+                DeviceEvents::Never
+            },
+        );
+        self.event_loop.listen_device_events(device_events);
+        let render_on_demand = self.render_on_demand;
+        let mut idle = false;
+        let mut active = true;
+        if render_on_demand {
+            window.request_redraw();
+        }
+        let mut next_redraw = (max_fps.is_some() && !render_on_demand).then(Instant::now);
+        let frame_callbacks = has_frame_callbacks(&window);
+
         self.event_loop
-            .set_control_flow(ControlFlow::WaitUntil(Instant::now() + update_delay));
-        self.event_loop.run(move |event, elwt| {
+            .set_control_flow(ControlFlow::WaitUntil(next_update));
+        let coalesce_cursor = self.coalesce_cursor;
+        let mut pending_cursor = None;
+        let mut motion = std::mem::take(&mut control.motion);
+        let mut pointer = PointerTracker::default();
+        let mut present_stats = control.present_stats;
+        let mut tick = control.tick;
+        let mut savestates = std::mem::take(&mut control.savestates);
+        let mut next_window = control.next_window;
+        let mut windows = std::mem::take(&mut control.windows);
+        let mut window_requests = std::mem::take(&mut control.window_requests);
+        let mut extra_windows: Vec<ExtraWindow> = Vec::new();
+        let handler = move |event: Event<BackendRequest>,
+                            elwt: &EventLoopWindowTarget<BackendRequest>| {
             let mut control = SoftControl {
                 should_quit: false,
+                paused: None,
+                redraw_requested: false,
+                pointer_captured: pointer.is_captured(),
+                motion: std::mem::take(&mut motion),
+                present_stats,
+                tick,
+                savestate: None,
+                savestates: std::mem::take(&mut savestates),
+                fixed_timestep,
+                max_fps,
+                next_window,
+                windows: std::mem::take(&mut windows),
+                window_requests: std::mem::take(&mut window_requests),
+                window_sized,
                 window: window.clone(),
             };
 
+            if let Event::WindowEvent { window_id, event } = &event {
+                if *window_id == window.id() {
+                    match event {
+                        WindowEvent::CursorEntered { .. } => pointer.enter(),
+                        WindowEvent::CursorLeft { .. } => pointer.leave(),
+                        WindowEvent::CursorMoved { position, .. } => {
+                            pointer.moved((position.x, position.y))
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            let event = match event {
+                Event::DeviceEvent {
+                    device_id,
+                    event: DeviceEvent::MouseMotion { delta },
+                } => {
+                    control.motion.register_delta(delta);
+                    pointer.follow(delta).map_or(
+                        Event::DeviceEvent {
+                            device_id,
+                            event: DeviceEvent::MouseMotion { delta },
+                        },
+                        |(x, y)| Event::WindowEvent {
+                            window_id: window.id(),
+                            event: WindowEvent::CursorMoved {
+                                device_id,
+                                position: PhysicalPosition::new(x, y),
+                            },
+                        },
+                    )
+                }
+                event => event,
+            };
+
             match event {
-                Event::NewEvents(StartCause::ResumeTimeReached {
-                    requested_resume, ..
-                }) => {
-                    let context = middleware.update(&mut control, update_delay);
-                    app.update(context);
-                    elwt.set_control_flow(ControlFlow::WaitUntil(requested_resume + update_delay));
-                    window.request_redraw();
+                Event::NewEvents(_) => {
+                    let now = Instant::now();
+                    if !idle && now >= next_update {
+                        if let Some(pending) = pending_cursor.take() {
+                            middleware.handle_event(pending, &window, &mut control);
+                        }
+                        let mut steps = 0;
+                        while now >= next_update {
+                            let context = middleware.update(&mut control, update_delay);
+                            app.update(context);
+                            control.tick += 1;
+                            control.motion.reset();
+                            next_update += update_delay;
+                            steps += 1;
+                            if !fixed_timestep || control.should_quit {
+                                break;
+                            }
+                            if steps >= MAX_FIXED_STEPS {
+                                // Drop the backlog instead of falling further behind.
+                                next_update = now + update_delay;
+                                break;
+                            }
+                        }
+                        if render_on_demand {
+                            idle = !active && !control.redraw_requested;
+                            active = false;
+                        } else if present_mode == PresentMode::Timer && max_fps.is_none() {
+                            window.request_redraw();
+                        }
+                    }
+                    if next_redraw.is_some_and(|next_redraw| now >= next_redraw) {
+                        next_redraw = None;
+                        window.request_redraw();
+                    }
                 }
-                Event::WindowEvent { event, .. } => {
-                    if let Some(event) = middleware.handle_event(event, &window, &mut control) {
+                Event::AboutToWait => {
+                    if idle {
+                        elwt.set_control_flow(ControlFlow::Wait);
+                    } else {
+                        let deadline = next_redraw
+                            .map_or(next_update, |next_redraw| next_redraw.min(next_update));
+                        elwt.set_control_flow(ControlFlow::WaitUntil(deadline));
+                    }
+                }
+                Event::WindowEvent { window_id, event } if window_id != window.id() => {
+                    let extra = extra_windows
+                        .iter_mut()
+                        .find(|extra| extra.window.id() == window_id);
+                    if let Some(extra) = extra {
+                        if render_on_demand && event != WindowEvent::RedrawRequested {
+                            active = true;
+                            if idle {
+                                idle = false;
+                                next_update = Instant::now();
+                            }
+                        }
+                        if let WindowEvent::Resized(size) = event {
+                            let width = size.width.try_into();
+                            let height = size.height.try_into();
+                            if let (Ok(width), Ok(height)) = (width, height) {
+                                let _ = extra.surface.resize(width, height);
+                            }
+                        }
+                        let event = if event_interest.contains(event_category(&event)) {
+                            middleware.handle_window_event(
+                                extra.token,
+                                event,
+                                &extra.window,
+                                &mut control,
+                            )
+                        } else {
+                            Some(event)
+                        };
                         match event {
-                            WindowEvent::Resized(size) => {
-                                let width = size.width.try_into();
-                                let height = size.height.try_into();
-                                if let (Ok(width), Ok(height)) = (width, height) {
-                                    let _ = surface.resize(width, height);
+                            Some(WindowEvent::RedrawRequested) => {
+                                match extra.surface.buffer_mut() {
+                                    Ok(buf) => {
+                                        if let Some(mut render_target) =
+                                            middleware.render_window(extra.token, buf)
+                                        {
+                                            let surface =
+                                                <SoftRenderTarget<'_, Rend> as RenderTarget<
+                                                    Conv,
+                                                >>::render_surface_mut(
+                                                    &mut render_target
+                                                );
+                                            app.render_window(extra.token, surface);
+                                            extra.window.pre_present_notify();
+                                            if let Err(error) =
+                                                devotee_backend::RenderTarget::present(
+                                                    render_target,
+                                                    app.converter(),
+                                                )
+                                            {
+                                                log::warn!("Window frame present failed: {error}");
+                                            }
+                                        }
+                                    }
+                                    Err(error) => {
+                                        log::warn!(
+                                            "Window frame buffer acquisition failed: {error}"
+                                        );
+                                    }
                                 }
                             }
-                            WindowEvent::RedrawRequested => {
-                                if let Ok(buf) = surface.buffer_mut() {
+                            Some(WindowEvent::CloseRequested) => {
+                                control.close_window(extra.token);
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                Event::WindowEvent { event, .. } => {
+                    if render_on_demand && event != WindowEvent::RedrawRequested {
+                        active = true;
+                        if idle {
+                            idle = false;
+                            next_update = Instant::now();
+                        }
+                    }
+                    if let WindowEvent::CursorMoved { position, .. } = event {
+                        control
+                            .motion
+                            .register_position((position.x as f32, position.y as f32));
+                    }
+                    let event = match event {
+                        WindowEvent::CursorMoved { .. } if coalesce_cursor => {
+                            pending_cursor = event_interest
+                                .contains(EventCategories::CURSOR)
+                                .then_some(event);
+                            None
+                        }
+                        event => {
+                            if let Some(pending) = pending_cursor.take() {
+                                middleware.handle_event(pending, &window, &mut control);
+                            }
+                            Some(event)
+                        }
+                    };
+                    if let Some(event) = event {
+                        if let WindowEvent::Resized(size) = event {
+                            let width = size.width.try_into();
+                            let height = size.height.try_into();
+                            if let (Ok(width), Ok(height)) = (width, height) {
+                                let _ = surface.resize(width, height);
+                            }
+                        }
+                        let event = if event_interest.contains(event_category(&event)) {
+                            middleware.handle_event(event, &window, &mut control)
+                        } else {
+                            Some(event)
+                        };
+                        if let Some(WindowEvent::RedrawRequested) = event {
+                            let frame_start = Instant::now();
+                            match surface.buffer_mut() {
+                                Ok(buf) => {
                                     let mut render_target = middleware.render(buf);
                                     let surface = <SoftRenderTarget<'_, Rend> as RenderTarget<
                                         Conv,
                                     >>::render_surface_mut(
                                         &mut render_target
                                     );
+                                    if fixed_timestep {
+                                        app.interpolate(interpolation(next_update, update_delay));
+                                    }
                                     app.render(surface);
-                                    let _ = devotee_backend::RenderTarget::present(
+                                    window.pre_present_notify();
+                                    match devotee_backend::RenderTarget::present(
                                         render_target,
                                         app.converter(),
-                                    );
+                                    ) {
+                                        Ok(()) => present_stats.register_present(Instant::now()),
+                                        Err(error) => {
+                                            log::warn!("Frame present failed: {error}");
+                                            present_stats.register_skip(Instant::now());
+                                        }
+                                    }
+                                }
+                                Err(error) => {
+                                    log::warn!("Frame buffer acquisition failed: {error}");
+                                    present_stats.register_acquire_failure(Instant::now());
+                                }
+                            }
+                            for extra in extra_windows.iter() {
+                                extra.window.request_redraw();
+                            }
+                            match (present_mode, max_fps) {
+                                _ if render_on_demand => (),
+                                (PresentMode::Timer, None) => (),
+                                (PresentMode::Timer, Some(max_fps)) => {
+                                    next_redraw = Some(frame_start + frame_interval(max_fps));
+                                }
+                                (PresentMode::Sync, None) if frame_callbacks => {
+                                    // Winit holds the redraw until the compositor asks for the next frame.
+                                    window.request_redraw();
+                                }
+                                (PresentMode::Sync, max_fps) => {
+                                    let interval = refresh_interval(&window)
+                                        .max(max_fps.map_or(Duration::ZERO, frame_interval));
+                                    next_redraw = Some(frame_start + interval);
                                 }
-                                window.request_redraw();
                             }
-                            _ => (),
                         }
                     }
                 }
+                Event::UserEvent(request) => {
+                    if render_on_demand {
+                        active = true;
+                        if idle {
+                            idle = false;
+                            next_update = Instant::now();
+                        }
+                    }
+                    if request == BackendRequest::Activate {
+                        window.set_minimized(false);
+                        window.focus_window();
+                    }
+                }
+
                 _ => (),
             }
 
+            if control.redraw_requested {
+                window.request_redraw();
+            }
+            if control.max_fps != max_fps {
+                max_fps = control.max_fps;
+                if !render_on_demand {
+                    next_redraw = Some(Instant::now());
+                }
+            }
             if control.should_quit {
                 elwt.exit();
             }
-        })?;
+            for request in std::mem::take(&mut control.window_requests) {
+                match request {
+                    WindowRequest::Open(token, config) => {
+                        match ExtraWindow::open(elwt, &context, token, &config) {
+                            Ok(extra) => {
+                                // Let the middleware know the initial window size.
+                                let size = WindowEvent::Resized(extra.window.inner_size());
+                                middleware.handle_window_event(
+                                    token,
+                                    size,
+                                    &extra.window,
+                                    &mut control,
+                                );
+                                extra_windows.push(extra);
+                            }
+                            Err(error) => {
+                                log::warn!("Window opening failed: {error:?}");
+                                control.windows.retain(|window| *window != token);
+                            }
+                        }
+                    }
+                    WindowRequest::Close(token) => {
+                        extra_windows.retain(|extra| extra.token != token);
+                    }
+                }
+            }
+            motion = std::mem::take(&mut control.motion);
+            tick = control.tick;
+            savestates = std::mem::take(&mut control.savestates);
+            next_window = control.next_window;
+            windows = std::mem::take(&mut control.windows);
+            match control.savestate {
+                Some(SavestateRequest::Save(slot)) => {
+                    if let Some(data) = app.save_state() {
+                        savestates.insert(slot, Savestate::new(tick, data));
+                    }
+                }
+                Some(SavestateRequest::Load(slot)) => {
+                    if let Some(savestate) = savestates.get(slot) {
+                        if app.load_state(savestate.data()) {
+                            tick = savestate.tick();
+                            window.request_redraw();
+                        }
+                    }
+                }
+                None => (),
+            }
+            if control.pointer_captured != pointer.is_captured() {
+                pointer.set_captured(control.pointer_captured);
+                // Motion outside of the window is only reported with device events.
+                elwt.listen_device_events(if pointer.is_captured() {
+                    DeviceEvents::WhenFocused
+                } else {
+                    device_events
+                });
+            }
+            if let Some(paused) = control.paused {
+                if paused {
+                    app.pause();
+                } else {
+                    app.resume();
+                }
+            }
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::EventLoopExtWebSys;
+            self.event_loop.spawn(handler);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        self.event_loop.run(handler)?;
 
         Ok(())
     }
 }
 
+fn window_builder(config: &WindowConfig) -> Result<WindowBuilder, Error> {
+    let icon = match config.icon() {
+        Some(icon) => Some(Icon::from_rgba(
+            icon.rgba().to_vec(),
+            icon.width(),
+            icon.height(),
+        )?),
+        None => None,
+    };
+    let level = if config.always_on_top() {
+        WindowLevel::AlwaysOnTop
+    } else {
+        WindowLevel::Normal
+    };
+    let mut builder = WindowBuilder::new()
+        .with_title(config.title())
+        .with_resizable(config.resizable())
+        .with_decorations(config.decorations())
+        .with_fullscreen(config.fullscreen().then_some(Fullscreen::Borderless(None)))
+        .with_window_level(level)
+        .with_window_icon(icon);
+    if let Some((width, height)) = config.size() {
+        builder = builder.with_inner_size(PhysicalSize::new(width, height));
+    }
+    #[cfg(target_arch = "wasm32")]
+    let builder = {
+        use winit::platform::web::WindowBuilderExtWebSys;
+        builder.with_append(true)
+    };
+    Ok(builder)
+}
+
+/// Additional window opened by the application.
+struct ExtraWindow {
+    token: WindowToken,
+    window: Rc<Window>,
+    surface: Surface<Rc<Window>, Rc<Window>>,
+}
+
+impl ExtraWindow {
+    fn open(
+        elwt: &EventLoopWindowTarget<BackendRequest>,
+        context: &softbuffer::Context<Rc<Window>>,
+        token: WindowToken,
+        config: &WindowConfig,
+    ) -> Result<Self, Error> {
+        let window = Rc::new(window_builder(config)?.build(elwt)?);
+        let mut surface = Surface::new(context, window.clone())?;
+        let size = window.inner_size();
+        surface.resize(size.width.try_into()?, size.height.try_into()?)?;
+        Ok(Self {
+            token,
+            window,
+            surface,
+        })
+    }
+}
+
+fn interpolation(next_update: Instant, update_delay: Duration) -> f32 {
+    let remaining = next_update.saturating_duration_since(Instant::now());
+    (1.0 - remaining.as_secs_f32() / update_delay.as_secs_f32()).clamp(0.0, 1.0)
+}
+
+fn frame_interval(max_fps: u32) -> Duration {
+    Duration::from_secs(1) / max_fps
+}
+
+/// Check if the window system paces redraws after `pre_present_notify` with frame callbacks.
+fn has_frame_callbacks(window: &Window) -> bool {
+    matches!(
+        window.window_handle().map(|handle| handle.as_raw()),
+        Ok(RawWindowHandle::Wayland(_))
+    )
+}
+
+fn refresh_interval(window: &Window) -> Duration {
+    let millihertz = window
+        .current_monitor()
+        .and_then(|monitor| monitor.refresh_rate_millihertz())
+        .unwrap_or(60_000);
+    Duration::from_secs_f64(1000.0 / millihertz as f64)
+}
+
 /// Default Middleware for the Softbuffer backend.
 pub struct SoftMiddleware<RenderSurface, Input> {
     background_color: u32,
@@ -148,6 +719,55 @@ pub struct SoftMiddleware<RenderSurface, Input> {
     render_surface: RenderSurface,
     input: Input,
     default_scale: u32,
+    latency: Option<LatencyRecorder>,
+    drag_regions: Vec<((i32, i32), (i32, i32))>,
+    cursor_position: (f32, f32),
+    event_interest: EventCategories,
+    adjustment: ColorAdjustment,
+    safe_area: SafeArea,
+    viewport: Viewport,
+    scale_mode: ScaleMode,
+    pause_in_background: bool,
+    magnifier: Magnifier,
+    magnifier_hotkey: Option<(KeyCode, ModifiersState)>,
+    savestate_hotkeys: Option<(KeyCode, KeyCode)>,
+    frame: Option<Frame>,
+    modifiers: ModifiersState,
+    magnifier_controls: MagnifierControls,
+    window_surface_factory: Option<Box<WindowSurfaceFactory<RenderSurface>>>,
+    window_surfaces: Vec<WindowSurface<RenderSurface>>,
+    window_layouts: Vec<WindowLayout>,
+    window_events: Vec<(WindowToken, WindowEvent)>,
+    cursor_image: Option<CursorImage>,
+    cursor_inside: bool,
+    presented: Option<Presented>,
+}
+
+type WindowSurfaceFactory<RenderSurface> = dyn FnMut(WindowToken) -> RenderSurface;
+
+/// Render surface of the additional window.
+struct WindowSurface<RenderSurface> {
+    token: WindowToken,
+    buffer_dimensions: (usize, usize),
+    render_surface: RenderSurface,
+    presented: Option<Presented>,
+}
+
+/// Settings of the last presented frame, any change of them requires redrawing the whole buffer.
+#[derive(Clone, PartialEq)]
+struct Presented {
+    buffer_dimensions: (usize, usize),
+    placement: Option<((u32, u32), u32)>,
+    background_color: u32,
+    adjustment: ColorAdjustment,
+}
+
+/// Placement of the additional window render surface used to convert cursor positions.
+#[derive(Clone, Copy)]
+struct WindowLayout {
+    token: WindowToken,
+    dimensions: (u32, u32),
+    resolution: (u32, u32),
 }
 
 impl<RenderSurface, Input> SoftMiddleware<RenderSurface, Input>
@@ -159,16 +779,61 @@ where
         let buffer_dimensions = (render_surface.width(), render_surface.height());
         let background_color = 0;
         let default_scale = 1;
+        let latency = None;
+        let drag_regions = Vec::new();
+        let cursor_position = (0.0, 0.0);
+        let event_interest = EventCategories::ALL;
+        let adjustment = ColorAdjustment::new();
+        let safe_area = SafeArea::default();
+        let viewport = Viewport::default();
+        let scale_mode = ScaleMode::default();
+        let pause_in_background = false;
+        let magnifier = Magnifier::new();
+        let magnifier_hotkey = None;
+        let savestate_hotkeys = None;
+        let modifiers = ModifiersState::empty();
+        let magnifier_controls = MagnifierControls::new();
+        let frame = None;
+        let window_surface_factory = None;
+        let window_surfaces = Vec::new();
+        let window_layouts = Vec::new();
+        let window_events = Vec::new();
+        let cursor_image = None;
+        let cursor_inside = false;
+        let presented = None;
         Self {
             background_color,
             buffer_dimensions,
             render_surface,
             input,
             default_scale,
+            latency,
+            drag_regions,
+            cursor_position,
+            event_interest,
+            adjustment,
+            safe_area,
+            viewport,
+            scale_mode,
+            pause_in_background,
+            magnifier,
+            magnifier_hotkey,
+            savestate_hotkeys,
+            modifiers,
+            magnifier_controls,
+            frame,
+            window_surface_factory,
+            window_surfaces,
+            window_layouts,
+            window_events,
+            cursor_image,
+            cursor_inside,
+            presented,
         }
     }
 
     /// Set default scale for the window.
+    /// Ignored if the window size is set with the `WindowConfig`.
     ///
     /// # Panics
     /// Panics if `default_scale` is zero.
@@ -187,6 +852,206 @@ where
             ..self
         }
     }
+
+    /// Enable input latency diagnostics using the provided recorder.
+    pub fn with_latency_recorder(self, latency: LatencyRecorder) -> Self {
+        Self {
+            latency: Some(latency),
+            ..self
+        }
+    }
+
+    /// Mark rectangular region of the render surface as a window drag area.
+    /// Pressing the left mouse button inside of it starts window dragging instead of passing the event further.
+    pub fn with_drag_region(self, origin: (i32, i32), dimensions: (i32, i32)) -> Self {
+        let mut drag_regions = self.drag_regions;
+        drag_regions.push((origin, dimensions));
+        Self {
+            drag_regions,
+            ..self
+        }
+    }
+
+    /// Set categories of events to be handled.
+    /// Events of other categories skip the input handling completely.
+    pub fn with_event_interest(self, event_interest: EventCategories) -> Self {
+        Self {
+            event_interest,
+            ..self
+        }
+    }
+
+    /// Set color adjustment applied as the final conversion step.
+    pub fn with_color_adjustment(self, adjustment: ColorAdjustment) -> Self {
+        Self { adjustment, ..self }
+    }
+
+    /// Set safe area margins the render surface is kept out of.
+    pub fn with_safe_area(self, safe_area: SafeArea) -> Self {
+        Self { safe_area, ..self }
+    }
+
+    /// Set placement of the render surface within the window.
+    pub fn with_viewport(self, viewport: Viewport) -> Self {
+        Self { viewport, ..self }
+    }
+
+    /// Set integer scale selection of the render surface within the window.
+    pub fn with_scale_mode(self, scale_mode: ScaleMode) -> Self {
+        Self { scale_mode, ..self }
+    }
+
+    /// Pause the application while the window is not focused.
+    pub fn with_pause_in_background(self, pause_in_background: bool) -> Self {
+        Self {
+            pause_in_background,
+            ..self
+        }
+    }
+
+    /// Apply middleware-level settings of the `config`.
+    pub fn with_config(self, config: &BackendConfig) -> Self {
+        let mut middleware = self;
+        if let Some((width, height)) = config.resolution {
+            middleware.render_surface.resize(width, height, false);
+        }
+        middleware
+            .with_default_scale(config.scale.max(1))
+            .with_background_color(config.border_color)
+            .with_safe_area(config.safe_area)
+            .with_viewport(config.viewport)
+            .with_scale_mode(config.scale_mode)
+            .with_pause_in_background(config.pause_in_background)
+    }
+
+    /// Save the application state into the first savestate slot on pressing the `save` key
+    /// and load it on pressing the `load` key.
+    pub fn with_savestate_hotkeys(self, save: KeyCode, load: KeyCode) -> Self {
+        Self {
+            savestate_hotkeys: Some((save, load)),
+            ..self
+        }
+    }
+
+    /// Enable the debug magnifier toggled by pressing `key` with exactly the `modifiers` held.
+    /// While it is active the mouse wheel changes zoom and dragging with the left mouse button pans the view.
+    pub fn with_magnifier_hotkey(self, key: KeyCode, modifiers: ModifiersState) -> Self {
+        Self {
+            magnifier_hotkey: Some((key, modifiers)),
+            ..self
+        }
+    }
+
+    /// Enable or disable capturing of the presented frames.
+    /// Captured frames are available with the context `capture_frame` method.
+    pub fn with_frame_capture(self, enabled: bool) -> Self {
+        let frame = enabled.then(Frame::new);
+        Self { frame, ..self }
+    }
+
+    /// Set factory of render surfaces for the additional windows opened with `SoftControl::create_window`.
+    /// Without it additional windows are not rendered.
+    pub fn with_window_surfaces<F>(self, factory: F) -> Self
+    where
+        F: FnMut(WindowToken) -> RenderSurface + 'static,
+    {
+        Self {
+            window_surface_factory: Some(Box::new(factory)),
+            ..self
+        }
+    }
+
+    fn window_surface(&mut self, window: WindowToken) -> Option<&mut WindowSurface<RenderSurface>> {
+        let index = match self
+            .window_surfaces
+            .iter()
+            .position(|surface| surface.token == window)
+        {
+            Some(index) => index,
+            None => {
+                let factory = self.window_surface_factory.as_mut()?;
+                let render_surface = factory(window);
+                self.window_surfaces.push(WindowSurface {
+                    token: window,
+                    buffer_dimensions: (0, 0),
+                    render_surface,
+                    presented: None,
+                });
+                self.window_surfaces.len() - 1
+            }
+        };
+        self.window_surfaces.get_mut(index)
+    }
+
+    /// Handle debug magnifier controls, return `true` if the event is consumed.
+    fn handle_magnifier<Context>(
+        &mut self,
+        event: &WindowEvent,
+        context: &Context,
+        control: &mut SoftControl,
+    ) -> bool
+    where
+        Context: EventContext,
+    {
+        let Some((key, modifiers)) = self.magnifier_hotkey else {
+            return false;
+        };
+        let resolution = (self.render_surface.width(), self.render_surface.height());
+        let surface_position = |position| {
+            let (x, y) = context
+                .position_into_render_surface_space(position)
+                .unwrap_or_else(|position| position);
+            (x as f32, y as f32)
+        };
+        match event {
+            WindowEvent::ModifiersChanged(state) => {
+                self.modifiers = state.state();
+                false
+            }
+            WindowEvent::KeyboardInput { event, .. }
+                if event.physical_key == PhysicalKey::Code(key) && self.modifiers == modifiers =>
+            {
+                if event.state == ElementState::Pressed && !event.repeat {
+                    self.magnifier_controls
+                        .toggle(&mut self.magnifier, surface_position(self.cursor_position));
+                    control.request_redraw();
+                }
+                true
+            }
+            _ if !self.magnifier.is_active() => false,
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.magnifier_controls.set_panning(
+                    *state == ElementState::Pressed,
+                    surface_position(self.cursor_position),
+                );
+                true
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let steps = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => y.signum() as i32,
+                    MouseScrollDelta::PixelDelta(position) => position.y.signum() as i32,
+                };
+                self.magnifier.zoom_by(steps);
+                control.request_redraw();
+                true
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = surface_position((position.x as f32, position.y as f32));
+                if self
+                    .magnifier_controls
+                    .pan(&mut self.magnifier, position, resolution)
+                {
+                    control.request_redraw();
+                }
+                false
+            }
+            _ => false,
+        }
+    }
 }
 
 impl<'a, RenderSurface, Input> Middleware<'a, SoftControl> for SoftMiddleware<RenderSurface, Input>
@@ -210,76 +1075,311 @@ where
         control
             .window
             .set_min_inner_size(Some(PhysicalSize::new(dimensions.0, dimensions.1)));
-        let _ = control.window.request_inner_size(PhysicalSize::new(
-            dimensions.0 * self.default_scale,
-            dimensions.1 * self.default_scale,
-        ));
+        // Keep the size set with the window config.
+        if !control.window_sized {
+            let _ = control.window.request_inner_size(PhysicalSize::new(
+                dimensions.0 * self.default_scale,
+                dimensions.1 * self.default_scale,
+            ));
+        }
         let actual_dimensions = control.window.inner_size();
         self.buffer_dimensions = (
             actual_dimensions.width as usize,
             actual_dimensions.height as usize,
         );
 
-        SoftInit { control }
+        let scale_mode = &mut self.scale_mode;
+        let cursor_image = &mut self.cursor_image;
+        SoftInit {
+            control,
+            scale_mode,
+            cursor_image,
+        }
     }
 
     fn update(&'a mut self, control: &'a mut SoftControl, delta: Duration) -> Self::Context {
+        if let Some(latency) = &mut self.latency {
+            latency.register_tick(Instant::now());
+        }
         let input = &mut self.input;
+        let latency = self.latency.as_ref();
+        let adjustment = &mut self.adjustment;
+        let safe_area = &mut self.safe_area;
+        let viewport = &mut self.viewport;
+        let scale_mode = &mut self.scale_mode;
+        let background_color = &mut self.background_color;
+        let pause_in_background = &mut self.pause_in_background;
+        let magnifier = &mut self.magnifier;
+        let frame = self.frame.as_ref();
+        let render_surface = &mut self.render_surface;
+        self.window_surfaces
+            .retain(|surface| control.windows.contains(&surface.token));
+        self.window_layouts.clear();
+        self.window_layouts
+            .extend(self.window_surfaces.iter().map(|surface| WindowLayout {
+                token: surface.token,
+                dimensions: (
+                    surface.buffer_dimensions.0 as u32,
+                    surface.buffer_dimensions.1 as u32,
+                ),
+                resolution: (
+                    surface.render_surface.width() as u32,
+                    surface.render_surface.height() as u32,
+                ),
+            }));
+        let window_layouts = &self.window_layouts;
+        let window_events = &mut self.window_events;
+        let cursor_image = &mut self.cursor_image;
         SoftContext {
             control,
             delta,
             input,
+            latency,
+            adjustment,
+            safe_area,
+            viewport,
+            scale_mode,
+            background_color,
+            pause_in_background,
+            magnifier,
+            frame,
+            render_surface,
+            window_layouts,
+            window_events,
+            cursor_image,
         }
     }
 
+    fn event_interest(&self) -> EventCategories {
+        // The middleware keeps tracking the cursor, hotkeys and drag regions on its own.
+        let mut interest = self.event_interest | EventCategories::CURSOR;
+        if self.magnifier_hotkey.is_some()
+            || self.savestate_hotkeys.is_some()
+            || self.latency.is_some()
+        {
+            interest |= EventCategories::KEYBOARD;
+        }
+        if self.magnifier_hotkey.is_some()
+            || self.latency.is_some()
+            || !self.drag_regions.is_empty()
+        {
+            interest |= EventCategories::MOUSE;
+        }
+        interest
+    }
+
     fn handle_event(
         &mut self,
         event: Self::Event,
         event_context: Self::EventContext,
         control: &mut SoftControl,
     ) -> Option<Self::Event> {
+        if let WindowEvent::Resized(internal_size) = event {
+            self.buffer_dimensions = (internal_size.width as usize, internal_size.height as usize);
+        }
         let context = SoftEventContext {
             window: event_context,
+            pointer_captured: control.pointer_captured,
+            dimensions: (
+                self.buffer_dimensions.0 as u32,
+                self.buffer_dimensions.1 as u32,
+            ),
             resolution: (
                 self.render_surface.width() as u32,
                 self.render_surface.height() as u32,
             ),
+            safe_area: self.safe_area,
+            viewport: self.viewport,
+            scale_mode: self.scale_mode,
         };
 
-        if let Some(event) = self.input.handle_event(event, &context) {
-            match event {
-                WindowEvent::CloseRequested => {
-                    control.shutdown();
+        if let Some(latency) = &mut self.latency {
+            if is_press(&event) {
+                latency.register_arrival(Instant::now());
+            }
+        }
+
+        if self.handle_magnifier(&event, &context, control) {
+            return None;
+        }
+
+        if let (Some((save, load)), WindowEvent::KeyboardInput { event: key, .. }) =
+            (self.savestate_hotkeys, &event)
+        {
+            if let PhysicalKey::Code(code) = key.physical_key {
+                if code == save || code == load {
+                    if key.state == ElementState::Pressed && !key.repeat {
+                        if code == save {
+                            control.save_state(0);
+                        } else {
+                            control.load_state(0);
+                        }
+                    }
+                    return None;
                 }
-                WindowEvent::Resized(internal_size) => {
-                    self.buffer_dimensions =
-                        (internal_size.width as usize, internal_size.height as usize);
+            }
+        }
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_position = (position.x as f32, position.y as f32);
+                self.cursor_inside = true;
+                if self.cursor_image.is_some() {
+                    control.request_redraw();
+                }
+            }
+            WindowEvent::CursorEntered { .. } => {
+                self.cursor_inside = true;
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.cursor_inside = false;
+                if self.cursor_image.is_some() {
+                    control.request_redraw();
                 }
-                _ => {}
             }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if let Ok((x, y)) = context.position_into_render_surface_space(self.cursor_position)
+                {
+                    let in_drag_region = self.drag_regions.iter().any(|(origin, dimensions)| {
+                        x >= origin.0
+                            && y >= origin.1
+                            && x < origin.0 + dimensions.0
+                            && y < origin.1 + dimensions.1
+                    });
+                    if in_drag_region && control.start_drag().is_ok() {
+                        return None;
+                    }
+                }
+            }
+            _ => {}
+        }
 
-            Some(event)
+        let event = if self.event_interest.contains(event_category(&event)) {
+            self.input.handle_event(event, &context)?
         } else {
-            None
+            event
+        };
+        match event {
+            WindowEvent::CloseRequested => {
+                control.shutdown();
+            }
+            WindowEvent::Focused(gained) if self.pause_in_background => {
+                control.set_paused(!gained);
+            }
+            _ => {}
         }
+
+        Some(event)
     }
 
     fn render(&'a mut self, surface: Self::Surface) -> Self::RenderTarget {
         let background_color = self.background_color;
         let buffer_dimensions = self.buffer_dimensions;
         let render_surface = &mut self.render_surface;
+        let latency = self.latency.as_mut();
+        let adjustment = &self.adjustment;
+        let safe_area = self.safe_area;
+        let viewport = self.viewport;
+        let scale_mode = self.scale_mode;
+        let magnifier = Some(&self.magnifier);
+        let frame = self.frame.as_mut();
+        let cursor_position = self.cursor_position;
+        let cursor = self
+            .cursor_image
+            .as_ref()
+            .filter(|_| self.cursor_inside)
+            .and_then(|image| {
+                let position = surface_position(
+                    cursor_position,
+                    (buffer_dimensions.0 as u32, buffer_dimensions.1 as u32),
+                    (
+                        render_surface.width() as u32,
+                        render_surface.height() as u32,
+                    ),
+                    safe_area,
+                    viewport,
+                    scale_mode,
+                    false,
+                );
+                Some((image, position.ok()?))
+            });
         SoftRenderTarget {
             background_color,
             buffer_dimensions,
             render_surface,
             buffer: surface,
+            latency,
+            adjustment,
+            safe_area,
+            viewport,
+            scale_mode,
+            magnifier,
+            frame,
+            cursor,
+            presented: &mut self.presented,
+        }
+    }
+
+    fn handle_window_event(
+        &mut self,
+        window: WindowToken,
+        event: Self::Event,
+        event_context: Self::EventContext,
+        control: &mut SoftControl,
+    ) -> Option<Self::Event> {
+        let _ = (event_context, control);
+        if let WindowEvent::Resized(size) = event {
+            if let Some(surface) = self.window_surface(window) {
+                surface.buffer_dimensions = (size.width as usize, size.height as usize);
+            }
         }
+        if event != WindowEvent::RedrawRequested
+            && self.event_interest.contains(event_category(&event))
+        {
+            self.window_events.push((window, event.clone()));
+        }
+        Some(event)
+    }
+
+    fn render_window(
+        &'a mut self,
+        window: WindowToken,
+        surface: Self::Surface,
+    ) -> Option<Self::RenderTarget> {
+        let background_color = self.background_color;
+        let adjustment = &self.adjustment;
+        let index = self
+            .window_surfaces
+            .iter()
+            .position(|surface| surface.token == window)?;
+        let window_surface = &mut self.window_surfaces[index];
+        Some(SoftRenderTarget {
+            background_color,
+            buffer_dimensions: window_surface.buffer_dimensions,
+            render_surface: &mut window_surface.render_surface,
+            presented: &mut window_surface.presented,
+            buffer: surface,
+            latency: None,
+            adjustment,
+            safe_area: SafeArea::default(),
+            viewport: Viewport::default(),
+            scale_mode: ScaleMode::default(),
+            magnifier: None,
+            frame: None,
+            cursor: None,
+        })
     }
 }
 
 /// Default Init for the Softbuffer backend.
 pub struct SoftInit<'a> {
     control: &'a mut SoftControl,
+    scale_mode: &'a mut ScaleMode,
+    cursor_image: &'a mut Option<CursorImage>,
 }
 
 impl<'a> SoftInit<'a> {
@@ -292,6 +1392,70 @@ impl<'a> SoftInit<'a> {
     pub fn control_mut(&mut self) -> &mut SoftControl {
         self.control
     }
+
+    /// Enable or disable fixed timestep updates.
+    /// When enabled, the backend performs as many updates with the fixed delta as needed to catch up
+    /// with the wall clock and passes the interpolation factor to the application before rendering.
+    pub fn set_fixed_timestep(&mut self, fixed_timestep: bool) {
+        self.control.fixed_timestep = fixed_timestep;
+    }
+
+    /// Set render frame rate cap independent of the update rate.
+    /// `None` or zero removes the cap leaving redraws to the present mode.
+    pub fn set_max_fps(&mut self, max_fps: Option<u32>) {
+        self.control.max_fps = max_fps.filter(|&fps| fps > 0);
+    }
+
+    /// Set integer scale selection of the render surface within the window.
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) {
+        *self.scale_mode = scale_mode;
+    }
+
+    /// Open additional window with the `config` once the backend starts.
+    pub fn create_window(&mut self, config: WindowConfig) -> WindowToken {
+        self.control.create_window(config)
+    }
+
+    /// Show or hide the system cursor over the window.
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.control.set_cursor_visible(visible);
+    }
+
+    /// Confine or lock the cursor to the window.
+    pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.control.set_cursor_grab(mode)
+    }
+
+    /// Set image drawn over the frame at the cursor position instead of the system cursor.
+    /// The system cursor is hidden while the image is set.
+    pub fn set_cursor_image(&mut self, image: Option<CursorImage>) {
+        self.control.set_cursor_visible(image.is_none());
+        *self.cursor_image = image;
+    }
+
+    /// Move the window canvas into the page element with the `element_id`.
+    /// By default the canvas is appended to the document body.
+    /// Returns `false` if there is no such element.
+    #[cfg(target_arch = "wasm32")]
+    pub fn set_canvas_parent(&mut self, element_id: &str) -> bool {
+        append_canvas(&self.control.window, element_id)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn append_canvas(window: &Window, element_id: &str) -> bool {
+    use winit::platform::web::WindowExtWebSys;
+
+    let Some(canvas) = window.canvas() else {
+        return false;
+    };
+    let parent = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(element_id));
+    match parent {
+        Some(parent) => parent.append_child(&canvas).is_ok(),
+        None => false,
+    }
 }
 
 /// Default Context for the Softbuffer backend.
@@ -302,6 +1466,19 @@ where
     control: &'a mut SoftControl,
     input: &'a mut Input,
     delta: Duration,
+    latency: Option<&'a LatencyRecorder>,
+    adjustment: &'a mut ColorAdjustment,
+    safe_area: &'a mut SafeArea,
+    viewport: &'a mut Viewport,
+    scale_mode: &'a mut ScaleMode,
+    background_color: &'a mut u32,
+    pause_in_background: &'a mut bool,
+    magnifier: &'a mut Magnifier,
+    frame: Option<&'a Frame>,
+    render_surface: &'a mut dyn ResizeRenderSurface,
+    window_layouts: &'a [WindowLayout],
+    window_events: &'a mut Vec<(WindowToken, WindowEvent)>,
+    cursor_image: &'a mut Option<CursorImage>,
 }
 
 impl<'a, Input> SoftContext<'a, Input>
@@ -317,6 +1494,164 @@ where
     pub fn control_mut(&mut self) -> &mut SoftControl {
         self.control
     }
+
+    /// Get input latency report if latency diagnostics are enabled.
+    pub fn latency_report(&self) -> Option<LatencyReport> {
+        self.latency.map(LatencyRecorder::report)
+    }
+
+    /// Get reference to the color adjustment applied at presentation.
+    pub fn color_adjustment(&self) -> &ColorAdjustment {
+        self.adjustment
+    }
+
+    /// Get mutable reference to the color adjustment applied at presentation.
+    pub fn color_adjustment_mut(&mut self) -> &mut ColorAdjustment {
+        self.adjustment
+    }
+
+    /// Get safe area margins the render surface is kept out of.
+    pub fn safe_area(&self) -> SafeArea {
+        *self.safe_area
+    }
+
+    /// Set safe area margins the render surface is kept out of.
+    pub fn set_safe_area(&mut self, safe_area: SafeArea) {
+        *self.safe_area = safe_area;
+    }
+
+    /// Get placement of the render surface within the window.
+    pub fn viewport(&self) -> Viewport {
+        *self.viewport
+    }
+
+    /// Set placement of the render surface within the window, applied starting with the next frame.
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        *self.viewport = viewport;
+    }
+
+    /// Get integer scale selection of the render surface within the window.
+    pub fn scale_mode(&self) -> ScaleMode {
+        *self.scale_mode
+    }
+
+    /// Set integer scale selection of the render surface within the window, applied starting with the next frame.
+    pub fn set_scale_mode(&mut self, scale_mode: ScaleMode) {
+        *self.scale_mode = scale_mode;
+    }
+
+    /// Re-apply settings of the `config` which can be changed at runtime:
+    /// resolution, viewport, scale mode, border color, frame rate cap and background behavior.
+    pub fn apply_config(&mut self, config: &BackendConfig) {
+        if let Some((width, height)) = config.resolution {
+            self.resize_render_target(width, height, true);
+        }
+        *self.viewport = config.viewport;
+        *self.scale_mode = config.scale_mode;
+        *self.background_color = config.border_color;
+        *self.pause_in_background = config.pause_in_background;
+        self.set_max_fps(config.max_fps);
+    }
+
+    /// Get render frame rate cap.
+    pub fn max_fps(&self) -> Option<u32> {
+        self.control.max_fps
+    }
+
+    /// Set render frame rate cap independent of the update rate.
+    /// `None` or zero removes the cap leaving redraws to the present mode.
+    pub fn set_max_fps(&mut self, max_fps: Option<u32>) {
+        self.control.max_fps = max_fps.filter(|&fps| fps > 0);
+    }
+
+    /// Get reference to the debug magnifier.
+    pub fn magnifier(&self) -> &Magnifier {
+        self.magnifier
+    }
+
+    /// Get mutable reference to the debug magnifier.
+    pub fn magnifier_mut(&mut self) -> &mut Magnifier {
+        self.magnifier
+    }
+
+    /// Get copy of the last presented frame.
+    /// Returns `None` if frame capture is disabled in the middleware or nothing was presented yet.
+    pub fn capture_frame(&self) -> Option<Frame> {
+        self.frame.filter(|frame| !frame.is_empty()).cloned()
+    }
+
+    /// Save the last presented frame as a PNG image into the file at `path`.
+    #[cfg(feature = "screenshot")]
+    pub fn save_screenshot<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+    ) -> Result<(), ScreenshotError> {
+        self.frame.ok_or(ScreenshotError::NoFrame)?.save_png(path)
+    }
+
+    /// Resize the render surface, keeping its contents in the overlapping area if `preserve` is set.
+    /// The presentation scale is adjusted to the new resolution starting with the next frame.
+    /// Return `false` if the render surface does not support resizing.
+    pub fn resize_render_target(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        let resized = self.render_surface.resize(width, height, preserve);
+        if resized {
+            self.control
+                .window
+                .set_min_inner_size(Some(PhysicalSize::new(width as u32, height as u32)));
+        }
+        resized
+    }
+
+    /// Get events of the additional windows received since the previous update.
+    pub fn window_events(&self) -> &[(WindowToken, WindowEvent)] {
+        self.window_events
+    }
+
+    /// Show or hide the system cursor over the window.
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.control.set_cursor_visible(visible);
+    }
+
+    /// Confine or lock the cursor to the window.
+    pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.control.set_cursor_grab(mode)
+    }
+
+    /// Get image drawn over the frame at the cursor position.
+    pub fn cursor_image(&self) -> Option<&CursorImage> {
+        self.cursor_image.as_ref()
+    }
+
+    /// Set image drawn over the frame at the cursor position instead of the system cursor.
+    /// The system cursor is hidden while the image is set.
+    pub fn set_cursor_image(&mut self, image: Option<CursorImage>) {
+        self.control.set_cursor_visible(image.is_none());
+        self.control.request_redraw();
+        *self.cursor_image = image;
+    }
+
+    /// Convert position in the additional `window` into its render surface space.
+    /// The `Ok` result means that the position is inside the surface, `Err` otherwise.
+    /// Returns `None` if the window has no render surface.
+    pub fn window_position_into_render_surface_space(
+        &self,
+        window: WindowToken,
+        position: (f32, f32),
+    ) -> Option<Result<(i32, i32), (i32, i32)>> {
+        let layout = self
+            .window_layouts
+            .iter()
+            .find(|layout| layout.token == window)?;
+        Some(surface_position(
+            position,
+            layout.dimensions,
+            layout.resolution,
+            SafeArea::default(),
+            Viewport::default(),
+            ScaleMode::default(),
+            false,
+        ))
+    }
 }
 
 impl<'a, Input> Context<'a, Input> for SoftContext<'a, Input>
@@ -342,6 +1677,7 @@ where
 {
     fn drop(&mut self) {
         self.input.tick();
+        self.window_events.clear();
     }
 }
 
@@ -351,12 +1687,22 @@ pub struct SoftRenderTarget<'a, RenderSurface> {
     buffer_dimensions: (usize, usize),
     render_surface: &'a mut RenderSurface,
     buffer: Buf<'a>,
+    latency: Option<&'a mut LatencyRecorder>,
+    adjustment: &'a ColorAdjustment,
+    safe_area: SafeArea,
+    viewport: Viewport,
+    scale_mode: ScaleMode,
+    magnifier: Option<&'a Magnifier>,
+    frame: Option<&'a mut Frame>,
+    cursor: Option<(&'a CursorImage, (i32, i32))>,
+    presented: &'a mut Option<Presented>,
 }
 
 impl<'a, RenderSurface, Converter> RenderTarget<Converter> for SoftRenderTarget<'a, RenderSurface>
 where
-    RenderSurface: devotee_backend::RenderSurface,
-    Converter: devotee_backend::Converter<Data = RenderSurface::Data>,
+    RenderSurface: devotee_backend::RenderSurface + MaybeSync,
+    RenderSurface::Data: Clone,
+    Converter: devotee_backend::Converter<Data = RenderSurface::Data> + MaybeSync,
 {
     type RenderSurface = RenderSurface;
     type PresentError = SoftBufferError;
@@ -372,22 +1718,98 @@ where
     fn present(mut self, converter: Converter) -> Result<(), Self::PresentError> {
         let render_surface_dimensions = (self.render_surface.width(), self.render_surface.height());
 
-        let scale_x = self.buffer_dimensions.0 / render_surface_dimensions.0;
-        let scale_y = self.buffer_dimensions.1 / render_surface_dimensions.1;
+        let placement = placement(
+            (
+                self.buffer_dimensions.0 as u32,
+                self.buffer_dimensions.1 as u32,
+            ),
+            (
+                render_surface_dimensions.0 as u32,
+                render_surface_dimensions.1 as u32,
+            ),
+            self.safe_area,
+            self.viewport,
+            self.scale_mode,
+        );
 
-        let minimal_scale = scale_x.min(scale_y);
+        let presented = Presented {
+            buffer_dimensions: self.buffer_dimensions,
+            placement,
+            background_color: self.background_color,
+            adjustment: self.adjustment.clone(),
+        };
+        let flash_color = self.latency.as_ref().and_then(|l| l.flash_color());
+        // The buffer keeps the previous frame only if it was presented last with the same settings.
+        let partial = self.buffer.age() == 1
+            && self.presented.as_ref() == Some(&presented)
+            && !self.magnifier.is_some_and(Magnifier::is_active)
+            && self.cursor.is_none()
+            && flash_color.is_none();
+        let damage = if partial {
+            self.render_surface.damage()
+        } else {
+            Damage::Full
+        };
+        let region = damage.region(render_surface_dimensions);
 
-        self.buffer.fill(self.background_color);
-        if minimal_scale >= 1 {
-            let start_x =
-                (self.buffer_dimensions.0 - render_surface_dimensions.0 * minimal_scale) / 2;
-            let start_y =
-                (self.buffer_dimensions.1 - render_surface_dimensions.1 * minimal_scale) / 2;
+        let adjustment = (!self.adjustment.is_identity()).then_some(self.adjustment);
+        if damage == Damage::Full {
+            self.buffer.fill(self.background_color);
+        }
+        if let Some(frame) = &mut self.frame {
+            frame.resize(render_surface_dimensions.0, render_surface_dimensions.1);
+        }
+        let mut damage_rects = Vec::new();
+        if let (Some(((start_x, start_y), minimal_scale)), Some((start, end))) = (placement, region)
+        {
+            let (start_x, start_y) = (start_x as usize, start_y as usize);
+            let minimal_scale = minimal_scale as usize;
+            if let (Some(width), Some(height)) = (
+                NonZeroU32::new(((end.0 - start.0) * minimal_scale) as u32),
+                NonZeroU32::new(((end.1 - start.1) * minimal_scale) as u32),
+            ) {
+                damage_rects.push(Rect {
+                    x: (start_x + start.0 * minimal_scale) as u32,
+                    y: (start_y + start.1 * minimal_scale) as u32,
+                    width,
+                    height,
+                });
+            }
 
-            for y in 0..render_surface_dimensions.1 {
-                for x in 0..render_surface_dimensions.0 {
-                    let pixel_color = self.render_surface.data(x, y);
-                    let pixel_value = converter.convert(x, y, pixel_color);
+            let region_width = end.0 - start.0;
+            let converted = (!self.magnifier.is_some_and(Magnifier::is_active)).then(|| {
+                let mut converted = vec![0; region_width * (end.1 - start.1)];
+                convert_region(
+                    &*self.render_surface,
+                    &converter,
+                    start,
+                    end,
+                    &mut converted,
+                );
+                converted
+            });
+
+            for y in start.1..end.1 {
+                for x in start.0..end.0 {
+                    let pixel_value = match &converted {
+                        Some(converted) => converted[(x - start.0) + (y - start.1) * region_width],
+                        None => {
+                            let (source_x, source_y) = self.magnifier.map_or((x, y), |magnifier| {
+                                magnifier.source((x, y), render_surface_dimensions)
+                            });
+                            let pixel_color = self.render_surface.data(source_x, source_y);
+                            converter.convert(source_x, source_y, pixel_color)
+                        }
+                    };
+                    let pixel_value =
+                        adjustment.map_or(pixel_value, |adjustment| adjustment.apply(pixel_value));
+                    if let Some(frame) = &mut self.frame {
+                        frame.set(x, y, pixel_value);
+                    }
+                    let pixel_value = self
+                        .cursor
+                        .and_then(|(image, cursor)| image.pixel_at(cursor, (x, y)))
+                        .unwrap_or(pixel_value);
                     for iy in 0..minimal_scale {
                         let index = (start_x + x * minimal_scale)
                             + (iy + start_y + y * minimal_scale) * self.buffer_dimensions.0;
@@ -397,16 +1819,50 @@ where
             }
         }
 
-        self.buffer.present()
+        if let Some(flash_color) = flash_color {
+            self.buffer.fill(flash_color);
+        }
+
+        if damage == Damage::Full {
+            self.buffer.present()?;
+        } else {
+            self.buffer.present_with_damage(&damage_rects)?;
+        }
+        *self.presented = flash_color.is_none().then_some(presented);
+        self.render_surface.clear_damage();
+        if let Some(latency) = self.latency {
+            latency.register_present(Instant::now());
+        }
+        Ok(())
     }
 }
 
 /// Default Control instance for the Softbuffer backend.
 pub struct SoftControl {
     should_quit: bool,
+    paused: Option<bool>,
+    redraw_requested: bool,
+    pointer_captured: bool,
+    motion: CursorMotion,
+    present_stats: PresentStats,
+    tick: u64,
+    savestate: Option<SavestateRequest>,
+    savestates: Savestates,
+    fixed_timestep: bool,
+    max_fps: Option<u32>,
+    next_window: u64,
+    windows: Vec<WindowToken>,
+    window_requests: Vec<WindowRequest>,
+    window_sized: bool,
     window: Rc<Window>,
 }
 
+/// Request to open or close the additional window, processed by the backend after the current event.
+enum WindowRequest {
+    Open(WindowToken, WindowConfig),
+    Close(WindowToken),
+}
+
 impl SoftControl {
     /// Tell backend to shut down.
     pub fn shutdown(&mut self) -> &mut Self {
@@ -414,16 +1870,155 @@ impl SoftControl {
         self
     }
 
+    /// Keep reporting cursor movement even when the cursor leaves the window, e.g. during drag operations.
+    /// Positions of the captured pointer outside of the render surface are not clamped to its edges.
+    pub fn capture_pointer(&mut self) -> &mut Self {
+        self.pointer_captured = true;
+        self
+    }
+
+    /// Stop capturing the pointer.
+    pub fn release_pointer(&mut self) -> &mut Self {
+        self.pointer_captured = false;
+        self
+    }
+
+    /// Check if the pointer is captured.
+    pub fn is_pointer_captured(&self) -> bool {
+        self.pointer_captured
+    }
+
+    /// Get cursor motion aggregated since the last update.
+    pub fn cursor_motion(&self) -> &CursorMotion {
+        &self.motion
+    }
+
+    /// Get mutable reference to the cursor motion aggregate, e.g. to enable positions recording.
+    pub fn cursor_motion_mut(&mut self) -> &mut CursorMotion {
+        &mut self.motion
+    }
+
+    /// Request redraw of the window.
+    /// Redraws happen regardless of requests unless the render-on-demand mode is enabled.
+    pub fn request_redraw(&mut self) -> &mut Self {
+        self.redraw_requested = true;
+        self
+    }
+
+    /// Get statistics of presented and dropped frames.
+    pub fn present_stats(&self) -> &PresentStats {
+        &self.present_stats
+    }
+
+    /// Get amount of updates performed, restored along with savestates.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Save the application state into the savestate `slot` after the current update or event.
+    /// Requires the application to implement `Application::save_state`.
+    pub fn save_state(&mut self, slot: usize) -> &mut Self {
+        self.savestate = Some(SavestateRequest::Save(slot));
+        self
+    }
+
+    /// Load the application state from the savestate `slot` after the current update or event.
+    /// Requires the application to implement `Application::load_state`.
+    pub fn load_state(&mut self, slot: usize) -> &mut Self {
+        self.savestate = Some(SavestateRequest::Load(slot));
+        self
+    }
+
+    /// Get savestates taken so far.
+    pub fn savestates(&self) -> &Savestates {
+        &self.savestates
+    }
+
+    /// Get mutable reference to the savestates, e.g. to persist or import them.
+    pub fn savestates_mut(&mut self) -> &mut Savestates {
+        &mut self.savestates
+    }
+
+    /// Open additional window with the `config` after the current update or event.
+    /// Its events and rendering are passed to the middleware along with the returned token.
+    pub fn create_window(&mut self, config: WindowConfig) -> WindowToken {
+        self.next_window += 1;
+        let token = WindowToken::new(self.next_window);
+        self.windows.push(token);
+        self.window_requests
+            .push(WindowRequest::Open(token, config));
+        token
+    }
+
+    /// Close the additional `window` after the current update or event.
+    pub fn close_window(&mut self, window: WindowToken) -> &mut Self {
+        if let Some(index) = self.windows.iter().position(|token| *token == window) {
+            self.windows.remove(index);
+            self.window_requests.push(WindowRequest::Close(window));
+        }
+        self
+    }
+
+    /// Get tokens of the open additional windows.
+    pub fn windows(&self) -> &[WindowToken] {
+        &self.windows
+    }
+
+    fn set_paused(&mut self, paused: bool) -> &mut Self {
+        self.paused = Some(paused);
+        self
+    }
+
     /// Get reference to the underlying window.
     pub fn window_ref(&self) -> &Window {
         &self.window
     }
+
+    /// Show or hide the system cursor over the window.
+    pub fn set_cursor_visible(&mut self, visible: bool) -> &mut Self {
+        self.window.set_cursor_visible(visible);
+        self
+    }
+
+    /// Confine or lock the cursor to the window.
+    /// Not every platform supports every mode.
+    pub fn set_cursor_grab(&mut self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+        self.window.set_cursor_grab(mode)
+    }
+
+    /// Set the system cursor icon.
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) -> &mut Self {
+        self.window.set_cursor_icon(icon);
+        self
+    }
+
+    /// Start moving the window with the left mouse button until it is released.
+    pub fn start_drag(&mut self) -> Result<(), ExternalError> {
+        self.window.drag_window()
+    }
+
+    /// Start resizing the window with the left mouse button until it is released.
+    pub fn start_resize(&mut self, direction: ResizeDirection) -> Result<(), ExternalError> {
+        self.window.drag_resize_window(direction)
+    }
 }
 
 /// Default Event Context for the Softbuffer backend.
 pub struct SoftEventContext<'a> {
     window: &'a Window,
+    pointer_captured: bool,
+    dimensions: (u32, u32),
     resolution: (u32, u32),
+    safe_area: SafeArea,
+    viewport: Viewport,
+    scale_mode: ScaleMode,
+}
+
+impl<'a> SoftEventContext<'a> {
+    /// Get reference to the underlying window.
+    pub fn window_ref(&self) -> &'a Window {
+        self.window
+    }
 }
 
 impl<'a> EventContext for SoftEventContext<'a> {
@@ -431,37 +2026,72 @@ impl<'a> EventContext for SoftEventContext<'a> {
         &self,
         position: (f32, f32),
     ) -> Result<(i32, i32), (i32, i32)> {
-        let size = self.window.inner_size();
-        let scale_x = size.width / self.resolution.0;
-        let scale_y = size.height / self.resolution.1;
+        surface_position(
+            position,
+            self.dimensions,
+            self.resolution,
+            self.safe_area,
+            self.viewport,
+            self.scale_mode,
+            self.pointer_captured,
+        )
+    }
+}
+
+fn surface_position(
+    position: (f32, f32),
+    dimensions: (u32, u32),
+    resolution: (u32, u32),
+    safe_area: SafeArea,
+    viewport: Viewport,
+    scale_mode: ScaleMode,
+    pointer_captured: bool,
+) -> Result<(i32, i32), (i32, i32)> {
+    let (width, height) = (resolution.0 as i32, resolution.1 as i32);
+    let placement = placement(dimensions, resolution, safe_area, viewport, scale_mode);
+    let ((start_x, start_y), scale) = match placement {
+        Some(((start_x, start_y), scale)) => ((start_x as i32, start_y as i32), scale as i32),
+        None => {
+            // The surface is smaller than the resolution, place it without scaling.
+            let area = safe_area.apply(dimensions);
+            (viewport.origin(area, resolution), 1)
+        }
+    };
 
-        let minimal_scale = scale_x.min(scale_y);
+    let position = (
+        (position.0.floor() as i32 - start_x).div_euclid(scale),
+        (position.1.floor() as i32 - start_y).div_euclid(scale),
+    );
 
-        if minimal_scale < 1 {
-            Err((0, 0))
-        } else {
-            let position = (position.0 as i32, position.1 as i32);
-            let start_x = ((size.width - self.resolution.0 * minimal_scale) / 2) as i32;
-            let start_y = ((size.height - self.resolution.1 * minimal_scale) / 2) as i32;
-
-            let position = (
-                (position.0 - start_x) / minimal_scale as i32,
-                (position.1 - start_y) / minimal_scale as i32,
-            );
-
-            if position.0 < 0
-                || position.0 >= self.resolution.0 as i32
-                || position.1 < 0
-                || position.1 >= self.resolution.1 as i32
-            {
-                Err(position)
-            } else {
-                Ok(position)
-            }
+    if position.0 < 0 || position.0 >= width || position.1 < 0 || position.1 >= height {
+        if pointer_captured {
+            return Err(position);
         }
+        Err((
+            position.0.clamp(0, (width - 1).max(0)),
+            position.1.clamp(0, (height - 1).max(0)),
+        ))
+    } else {
+        Ok(position)
     }
 }
 
+fn placement(
+    window: (u32, u32),
+    resolution: (u32, u32),
+    safe_area: SafeArea,
+    viewport: Viewport,
+    scale_mode: ScaleMode,
+) -> Option<((u32, u32), u32)> {
+    let area = safe_area.apply(window);
+    let scale = scale_mode.scale(area.1, resolution);
+    (scale >= 1).then(|| {
+        let (start_x, start_y) =
+            viewport.origin(area, (resolution.0 * scale, resolution.1 * scale));
+        ((start_x as u32, start_y as u32), scale)
+    })
+}
+
 /// Softbuffer backend error enumeration.
 #[derive(Debug)]
 pub enum Error {
@@ -476,6 +2106,9 @@ pub enum Error {
 
     /// Window resolution retrieval error.
     WindowResolutionError(TryFromIntError),
+
+    /// Window icon creation error.
+    WinitIconError(BadIcon),
 }
 
 impl From<EventLoopError> for Error {
@@ -496,6 +2129,12 @@ impl From<SoftBufferError> for Error {
     }
 }
 
+impl From<BadIcon> for Error {
+    fn from(value: BadIcon) -> Self {
+        Self::WinitIconError(value)
+    }
+}
+
 impl From<TryFromIntError> for Error {
     fn from(value: TryFromIntError) -> Self {
         Self::WindowResolutionError(value)
@@ -0,0 +1,584 @@
+#![deny(missing_docs)]
+
+//! Terminal backend for the devotee project.
+//!
+//! Renders with ANSI 24-bit colors, packing two pixels into a single half-block character cell,
+//! and reads keyboard events from the terminal, so applications can run with no window system.
+
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::{cursor, execute, terminal};
+use devotee_backend::{
+    Application, Context, Converter, EventCategories, EventContext, Middleware, RenderSurface,
+    RenderTarget, ResizeRenderSurface,
+};
+
+pub use crossterm;
+
+/// Maximal amount of fixed timestep updates performed to catch up before a single frame.
+const MAX_FIXED_STEPS: u32 = 8;
+
+/// Backend rendering into the terminal.
+pub struct TermBackend {
+    title: String,
+}
+
+impl TermBackend {
+    /// Create new backend instance with desired terminal title.
+    pub fn try_new(title: &str) -> Result<Self, Error> {
+        terminal::size()?;
+        let title = title.to_owned();
+        Ok(Self { title })
+    }
+}
+
+impl TermBackend {
+    /// Run this backend to completion.
+    pub fn run<App, Mid, Rend, Data, Conv>(
+        self,
+        app: App,
+        middleware: Mid,
+        update_delay: Duration,
+    ) -> Result<(), Error>
+    where
+        App: for<'a> Application<
+            'a,
+            <Mid as Middleware<'a, TermControl>>::Init,
+            <Mid as Middleware<'a, TermControl>>::Context,
+            Rend,
+            Conv,
+        >,
+        Mid: for<'a> Middleware<
+            'a,
+            TermControl,
+            Event = Event,
+            EventContext = &'a TermSurface,
+            Surface = &'a mut TermSurface,
+            RenderTarget = TermRenderTarget<'a, Rend>,
+        >,
+        Rend: RenderSurface<Data = Data>,
+        Conv: Converter<Data = Data>,
+    {
+        let mut app = app;
+        let mut middleware = middleware;
+
+        let _session = Session::start(&self.title)?;
+        let mut surface = TermSurface::new(terminal::size()?);
+
+        let mut control = TermControl {
+            should_quit: false,
+            fixed_timestep: false,
+        };
+        let init = middleware.init(&mut control);
+        app.init(init);
+        let fixed_timestep = control.fixed_timestep;
+
+        let event_interest = middleware.event_interest();
+        let mut next_update = Instant::now() + update_delay;
+
+        while !control.should_quit {
+            let timeout = next_update.saturating_duration_since(Instant::now());
+            if event::poll(timeout)? {
+                let event = event::read()?;
+                if let Event::Resize(columns, rows) = event {
+                    surface.resize((columns, rows));
+                }
+                if event_interest.contains(event_category(&event)) {
+                    middleware.handle_event(event, &surface, &mut control);
+                }
+                if Instant::now() < next_update {
+                    continue;
+                }
+            }
+
+            let now = Instant::now();
+            let mut steps = 0;
+            while !fixed_timestep || now >= next_update {
+                let context = middleware.update(&mut control, update_delay);
+                app.update(context);
+                next_update += update_delay;
+                steps += 1;
+                if !fixed_timestep || control.should_quit {
+                    break;
+                }
+                if steps >= MAX_FIXED_STEPS {
+                    // Drop the backlog instead of falling further behind.
+                    next_update = now + update_delay;
+                    break;
+                }
+            }
+
+            let mut render_target = middleware.render(&mut surface);
+            let render_surface =
+                <TermRenderTarget<'_, Rend> as RenderTarget<Conv>>::render_surface_mut(
+                    &mut render_target,
+                );
+            if fixed_timestep {
+                let remaining = next_update.saturating_duration_since(Instant::now());
+                app.interpolate(interpolation(remaining, update_delay));
+            }
+            app.render(render_surface);
+            devotee_backend::RenderTarget::present(render_target, app.converter())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Terminal state for the backend run duration, restored on drop.
+struct Session {
+    enhanced: bool,
+}
+
+impl Session {
+    fn start(title: &str) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+        let session = Session { enhanced };
+        let mut stdout = io::stdout();
+        execute!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            terminal::SetTitle(title),
+            cursor::Hide
+        )?;
+        if enhanced {
+            execute!(
+                stdout,
+                PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+            )?;
+        }
+        Ok(session)
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        let mut stdout = io::stdout();
+        if self.enhanced {
+            let _ = execute!(stdout, PopKeyboardEnhancementFlags);
+        }
+        let _ = execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn event_category(event: &Event) -> EventCategories {
+    match event {
+        Event::Key(_) | Event::Paste(_) => EventCategories::KEYBOARD,
+        Event::Mouse(_) => EventCategories::MOUSE,
+        _ => EventCategories::NONE,
+    }
+}
+
+/// Default Middleware for the terminal backend.
+pub struct TermMiddleware<RenderSurface, Input> {
+    background_color: u32,
+    render_surface: RenderSurface,
+    input: Input,
+    event_interest: EventCategories,
+}
+
+impl<RenderSurface, Input> TermMiddleware<RenderSurface, Input>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+{
+    /// Create new middleware instance with desired render surface and input handler.
+    pub fn new(render_surface: RenderSurface, input: Input) -> Self {
+        let background_color = 0;
+        let event_interest = EventCategories::ALL;
+        Self {
+            background_color,
+            render_surface,
+            input,
+            event_interest,
+        }
+    }
+
+    /// Set background color for the unoccupied space.
+    pub fn with_background_color(self, background_color: u32) -> Self {
+        Self {
+            background_color,
+            ..self
+        }
+    }
+
+    /// Set categories of events to be handled.
+    /// Events of other categories skip the input handling completely.
+    pub fn with_event_interest(self, event_interest: EventCategories) -> Self {
+        Self {
+            event_interest,
+            ..self
+        }
+    }
+}
+
+impl<'a, RenderSurface, Input> Middleware<'a, TermControl> for TermMiddleware<RenderSurface, Input>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+    RenderSurface: 'a,
+    Input: 'a + devotee_backend::Input<'a, TermEventContext<'a>, Event = Event>,
+{
+    type Event = Event;
+    type EventContext = &'a TermSurface;
+    type Surface = &'a mut TermSurface;
+    type Init = TermInit<'a>;
+    type Context = TermContext<'a, Input>;
+    type RenderTarget = TermRenderTarget<'a, RenderSurface>;
+
+    fn init(&'a mut self, control: &'a mut TermControl) -> Self::Init {
+        TermInit { control }
+    }
+
+    fn update(&'a mut self, control: &'a mut TermControl, delta: Duration) -> Self::Context {
+        let input = &mut self.input;
+        let render_surface = &mut self.render_surface;
+        TermContext {
+            control,
+            delta,
+            input,
+            render_surface,
+        }
+    }
+
+    fn event_interest(&self) -> EventCategories {
+        self.event_interest
+    }
+
+    fn handle_event(
+        &mut self,
+        event: Self::Event,
+        event_context: Self::EventContext,
+        control: &mut TermControl,
+    ) -> Option<Self::Event> {
+        let context = TermEventContext {
+            surface: event_context,
+            resolution: (
+                self.render_surface.width() as u32,
+                self.render_surface.height() as u32,
+            ),
+        };
+
+        // Raw mode disables the interrupt signal, so treat Ctrl+C as a close request.
+        if let Event::Key(KeyEvent {
+            code: KeyCode::Char('c'),
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event
+        {
+            if modifiers.contains(KeyModifiers::CONTROL) {
+                control.shutdown();
+            }
+        }
+
+        self.input.handle_event(event, &context)
+    }
+
+    fn render(&'a mut self, surface: Self::Surface) -> Self::RenderTarget {
+        let background_color = self.background_color;
+        let render_surface = &mut self.render_surface;
+        TermRenderTarget {
+            background_color,
+            render_surface,
+            surface,
+        }
+    }
+}
+
+/// Default Init for the terminal backend.
+pub struct TermInit<'a> {
+    control: &'a mut TermControl,
+}
+
+impl<'a> TermInit<'a> {
+    /// Get reference to `TermControl`.
+    pub fn control(&self) -> &TermControl {
+        self.control
+    }
+
+    /// Get mutable reference to `TermControl`.
+    pub fn control_mut(&mut self) -> &mut TermControl {
+        self.control
+    }
+
+    /// Enable or disable fixed timestep updates.
+    /// When enabled, the backend performs as many updates with the fixed delta as needed to catch up
+    /// with the wall clock and passes the interpolation factor to the application before rendering.
+    pub fn set_fixed_timestep(&mut self, fixed_timestep: bool) {
+        self.control.fixed_timestep = fixed_timestep;
+    }
+}
+
+/// Default Context for the terminal backend.
+pub struct TermContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, TermEventContext<'a>>,
+{
+    control: &'a mut TermControl,
+    input: &'a mut Input,
+    delta: Duration,
+    render_surface: &'a mut dyn ResizeRenderSurface,
+}
+
+impl<'a, Input> TermContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, TermEventContext<'a>>,
+{
+    /// Get reference to `TermControl`.
+    pub fn control(&self) -> &TermControl {
+        self.control
+    }
+
+    /// Get mutable reference to `TermControl`.
+    pub fn control_mut(&mut self) -> &mut TermControl {
+        self.control
+    }
+
+    /// Resize the render surface, keeping its contents in the overlapping area if `preserve` is set.
+    /// Return `false` if the render surface does not support resizing.
+    pub fn resize_render_target(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        self.render_surface.resize(width, height, preserve)
+    }
+}
+
+impl<'a, Input> Context<'a, Input> for TermContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, TermEventContext<'a>>,
+{
+    fn input(&self) -> &Input {
+        self.input
+    }
+
+    fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    fn shutdown(&mut self) {
+        self.control.shutdown();
+    }
+}
+
+impl<'a, Input> Drop for TermContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, TermEventContext<'a>>,
+{
+    fn drop(&mut self) {
+        self.input.tick();
+    }
+}
+
+/// Terminal pixel buffer.
+/// Each character cell holds two pixels stacked vertically.
+pub struct TermSurface {
+    dimensions: (usize, usize),
+    pixels: Vec<u32>,
+    previous: Vec<u32>,
+    output: Vec<u8>,
+}
+
+impl TermSurface {
+    fn new(size: (u16, u16)) -> Self {
+        let mut surface = Self {
+            dimensions: (0, 0),
+            pixels: Vec::new(),
+            previous: Vec::new(),
+            output: Vec::new(),
+        };
+        surface.resize(size);
+        surface
+    }
+
+    /// Get dimensions of the surface in pixels.
+    pub fn dimensions(&self) -> (usize, usize) {
+        self.dimensions
+    }
+
+    fn resize(&mut self, (columns, rows): (u16, u16)) {
+        self.dimensions = (columns as usize, rows as usize * 2);
+        let length = self.dimensions.0 * self.dimensions.1;
+        self.pixels = vec![0; length];
+        // Nothing matches the previous frame, so everything is redrawn.
+        self.previous = vec![u32::MAX; length];
+    }
+
+    fn fill(&mut self, color: u32) {
+        self.pixels.fill(color);
+    }
+
+    fn present(&mut self) -> io::Result<()> {
+        let width = self.dimensions.0;
+        self.output.clear();
+        for row in 0..self.dimensions.1 / 2 {
+            let cells = 2 * row * width..2 * (row + 1) * width;
+            if self.pixels[cells.clone()] == self.previous[cells] {
+                continue;
+            }
+            write!(self.output, "\x1b[{};1H", row + 1)?;
+            let (mut foreground, mut background) = (None, None);
+            for column in 0..width {
+                let top = self.pixels[2 * row * width + column] & 0xffffff;
+                let bottom = self.pixels[(2 * row + 1) * width + column] & 0xffffff;
+                if foreground != Some(top) {
+                    let [_, r, g, b] = top.to_be_bytes();
+                    write!(self.output, "\x1b[38;2;{};{};{}m", r, g, b)?;
+                    foreground = Some(top);
+                }
+                if background != Some(bottom) {
+                    let [_, r, g, b] = bottom.to_be_bytes();
+                    write!(self.output, "\x1b[48;2;{};{};{}m", r, g, b)?;
+                    background = Some(bottom);
+                }
+                self.output.extend_from_slice("▀".as_bytes());
+            }
+        }
+        if !self.output.is_empty() {
+            self.output.extend_from_slice(b"\x1b[0m");
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(&self.output)?;
+            stdout.flush()?;
+        }
+        self.previous.copy_from_slice(&self.pixels);
+        Ok(())
+    }
+}
+
+/// Default Render Target for the terminal backend.
+pub struct TermRenderTarget<'a, RenderSurface> {
+    background_color: u32,
+    render_surface: &'a mut RenderSurface,
+    surface: &'a mut TermSurface,
+}
+
+impl<'a, RenderSurface, Converter> RenderTarget<Converter> for TermRenderTarget<'a, RenderSurface>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+    Converter: devotee_backend::Converter<Data = RenderSurface::Data>,
+{
+    type RenderSurface = RenderSurface;
+    type PresentError = io::Error;
+
+    fn render_surface(&self) -> &Self::RenderSurface {
+        self.render_surface
+    }
+
+    fn render_surface_mut(&mut self) -> &mut Self::RenderSurface {
+        self.render_surface
+    }
+
+    fn present(self, converter: Converter) -> Result<(), Self::PresentError> {
+        let resolution = (self.render_surface.width(), self.render_surface.height());
+        let surface_dimensions = self.surface.dimensions();
+
+        self.surface.fill(self.background_color);
+        if let Some(((start_x, start_y), (width, height), scale)) =
+            placement(surface_dimensions, resolution)
+        {
+            for y in 0..height {
+                let source_y = ((y as f32 / scale) as usize).min(resolution.1 - 1);
+                for x in 0..width {
+                    let source_x = ((x as f32 / scale) as usize).min(resolution.0 - 1);
+                    let pixel_color = self.render_surface.data(source_x, source_y);
+                    let pixel_value = converter.convert(source_x, source_y, pixel_color);
+                    let index = start_x + x + (start_y + y) * surface_dimensions.0;
+                    self.surface.pixels[index] = pixel_value;
+                }
+            }
+        }
+
+        self.surface.present()
+    }
+}
+
+/// Default Control instance for the terminal backend.
+pub struct TermControl {
+    should_quit: bool,
+    fixed_timestep: bool,
+}
+
+impl TermControl {
+    /// Tell backend to shut down.
+    pub fn shutdown(&mut self) -> &mut Self {
+        self.should_quit = true;
+        self
+    }
+}
+
+/// Default Event Context for the terminal backend.
+pub struct TermEventContext<'a> {
+    surface: &'a TermSurface,
+    resolution: (u32, u32),
+}
+
+impl<'a> EventContext for TermEventContext<'a> {
+    /// The `position` is measured in terminal pixels, two of them per character cell.
+    fn position_into_render_surface_space(
+        &self,
+        position: (f32, f32),
+    ) -> Result<(i32, i32), (i32, i32)> {
+        let resolution = (self.resolution.0 as usize, self.resolution.1 as usize);
+        if let Some(((start_x, start_y), _, scale)) =
+            placement(self.surface.dimensions(), resolution)
+        {
+            let position = (
+                ((position.0 - start_x as f32) / scale).floor() as i32,
+                ((position.1 - start_y as f32) / scale).floor() as i32,
+            );
+
+            if position.0 < 0
+                || position.0 >= self.resolution.0 as i32
+                || position.1 < 0
+                || position.1 >= self.resolution.1 as i32
+            {
+                Err(position)
+            } else {
+                Ok(position)
+            }
+        } else {
+            Err((0, 0))
+        }
+    }
+}
+
+/// Origin, dimensions and scale of the render surface on the terminal surface.
+type Placement = ((usize, usize), (usize, usize), f32);
+
+/// Get placement of the render surface on the terminal surface.
+/// Render surfaces larger than the terminal are scaled down to fit it.
+fn interpolation(remaining: Duration, update_delay: Duration) -> f32 {
+    (1.0 - remaining.as_secs_f32() / update_delay.as_secs_f32()).clamp(0.0, 1.0)
+}
+
+fn placement(surface: (usize, usize), resolution: (usize, usize)) -> Option<Placement> {
+    if resolution.0 == 0 || resolution.1 == 0 {
+        return None;
+    }
+    let scale =
+        (surface.0 as f32 / resolution.0 as f32).min(surface.1 as f32 / resolution.1 as f32);
+    let scale = if scale >= 1.0 { scale.floor() } else { scale };
+    let width = ((resolution.0 as f32 * scale) as usize).min(surface.0);
+    let height = ((resolution.1 as f32 * scale) as usize).min(surface.1);
+    (width > 0 && height > 0).then(|| {
+        let start_x = (surface.0 - width) / 2;
+        let start_y = (surface.1 - height) / 2;
+        ((start_x, start_y), (width, height), scale)
+    })
+}
+
+/// Terminal backend error enumeration.
+#[derive(Debug)]
+pub enum Error {
+    /// Terminal input or output error.
+    IoError(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
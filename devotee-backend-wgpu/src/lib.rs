@@ -0,0 +1,1438 @@
+#![deny(missing_docs)]
+
+//! [Wgpu](https://crates.io/crates/wgpu)-based hardware-accelerated backend for the devotee project.
+//!
+//! The render surface is uploaded as a texture and scaled with letterboxing on the GPU.
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use devotee_backend::adjustment::ColorAdjustment;
+use devotee_backend::capture::Frame;
+#[cfg(feature = "screenshot")]
+use devotee_backend::capture::ScreenshotError;
+use devotee_backend::diagnostics::{LatencyRecorder, LatencyReport};
+use devotee_backend::magnifier::{Magnifier, MagnifierControls};
+use devotee_backend::motion::CursorMotion;
+use devotee_backend::pointer::PointerTracker;
+use devotee_backend::winit_event::{event_category, is_press};
+use devotee_backend::{
+    Application, BackendRequest, Context, Converter, EventCategories, EventContext, Middleware,
+    PresentMode, RenderSurface, RenderTarget, ResizeRenderSurface, SafeArea,
+};
+use wgpu::{
+    CreateSurfaceError, Device, Queue, RenderPipeline, RequestDeviceError, Sampler, Surface,
+    SurfaceConfiguration, SurfaceError, TextureFormat,
+};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::error::{EventLoopError, ExternalError, OsError};
+use winit::event::{DeviceEvent, ElementState, Event, MouseButton, MouseScrollDelta, WindowEvent};
+use winit::event_loop::{ControlFlow, DeviceEvents, EventLoop, EventLoopBuilder, EventLoopProxy};
+use winit::keyboard::{KeyCode, ModifiersState, PhysicalKey};
+use winit::window::{ResizeDirection, Window, WindowBuilder};
+
+pub use winit;
+
+/// Maximal amount of fixed timestep updates performed to catch up before a single frame.
+const MAX_FIXED_STEPS: u32 = 8;
+
+/// Backend based on the [Wgpu](https://crates.io/crates/wgpu) project.
+pub struct WgpuBackend {
+    window: Rc<Window>,
+    event_loop: EventLoop<BackendRequest>,
+    present_mode: PresentMode,
+    device_events: Option<DeviceEvents>,
+    render_on_demand: bool,
+    coalesce_cursor: bool,
+}
+
+impl WgpuBackend {
+    /// Create new backend instance with desired window title.
+    pub fn try_new(title: &str) -> Result<Self, Error> {
+        let event_loop = EventLoopBuilder::with_user_event().build()?;
+        let window = Rc::new(WindowBuilder::new().with_title(title).build(&event_loop)?);
+        let present_mode = PresentMode::default();
+        let device_events = None;
+        let render_on_demand = false;
+        let coalesce_cursor = true;
+        Ok(Self {
+            window,
+            event_loop,
+            present_mode,
+            device_events,
+            render_on_demand,
+            coalesce_cursor,
+        })
+    }
+
+    /// Set frame presentation mode.
+    /// In the `Sync` mode redraws are paced by the vertical synchronization.
+    pub fn with_present_mode(self, present_mode: PresentMode) -> Self {
+        Self {
+            present_mode,
+            ..self
+        }
+    }
+
+    /// Set device events listening policy.
+    /// By default device events are listened to only if the middleware is interested in device motion.
+    pub fn with_device_events(self, device_events: DeviceEvents) -> Self {
+        Self {
+            device_events: Some(device_events),
+            ..self
+        }
+    }
+
+    /// Enable or disable render-on-demand mode.
+    /// In this mode redraws happen only when requested with `WgpuControl::request_redraw`,
+    /// and the backend stops waking up for updates until the next event
+    /// if an update tick neither received events nor requested a redraw.
+    pub fn with_render_on_demand(self, render_on_demand: bool) -> Self {
+        Self {
+            render_on_demand,
+            ..self
+        }
+    }
+
+    /// Enable or disable coalescing of cursor movement events.
+    /// When enabled, only the latest cursor movement is passed to the middleware
+    /// before any other event or the next update.
+    /// Every movement is still registered in the control `CursorMotion`.
+    /// Enabled by default.
+    pub fn with_cursor_coalescing(self, coalesce_cursor: bool) -> Self {
+        Self {
+            coalesce_cursor,
+            ..self
+        }
+    }
+
+    /// Get proxy sending requests to the event loop from other threads,
+    /// e.g. to activate the window on launching another application instance.
+    pub fn event_proxy(&self) -> EventLoopProxy<BackendRequest> {
+        self.event_loop.create_proxy()
+    }
+}
+
+impl WgpuBackend {
+    /// Run this backend to completion.
+    pub fn run<App, Mid, Rend, Data, Conv>(
+        self,
+        app: App,
+        middleware: Mid,
+        update_delay: Duration,
+    ) -> Result<(), Error>
+    where
+        App: for<'a> Application<
+            'a,
+            <Mid as Middleware<'a, WgpuControl>>::Init,
+            <Mid as Middleware<'a, WgpuControl>>::Context,
+            Rend,
+            Conv,
+        >,
+        Mid: for<'a> Middleware<
+            'a,
+            WgpuControl,
+            Event = WindowEvent,
+            EventContext = &'a Window,
+            Surface = &'a mut WgpuSurface,
+            RenderTarget = WgpuRenderTarget<'a, Rend>,
+        >,
+        Rend: RenderSurface<Data = Data>,
+        Conv: Converter<Data = Data>,
+    {
+        let mut app = app;
+        let mut middleware = middleware;
+
+        let window = self.window;
+
+        let mut control = WgpuControl {
+            should_quit: false,
+            redraw_requested: false,
+            pointer_captured: false,
+            motion: CursorMotion::new(),
+            fixed_timestep: false,
+            window: window.clone(),
+        };
+        let init = middleware.init(&mut control);
+        app.init(init);
+        let fixed_timestep = control.fixed_timestep;
+
+        let present_mode = self.present_mode;
+        let mut surface = WgpuSurface::new(window.clone(), present_mode)?;
+
+        let mut next_update = Instant::now() + update_delay;
+        let event_interest = middleware.event_interest();
+        let device_events = self.device_events.unwrap_or(
+            if event_interest.contains(EventCategories::DEVICE_MOTION) {
+                DeviceEvents::WhenFocused
+            } else {
+                DeviceEvents::Never
+            },
+        );
+        self.event_loop.listen_device_events(device_events);
+        let render_on_demand = self.render_on_demand;
+        let mut idle = false;
+        let mut active = true;
+        if render_on_demand {
+            window.request_redraw();
+        }
+
+        self.event_loop
+            .set_control_flow(ControlFlow::WaitUntil(next_update));
+        let coalesce_cursor = self.coalesce_cursor;
+        let mut pending_cursor = None;
+        let mut motion = std::mem::take(&mut control.motion);
+        let mut pointer = PointerTracker::default();
+        self.event_loop.run(move |event, elwt| {
+            let mut control = WgpuControl {
+                should_quit: false,
+                redraw_requested: false,
+                pointer_captured: pointer.is_captured(),
+                motion: std::mem::take(&mut motion),
+                fixed_timestep,
+                window: window.clone(),
+            };
+
+            if let Event::WindowEvent { event, .. } = &event {
+                match event {
+                    WindowEvent::CursorEntered { .. } => pointer.enter(),
+                    WindowEvent::CursorLeft { .. } => pointer.leave(),
+                    WindowEvent::CursorMoved { position, .. } => {
+                        pointer.moved((position.x, position.y))
+                    }
+                    _ => (),
+                }
+            }
+            let event = match event {
+                Event::DeviceEvent {
+                    device_id,
+                    event: DeviceEvent::MouseMotion { delta },
+                } => {
+                    control.motion.register_delta(delta);
+                    pointer.follow(delta).map_or(
+                        Event::DeviceEvent {
+                            device_id,
+                            event: DeviceEvent::MouseMotion { delta },
+                        },
+                        |(x, y)| Event::WindowEvent {
+                            window_id: window.id(),
+                            event: WindowEvent::CursorMoved {
+                                device_id,
+                                position: PhysicalPosition::new(x, y),
+                            },
+                        },
+                    )
+                }
+                event => event,
+            };
+
+            match event {
+                Event::NewEvents(_) if !idle && Instant::now() >= next_update => {
+                    if let Some(pending) = pending_cursor.take() {
+                        middleware.handle_event(pending, &window, &mut control);
+                    }
+                    let now = Instant::now();
+                    let mut steps = 0;
+                    while now >= next_update {
+                        let context = middleware.update(&mut control, update_delay);
+                        app.update(context);
+                        control.motion.reset();
+                        next_update += update_delay;
+                        steps += 1;
+                        if !fixed_timestep || control.should_quit {
+                            break;
+                        }
+                        if steps >= MAX_FIXED_STEPS {
+                            // Drop the backlog instead of falling further behind.
+                            next_update = now + update_delay;
+                            break;
+                        }
+                    }
+                    if render_on_demand {
+                        idle = !active && !control.redraw_requested;
+                        active = false;
+                    } else if present_mode == PresentMode::Timer {
+                        window.request_redraw();
+                    }
+                }
+                Event::AboutToWait => {
+                    if idle {
+                        elwt.set_control_flow(ControlFlow::Wait);
+                    } else {
+                        elwt.set_control_flow(ControlFlow::WaitUntil(next_update));
+                    }
+                }
+                Event::WindowEvent { event, .. } => {
+                    if render_on_demand && event != WindowEvent::RedrawRequested {
+                        active = true;
+                        if idle {
+                            idle = false;
+                            next_update = Instant::now();
+                        }
+                    }
+                    if let WindowEvent::CursorMoved { position, .. } = event {
+                        control
+                            .motion
+                            .register_position((position.x as f32, position.y as f32));
+                    }
+                    let event = match event {
+                        WindowEvent::CursorMoved { .. } if coalesce_cursor => {
+                            pending_cursor = event_interest
+                                .contains(EventCategories::CURSOR)
+                                .then_some(event);
+                            None
+                        }
+                        event => {
+                            if let Some(pending) = pending_cursor.take() {
+                                middleware.handle_event(pending, &window, &mut control);
+                            }
+                            Some(event)
+                        }
+                    };
+                    if let Some(event) = event {
+                        if let WindowEvent::Resized(size) = event {
+                            surface.resize(size.width, size.height);
+                        }
+                        let event = if event_interest.contains(event_category(&event)) {
+                            middleware.handle_event(event, &window, &mut control)
+                        } else {
+                            Some(event)
+                        };
+                        if let Some(WindowEvent::RedrawRequested) = event {
+                            let mut render_target = middleware.render(&mut surface);
+                            let surface =
+                            <WgpuRenderTarget<'_, Rend> as RenderTarget<Conv>>::render_surface_mut(
+                                &mut render_target,
+                            );
+                            if fixed_timestep {
+                                app.interpolate(interpolation(next_update, update_delay));
+                            }
+                            app.render(surface);
+                            window.pre_present_notify();
+                            let _ = devotee_backend::RenderTarget::present(
+                                render_target,
+                                app.converter(),
+                            );
+                            if present_mode == PresentMode::Sync && !render_on_demand {
+                                window.request_redraw();
+                            }
+                        }
+                    }
+                }
+                Event::UserEvent(request) => {
+                    if render_on_demand {
+                        active = true;
+                        if idle {
+                            idle = false;
+                            next_update = Instant::now();
+                        }
+                    }
+                    if request == BackendRequest::Activate {
+                        window.set_minimized(false);
+                        window.focus_window();
+                    }
+                }
+
+                _ => (),
+            }
+
+            if control.redraw_requested {
+                window.request_redraw();
+            }
+            if control.should_quit {
+                elwt.exit();
+            }
+            motion = std::mem::take(&mut control.motion);
+            if control.pointer_captured != pointer.is_captured() {
+                pointer.set_captured(control.pointer_captured);
+                // Motion outside of the window is only reported with device events.
+                elwt.listen_device_events(if pointer.is_captured() {
+                    DeviceEvents::WhenFocused
+                } else {
+                    device_events
+                });
+            }
+        })?;
+
+        Ok(())
+    }
+}
+
+const SHADER: &str = "
+@group(0) @binding(0) var frame: texture_2d<f32>;
+@group(0) @binding(1) var frame_sampler: sampler;
+
+struct Varyings {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> Varyings {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var varyings: Varyings;
+    varyings.position = vec4<f32>(uv.x * 2.0 - 1.0, 1.0 - uv.y * 2.0, 0.0, 1.0);
+    varyings.uv = uv;
+    return varyings;
+}
+
+@fragment
+fn fs_main(varyings: Varyings) -> @location(0) vec4<f32> {
+    return textureSample(frame, frame_sampler, varyings.uv);
+}
+";
+
+/// GPU surface the render surface is uploaded to as a texture and scaled on.
+pub struct WgpuSurface {
+    surface: Surface,
+    device: Device,
+    queue: Queue,
+    config: SurfaceConfiguration,
+    pipeline: RenderPipeline,
+    sampler: Sampler,
+    texture_format: TextureFormat,
+    texture: Option<FrameTexture>,
+    frame: Vec<u8>,
+    // Declared after `surface` so the window is dropped only after the surface.
+    _window: Rc<Window>,
+}
+
+struct FrameTexture {
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+    dimensions: (usize, usize),
+}
+
+impl WgpuSurface {
+    fn new(window: Rc<Window>, present_mode: PresentMode) -> Result<Self, Error> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
+        // SAFETY: the returned value keeps the window alive and drops the surface first.
+        let surface = unsafe { instance.create_surface(window.as_ref()) }?;
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            compatible_surface: Some(&surface),
+        }))
+        .ok_or(Error::AdapterNotFound)?;
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                features: wgpu::Features::empty(),
+                limits:
+                    wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits()),
+            },
+            None,
+        ))?;
+
+        let capabilities = surface.get_capabilities(&adapter);
+        let format = capabilities
+            .formats
+            .iter()
+            .copied()
+            .find(|format| !format.is_srgb())
+            .or(capabilities.formats.first().copied())
+            .ok_or(Error::SurfaceUnsupported)?;
+        // Colors are already sRGB encoded, so sRGB surfaces get sRGB textures to cancel the conversion out.
+        let texture_format = if format.is_srgb() {
+            TextureFormat::Rgba8UnormSrgb
+        } else {
+            TextureFormat::Rgba8Unorm
+        };
+        let size = window.inner_size();
+        let config = SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            present_mode: match present_mode {
+                PresentMode::Timer => wgpu::PresentMode::AutoNoVsync,
+                PresentMode::Sync => wgpu::PresentMode::AutoVsync,
+            },
+            alpha_mode: capabilities.alpha_modes[0],
+            view_formats: Vec::new(),
+        };
+        surface.configure(&device, &config);
+
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("devotee scaling shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("devotee scaling pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Ok(Self {
+            surface,
+            device,
+            queue,
+            config,
+            pipeline,
+            sampler,
+            texture_format,
+            texture: None,
+            frame: Vec::new(),
+            _window: window,
+        })
+    }
+
+    /// Get surface dimensions in physical pixels.
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.config.width, self.config.height)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width > 0 && height > 0 {
+            self.config.width = width;
+            self.config.height = height;
+            self.surface.configure(&self.device, &self.config);
+        }
+    }
+
+    /// Get `[r, g, b, a]` frame buffer of `dimensions` size to be uploaded.
+    fn frame_mut(&mut self, dimensions: (usize, usize)) -> &mut [u8] {
+        let is_outdated = self
+            .texture
+            .as_ref()
+            .is_none_or(|texture| texture.dimensions != dimensions);
+        if is_outdated {
+            let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("devotee render surface"),
+                size: extent(dimensions),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.texture_format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &self.pipeline.get_bind_group_layout(0),
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+            self.texture = Some(FrameTexture {
+                texture,
+                bind_group,
+                dimensions,
+            });
+            self.frame.resize(dimensions.0 * dimensions.1 * 4, 0);
+        }
+        &mut self.frame
+    }
+
+    /// Upload the frame and draw it scaled at the `placement`, clearing the rest with the `background_color`.
+    fn draw(
+        &mut self,
+        background_color: u32,
+        placement: Option<((u32, u32), u32)>,
+    ) -> Result<(), SurfaceError> {
+        let output = match self.surface.get_current_texture() {
+            Ok(output) => output,
+            Err(SurfaceError::Lost | SurfaceError::Outdated) => {
+                self.surface.configure(&self.device, &self.config);
+                self.surface.get_current_texture()?
+            }
+            Err(error) => return Err(error),
+        };
+        let view = output
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color(
+                            background_color,
+                            self.config.format.is_srgb(),
+                        )),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+            });
+            if let (Some(((start_x, start_y), scale)), Some(texture)) = (placement, &self.texture) {
+                self.queue.write_texture(
+                    wgpu::ImageCopyTexture {
+                        texture: &texture.texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    &self.frame,
+                    wgpu::ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: Some(texture.dimensions.0 as u32 * 4),
+                        rows_per_image: Some(texture.dimensions.1 as u32),
+                    },
+                    extent(texture.dimensions),
+                );
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &texture.bind_group, &[]);
+                pass.set_viewport(
+                    start_x as f32,
+                    start_y as f32,
+                    (texture.dimensions.0 as u32 * scale) as f32,
+                    (texture.dimensions.1 as u32 * scale) as f32,
+                    0.0,
+                    1.0,
+                );
+                pass.draw(0..3, 0..1);
+            }
+        }
+        self.queue.submit(Some(encoder.finish()));
+        output.present();
+        Ok(())
+    }
+}
+
+fn interpolation(next_update: Instant, update_delay: Duration) -> f32 {
+    let remaining = next_update.saturating_duration_since(Instant::now());
+    (1.0 - remaining.as_secs_f32() / update_delay.as_secs_f32()).clamp(0.0, 1.0)
+}
+
+fn extent(dimensions: (usize, usize)) -> wgpu::Extent3d {
+    wgpu::Extent3d {
+        width: dimensions.0 as u32,
+        height: dimensions.1 as u32,
+        depth_or_array_layers: 1,
+    }
+}
+
+fn rgba(color: u32) -> [u8; 4] {
+    [
+        ((color & 0x00_ff_00_00) >> 16) as u8,
+        ((color & 0x00_00_ff_00) >> 8) as u8,
+        (color & 0x00_00_00_ff) as u8,
+        0xff,
+    ]
+}
+
+fn clear_color(color: u32, linear: bool) -> wgpu::Color {
+    let [r, g, b, _] = rgba(color);
+    let channel = |value: u8| {
+        let value = value as f64 / 255.0;
+        if !linear {
+            value
+        } else if value <= 0.04045 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    wgpu::Color {
+        r: channel(r),
+        g: channel(g),
+        b: channel(b),
+        a: 1.0,
+    }
+}
+
+/// Default Middleware for the wgpu backend.
+pub struct WgpuMiddleware<RenderSurface, Input> {
+    background_color: u32,
+    render_surface: RenderSurface,
+    input: Input,
+    default_scale: u32,
+    latency: Option<LatencyRecorder>,
+    drag_regions: Vec<((i32, i32), (i32, i32))>,
+    cursor_position: (f32, f32),
+    event_interest: EventCategories,
+    adjustment: ColorAdjustment,
+    safe_area: SafeArea,
+    magnifier: Magnifier,
+    magnifier_hotkey: Option<(KeyCode, ModifiersState)>,
+    frame: Option<Frame>,
+    modifiers: ModifiersState,
+    magnifier_controls: MagnifierControls,
+    surface_dimensions: (u32, u32),
+}
+
+impl<RenderSurface, Input> WgpuMiddleware<RenderSurface, Input>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+{
+    /// Create new middleware instance with desired render surface and input handler.
+    pub fn new(render_surface: RenderSurface, input: Input) -> Self {
+        let background_color = 0;
+        let default_scale = 1;
+        let latency = None;
+        let drag_regions = Vec::new();
+        let cursor_position = (0.0, 0.0);
+        let event_interest = EventCategories::ALL;
+        let adjustment = ColorAdjustment::new();
+        let safe_area = SafeArea::default();
+        let magnifier = Magnifier::new();
+        let magnifier_hotkey = None;
+        let modifiers = ModifiersState::empty();
+        let magnifier_controls = MagnifierControls::new();
+        let frame = None;
+        let surface_dimensions = (
+            render_surface.width() as u32,
+            render_surface.height() as u32,
+        );
+        Self {
+            background_color,
+            render_surface,
+            input,
+            default_scale,
+            latency,
+            drag_regions,
+            cursor_position,
+            event_interest,
+            adjustment,
+            safe_area,
+            magnifier,
+            magnifier_hotkey,
+            modifiers,
+            magnifier_controls,
+            frame,
+            surface_dimensions,
+        }
+    }
+
+    /// Set default scale for the window.
+    ///
+    /// # Panics
+    /// Panics if `default_scale` is zero.
+    pub fn with_default_scale(self, default_scale: u32) -> Self {
+        assert_ne!(default_scale, 0, "Default scale can't be zero");
+        Self {
+            default_scale,
+            ..self
+        }
+    }
+
+    /// Set background color for the unoccupied space.
+    pub fn with_background_color(self, background_color: u32) -> Self {
+        Self {
+            background_color,
+            ..self
+        }
+    }
+
+    /// Enable input latency diagnostics using the provided recorder.
+    pub fn with_latency_recorder(self, latency: LatencyRecorder) -> Self {
+        Self {
+            latency: Some(latency),
+            ..self
+        }
+    }
+
+    /// Mark rectangular region of the render surface as a window drag area.
+    /// Pressing the left mouse button inside of it starts window dragging instead of passing the event further.
+    pub fn with_drag_region(self, origin: (i32, i32), dimensions: (i32, i32)) -> Self {
+        let mut drag_regions = self.drag_regions;
+        drag_regions.push((origin, dimensions));
+        Self {
+            drag_regions,
+            ..self
+        }
+    }
+
+    /// Set categories of events to be handled.
+    /// Events of other categories skip the input handling completely.
+    pub fn with_event_interest(self, event_interest: EventCategories) -> Self {
+        Self {
+            event_interest,
+            ..self
+        }
+    }
+
+    /// Set color adjustment applied as the final conversion step.
+    pub fn with_color_adjustment(self, adjustment: ColorAdjustment) -> Self {
+        Self { adjustment, ..self }
+    }
+
+    /// Set safe area margins the render surface is kept out of.
+    pub fn with_safe_area(self, safe_area: SafeArea) -> Self {
+        Self { safe_area, ..self }
+    }
+
+    /// Enable the debug magnifier toggled by pressing `key` with exactly the `modifiers` held.
+    /// While it is active the mouse wheel changes zoom and dragging with the left mouse button pans the view.
+    pub fn with_magnifier_hotkey(self, key: KeyCode, modifiers: ModifiersState) -> Self {
+        Self {
+            magnifier_hotkey: Some((key, modifiers)),
+            ..self
+        }
+    }
+
+    /// Enable or disable capturing of the presented frames.
+    /// Captured frames are available with the context `capture_frame` method.
+    pub fn with_frame_capture(self, enabled: bool) -> Self {
+        let frame = enabled.then(Frame::new);
+        Self { frame, ..self }
+    }
+
+    /// Handle debug magnifier controls, return `true` if the event is consumed.
+    fn handle_magnifier<Context>(
+        &mut self,
+        event: &WindowEvent,
+        context: &Context,
+        control: &mut WgpuControl,
+    ) -> bool
+    where
+        Context: EventContext,
+    {
+        let Some((key, modifiers)) = self.magnifier_hotkey else {
+            return false;
+        };
+        let resolution = (self.render_surface.width(), self.render_surface.height());
+        let surface_position = |position| {
+            let (x, y) = context
+                .position_into_render_surface_space(position)
+                .unwrap_or_else(|position| position);
+            (x as f32, y as f32)
+        };
+        match event {
+            WindowEvent::ModifiersChanged(state) => {
+                self.modifiers = state.state();
+                false
+            }
+            WindowEvent::KeyboardInput { event, .. }
+                if event.physical_key == PhysicalKey::Code(key) && self.modifiers == modifiers =>
+            {
+                if event.state == ElementState::Pressed && !event.repeat {
+                    self.magnifier_controls
+                        .toggle(&mut self.magnifier, surface_position(self.cursor_position));
+                    control.request_redraw();
+                }
+                true
+            }
+            _ if !self.magnifier.is_active() => false,
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+                ..
+            } => {
+                self.magnifier_controls.set_panning(
+                    *state == ElementState::Pressed,
+                    surface_position(self.cursor_position),
+                );
+                true
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let steps = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => y.signum() as i32,
+                    MouseScrollDelta::PixelDelta(position) => position.y.signum() as i32,
+                };
+                self.magnifier.zoom_by(steps);
+                control.request_redraw();
+                true
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let position = surface_position((position.x as f32, position.y as f32));
+                if self
+                    .magnifier_controls
+                    .pan(&mut self.magnifier, position, resolution)
+                {
+                    control.request_redraw();
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a, RenderSurface, Input> Middleware<'a, WgpuControl> for WgpuMiddleware<RenderSurface, Input>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+    RenderSurface: 'a,
+    Input: 'a + devotee_backend::Input<'a, WgpuEventContext<'a>, Event = WindowEvent>,
+{
+    type Event = WindowEvent;
+    type EventContext = &'a Window;
+    type Surface = &'a mut WgpuSurface;
+    type Init = WgpuInit<'a>;
+    type Context = WgpuContext<'a, Input>;
+    type RenderTarget = WgpuRenderTarget<'a, RenderSurface>;
+
+    fn init(&'a mut self, control: &'a mut WgpuControl) -> Self::Init {
+        let dimensions = (
+            self.render_surface.width() as u32,
+            self.render_surface.height() as u32,
+        );
+        control
+            .window
+            .set_min_inner_size(Some(PhysicalSize::new(dimensions.0, dimensions.1)));
+        let _ = control.window.request_inner_size(PhysicalSize::new(
+            dimensions.0 * self.default_scale,
+            dimensions.1 * self.default_scale,
+        ));
+        let actual_dimensions = control.window.inner_size();
+        self.surface_dimensions = (actual_dimensions.width, actual_dimensions.height);
+
+        WgpuInit { control }
+    }
+
+    fn update(&'a mut self, control: &'a mut WgpuControl, delta: Duration) -> Self::Context {
+        if let Some(latency) = &mut self.latency {
+            latency.register_tick(Instant::now());
+        }
+        let input = &mut self.input;
+        let latency = self.latency.as_ref();
+        let adjustment = &mut self.adjustment;
+        let safe_area = &mut self.safe_area;
+        let magnifier = &mut self.magnifier;
+        let frame = self.frame.as_ref();
+        let render_surface = &mut self.render_surface;
+        WgpuContext {
+            control,
+            delta,
+            input,
+            latency,
+            adjustment,
+            safe_area,
+            magnifier,
+            frame,
+            render_surface,
+        }
+    }
+
+    fn event_interest(&self) -> EventCategories {
+        // The middleware keeps tracking the cursor, hotkeys and drag regions on its own.
+        let mut interest = self.event_interest | EventCategories::CURSOR;
+        if self.magnifier_hotkey.is_some() || self.latency.is_some() {
+            interest |= EventCategories::KEYBOARD;
+        }
+        if self.magnifier_hotkey.is_some()
+            || self.latency.is_some()
+            || !self.drag_regions.is_empty()
+        {
+            interest |= EventCategories::MOUSE;
+        }
+        interest
+    }
+
+    fn handle_event(
+        &mut self,
+        event: Self::Event,
+        event_context: Self::EventContext,
+        control: &mut WgpuControl,
+    ) -> Option<Self::Event> {
+        if let WindowEvent::Resized(size) = event {
+            self.surface_dimensions = (size.width, size.height);
+        }
+        let context = WgpuEventContext {
+            window: event_context,
+            pointer_captured: control.pointer_captured,
+            dimensions: self.surface_dimensions,
+            resolution: (
+                self.render_surface.width() as u32,
+                self.render_surface.height() as u32,
+            ),
+            safe_area: self.safe_area,
+        };
+
+        if let Some(latency) = &mut self.latency {
+            if is_press(&event) {
+                latency.register_arrival(Instant::now());
+            }
+        }
+
+        if self.handle_magnifier(&event, &context, control) {
+            return None;
+        }
+
+        match event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor_position = (position.x as f32, position.y as f32);
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if let Ok((x, y)) = context.position_into_render_surface_space(self.cursor_position)
+                {
+                    let in_drag_region = self.drag_regions.iter().any(|(origin, dimensions)| {
+                        x >= origin.0
+                            && y >= origin.1
+                            && x < origin.0 + dimensions.0
+                            && y < origin.1 + dimensions.1
+                    });
+                    if in_drag_region && control.start_drag().is_ok() {
+                        return None;
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        let event = if self.event_interest.contains(event_category(&event)) {
+            self.input.handle_event(event, &context)?
+        } else {
+            event
+        };
+        if event == WindowEvent::CloseRequested {
+            control.shutdown();
+        }
+
+        Some(event)
+    }
+
+    fn render(&'a mut self, surface: Self::Surface) -> Self::RenderTarget {
+        let background_color = self.background_color;
+        let render_surface = &mut self.render_surface;
+        let latency = self.latency.as_mut();
+        let adjustment = &self.adjustment;
+        let safe_area = self.safe_area;
+        let magnifier = &self.magnifier;
+        let frame = self.frame.as_mut();
+        WgpuRenderTarget {
+            background_color,
+            render_surface,
+            surface,
+            latency,
+            adjustment,
+            safe_area,
+            magnifier,
+            frame,
+        }
+    }
+}
+
+/// Default Init for the wgpu backend.
+pub struct WgpuInit<'a> {
+    control: &'a mut WgpuControl,
+}
+
+impl<'a> WgpuInit<'a> {
+    /// Get reference to `WgpuControl`.
+    pub fn control(&self) -> &WgpuControl {
+        self.control
+    }
+
+    /// Get mutable reference to `WgpuControl`.
+    pub fn control_mut(&mut self) -> &mut WgpuControl {
+        self.control
+    }
+
+    /// Enable or disable fixed timestep updates.
+    /// When enabled, the backend performs as many updates with the fixed delta as needed to catch up
+    /// with the wall clock and passes the interpolation factor to the application before rendering.
+    pub fn set_fixed_timestep(&mut self, fixed_timestep: bool) {
+        self.control.fixed_timestep = fixed_timestep;
+    }
+}
+
+/// Default Context for the wgpu backend.
+pub struct WgpuContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, WgpuEventContext<'a>>,
+{
+    control: &'a mut WgpuControl,
+    input: &'a mut Input,
+    delta: Duration,
+    latency: Option<&'a LatencyRecorder>,
+    adjustment: &'a mut ColorAdjustment,
+    safe_area: &'a mut SafeArea,
+    magnifier: &'a mut Magnifier,
+    frame: Option<&'a Frame>,
+    render_surface: &'a mut dyn ResizeRenderSurface,
+}
+
+impl<'a, Input> WgpuContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, WgpuEventContext<'a>>,
+{
+    /// Get reference to `WgpuControl`.
+    pub fn control(&self) -> &WgpuControl {
+        self.control
+    }
+
+    /// Get mutable reference to `WgpuControl`.
+    pub fn control_mut(&mut self) -> &mut WgpuControl {
+        self.control
+    }
+
+    /// Get input latency report if latency diagnostics are enabled.
+    pub fn latency_report(&self) -> Option<LatencyReport> {
+        self.latency.map(LatencyRecorder::report)
+    }
+
+    /// Get reference to the color adjustment applied at presentation.
+    pub fn color_adjustment(&self) -> &ColorAdjustment {
+        self.adjustment
+    }
+
+    /// Get mutable reference to the color adjustment applied at presentation.
+    pub fn color_adjustment_mut(&mut self) -> &mut ColorAdjustment {
+        self.adjustment
+    }
+
+    /// Get safe area margins the render surface is kept out of.
+    pub fn safe_area(&self) -> SafeArea {
+        *self.safe_area
+    }
+
+    /// Set safe area margins the render surface is kept out of.
+    pub fn set_safe_area(&mut self, safe_area: SafeArea) {
+        *self.safe_area = safe_area;
+    }
+
+    /// Get reference to the debug magnifier.
+    pub fn magnifier(&self) -> &Magnifier {
+        self.magnifier
+    }
+
+    /// Get mutable reference to the debug magnifier.
+    pub fn magnifier_mut(&mut self) -> &mut Magnifier {
+        self.magnifier
+    }
+
+    /// Get copy of the last presented frame.
+    /// Returns `None` if frame capture is disabled in the middleware or nothing was presented yet.
+    pub fn capture_frame(&self) -> Option<Frame> {
+        self.frame.filter(|frame| !frame.is_empty()).cloned()
+    }
+
+    /// Save the last presented frame as a PNG image into the file at `path`.
+    #[cfg(feature = "screenshot")]
+    pub fn save_screenshot<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+    ) -> Result<(), ScreenshotError> {
+        self.frame.ok_or(ScreenshotError::NoFrame)?.save_png(path)
+    }
+
+    /// Resize the render surface, keeping its contents in the overlapping area if `preserve` is set.
+    /// The presentation scale is adjusted to the new resolution starting with the next frame.
+    /// Return `false` if the render surface does not support resizing.
+    pub fn resize_render_target(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        let resized = self.render_surface.resize(width, height, preserve);
+        if resized {
+            self.control
+                .window
+                .set_min_inner_size(Some(PhysicalSize::new(width as u32, height as u32)));
+        }
+        resized
+    }
+}
+
+impl<'a, Input> Context<'a, Input> for WgpuContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, WgpuEventContext<'a>>,
+{
+    fn input(&self) -> &Input {
+        self.input
+    }
+
+    fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    fn shutdown(&mut self) {
+        self.control.shutdown();
+    }
+}
+
+impl<'a, Input> Drop for WgpuContext<'a, Input>
+where
+    Input: devotee_backend::Input<'a, WgpuEventContext<'a>>,
+{
+    fn drop(&mut self) {
+        self.input.tick();
+    }
+}
+
+/// Default Render Target for the wgpu backend.
+pub struct WgpuRenderTarget<'a, RenderSurface> {
+    background_color: u32,
+    render_surface: &'a mut RenderSurface,
+    surface: &'a mut WgpuSurface,
+    latency: Option<&'a mut LatencyRecorder>,
+    adjustment: &'a ColorAdjustment,
+    safe_area: SafeArea,
+    magnifier: &'a Magnifier,
+    frame: Option<&'a mut Frame>,
+}
+
+impl<'a, RenderSurface, Converter> RenderTarget<Converter> for WgpuRenderTarget<'a, RenderSurface>
+where
+    RenderSurface: devotee_backend::RenderSurface,
+    Converter: devotee_backend::Converter<Data = RenderSurface::Data>,
+{
+    type RenderSurface = RenderSurface;
+    type PresentError = SurfaceError;
+
+    fn render_surface(&self) -> &Self::RenderSurface {
+        self.render_surface
+    }
+
+    fn render_surface_mut(&mut self) -> &mut Self::RenderSurface {
+        self.render_surface
+    }
+
+    fn present(self, converter: Converter) -> Result<(), Self::PresentError> {
+        let render_surface_dimensions = (self.render_surface.width(), self.render_surface.height());
+
+        let placement = placement(
+            self.surface.dimensions(),
+            (
+                render_surface_dimensions.0 as u32,
+                render_surface_dimensions.1 as u32,
+            ),
+            self.safe_area,
+        );
+
+        let adjustment = (!self.adjustment.is_identity()).then_some(self.adjustment);
+        let frame = self.surface.frame_mut(render_surface_dimensions);
+        for (index, pixel) in frame.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (
+                index % render_surface_dimensions.0,
+                index / render_surface_dimensions.0,
+            );
+            let (x, y) = self.magnifier.source((x, y), render_surface_dimensions);
+            let pixel_color = self.render_surface.data(x, y);
+            let pixel_value = converter.convert(x, y, pixel_color);
+            let pixel_value =
+                adjustment.map_or(pixel_value, |adjustment| adjustment.apply(pixel_value));
+            pixel.copy_from_slice(&rgba(pixel_value));
+        }
+        if let Some(capture) = self.frame {
+            capture.resize(render_surface_dimensions.0, render_surface_dimensions.1);
+            capture.data_mut().copy_from_slice(frame);
+        }
+
+        let flash_color = self.latency.as_ref().and_then(|l| l.flash_color());
+        let (background_color, placement) = match flash_color {
+            Some(flash_color) => (flash_color, None),
+            None => (self.background_color, placement),
+        };
+        self.surface.draw(background_color, placement)?;
+        if let Some(latency) = self.latency {
+            latency.register_present(Instant::now());
+        }
+        Ok(())
+    }
+}
+
+/// Default Control instance for the wgpu backend.
+pub struct WgpuControl {
+    should_quit: bool,
+    redraw_requested: bool,
+    pointer_captured: bool,
+    motion: CursorMotion,
+    fixed_timestep: bool,
+    window: Rc<Window>,
+}
+
+impl WgpuControl {
+    /// Tell backend to shut down.
+    pub fn shutdown(&mut self) -> &mut Self {
+        self.should_quit = true;
+        self
+    }
+
+    /// Keep reporting cursor movement even when the cursor leaves the window, e.g. during drag operations.
+    /// Positions of the captured pointer outside of the render surface are not clamped to its edges.
+    pub fn capture_pointer(&mut self) -> &mut Self {
+        self.pointer_captured = true;
+        self
+    }
+
+    /// Stop capturing the pointer.
+    pub fn release_pointer(&mut self) -> &mut Self {
+        self.pointer_captured = false;
+        self
+    }
+
+    /// Check if the pointer is captured.
+    pub fn is_pointer_captured(&self) -> bool {
+        self.pointer_captured
+    }
+
+    /// Get cursor motion aggregated since the last update.
+    pub fn cursor_motion(&self) -> &CursorMotion {
+        &self.motion
+    }
+
+    /// Get mutable reference to the cursor motion aggregate, e.g. to enable positions recording.
+    pub fn cursor_motion_mut(&mut self) -> &mut CursorMotion {
+        &mut self.motion
+    }
+
+    /// Request redraw of the window.
+    /// Redraws happen regardless of requests unless the render-on-demand mode is enabled.
+    pub fn request_redraw(&mut self) -> &mut Self {
+        self.redraw_requested = true;
+        self
+    }
+
+    /// Get reference to the underlying window.
+    pub fn window_ref(&self) -> &Window {
+        &self.window
+    }
+
+    /// Start moving the window with the left mouse button until it is released.
+    pub fn start_drag(&mut self) -> Result<(), ExternalError> {
+        self.window.drag_window()
+    }
+
+    /// Start resizing the window with the left mouse button until it is released.
+    pub fn start_resize(&mut self, direction: ResizeDirection) -> Result<(), ExternalError> {
+        self.window.drag_resize_window(direction)
+    }
+}
+
+/// Default Event Context for the wgpu backend.
+pub struct WgpuEventContext<'a> {
+    window: &'a Window,
+    pointer_captured: bool,
+    dimensions: (u32, u32),
+    resolution: (u32, u32),
+    safe_area: SafeArea,
+}
+
+impl<'a> WgpuEventContext<'a> {
+    /// Get reference to the underlying window.
+    pub fn window_ref(&self) -> &'a Window {
+        self.window
+    }
+}
+
+impl<'a> EventContext for WgpuEventContext<'a> {
+    fn position_into_render_surface_space(
+        &self,
+        position: (f32, f32),
+    ) -> Result<(i32, i32), (i32, i32)> {
+        let (width, height) = (self.resolution.0 as i32, self.resolution.1 as i32);
+        let ((start_x, start_y), scale) =
+            match placement(self.dimensions, self.resolution, self.safe_area) {
+                Some(((start_x, start_y), scale)) => {
+                    ((start_x as i32, start_y as i32), scale as i32)
+                }
+                None => {
+                    // The surface is smaller than the resolution, center it without scaling.
+                    let ((left, top), (area_width, area_height)) =
+                        self.safe_area.apply(self.dimensions);
+                    (
+                        (
+                            left as i32 + (area_width as i32 - width) / 2,
+                            top as i32 + (area_height as i32 - height) / 2,
+                        ),
+                        1,
+                    )
+                }
+            };
+
+        let position = (
+            (position.0.floor() as i32 - start_x).div_euclid(scale),
+            (position.1.floor() as i32 - start_y).div_euclid(scale),
+        );
+
+        if position.0 < 0 || position.0 >= width || position.1 < 0 || position.1 >= height {
+            if self.pointer_captured {
+                return Err(position);
+            }
+            Err((
+                position.0.clamp(0, (width - 1).max(0)),
+                position.1.clamp(0, (height - 1).max(0)),
+            ))
+        } else {
+            Ok(position)
+        }
+    }
+}
+
+fn placement(
+    window: (u32, u32),
+    resolution: (u32, u32),
+    safe_area: SafeArea,
+) -> Option<((u32, u32), u32)> {
+    let ((left, top), (width, height)) = safe_area.apply(window);
+    let scale = (width / resolution.0).min(height / resolution.1);
+    (scale >= 1).then(|| {
+        let start_x = left + (width - resolution.0 * scale) / 2;
+        let start_y = top + (height - resolution.1 * scale) / 2;
+        ((start_x, start_y), scale)
+    })
+}
+
+/// Wgpu backend error enumeration.
+#[derive(Debug)]
+pub enum Error {
+    /// Winit event loop error.
+    WinitEventLoopError(EventLoopError),
+
+    /// Winit OS error.
+    WinitOsError(OsError),
+
+    /// Wgpu surface creation error.
+    WgpuCreateSurfaceError(CreateSurfaceError),
+
+    /// Wgpu device request error.
+    WgpuRequestDeviceError(RequestDeviceError),
+
+    /// No suitable graphics adapter found.
+    AdapterNotFound,
+
+    /// Window surface is not supported by the graphics adapter.
+    SurfaceUnsupported,
+}
+
+impl From<EventLoopError> for Error {
+    fn from(value: EventLoopError) -> Self {
+        Self::WinitEventLoopError(value)
+    }
+}
+
+impl From<OsError> for Error {
+    fn from(value: OsError) -> Self {
+        Self::WinitOsError(value)
+    }
+}
+
+impl From<CreateSurfaceError> for Error {
+    fn from(value: CreateSurfaceError) -> Self {
+        Self::WgpuCreateSurfaceError(value)
+    }
+}
+
+impl From<RequestDeviceError> for Error {
+    fn from(value: RequestDeviceError) -> Self {
+        Self::WgpuRequestDeviceError(value)
+    }
+}
@@ -0,0 +1,114 @@
+/// User-facing color adjustment applied to the final `0xff_rr_gg_bb` values.
+///
+/// Values are mapped through a 256-entry lookup table per channel,
+/// rebuilt every time any of the settings changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorAdjustment {
+    brightness: f32,
+    contrast: f32,
+    gamma: f32,
+    table: [u8; 256],
+}
+
+impl ColorAdjustment {
+    /// Create new adjustment leaving colors unchanged.
+    pub fn new() -> Self {
+        let mut adjustment = Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            gamma: 1.0,
+            table: [0; 256],
+        };
+        adjustment.rebuild();
+        adjustment
+    }
+
+    /// Set brightness offset in `[-1.0, 1.0]` range, `0.0` leaves colors unchanged.
+    pub fn with_brightness(self, brightness: f32) -> Self {
+        let mut adjustment = self;
+        adjustment.set_brightness(brightness);
+        adjustment
+    }
+
+    /// Set contrast multiplier, `1.0` leaves colors unchanged.
+    pub fn with_contrast(self, contrast: f32) -> Self {
+        let mut adjustment = self;
+        adjustment.set_contrast(contrast);
+        adjustment
+    }
+
+    /// Set gamma, `1.0` leaves colors unchanged and greater values lighten dark tones.
+    pub fn with_gamma(self, gamma: f32) -> Self {
+        let mut adjustment = self;
+        adjustment.set_gamma(gamma);
+        adjustment
+    }
+
+    /// Get brightness offset.
+    pub fn brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    /// Get contrast multiplier.
+    pub fn contrast(&self) -> f32 {
+        self.contrast
+    }
+
+    /// Get gamma.
+    pub fn gamma(&self) -> f32 {
+        self.gamma
+    }
+
+    /// Set brightness offset in `[-1.0, 1.0]` range.
+    pub fn set_brightness(&mut self, brightness: f32) -> &mut Self {
+        self.brightness = brightness.clamp(-1.0, 1.0);
+        self.rebuild();
+        self
+    }
+
+    /// Set non-negative contrast multiplier.
+    pub fn set_contrast(&mut self, contrast: f32) -> &mut Self {
+        self.contrast = contrast.max(0.0);
+        self.rebuild();
+        self
+    }
+
+    /// Set positive gamma.
+    pub fn set_gamma(&mut self, gamma: f32) -> &mut Self {
+        self.gamma = gamma.max(f32::EPSILON);
+        self.rebuild();
+        self
+    }
+
+    /// Check if the adjustment leaves colors unchanged.
+    pub fn is_identity(&self) -> bool {
+        self.table
+            .iter()
+            .enumerate()
+            .all(|(index, value)| index == *value as usize)
+    }
+
+    /// Apply the adjustment to the `0xff_rr_gg_bb` color.
+    pub fn apply(&self, color: u32) -> u32 {
+        let mut result = color & 0xff_00_00_00;
+        for shift in [16, 8, 0] {
+            let value = self.table[((color >> shift) & 0xff) as usize];
+            result |= (value as u32) << shift;
+        }
+        result
+    }
+
+    fn rebuild(&mut self) {
+        for (index, entry) in self.table.iter_mut().enumerate() {
+            let value = (index as f32 / 255.0).powf(1.0 / self.gamma);
+            let value = (value - 0.5) * self.contrast + 0.5 + self.brightness;
+            *entry = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        }
+    }
+}
+
+impl Default for ColorAdjustment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "screenshot")]
+use std::fs::File;
+#[cfg(feature = "screenshot")]
+use std::io::BufWriter;
+#[cfg(feature = "screenshot")]
+use std::path::Path;
+
+/// Frame captured during presentation, stored as `[r, g, b, a]` texels row by row.
+///
+/// It has the render surface resolution and includes presentation effects like color adjustment.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+impl Frame {
+    /// Create new empty frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get width of the frame in texels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get height of the frame in texels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Check if the frame has no texels.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Get `[r, g, b, a]` texel data.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get mutable reference to `[r, g, b, a]` texel data.
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    /// Consume the frame and get its `[r, g, b, a]` texel data.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Resize the frame, keeping the allocation if possible.
+    /// Texel values are unspecified after resizing.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+        self.data.resize(width * height * 4, 0);
+    }
+
+    /// Set the texel at `x` and `y` to the `0xff_rr_gg_bb` color.
+    ///
+    /// # Panics
+    /// Panics if the position is out of the frame bounds.
+    pub fn set(&mut self, x: usize, y: usize, color: u32) {
+        let index = (x + y * self.width) * 4;
+        let [_, r, g, b] = color.to_be_bytes();
+        self.data[index..index + 4].copy_from_slice(&[r, g, b, 0xff]);
+    }
+
+    /// Save the frame as an 8-bit RGBA PNG image into the file at `path`.
+    #[cfg(feature = "screenshot")]
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), ScreenshotError> {
+        if self.is_empty() {
+            return Err(ScreenshotError::NoFrame);
+        }
+        let writer = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(writer, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.data)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Screenshot saving error enumeration.
+#[cfg(feature = "screenshot")]
+#[derive(Debug)]
+pub enum ScreenshotError {
+    /// No frame was captured yet or frame capture is disabled.
+    NoFrame,
+
+    /// File output error.
+    IoError(std::io::Error),
+
+    /// PNG encoding error.
+    PngEncodingError(png::EncodingError),
+}
+
+#[cfg(feature = "screenshot")]
+impl From<std::io::Error> for ScreenshotError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+#[cfg(feature = "screenshot")]
+impl From<png::EncodingError> for ScreenshotError {
+    fn from(value: png::EncodingError) -> Self {
+        Self::PngEncodingError(value)
+    }
+}
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PresentMode, SafeArea, ScaleMode, Viewport};
+
+/// Backend settings in a single structure, serializable with the `serde` feature, e.g. into TOML or RON.
+///
+/// Backends ignore settings they do not support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct BackendConfig {
+    /// Render surface resolution, `None` keeps the resolution of the render surface.
+    pub resolution: Option<(usize, usize)>,
+    /// Initial window scale relative to the resolution.
+    pub scale: u32,
+    /// Window margins excluded from the presentation area, e.g. TV overscan.
+    pub safe_area: SafeArea,
+    /// Placement of the render surface within the window.
+    pub viewport: Viewport,
+    /// Integer scale selection of the render surface within the window.
+    pub scale_mode: ScaleMode,
+    /// Color of the window area not covered by the render surface in `0xff_rr_gg_bb` format.
+    pub border_color: u32,
+    /// Amount of updates per second.
+    pub updates_per_second: u32,
+    /// Frame presentation scheduling mode.
+    pub present_mode: PresentMode,
+    /// Render frame rate cap independent of the update rate, `None` leaves redraws to the present mode.
+    pub max_fps: Option<u32>,
+    /// Pause the application while its window is not focused.
+    pub pause_in_background: bool,
+}
+
+impl BackendConfig {
+    /// Get delay between consecutive updates.
+    pub fn update_delay(&self) -> Duration {
+        Duration::from_secs(1) / self.updates_per_second.max(1)
+    }
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self {
+            resolution: None,
+            scale: 1,
+            safe_area: SafeArea::default(),
+            viewport: Viewport::default(),
+            scale_mode: ScaleMode::default(),
+            border_color: 0xff_00_00_00,
+            updates_per_second: 60,
+            present_mode: PresentMode::default(),
+            max_fps: None,
+            pause_in_background: false,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{Converter, RenderSurface};
+
+/// Bound on the render surfaces and converters shared between conversion threads.
+///
+/// With the `rayon` feature the rows are converted in parallel, so the types must be `Sync`.
+/// Otherwise there is no bound.
+#[cfg(feature = "rayon")]
+pub trait MaybeSync: Sync {}
+
+#[cfg(feature = "rayon")]
+impl<T> MaybeSync for T where T: Sync + ?Sized {}
+
+/// Bound on the render surfaces and converters shared between conversion threads.
+///
+/// With the `rayon` feature the rows are converted in parallel, so the types must be `Sync`.
+/// Otherwise there is no bound.
+#[cfg(not(feature = "rayon"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "rayon"))]
+impl<T> MaybeSync for T where T: ?Sized {}
+
+/// Convert the rectangle between the `start` corner and the exclusive `end` corner of the `surface`
+/// into `0xff_rr_gg_bb` values stored row by row in `out`.
+/// Rows exposed by the surface are converted in bulk, in parallel with the `rayon` feature.
+///
+/// # Panics
+/// Panics if the rectangle is outside of the surface or `out` is shorter than the rectangle.
+pub fn convert_region<S, C>(
+    surface: &S,
+    converter: &C,
+    start: (usize, usize),
+    end: (usize, usize),
+    out: &mut [u32],
+) where
+    S: RenderSurface + MaybeSync + ?Sized,
+    S::Data: Clone,
+    C: Converter<Data = S::Data> + MaybeSync,
+{
+    let width = end.0.saturating_sub(start.0);
+    let height = end.1.saturating_sub(start.1);
+    if width == 0 || height == 0 {
+        return;
+    }
+    let out = &mut out[..width * height];
+    let convert = |(index, out): (usize, &mut [u32])| {
+        let y = start.1 + index;
+        match surface.row(y) {
+            Some(row) => converter.convert_row(start.0, y, &row[start.0..end.0], out),
+            None => {
+                for (x, out) in (start.0..end.0).zip(out) {
+                    *out = converter.convert(x, y, surface.data(x, y));
+                }
+            }
+        }
+    };
+    #[cfg(feature = "rayon")]
+    out.par_chunks_exact_mut(width)
+        .enumerate()
+        .for_each(convert);
+    #[cfg(not(feature = "rayon"))]
+    out.chunks_exact_mut(width).enumerate().for_each(convert);
+}
@@ -0,0 +1,84 @@
+use crate::{Converter, RenderSurface};
+
+/// Cursor image drawn by the backend over the presented frame, e.g. to replace the system cursor in pixel-art games.
+///
+/// Pixels are in `0xaa_rr_gg_bb` format, pixels with zero alpha are transparent, others are opaque.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CursorImage {
+    width: usize,
+    height: usize,
+    hotspot: (i32, i32),
+    pixels: Vec<u32>,
+}
+
+impl CursorImage {
+    /// Create new cursor image of the `width` and `height` from the `pixels` stored row by row.
+    /// Missing pixels are transparent, extra pixels are discarded.
+    pub fn new(width: usize, height: usize, pixels: Vec<u32>) -> Self {
+        let mut pixels = pixels;
+        pixels.resize(width * height, 0);
+        Self {
+            width,
+            height,
+            hotspot: (0, 0),
+            pixels,
+        }
+    }
+
+    /// Create new cursor image from the render `surface`, e.g. a sprite or a canvas, converted with the `converter`.
+    /// Pixels matching the `transparent` predicate are transparent.
+    pub fn from_surface<S, C, F>(surface: &S, converter: &C, transparent: F) -> Self
+    where
+        S: RenderSurface,
+        C: Converter<Data = S::Data>,
+        F: Fn(&S::Data) -> bool,
+    {
+        let (width, height) = (surface.width(), surface.height());
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let data = surface.data(x, y);
+                if transparent(&data) {
+                    pixels.push(0);
+                } else {
+                    pixels.push(converter.convert(x, y, data) | 0xff_00_00_00);
+                }
+            }
+        }
+        Self::new(width, height, pixels)
+    }
+
+    /// Set the point of the image placed at the cursor position, top left corner by default.
+    pub fn with_hotspot(self, x: i32, y: i32) -> Self {
+        Self {
+            hotspot: (x, y),
+            ..self
+        }
+    }
+
+    /// Get image width in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get image height in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the point of the image placed at the cursor position.
+    pub fn hotspot(&self) -> (i32, i32) {
+        self.hotspot
+    }
+
+    /// Get the opaque pixel covering the `position` with the cursor at the `cursor` position.
+    pub fn pixel_at(&self, cursor: (i32, i32), position: (usize, usize)) -> Option<u32> {
+        let x = position.0 as i32 - cursor.0 + self.hotspot.0;
+        let y = position.1 as i32 - cursor.1 + self.hotspot.1;
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+        let pixel = self.pixels[x as usize + y as usize * self.width];
+        (pixel >> 24 != 0).then_some(pixel)
+    }
+}
@@ -0,0 +1,60 @@
+/// Region of the render surface changed since the last presentation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Damage {
+    /// Nothing has changed.
+    None,
+    /// The whole render surface has to be presented.
+    #[default]
+    Full,
+    /// Rectangle between the `start` corner and the exclusive `end` corner has changed.
+    Region {
+        /// Top left corner of the rectangle.
+        start: (usize, usize),
+        /// Exclusive bottom right corner of the rectangle.
+        end: (usize, usize),
+    },
+}
+
+impl Damage {
+    /// Extend the damage to cover the rectangle between the `start` corner and the exclusive `end` corner.
+    /// Empty rectangles are ignored.
+    pub fn add(&mut self, start: (usize, usize), end: (usize, usize)) {
+        if start.0 >= end.0 || start.1 >= end.1 {
+            return;
+        }
+        *self = match *self {
+            Damage::None => Damage::Region { start, end },
+            Damage::Full => Damage::Full,
+            Damage::Region {
+                start: old_start,
+                end: old_end,
+            } => Damage::Region {
+                start: (old_start.0.min(start.0), old_start.1.min(start.1)),
+                end: (old_end.0.max(end.0), old_end.1.max(end.1)),
+            },
+        };
+    }
+
+    /// Extend the damage to cover the single texel at `x` and `y`.
+    pub fn add_texel(&mut self, x: usize, y: usize) {
+        self.add((x, y), (x + 1, y + 1));
+    }
+
+    /// Extend the damage to cover the whole render surface.
+    pub fn add_all(&mut self) {
+        *self = Damage::Full;
+    }
+
+    /// Get the damaged rectangle as `start` and exclusive `end` corners clipped to the surface with `dimensions`.
+    /// Returns `None` if nothing visible is damaged.
+    pub fn region(&self, dimensions: (usize, usize)) -> Option<((usize, usize), (usize, usize))> {
+        let (start, end) = match *self {
+            Damage::None => return None,
+            Damage::Full => ((0, 0), dimensions),
+            Damage::Region { start, end } => {
+                (start, (end.0.min(dimensions.0), end.1.min(dimensions.1)))
+            }
+        };
+        (start.0 < end.0 && start.1 < end.1).then_some((start, end))
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::platform::Instant;
+
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Input latency recorder.
+///
+/// Tracks the moment an input event arrives, the update tick consuming it and
+/// the presentation of the frame reflecting it.
+#[derive(Clone, Debug)]
+pub struct LatencyRecorder {
+    capacity: usize,
+    arrived: Vec<Instant>,
+    consumed: Vec<(Instant, Instant)>,
+    samples: VecDeque<LatencySample>,
+    test_pattern: Option<u32>,
+    flash: bool,
+}
+
+impl LatencyRecorder {
+    /// Create new recorder keeping up to 1024 latest samples.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create new recorder keeping up to `capacity` latest samples.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert_ne!(capacity, 0, "Latency recorder capacity can't be zero");
+        Self {
+            capacity,
+            arrived: Vec::new(),
+            consumed: Vec::new(),
+            samples: VecDeque::with_capacity(capacity),
+            test_pattern: None,
+            flash: false,
+        }
+    }
+
+    /// Enable the test pattern: the whole frame reflecting an input event is flashed with `color`.
+    pub fn with_test_pattern(self, color: u32) -> Self {
+        Self {
+            test_pattern: Some(color),
+            ..self
+        }
+    }
+
+    /// Register input event arrival.
+    pub fn register_arrival(&mut self, at: Instant) {
+        self.arrived.push(at);
+    }
+
+    /// Register update tick consuming all the arrived events.
+    pub fn register_tick(&mut self, at: Instant) {
+        self.flash |= !self.arrived.is_empty();
+        self.consumed
+            .extend(self.arrived.drain(..).map(|arrival| (arrival, at)));
+    }
+
+    /// Get test pattern color if the frame about to be presented should be flashed.
+    pub fn flash_color(&self) -> Option<u32> {
+        self.test_pattern.filter(|_| self.flash)
+    }
+
+    /// Register presentation of the frame reflecting all the consumed events.
+    pub fn register_present(&mut self, at: Instant) {
+        self.flash = false;
+        for (arrival, tick) in self.consumed.drain(..) {
+            if self.samples.len() == self.capacity {
+                self.samples.pop_front();
+            }
+            self.samples.push_back(LatencySample {
+                arrival_to_tick: tick.saturating_duration_since(arrival),
+                arrival_to_present: at.saturating_duration_since(arrival),
+            });
+        }
+    }
+
+    /// Get recorded samples, oldest first.
+    pub fn samples(&self) -> impl Iterator<Item = &LatencySample> {
+        self.samples.iter()
+    }
+
+    /// Drop all recorded samples.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Calculate report over the recorded samples.
+    pub fn report(&self) -> LatencyReport {
+        let arrival_to_tick =
+            Percentiles::calculate(self.samples.iter().map(|sample| sample.arrival_to_tick));
+        let arrival_to_present =
+            Percentiles::calculate(self.samples.iter().map(|sample| sample.arrival_to_present));
+        LatencyReport {
+            count: self.samples.len(),
+            arrival_to_tick,
+            arrival_to_present,
+        }
+    }
+}
+
+impl Default for LatencyRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Single latency measurement.
+#[derive(Clone, Copy, Debug)]
+pub struct LatencySample {
+    arrival_to_tick: Duration,
+    arrival_to_present: Duration,
+}
+
+impl LatencySample {
+    /// Get time passed between event arrival and the update tick consuming it.
+    pub fn arrival_to_tick(&self) -> Duration {
+        self.arrival_to_tick
+    }
+
+    /// Get time passed between event arrival and presentation of the frame reflecting it.
+    pub fn arrival_to_present(&self) -> Duration {
+        self.arrival_to_present
+    }
+}
+
+/// Latency percentiles report.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LatencyReport {
+    count: usize,
+    arrival_to_tick: Percentiles,
+    arrival_to_present: Percentiles,
+}
+
+impl LatencyReport {
+    /// Get the amount of samples the report is based on.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get percentiles of time between event arrival and the update tick consuming it.
+    pub fn arrival_to_tick(&self) -> Percentiles {
+        self.arrival_to_tick
+    }
+
+    /// Get percentiles of time between event arrival and presentation of the frame reflecting it.
+    pub fn arrival_to_present(&self) -> Percentiles {
+        self.arrival_to_present
+    }
+}
+
+/// Set of duration percentiles.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Percentiles {
+    p50: Duration,
+    p90: Duration,
+    p99: Duration,
+    max: Duration,
+}
+
+impl Percentiles {
+    fn calculate(durations: impl Iterator<Item = Duration>) -> Self {
+        let mut durations: Vec<_> = durations.collect();
+        if durations.is_empty() {
+            return Self::default();
+        }
+        durations.sort_unstable();
+        let rank = |percentile: usize| {
+            let index = (durations.len() * percentile).div_ceil(100);
+            durations[index.saturating_sub(1)]
+        };
+        Self {
+            p50: rank(50),
+            p90: rank(90),
+            p99: rank(99),
+            max: durations[durations.len() - 1],
+        }
+    }
+
+    /// Get the median value.
+    pub fn p50(&self) -> Duration {
+        self.p50
+    }
+
+    /// Get the 90th percentile.
+    pub fn p90(&self) -> Duration {
+        self.p90
+    }
+
+    /// Get the 99th percentile.
+    pub fn p99(&self) -> Duration {
+        self.p99
+    }
+
+    /// Get the maximal value.
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+}
+
+/// Presentation statistics recorder.
+///
+/// Counts presented frames, skipped presents and buffer acquire failures
+/// over consecutive one second windows.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PresentStats {
+    window_start: Option<Instant>,
+    current: PresentCounts,
+    last_second: PresentCounts,
+    total: PresentCounts,
+}
+
+impl PresentStats {
+    /// Create new empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register successful frame presentation.
+    pub fn register_present(&mut self, at: Instant) {
+        self.advance(at);
+        self.current.presented += 1;
+        self.total.presented += 1;
+    }
+
+    /// Register frame skipped due to a presentation failure.
+    pub fn register_skip(&mut self, at: Instant) {
+        self.advance(at);
+        self.current.skipped += 1;
+        self.total.skipped += 1;
+    }
+
+    /// Register frame dropped due to a failure of the buffer acquisition.
+    pub fn register_acquire_failure(&mut self, at: Instant) {
+        self.advance(at);
+        self.current.acquire_failures += 1;
+        self.total.acquire_failures += 1;
+    }
+
+    fn advance(&mut self, at: Instant) {
+        let window_start = *self.window_start.get_or_insert(at);
+        let elapsed = at.saturating_duration_since(window_start);
+        if elapsed >= Duration::from_secs(1) {
+            self.last_second = if elapsed >= Duration::from_secs(2) {
+                PresentCounts::default()
+            } else {
+                self.current
+            };
+            self.current = PresentCounts::default();
+            self.window_start = Some(at);
+        }
+    }
+
+    /// Get counts of the last complete second.
+    pub fn last_second(&self) -> PresentCounts {
+        self.last_second
+    }
+
+    /// Get counts since the recording start.
+    pub fn total(&self) -> PresentCounts {
+        self.total
+    }
+}
+
+/// Presentation event counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PresentCounts {
+    presented: u64,
+    skipped: u64,
+    acquire_failures: u64,
+}
+
+impl PresentCounts {
+    /// Get the amount of presented frames.
+    pub fn presented(&self) -> u64 {
+        self.presented
+    }
+
+    /// Get the amount of frames skipped due to presentation failures.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Get the amount of frames dropped due to buffer acquisition failures.
+    pub fn acquire_failures(&self) -> u64 {
+        self.acquire_failures
+    }
+
+    /// Get the amount of frames not presented for any reason.
+    pub fn dropped(&self) -> u64 {
+        self.skipped + self.acquire_failures
+    }
+}
@@ -7,8 +7,44 @@
 //! - Middleware abstract backend specifics away;
 //! - Application works with Middleware abstractions;
 
+use std::ops::{BitOr, BitOrAssign};
 use std::time::Duration;
 
+use damage::Damage;
+use window::WindowToken;
+
+/// Color adjustments applied during presentation.
+pub mod adjustment;
+/// Capturing of the presented frames.
+pub mod capture;
+/// Structured backend settings.
+pub mod config;
+/// Bulk conversion of the render surface regions.
+pub mod convert;
+/// Software cursor images.
+pub mod cursor;
+/// Tracking of the render surface regions changed between presentations.
+pub mod damage;
+/// Diagnostics helpers shared between backends.
+pub mod diagnostics;
+/// Debug magnification of the presented render surface.
+pub mod magnifier;
+/// Coalescing of high-frequency cursor motion.
+pub mod motion;
+/// Platform abstractions, e.g. time measurement working in the browser.
+pub mod platform;
+/// Following of the captured pointer outside of the window.
+pub mod pointer;
+/// Savestates of the application state.
+pub mod savestate;
+/// Texel formats converters can target.
+pub mod texel;
+/// Additional application windows.
+pub mod window;
+/// Classification of the winit window events.
+#[cfg(feature = "winit")]
+pub mod winit_event;
+
 /// Middleware trait.
 pub trait Middleware<'a, Control> {
     /// Event type to be handled;
@@ -35,6 +71,12 @@ pub trait Middleware<'a, Control> {
     /// Provide context for the application update process.
     fn update(&'a mut self, control: &'a mut Control, delta: Duration) -> Self::Context;
 
+    /// Get categories of events this middleware is interested in.
+    /// Backends skip passing events of other categories to `handle_event`.
+    fn event_interest(&self) -> EventCategories {
+        EventCategories::ALL
+    }
+
     /// Handle event generated by the backend, return it if not consumed.
     fn handle_event(
         &mut self,
@@ -45,6 +87,93 @@ pub trait Middleware<'a, Control> {
 
     /// Provide render context for the application to draw on.
     fn render(&'a mut self, surface: Self::Surface) -> Self::RenderTarget;
+
+    /// Handle event generated by the additional `window`, return it if not consumed.
+    fn handle_window_event(
+        &mut self,
+        window: WindowToken,
+        event: Self::Event,
+        event_context: Self::EventContext,
+        control: &mut Control,
+    ) -> Option<Self::Event> {
+        let _ = (window, event_context, control);
+        Some(event)
+    }
+
+    /// Provide render context for the application to draw on the additional `window`.
+    /// Returns `None` if the middleware does not render the window.
+    fn render_window(
+        &'a mut self,
+        window: WindowToken,
+        surface: Self::Surface,
+    ) -> Option<Self::RenderTarget> {
+        let _ = (window, surface);
+        None
+    }
+}
+
+/// Set of event categories.
+/// Events not belonging to any category are always passed to the middleware.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EventCategories(u32);
+
+impl EventCategories {
+    /// No categories.
+    pub const NONE: Self = Self(0);
+    /// Keyboard and text input events.
+    pub const KEYBOARD: Self = Self(1);
+    /// Cursor movement, entering and leaving events.
+    pub const CURSOR: Self = Self(1 << 1);
+    /// Mouse button and wheel events.
+    pub const MOUSE: Self = Self(1 << 2);
+    /// Touch and touchpad events.
+    pub const TOUCH: Self = Self(1 << 3);
+    /// File hovering and dropping events.
+    pub const FILE_DROPS: Self = Self(1 << 4);
+    /// Raw device motion events.
+    pub const DEVICE_MOTION: Self = Self(1 << 5);
+    /// All the categories.
+    pub const ALL: Self = Self(u32::MAX);
+
+    /// Get union of two sets of categories.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Check if all of the `other` categories are present in this set.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl Default for EventCategories {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl BitOr for EventCategories {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for EventCategories {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+/// Request sent to the running backend from other threads through its event loop proxy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackendRequest {
+    /// Wake the event loop up, e.g. to run the update of the idle application.
+    Wake,
+    /// Wake the event loop up, restore the window and focus it,
+    /// e.g. when another instance of the application is launched.
+    Activate,
 }
 
 /// Application trait.
@@ -58,6 +187,17 @@ pub trait Application<'a, Init, Context, RenderSurface, Converter> {
     /// Render on the surface passed by the Middleware.
     fn render(&mut self, render_surface: &mut RenderSurface);
 
+    /// Register interpolation factor in `[0.0, 1.0]` range between the last two fixed timestep updates
+    /// to be used by the following render.
+    fn interpolate(&mut self, alpha: f32) {
+        let _ = alpha;
+    }
+
+    /// Render on the surface of the additional `window` passed by the Middleware.
+    fn render_window(&mut self, window: WindowToken, render_surface: &mut RenderSurface) {
+        let _ = (window, render_surface);
+    }
+
     /// Provide converter to convert data on the surface into `u32` values.
     fn converter(&self) -> Converter;
 
@@ -66,6 +206,19 @@ pub trait Application<'a, Init, Context, RenderSurface, Converter> {
 
     /// Register the resume event.
     fn resume(&mut self) {}
+
+    /// Serialize the application state for a savestate.
+    /// Returns `None` if savestates are not supported.
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Restore the application state from the savestate `data`.
+    /// Returns `false` if the state was not restored.
+    fn load_state(&mut self, data: &[u8]) -> bool {
+        let _ = data;
+        false
+    }
 }
 
 /// The surface for the Application to perform rendering on.
@@ -84,6 +237,43 @@ pub trait RenderSurface {
     /// # Panics
     /// For values outside of safe range may panic or may return garbage value.
     fn data(&self, x: usize, y: usize) -> Self::Data;
+
+    /// Get contiguous row of pixel data at `y` for bulk access.
+    /// Returns `None` if the row is out of bounds or the render surface does not store its rows contiguously.
+    fn row(&self, y: usize) -> Option<&[Self::Data]> {
+        let _ = y;
+        None
+    }
+
+    /// Resize the render surface, keeping its contents in the overlapping area if `preserve` is set.
+    /// Return `false` if the render surface does not support resizing.
+    fn resize(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        let _ = (width, height, preserve);
+        false
+    }
+
+    /// Get region changed since the last `clear_damage` call.
+    /// Render surfaces without damage tracking report the whole surface as changed.
+    /// Only the softbuffer backend presents the damaged region alone, other backends redraw the whole surface.
+    fn damage(&self) -> Damage {
+        Damage::Full
+    }
+
+    /// Forget the tracked damage after the render surface is presented.
+    fn clear_damage(&mut self) {}
+}
+
+/// Render surface resizing regardless of its data type.
+pub trait ResizeRenderSurface {
+    /// Resize the render surface, keeping its contents in the overlapping area if `preserve` is set.
+    /// Return `false` if the render surface does not support resizing.
+    fn resize(&mut self, width: usize, height: usize, preserve: bool) -> bool;
+}
+
+impl<T: RenderSurface> ResizeRenderSurface for T {
+    fn resize(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        RenderSurface::resize(self, width, height, preserve)
+    }
 }
 
 /// Converter from the Data value to `0xff_rr_gg_bb` format.
@@ -94,6 +284,17 @@ pub trait Converter {
     /// Convert passed data into `0xff_rr_gg_bb` value.
     /// `x` and `y` values represent pixel position in the surface, not in the target.
     fn convert(&self, x: usize, y: usize, data: Self::Data) -> u32;
+
+    /// Convert the row of `data` starting at `x` and `y` into `0xff_rr_gg_bb` values stored in `out`.
+    /// Override it for faster bulk conversion.
+    fn convert_row(&self, x: usize, y: usize, data: &[Self::Data], out: &mut [u32])
+    where
+        Self::Data: Clone,
+    {
+        for (index, (data, out)) in data.iter().zip(out).enumerate() {
+            *out = self.convert(x + index, y, data.clone());
+        }
+    }
 }
 
 /// Target to render to.
@@ -114,6 +315,137 @@ pub trait RenderTarget<Converter> {
     fn present(self, converter: Converter) -> Result<(), Self::PresentError>;
 }
 
+/// Frame presentation scheduling mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PresentMode {
+    /// Redraw once after each update tick.
+    #[default]
+    Timer,
+    /// Redraw in sync with the display refresh rate while updates stay fixed-timestep.
+    /// Falls back to 60 Hz when the refresh rate of the monitor is unknown.
+    Sync,
+}
+
+/// Window margins excluded from the presentation area, e.g. TV overscan or display notches.
+/// Margins are measured in physical window pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SafeArea {
+    left: u32,
+    top: u32,
+    right: u32,
+    bottom: u32,
+}
+
+impl SafeArea {
+    /// Create new safe area with the desired margins.
+    pub const fn new(left: u32, top: u32, right: u32, bottom: u32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Create new safe area with equal margins on each side.
+    pub const fn uniform(margin: u32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+
+    /// Get left margin.
+    pub fn left(&self) -> u32 {
+        self.left
+    }
+
+    /// Get top margin.
+    pub fn top(&self) -> u32 {
+        self.top
+    }
+
+    /// Get right margin.
+    pub fn right(&self) -> u32 {
+        self.right
+    }
+
+    /// Get bottom margin.
+    pub fn bottom(&self) -> u32 {
+        self.bottom
+    }
+
+    /// Get origin and dimensions of the safe part of the window with given dimensions.
+    pub fn apply(&self, dimensions: (u32, u32)) -> ((u32, u32), (u32, u32)) {
+        let left = self.left.min(dimensions.0);
+        let top = self.top.min(dimensions.1);
+        let width = dimensions
+            .0
+            .saturating_sub(self.left.saturating_add(self.right));
+        let height = dimensions
+            .1
+            .saturating_sub(self.top.saturating_add(self.bottom));
+        ((left, top), (width, height))
+    }
+}
+
+/// Placement of the scaled render surface within the presentation area.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Viewport {
+    /// Center the render surface.
+    #[default]
+    Centered,
+    /// Stick the render surface to the top left corner.
+    TopLeft,
+    /// Offset the render surface from the top left corner by the amount of physical pixels.
+    /// The offset is reduced if the render surface would not fit otherwise.
+    Offset(u32, u32),
+}
+
+impl Viewport {
+    /// Get origin of the content with `content` dimensions placed in the area with `area` origin and dimensions.
+    /// The origin may be negative if the content does not fit into the area.
+    pub fn origin(&self, area: ((u32, u32), (u32, u32)), content: (u32, u32)) -> (i32, i32) {
+        let ((left, top), (width, height)) = area;
+        let free = (
+            width as i32 - content.0 as i32,
+            height as i32 - content.1 as i32,
+        );
+        let (x, y) = match *self {
+            Viewport::Centered => (free.0 / 2, free.1 / 2),
+            Viewport::TopLeft => (0, 0),
+            Viewport::Offset(x, y) => (
+                (x.min(i32::MAX as u32) as i32).min(free.0.max(0)),
+                (y.min(i32::MAX as u32) as i32).min(free.1.max(0)),
+            ),
+        };
+        (left as i32 + x, top as i32 + y)
+    }
+}
+
+/// Integer scale selection of the render surface within the presentation area.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScaleMode {
+    /// Use the largest integer scale fitting into the area.
+    #[default]
+    Auto,
+    /// Use the provided scale, reduced if the render surface would not fit otherwise.
+    Fixed(u32),
+}
+
+impl ScaleMode {
+    /// Get integer scale of the content with `content` dimensions placed in the area with `area` dimensions.
+    /// Zero means that the content does not fit even without scaling.
+    pub fn scale(&self, area: (u32, u32), content: (u32, u32)) -> u32 {
+        let fit = (area.0 / content.0.max(1)).min(area.1 / content.1.max(1));
+        match *self {
+            ScaleMode::Auto => fit,
+            ScaleMode::Fixed(scale) => fit.min(scale.max(1)),
+        }
+    }
+}
+
 /// Context to be passed to the Application during the update routine.
 pub trait Context<'a, Input> {
     /// Get stored input system.
@@ -153,3 +485,22 @@ pub trait EventContext {
         position: (f32, f32),
     ) -> Result<(i32, i32), (i32, i32)>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_area_shrinks_the_window() {
+        let safe_area = SafeArea::new(10, 20, 30, 40);
+        assert_eq!(safe_area.apply((200, 100)), ((10, 20), (160, 40)));
+    }
+
+    #[test]
+    fn safe_area_saturates_huge_margins() {
+        let safe_area = SafeArea::uniform(u32::MAX);
+        assert_eq!(safe_area.apply((640, 480)), ((640, 480), (0, 0)));
+        let safe_area = SafeArea::new(0, 0, u32::MAX, u32::MAX);
+        assert_eq!(safe_area.apply((640, 480)), ((0, 0), (0, 0)));
+    }
+}
@@ -0,0 +1,154 @@
+/// Minimal magnifier zoom.
+pub const MIN_ZOOM: u32 = 2;
+/// Maximal magnifier zoom.
+pub const MAX_ZOOM: u32 = 16;
+
+/// Debug magnifier zooming into the presented render surface.
+///
+/// It only affects presentation: the render surface is rendered and addressed as usual.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Magnifier {
+    active: bool,
+    zoom: u32,
+    center: (f32, f32),
+}
+
+impl Magnifier {
+    /// Create new inactive magnifier with `4x` zoom.
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            zoom: 4,
+            center: (0.0, 0.0),
+        }
+    }
+
+    /// Set zoom, clamped to the `MIN_ZOOM..=MAX_ZOOM` range.
+    pub fn with_zoom(self, zoom: u32) -> Self {
+        let mut magnifier = self;
+        magnifier.set_zoom(zoom);
+        magnifier
+    }
+
+    /// Check if the magnifier is active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Activate or deactivate the magnifier.
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    /// Get zoom.
+    pub fn zoom(&self) -> u32 {
+        self.zoom
+    }
+
+    /// Set zoom, clamped to the `MIN_ZOOM..=MAX_ZOOM` range.
+    pub fn set_zoom(&mut self, zoom: u32) {
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    /// Get center of the view in render surface pixels.
+    pub fn center(&self) -> (f32, f32) {
+        self.center
+    }
+
+    /// Set center of the view in render surface pixels.
+    pub fn set_center(&mut self, center: (f32, f32)) {
+        self.center = center;
+    }
+
+    /// Change zoom by `steps`, clamped to the `MIN_ZOOM..=MAX_ZOOM` range.
+    pub fn zoom_by(&mut self, steps: i32) {
+        self.set_zoom(self.zoom.saturating_add_signed(steps));
+    }
+
+    /// Move the view to follow the cursor dragged by `delta` unmagnified render surface pixels.
+    /// The center is kept inside of the render surface of `resolution` size.
+    pub fn drag(&mut self, delta: (f32, f32), resolution: (usize, usize)) {
+        let zoom = self.zoom as f32;
+        self.center = (
+            (self.center.0 - delta.0 / zoom).clamp(0.0, resolution.0 as f32),
+            (self.center.1 - delta.1 / zoom).clamp(0.0, resolution.1 as f32),
+        );
+    }
+
+    /// Get top left corner of the visible area of the render surface of `resolution` size.
+    pub fn origin(&self, resolution: (usize, usize)) -> (usize, usize) {
+        let zoom = self.zoom as usize;
+        let axis = |center: f32, length: usize| {
+            let visible = length.div_ceil(zoom);
+            let start = (center - visible as f32 / 2.0).max(0.0) as usize;
+            start.min(length - visible)
+        };
+        (
+            axis(self.center.0, resolution.0),
+            axis(self.center.1, resolution.1),
+        )
+    }
+
+    /// Map presented pixel `position` into the render surface pixel shown there.
+    pub fn source(&self, position: (usize, usize), resolution: (usize, usize)) -> (usize, usize) {
+        if !self.active {
+            return position;
+        }
+        let origin = self.origin(resolution);
+        (
+            (origin.0 + position.0 / self.zoom as usize).min(resolution.0 - 1),
+            (origin.1 + position.1 / self.zoom as usize).min(resolution.1 - 1),
+        )
+    }
+}
+
+impl Default for Magnifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Interactive magnifier controls: toggling with the cursor and panning by dragging.
+/// Positions are in unmagnified render surface pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MagnifierControls {
+    pan_anchor: Option<(f32, f32)>,
+}
+
+impl MagnifierControls {
+    /// Create new controls without panning in progress.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Toggle the `magnifier`, centering it at the `cursor` when activated.
+    pub fn toggle(&mut self, magnifier: &mut Magnifier, cursor: (f32, f32)) {
+        let active = !magnifier.is_active();
+        if active {
+            magnifier.set_center(cursor);
+        }
+        magnifier.set_active(active);
+        self.pan_anchor = None;
+    }
+
+    /// Start panning from the `cursor` if `pressed`, stop it otherwise.
+    pub fn set_panning(&mut self, pressed: bool, cursor: (f32, f32)) {
+        self.pan_anchor = pressed.then_some(cursor);
+    }
+
+    /// Pan the `magnifier` of the render surface of `resolution` size following the `cursor`.
+    /// Return `true` if the view is moved.
+    pub fn pan(
+        &mut self,
+        magnifier: &mut Magnifier,
+        cursor: (f32, f32),
+        resolution: (usize, usize),
+    ) -> bool {
+        let Some(anchor) = self.pan_anchor else {
+            return false;
+        };
+        magnifier.drag((cursor.0 - anchor.0, cursor.1 - anchor.1), resolution);
+        self.pan_anchor = Some(cursor);
+        true
+    }
+}
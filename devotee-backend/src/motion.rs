@@ -0,0 +1,85 @@
+/// Cursor motion aggregated between application updates.
+///
+/// Backends register every cursor movement here, even if the events are coalesced
+/// before reaching the middleware.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CursorMotion {
+    position: Option<(f32, f32)>,
+    delta: (f64, f64),
+    count: usize,
+    recording: bool,
+    positions: Vec<(f32, f32)>,
+}
+
+impl CursorMotion {
+    /// Create new motion aggregate without positions recording.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable or disable recording of every cursor position.
+    pub fn with_recording(self, recording: bool) -> Self {
+        let mut motion = self;
+        motion.set_recording(recording);
+        motion
+    }
+
+    /// Enable or disable recording of every cursor position.
+    /// Disabling recording drops the recorded positions.
+    pub fn set_recording(&mut self, recording: bool) -> &mut Self {
+        self.recording = recording;
+        if !recording {
+            self.positions = Vec::new();
+        }
+        self
+    }
+
+    /// Check if every cursor position is recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Get the latest known cursor position in window space.
+    pub fn position(&self) -> Option<(f32, f32)> {
+        self.position
+    }
+
+    /// Get raw device motion accumulated since the last update.
+    /// It is only reported while the backend listens to device events.
+    pub fn delta(&self) -> (f64, f64) {
+        self.delta
+    }
+
+    /// Get number of cursor movement events since the last update.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get every cursor position in window space since the last update.
+    /// The positions are only kept while recording is enabled.
+    pub fn positions(&self) -> &[(f32, f32)] {
+        &self.positions
+    }
+
+    /// Register cursor movement to the `position` in window space.
+    pub fn register_position(&mut self, position: (f32, f32)) {
+        self.position = Some(position);
+        self.count += 1;
+        if self.recording {
+            self.positions.push(position);
+        }
+    }
+
+    /// Register raw device motion by `delta`.
+    pub fn register_delta(&mut self, delta: (f64, f64)) {
+        self.delta.0 += delta.0;
+        self.delta.1 += delta.1;
+    }
+
+    /// Start aggregating the next update period, keeping the latest position.
+    pub fn reset(&mut self) {
+        self.delta = (0.0, 0.0);
+        self.count = 0;
+        self.positions.clear();
+    }
+}
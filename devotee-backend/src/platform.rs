@@ -0,0 +1,24 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub use web_time::Instant;
+
+/// Bound on the types moved into the backend event loop.
+///
+/// The browser event loop is spawned and outlives the `run` call, so on `wasm32` the types must be `'static`.
+/// On other targets there is no bound.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeStatic: 'static {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeStatic for T where T: 'static {}
+
+/// Bound on the types moved into the backend event loop.
+///
+/// The browser event loop is spawned and outlives the `run` call, so on `wasm32` the types must be `'static`.
+/// On other targets there is no bound.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeStatic {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T> MaybeStatic for T where T: ?Sized {}
@@ -0,0 +1,53 @@
+/// Cursor state used to keep following the captured pointer outside of the window.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PointerTracker {
+    captured: bool,
+    inside: bool,
+    reported: bool,
+    position: (f64, f64),
+}
+
+impl PointerTracker {
+    /// Create new tracker of the released pointer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if the pointer is captured.
+    pub fn is_captured(&self) -> bool {
+        self.captured
+    }
+
+    /// Set the pointer captured state.
+    pub fn set_captured(&mut self, captured: bool) {
+        self.captured = captured;
+    }
+
+    /// Register the cursor entering the window.
+    pub fn enter(&mut self) {
+        self.inside = true;
+    }
+
+    /// Register the cursor leaving the window.
+    pub fn leave(&mut self) {
+        self.inside = false;
+        self.reported = false;
+    }
+
+    /// Register the cursor `position` reported by the platform.
+    pub fn moved(&mut self, position: (f64, f64)) {
+        self.position = position;
+        // The platform keeps reporting the cursor outside of the window by itself.
+        self.reported = !self.inside;
+    }
+
+    /// Apply raw pointer motion `delta`.
+    /// Return the cursor position to report if the platform does not report it itself.
+    pub fn follow(&mut self, delta: (f64, f64)) -> Option<(f64, f64)> {
+        if !self.captured || self.inside || self.reported {
+            return None;
+        }
+        self.position = (self.position.0 + delta.0, self.position.1 + delta.1);
+        Some(self.position)
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "savestate")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "savestate")]
+use serde::Serialize;
+
+/// Serialized application state along with the update tick it was taken at.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Savestate {
+    tick: u64,
+    data: Vec<u8>,
+}
+
+impl Savestate {
+    /// Create new savestate of the `data` taken at the `tick`.
+    pub fn new(tick: u64, data: Vec<u8>) -> Self {
+        Self { tick, data }
+    }
+
+    /// Get the update tick the savestate was taken at.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Get the serialized application state.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Savestates stored in numbered slots.
+#[derive(Clone, Debug, Default)]
+pub struct Savestates {
+    slots: BTreeMap<usize, Savestate>,
+}
+
+impl Savestates {
+    /// Create new empty savestates storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get savestate in the `slot`.
+    pub fn get(&self, slot: usize) -> Option<&Savestate> {
+        self.slots.get(&slot)
+    }
+
+    /// Store the `savestate` in the `slot`, returning the replaced one.
+    pub fn insert(&mut self, slot: usize, savestate: Savestate) -> Option<Savestate> {
+        self.slots.insert(slot, savestate)
+    }
+
+    /// Remove savestate from the `slot`.
+    pub fn remove(&mut self, slot: usize) -> Option<Savestate> {
+        self.slots.remove(&slot)
+    }
+
+    /// Iterate over occupied slots and their savestates.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Savestate)> {
+        self.slots
+            .iter()
+            .map(|(slot, savestate)| (*slot, savestate))
+    }
+}
+
+/// Request to save or load the savestate, processed by the backend after the current event.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SavestateRequest {
+    /// Save the application state into the slot.
+    Save(usize),
+    /// Load the application state from the slot.
+    Load(usize),
+}
+
+/// State which can be captured and restored with serde, e.g. for emulator-style savestates.
+///
+/// Implement it for the game state, including random number generators and tick counters,
+/// and use `encode` and `decode` in `Application::save_state` and `Application::load_state`.
+#[cfg(feature = "savestate")]
+pub trait Snapshot {
+    /// Serializable representation of the state.
+    type State: Serialize + DeserializeOwned;
+
+    /// Capture the current state.
+    fn snapshot(&self) -> Self::State;
+
+    /// Restore the previously captured state.
+    fn restore(&mut self, state: Self::State);
+}
+
+/// Capture and serialize the `value` state.
+/// Returns `None` if the state could not be serialized.
+#[cfg(feature = "savestate")]
+pub fn encode<S>(value: &S) -> Option<Vec<u8>>
+where
+    S: Snapshot + ?Sized,
+{
+    bincode::serialize(&value.snapshot()).ok()
+}
+
+/// Deserialize and restore the `value` state from the `data`.
+/// Returns `false` leaving the `value` intact if the data could not be deserialized.
+#[cfg(feature = "savestate")]
+pub fn decode<S>(value: &mut S, data: &[u8]) -> bool
+where
+    S: Snapshot + ?Sized,
+{
+    match bincode::deserialize(data) {
+        Ok(state) => {
+            value.restore(state);
+            true
+        }
+        Err(_) => false,
+    }
+}
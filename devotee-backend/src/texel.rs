@@ -0,0 +1,76 @@
+use crate::Converter;
+
+/// Texel format packable into the `0xff_rr_gg_bb` value expected by the presentation buffers.
+pub trait Texel: Copy {
+    /// Pack the texel into the `0xff_rr_gg_bb` value.
+    fn pack(self) -> u32;
+}
+
+impl Texel for u32 {
+    fn pack(self) -> u32 {
+        self | 0xff_00_00_00
+    }
+}
+
+/// Packed 16 bit texel with 5 bits of red, 6 bits of green and 5 bits of blue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgb565(pub u16);
+
+impl Rgb565 {
+    /// Create new texel from 8 bit channel values, dropping the lower bits.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self(((r as u16 >> 3) << 11) | ((g as u16 >> 2) << 5) | (b as u16 >> 3))
+    }
+}
+
+impl Texel for Rgb565 {
+    fn pack(self) -> u32 {
+        let r = ((self.0 >> 11) & 0x1f) as u32;
+        let g = ((self.0 >> 5) & 0x3f) as u32;
+        let b = (self.0 & 0x1f) as u32;
+        // Replicate the upper bits so the full range is covered.
+        let r = (r << 3) | (r >> 2);
+        let g = (g << 2) | (g >> 4);
+        let b = (b << 3) | (b >> 2);
+        0xff_00_00_00 | (r << 16) | (g << 8) | b
+    }
+}
+
+/// 8 bit grayscale texel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Gray8(pub u8);
+
+impl Texel for Gray8 {
+    fn pack(self) -> u32 {
+        let value = self.0 as u32;
+        0xff_00_00_00 | (value << 16) | (value << 8) | value
+    }
+}
+
+/// Converter from the Data value to a specific texel format.
+pub trait TexelConverter {
+    /// Data to be converted from.
+    type Data;
+
+    /// Texel format to be converted into.
+    type Texel: Texel;
+
+    /// Convert passed data into the texel.
+    /// `x` and `y` values represent pixel position in the surface, not in the target.
+    fn convert_texel(&self, x: usize, y: usize, data: Self::Data) -> Self::Texel;
+}
+
+/// Converter adapter packing texels of the wrapped `TexelConverter` into `0xff_rr_gg_bb` values.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Packed<Inner>(pub Inner);
+
+impl<Inner> Converter for Packed<Inner>
+where
+    Inner: TexelConverter,
+{
+    type Data = Inner::Data;
+
+    fn convert(&self, x: usize, y: usize, data: Self::Data) -> u32 {
+        self.0.convert_texel(x, y, data).pack()
+    }
+}
@@ -0,0 +1,186 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identifier of the additional window opened by the application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WindowToken(u64);
+
+impl WindowToken {
+    /// Create new token with the raw `id`.
+    /// Backends hand out unique tokens, use this only to implement a backend.
+    pub const fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    /// Get raw id of the token.
+    pub const fn id(self) -> u64 {
+        self.0
+    }
+}
+
+/// Window icon image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WindowIcon {
+    rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl WindowIcon {
+    /// Create new icon from the `rgba` bytes, four bytes per pixel, row by row.
+    pub fn new(rgba: Vec<u8>, width: u32, height: u32) -> Self {
+        Self {
+            rgba,
+            width,
+            height,
+        }
+    }
+
+    /// Create new icon of the `width` and `height` from `0xaa_rr_gg_bb` pixels provided by the `pixel` function.
+    pub fn from_fn<F>(width: u32, height: u32, mut pixel: F) -> Self
+    where
+        F: FnMut(u32, u32) -> u32,
+    {
+        let mut rgba = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let [a, r, g, b] = pixel(x, y).to_be_bytes();
+                rgba.extend_from_slice(&[r, g, b, a]);
+            }
+        }
+        Self::new(rgba, width, height)
+    }
+
+    /// Get the icon bytes, four bytes per pixel.
+    pub fn rgba(&self) -> &[u8] {
+        &self.rgba
+    }
+
+    /// Get icon width in pixels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get icon height in pixels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// Window settings applied when the window is created, so it appears correctly from the first frame.
+///
+/// Backends ignore settings they do not support.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct WindowConfig {
+    title: String,
+    size: Option<(u32, u32)>,
+    resizable: bool,
+    decorations: bool,
+    fullscreen: bool,
+    always_on_top: bool,
+    icon: Option<WindowIcon>,
+}
+
+impl WindowConfig {
+    /// Create new config of the resizable decorated window with the `title`.
+    /// The size is left to the middleware, e.g. the render surface resolution multiplied by the default scale.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            ..Self::default()
+        }
+    }
+
+    /// Set initial inner size of the window in physical pixels.
+    pub fn with_size(self, width: u32, height: u32) -> Self {
+        Self {
+            size: Some((width, height)),
+            ..self
+        }
+    }
+
+    /// Allow or forbid resizing the window.
+    pub fn with_resizable(self, resizable: bool) -> Self {
+        Self { resizable, ..self }
+    }
+
+    /// Enable or disable window decorations, e.g. title bar and borders.
+    pub fn with_decorations(self, decorations: bool) -> Self {
+        Self {
+            decorations,
+            ..self
+        }
+    }
+
+    /// Enable or disable borderless fullscreen on the current monitor.
+    pub fn with_fullscreen(self, fullscreen: bool) -> Self {
+        Self { fullscreen, ..self }
+    }
+
+    /// Keep the window above other windows.
+    pub fn with_always_on_top(self, always_on_top: bool) -> Self {
+        Self {
+            always_on_top,
+            ..self
+        }
+    }
+
+    /// Set window icon.
+    pub fn with_icon(self, icon: WindowIcon) -> Self {
+        Self {
+            icon: Some(icon),
+            ..self
+        }
+    }
+
+    /// Get window title.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get initial inner size of the window in physical pixels, if set.
+    pub fn size(&self) -> Option<(u32, u32)> {
+        self.size
+    }
+
+    /// Check if the window can be resized.
+    pub fn resizable(&self) -> bool {
+        self.resizable
+    }
+
+    /// Check if the window is decorated.
+    pub fn decorations(&self) -> bool {
+        self.decorations
+    }
+
+    /// Check if the window is fullscreen.
+    pub fn fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Check if the window is kept above other windows.
+    pub fn always_on_top(&self) -> bool {
+        self.always_on_top
+    }
+
+    /// Get window icon, if set.
+    pub fn icon(&self) -> Option<&WindowIcon> {
+        self.icon.as_ref()
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: String::new(),
+            size: None,
+            resizable: true,
+            decorations: true,
+            fullscreen: false,
+            always_on_top: false,
+            icon: None,
+        }
+    }
+}
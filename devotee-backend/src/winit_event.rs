@@ -0,0 +1,38 @@
+use winit::event::{ElementState, WindowEvent};
+
+use crate::EventCategories;
+
+/// Get category of the window `event`.
+/// Events outside of any category, e.g. resizing or focus changes, get `EventCategories::NONE`.
+pub fn event_category(event: &WindowEvent) -> EventCategories {
+    match event {
+        WindowEvent::KeyboardInput { .. }
+        | WindowEvent::ModifiersChanged(_)
+        | WindowEvent::Ime(_) => EventCategories::KEYBOARD,
+        WindowEvent::CursorMoved { .. }
+        | WindowEvent::CursorEntered { .. }
+        | WindowEvent::CursorLeft { .. } => EventCategories::CURSOR,
+        WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. } => EventCategories::MOUSE,
+        WindowEvent::Touch(_)
+        | WindowEvent::TouchpadMagnify { .. }
+        | WindowEvent::SmartMagnify { .. }
+        | WindowEvent::TouchpadRotate { .. }
+        | WindowEvent::TouchpadPressure { .. } => EventCategories::TOUCH,
+        WindowEvent::DroppedFile(_)
+        | WindowEvent::HoveredFile(_)
+        | WindowEvent::HoveredFileCancelled => EventCategories::FILE_DROPS,
+        WindowEvent::AxisMotion { .. } => EventCategories::DEVICE_MOTION,
+        _ => EventCategories::NONE,
+    }
+}
+
+/// Check if the `event` is a fresh key or mouse button press, ignoring key repeats.
+pub fn is_press(event: &WindowEvent) -> bool {
+    match event {
+        WindowEvent::KeyboardInput { event, .. } => {
+            event.state == ElementState::Pressed && !event.repeat
+        }
+        WindowEvent::MouseInput { state, .. } => *state == ElementState::Pressed,
+        _ => false,
+    }
+}
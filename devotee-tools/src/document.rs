@@ -0,0 +1,140 @@
+use devotee::util::vector::Vector;
+use devotee::visual::canvas::Canvas;
+use devotee::visual::prelude::*;
+
+/// Rectangular region in sprite space, inclusive of both corners.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Selection {
+    anchor: Vector<i32>,
+    corner: Vector<i32>,
+}
+
+impl Selection {
+    /// Start selection at the `anchor` point.
+    pub fn new(anchor: Vector<i32>) -> Self {
+        Self {
+            anchor,
+            corner: anchor,
+        }
+    }
+
+    /// Move the selection corner opposite to the anchor.
+    pub fn drag_to(&mut self, corner: Vector<i32>) {
+        self.corner = corner;
+    }
+
+    /// Get top left corner and dimensions of the selection.
+    pub fn rect(&self) -> (Vector<i32>, Vector<i32>) {
+        let from = Vector::new(
+            self.anchor.x().min(self.corner.x()),
+            self.anchor.y().min(self.corner.y()),
+        );
+        let to = Vector::new(
+            self.anchor.x().max(self.corner.x()),
+            self.anchor.y().max(self.corner.y()),
+        );
+        (from, to - from + (1, 1))
+    }
+}
+
+/// Set of edited sprites with undo history.
+pub struct Document {
+    sprites: Vec<Canvas<u8>>,
+    current: usize,
+    undo: Vec<(usize, Canvas<u8>)>,
+    redo: Vec<(usize, Canvas<u8>)>,
+}
+
+impl Document {
+    /// Create document of `count` blank sprites of `dimensions` size.
+    pub fn new(count: usize, dimensions: Vector<i32>) -> Self {
+        let sprite = Canvas::with_resolution(0, dimensions.x() as usize, dimensions.y() as usize);
+        Self {
+            sprites: vec![sprite; count],
+            current: 0,
+            undo: Vec::new(),
+            redo: Vec::new(),
+        }
+    }
+
+    pub fn sprites(&self) -> &[Canvas<u8>] {
+        &self.sprites
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.sprites.len() {
+            self.current = index;
+        }
+    }
+
+    pub fn sprite(&self) -> &Canvas<u8> {
+        &self.sprites[self.current]
+    }
+
+    /// Remember the current sprite state so the following changes can be undone.
+    pub fn checkpoint(&mut self) {
+        self.undo.push((self.current, self.sprite().clone()));
+        self.redo.clear();
+    }
+
+    /// Get the current sprite for modification.
+    /// Call `checkpoint` before the modification to make it undoable.
+    pub fn sprite_mut(&mut self) -> &mut Canvas<u8> {
+        &mut self.sprites[self.current]
+    }
+
+    /// Replace the sprite at `index` as an undoable change.
+    pub fn replace(&mut self, index: usize, sprite: Canvas<u8>) {
+        if let Some(slot) = self.sprites.get_mut(index) {
+            let previous = std::mem::replace(slot, sprite);
+            self.undo.push((index, previous));
+            self.redo.clear();
+        }
+    }
+
+    pub fn undo(&mut self) {
+        Self::step(
+            &mut self.sprites,
+            &mut self.undo,
+            &mut self.redo,
+            &mut self.current,
+        );
+    }
+
+    pub fn redo(&mut self) {
+        Self::step(
+            &mut self.sprites,
+            &mut self.redo,
+            &mut self.undo,
+            &mut self.current,
+        );
+    }
+
+    fn step(
+        sprites: &mut [Canvas<u8>],
+        from: &mut Vec<(usize, Canvas<u8>)>,
+        to: &mut Vec<(usize, Canvas<u8>)>,
+        current: &mut usize,
+    ) {
+        if let Some((index, sprite)) = from.pop() {
+            let previous = std::mem::replace(&mut sprites[index], sprite);
+            to.push((index, previous));
+            *current = index;
+        }
+    }
+
+    /// Fill the selection, or the whole sprite without one, with the `color` as an undoable change.
+    pub fn fill(&mut self, selection: Option<Selection>, color: u8) {
+        self.checkpoint();
+        let sprite = self.sprite_mut();
+        let (from, dimensions) =
+            selection.map_or((Vector::new(0, 0), sprite.dimensions()), |s| s.rect());
+        sprite
+            .painter::<i32>()
+            .rect_f(from, dimensions, paint(color));
+    }
+}
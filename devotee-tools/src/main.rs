@@ -0,0 +1,264 @@
+//! Minimal in-engine sprite editor built on top of the devotee APIs.
+
+use std::time::Duration;
+
+use devotee::app::root::Root;
+use devotee::app::App;
+use devotee::input::winit_input::{KeyCode, KeyboardMouse, MouseButton};
+use devotee::ui::PaletteEditor;
+use devotee::util::assets::Assets;
+use devotee::util::vector::Vector;
+use devotee::visual::atlas::Atlas;
+use devotee::visual::canvas::Canvas;
+use devotee::visual::palette::Palette;
+use devotee::visual::prelude::*;
+use devotee_backend::Context;
+use devotee_backend_softbuffer::{Error, SoftBackend, SoftContext, SoftInit, SoftMiddleware};
+
+use document::{Document, Selection};
+use storage::StorageError;
+
+mod document;
+mod storage;
+
+const RESOLUTION: (usize, usize) = (192, 128);
+const SPRITE: (i32, i32) = (16, 16);
+const SPRITES: usize = 6;
+const ZOOM: i32 = 6;
+const CANVAS_ORIGIN: (i32, i32) = (4, 4);
+const THUMBNAILS_ORIGIN: (i32, i32) = (4, 106);
+const THUMBNAIL_STEP: i32 = SPRITE.0 + 4;
+const PALETTE_PATH: &str = "palette.hex";
+const ATLAS_PATH: &str = "atlas.png";
+const SPRITES_GROUP: &str = "sprites";
+
+const BACKGROUND: u8 = 1;
+const FOREGROUND: u8 = 7;
+const ACCENT: u8 = 8;
+
+fn main() -> Result<(), Error> {
+    let backend = SoftBackend::try_new("sprite editor")?;
+    backend.run(
+        App::new(SpriteEditor::new()),
+        SoftMiddleware::new(
+            Canvas::with_resolution(BACKGROUND, RESOLUTION.0, RESOLUTION.1),
+            KeyboardMouse::new(),
+        )
+        .with_default_scale(4),
+        Duration::from_secs_f32(1.0 / 60.0),
+    )
+}
+
+struct SpriteEditor {
+    document: Document,
+    palette: Palette,
+    palette_editor: PaletteEditor,
+    assets: Assets<Canvas<u8>>,
+    color: u8,
+    selection: Option<Selection>,
+    selecting: bool,
+    painting: bool,
+}
+
+impl SpriteEditor {
+    fn new() -> Self {
+        Self {
+            document: Document::new(SPRITES, SPRITE.into()),
+            palette: Palette::new(vec![
+                0x000000, 0x1d2b53, 0x7e2553, 0x008751, 0xab5236, 0x5f574f, 0xc2c3c7, 0xfff1e8,
+                0xff004d, 0xffa300, 0xffec27, 0x00e436, 0x29adff, 0x83769c, 0xff77a8, 0xffccaa,
+            ]),
+            palette_editor: PaletteEditor::new()
+                .with_position((112, 4).into())
+                .with_path(PALETTE_PATH),
+            assets: Assets::new(),
+            color: FOREGROUND,
+            selection: None,
+            selecting: false,
+            painting: false,
+        }
+    }
+
+    /// Get sprite pixel under the render surface `position`.
+    fn sprite_position(position: Vector<i32>) -> Option<Vector<i32>> {
+        let (x, y) = ((position - CANVAS_ORIGIN) / ZOOM).split();
+        let inside = position.x() >= CANVAS_ORIGIN.0
+            && position.y() >= CANVAS_ORIGIN.1
+            && x < SPRITE.0
+            && y < SPRITE.1;
+        inside.then(|| Vector::new(x, y))
+    }
+
+    fn thumbnail_at(position: Vector<i32>) -> Option<usize> {
+        let (x, y) = (position - THUMBNAILS_ORIGIN).split();
+        let index = x / THUMBNAIL_STEP;
+        let inside = x >= 0 && y >= 0 && y < SPRITE.1 && x % THUMBNAIL_STEP < SPRITE.0;
+        (inside && (index as usize) < SPRITES).then_some(index as usize)
+    }
+
+    fn save(&self) -> Result<(), StorageError> {
+        self.palette_editor.save(&self.palette)?;
+        let mut atlas = Atlas::with_resolution(0, 64, 64).with_padding(1);
+        for (index, sprite) in self.document.sprites().iter().enumerate() {
+            storage::save_png(format!("sprite-{}.png", index), sprite, &self.palette)?;
+            atlas.insert(sprite);
+        }
+        storage::save_png(ATLAS_PATH, atlas.canvas(), &self.palette)
+    }
+
+    /// Load sprites back through the asset storage.
+    fn load(&mut self) -> Result<(), StorageError> {
+        self.palette_editor.load(&mut self.palette)?;
+        self.assets.unload_group(SPRITES_GROUP);
+        let mut handles = Vec::new();
+        for index in 0..SPRITES {
+            let sprite = storage::load_png(format!("sprite-{}.png", index), &self.palette)?;
+            handles.push(self.assets.insert_in_group(SPRITES_GROUP, sprite));
+        }
+        for (index, handle) in handles.iter().enumerate() {
+            if let Some(sprite) = self.assets.get(handle) {
+                self.document.replace(index, sprite.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_keyboard(&mut self, input: &KeyboardMouse) {
+        let keyboard = input.keyboard();
+        let control =
+            keyboard.is_pressed(KeyCode::ControlLeft) || keyboard.is_pressed(KeyCode::ControlRight);
+        if control && keyboard.just_pressed(KeyCode::KeyZ) {
+            self.document.undo();
+        }
+        if control && keyboard.just_pressed(KeyCode::KeyY) {
+            self.document.redo();
+        }
+        if control && keyboard.just_pressed(KeyCode::KeyS) {
+            if let Err(error) = self.save() {
+                eprintln!("Failed to save: {}", error);
+            }
+        }
+        if control && keyboard.just_pressed(KeyCode::KeyO) {
+            if let Err(error) = self.load() {
+                eprintln!("Failed to load: {}", error);
+            }
+        }
+        if keyboard.just_pressed(KeyCode::Delete) {
+            self.document.fill(self.selection, 0);
+        }
+        if keyboard.just_pressed(KeyCode::Escape) {
+            self.selection = None;
+        }
+    }
+
+    fn handle_mouse(&mut self, input: &KeyboardMouse) {
+        let (keyboard, mouse) = (input.keyboard(), input.mouse());
+        let position = mouse.position().any();
+        let sprite_position = Self::sprite_position(position);
+        let shift =
+            keyboard.is_pressed(KeyCode::ShiftLeft) || keyboard.is_pressed(KeyCode::ShiftRight);
+
+        if mouse.just_pressed(MouseButton::Left) {
+            if let Some(index) = Self::thumbnail_at(position) {
+                self.document.select(index);
+                self.selection = None;
+            }
+            if let Some(sprite_position) = sprite_position {
+                if shift {
+                    self.selection = Some(Selection::new(sprite_position));
+                    self.selecting = true;
+                } else {
+                    self.document.checkpoint();
+                    self.painting = true;
+                }
+            }
+        }
+        if !mouse.is_pressed(MouseButton::Left) {
+            self.selecting = false;
+            self.painting = false;
+        }
+
+        if let Some(sprite_position) = sprite_position {
+            if self.selecting {
+                if let Some(selection) = &mut self.selection {
+                    selection.drag_to(sprite_position);
+                }
+            } else if self.painting {
+                if let Some(pixel) = self.document.sprite_mut().pixel_mut(sprite_position) {
+                    *pixel = self.color;
+                }
+            }
+            if mouse.just_pressed(MouseButton::Right) {
+                if let Some(pixel) = self.document.sprite().pixel(sprite_position) {
+                    self.color = *pixel;
+                    self.palette_editor.select(self.color as usize);
+                }
+            }
+        }
+    }
+}
+
+impl Root<SoftInit<'_>, SoftContext<'_, KeyboardMouse>> for SpriteEditor {
+    type Converter = Palette;
+    type RenderSurface = Canvas<u8>;
+
+    fn init(&mut self, _: &mut SoftInit) {}
+
+    fn update(&mut self, context: &mut SoftContext<KeyboardMouse>) {
+        let input = context.input();
+        self.handle_keyboard(input);
+        self.handle_mouse(input);
+        self.palette_editor.update(input.mouse(), &mut self.palette);
+        self.color = self.palette_editor.selected() as u8;
+    }
+
+    fn render(&mut self, surface: &mut Self::RenderSurface) {
+        surface.clear(BACKGROUND);
+        let mut painter = surface.painter::<i32>();
+
+        let sprite = self.document.sprite();
+        for y in 0..SPRITE.1 {
+            for x in 0..SPRITE.0 {
+                let color = sprite.pixel((x, y).into()).map_or(0, |pixel| *pixel);
+                let origin = Vector::new(x, y) * ZOOM + CANVAS_ORIGIN;
+                painter.rect_f(origin, (ZOOM, ZOOM).into(), paint(color));
+            }
+        }
+        let frame = Vector::new(SPRITE.0, SPRITE.1) * ZOOM + (2, 2);
+        painter.rect_b(
+            Vector::from(CANVAS_ORIGIN) - (1, 1),
+            frame,
+            paint(FOREGROUND),
+        );
+        if let Some(selection) = self.selection {
+            let (from, dimensions) = selection.rect();
+            painter.rect_b(
+                from * ZOOM + CANVAS_ORIGIN,
+                dimensions * ZOOM,
+                paint(ACCENT),
+            );
+        }
+
+        for (index, sprite) in self.document.sprites().iter().enumerate() {
+            let origin = Vector::from(THUMBNAILS_ORIGIN) + (index as i32 * THUMBNAIL_STEP, 0);
+            painter.image(origin, sprite, stamp());
+            if index == self.document.current() {
+                painter.rect_b(
+                    origin - (1, 1),
+                    Vector::from(SPRITE) + (2, 2),
+                    paint(ACCENT),
+                );
+            }
+        }
+
+        self.palette_editor.render(
+            surface,
+            &self.palette,
+            PaletteEditor::indexed(BACKGROUND, FOREGROUND),
+        );
+    }
+
+    fn converter(&self) -> Self::Converter {
+        self.palette.clone()
+    }
+}
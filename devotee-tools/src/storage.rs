@@ -0,0 +1,107 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+use devotee::visual::canvas::Canvas;
+use devotee::visual::image::Image;
+use devotee::visual::import::{self, ImportError};
+use devotee::visual::palette::Palette;
+use devotee_backend::Converter;
+
+/// Sprite storage error enumeration.
+#[derive(Debug)]
+pub enum StorageError {
+    /// File access error.
+    IoError(io::Error),
+
+    /// PNG encoding error.
+    EncodingError(png::EncodingError),
+
+    /// Image decoding error.
+    ImportError(ImportError),
+
+    /// The image has no frames.
+    EmptyImage,
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IoError(error) => write!(f, "file access failed: {}", error),
+            Self::EncodingError(error) => write!(f, "PNG encoding failed: {}", error),
+            Self::ImportError(error) => write!(f, "image decoding failed: {:?}", error),
+            Self::EmptyImage => write!(f, "image has no frames"),
+        }
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl From<png::EncodingError> for StorageError {
+    fn from(value: png::EncodingError) -> Self {
+        Self::EncodingError(value)
+    }
+}
+
+impl From<ImportError> for StorageError {
+    fn from(value: ImportError) -> Self {
+        Self::ImportError(value)
+    }
+}
+
+/// Write indexed `image` into RGB PNG file, resolving colors with the `palette`.
+pub fn save_png<P: AsRef<Path>>(
+    path: P,
+    image: &Canvas<u8>,
+    palette: &Palette,
+) -> Result<(), StorageError> {
+    let (width, height) = image.dimensions().split();
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        width as u32,
+        height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut data = Vec::with_capacity((width * height * 3) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let index = image.pixel((x, y).into()).map_or(0, |pixel| *pixel);
+            let color = palette.convert(x as usize, y as usize, index);
+            data.extend_from_slice(&color.to_be_bytes()[1..]);
+        }
+    }
+    encoder.write_header()?.write_image_data(&data)?;
+    Ok(())
+}
+
+/// Read PNG file into indexed image, mapping colors to the closest `palette` entries.
+pub fn load_png<P: AsRef<Path>>(path: P, palette: &Palette) -> Result<Canvas<u8>, StorageError> {
+    let colors = palette.colors().to_vec();
+    let animation = import::png::decode(BufReader::new(File::open(path)?), |[r, g, b, _]| {
+        closest(&colors, [r, g, b])
+    })?;
+    animation
+        .frames()
+        .first()
+        .map(|(frame, _)| frame.clone())
+        .ok_or(StorageError::EmptyImage)
+}
+
+fn closest(colors: &[u32], color: [u8; 3]) -> u8 {
+    let distance = |candidate: u32| {
+        let [_, r, g, b] = candidate.to_be_bytes();
+        [(r, color[0]), (g, color[1]), (b, color[2])]
+            .into_iter()
+            .map(|(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    (0..colors.len())
+        .min_by_key(|index| distance(colors[*index]))
+        .unwrap_or(0) as u8
+}
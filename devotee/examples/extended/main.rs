@@ -2,12 +2,12 @@ use std::time::Duration;
 
 use devotee::app::root::Root;
 use devotee::app::App;
+use devotee::extras::TwoColors;
 use devotee::input::winit_input::{KeyCode, Keyboard};
 use devotee::util::vector::Vector;
 use devotee::visual::canvas::Canvas;
 use devotee::visual::prelude::*;
-use devotee_backend::{Context, Converter};
-use devotee_backend_softbuffer::{Error, SoftBackend, SoftContext, SoftInit, SoftMiddleware};
+use devotee_backend_softbuffer::{Error, SoftBackend, SoftMiddleware};
 
 fn main() -> Result<(), Error> {
     let backend = SoftBackend::try_new("extended")?;
@@ -19,17 +19,20 @@ fn main() -> Result<(), Error> {
 }
 
 #[derive(Default)]
-struct Extended {
+pub struct Extended {
     counter: f32,
 }
 
-impl Root<SoftInit<'_>, SoftContext<'_, Keyboard>> for Extended {
-    type Converter = BlackWhiteConverter;
+impl<'a, Init, Context> Root<Init, Context> for Extended
+where
+    Context: devotee_backend::Context<'a, Keyboard>,
+{
+    type Converter = TwoColors;
     type RenderSurface = Canvas<bool>;
 
-    fn init(&mut self, _: &mut SoftInit) {}
+    fn init(&mut self, _: &mut Init) {}
 
-    fn update(&mut self, context: &mut SoftContext<Keyboard>) {
+    fn update(&mut self, context: &mut Context) {
         if context.input().just_pressed(KeyCode::Escape) {
             context.shutdown();
         }
@@ -51,20 +54,6 @@ impl Root<SoftInit<'_>, SoftContext<'_, Keyboard>> for Extended {
     }
 
     fn converter(&self) -> Self::Converter {
-        BlackWhiteConverter
-    }
-}
-
-struct BlackWhiteConverter;
-
-impl Converter for BlackWhiteConverter {
-    type Data = bool;
-
-    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
-        if data {
-            0xffffffff
-        } else {
-            0xff000000
-        }
+        TwoColors::black_white()
     }
 }
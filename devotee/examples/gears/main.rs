@@ -3,16 +3,18 @@ use std::time::Duration;
 
 use devotee::app::root::Root;
 use devotee::app::App;
+use devotee::extras::TwoColors;
 use devotee::input::winit_input::{KeyCode, Keyboard};
+use devotee::util::transform::Transform2;
 use devotee::util::vector::Vector;
 use devotee::visual::canvas::Canvas;
 use devotee::visual::{paint, Paint, PaintTarget, Painter};
-use devotee_backend::{Context, Converter};
-use devotee_backend_softbuffer::{Error, SoftBackend, SoftContext, SoftInit, SoftMiddleware};
-use winit::window::Fullscreen;
+use devotee_backend::window::WindowConfig;
+use devotee_backend_softbuffer::{Error, SoftBackend, SoftMiddleware};
 
 fn main() -> Result<(), Error> {
-    let backend = SoftBackend::try_new("gears")?;
+    let backend =
+        SoftBackend::try_with_window_config(&WindowConfig::new("gears").with_fullscreen(true))?;
     backend.run(
         App::new(Gears::new()),
         SoftMiddleware::new(Canvas::with_resolution(false, 320, 240), Keyboard::new())
@@ -21,13 +23,13 @@ fn main() -> Result<(), Error> {
     )
 }
 
-struct Gears {
+pub struct Gears {
     drive_gear: Gear,
     driven_gear: Gear,
 }
 
 impl Gears {
-    fn new() -> Self {
+    pub fn new() -> Self {
         let mut drive_gear = Gear::new_gear(128.0, 20);
         drive_gear.center = Vector::new(0.0, 32.0);
         let mut driven_gear = Gear::new_gear(384.0, 60);
@@ -39,20 +41,25 @@ impl Gears {
     }
 }
 
-impl Root<SoftInit<'_>, SoftContext<'_, Keyboard>> for Gears {
-    type Converter = TwoConverter;
-    type RenderSurface = Canvas<bool>;
+impl Default for Gears {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    fn init(&mut self, init: &mut SoftInit) {
-        init.control()
-            .window_ref()
-            .set_fullscreen(Some(Fullscreen::Borderless(None)));
+impl<'a, Init, Context> Root<Init, Context> for Gears
+where
+    Context: devotee_backend::Context<'a, Keyboard>,
+{
+    type Converter = TwoColors;
+    type RenderSurface = Canvas<bool>;
 
+    fn init(&mut self, _: &mut Init) {
         self.driven_gear.angle =
             -self.drive_gear.angle / 3.0 + PI / self.driven_gear.teeth_count as f32;
     }
 
-    fn update(&mut self, context: &mut SoftContext<Keyboard>) {
+    fn update(&mut self, context: &mut Context) {
         let keyboard = context.input();
 
         if keyboard.is_pressed(KeyCode::Space) {
@@ -74,21 +81,7 @@ impl Root<SoftInit<'_>, SoftContext<'_, Keyboard>> for Gears {
     }
 
     fn converter(&self) -> Self::Converter {
-        TwoConverter
-    }
-}
-
-struct TwoConverter;
-
-impl Converter for TwoConverter {
-    type Data = bool;
-
-    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
-        if data {
-            0xffc0b0a0
-        } else {
-            0xff101020
-        }
+        TwoColors::new(0xff101020, 0xffc0b0a0)
     }
 }
 
@@ -134,11 +127,11 @@ impl Gear {
 
             for i in 0..=GEAR_PRECISION {
                 let a = (i as f32) / (GEAR_PRECISION as f32) * a_max;
-                tooth.push(rotate_vector((x(a), y(a)).into(), -ang_extra));
+                tooth.push(Transform2::rotation(-ang_extra).apply((x(a), y(a)).into()));
             }
             for i in (0..=GEAR_PRECISION).rev() {
                 let a = i as f32 / GEAR_PRECISION as f32 * a_max;
-                tooth.push(rotate_vector((x(a), -y(a)).into(), ang_extra));
+                tooth.push(Transform2::rotation(ang_extra).apply((x(a), -y(a)).into()));
             }
 
             (r, tooth)
@@ -157,11 +150,12 @@ impl Gear {
         painter.set_offset(self.center);
         for i in 0..=self.teeth_count {
             let angle = i as f32 * 2.0 * PI / self.teeth_count as f32 + self.angle;
+            let rotation = Transform2::rotation(angle);
             let tooth = self
                 .tooth
                 .iter()
                 .copied()
-                .map(|v| rotate_vector(v, angle))
+                .map(|v| rotation.apply(v))
                 .collect::<Vec<_>>();
             painter.polygon_f(&tooth, paint(true));
         }
@@ -174,7 +168,8 @@ impl Gear {
         for i in 0..3 {
             let angle = PI * 2.0 * i as f32 / 3.0;
             painter.circle_f(
-                rotate_vector((self.internal_radius / 2.0, 0.0).into(), self.angle + angle),
+                Transform2::rotation(self.angle + angle)
+                    .apply((self.internal_radius / 2.0, 0.0).into()),
                 self.internal_radius / 4.0,
                 |x, y, _| (x + y) % 2 == 0,
             );
@@ -186,12 +181,3 @@ impl Gear {
         );
     }
 }
-
-fn rotate_vector(vector: Vector<f32>, rotation: f32) -> Vector<f32> {
-    let cos = rotation.cos();
-    let sin = rotation.sin();
-    Vector::new(
-        vector.x() * cos - vector.y() * sin,
-        vector.y() * cos + vector.x() * sin,
-    )
-}
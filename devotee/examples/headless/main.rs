@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use devotee::app::root::Root;
+use devotee::app::App;
+use devotee::diagnostics::dump_ansi;
+use devotee::extras::{TwoColors, XrgbConverter};
+use devotee::input::winit_input::NoInput;
+use devotee::visual::canvas::Canvas;
+use devotee::visual::prelude::*;
+use devotee_backend_headless::{
+    HeadlessBackend, HeadlessContext, HeadlessInit, HeadlessMiddleware,
+};
+
+fn main() {
+    let framebuffer = HeadlessBackend::new().with_frames(30).run(
+        App::new(Headless { frame: 0 }),
+        HeadlessMiddleware::new(Canvas::with_resolution(false, 64, 32), NoInput),
+        Duration::from_secs_f32(1.0 / 60.0),
+    );
+
+    let (width, height) = framebuffer.dimensions();
+    let mut image = Canvas::with_resolution(0, width, height);
+    for (y, row) in image.rows_mut().enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            if let Some(value) = framebuffer.pixel(x, y) {
+                *pixel = value;
+            }
+        }
+    }
+    dump_ansi(&image, &XrgbConverter);
+}
+
+pub struct Headless {
+    pub frame: i32,
+}
+
+impl Root<HeadlessInit<'_>, HeadlessContext<'_, NoInput>> for Headless {
+    type Converter = TwoColors;
+    type RenderSurface = Canvas<bool>;
+
+    fn init(&mut self, _: &mut HeadlessInit) {}
+
+    fn update(&mut self, _: &mut HeadlessContext<NoInput>) {
+        self.frame += 1;
+    }
+
+    fn render(&mut self, render: &mut Self::RenderSurface) {
+        let mut render = render.painter::<i32>();
+        render.clear(false);
+        render.circle_f((self.frame, 16).into(), 12, paint(true));
+    }
+
+    fn converter(&self) -> Self::Converter {
+        TwoColors::new(0xff101020, 0xffc0b0a0)
+    }
+}
@@ -2,10 +2,10 @@ use std::time::Duration;
 
 use devotee::app::root::Root;
 use devotee::app::App;
+use devotee::extras::TwoColors;
 use devotee::input::winit_input::NoInput;
 use devotee::visual::canvas::Canvas;
-use devotee_backend::Converter;
-use devotee_backend_softbuffer::{Error, SoftBackend, SoftContext, SoftInit, SoftMiddleware};
+use devotee_backend_softbuffer::{Error, SoftBackend, SoftMiddleware};
 
 fn main() -> Result<(), Error> {
     let backend = SoftBackend::try_new("minimal")?;
@@ -16,33 +16,19 @@ fn main() -> Result<(), Error> {
     )
 }
 
-struct Minimal;
+pub struct Minimal;
 
-impl Root<SoftInit<'_>, SoftContext<'_, NoInput>> for Minimal {
-    type Converter = BlackWhiteConverter;
+impl<Init, Context> Root<Init, Context> for Minimal {
+    type Converter = TwoColors;
     type RenderSurface = Canvas<bool>;
 
-    fn init(&mut self, _: &mut SoftInit) {}
+    fn init(&mut self, _: &mut Init) {}
 
-    fn update(&mut self, _: &mut SoftContext<NoInput>) {}
+    fn update(&mut self, _: &mut Context) {}
 
     fn render(&mut self, _: &mut Self::RenderSurface) {}
 
     fn converter(&self) -> Self::Converter {
-        BlackWhiteConverter
-    }
-}
-
-struct BlackWhiteConverter;
-
-impl Converter for BlackWhiteConverter {
-    type Data = bool;
-
-    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
-        if data {
-            0xffffffff
-        } else {
-            0xff000000
-        }
+        TwoColors::black_white()
     }
 }
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use devotee::app::root::Root;
+use devotee::app::App;
+use devotee::extras::TwoColors;
+use devotee::input::crossterm_input::{KeyCode, Keyboard};
+use devotee::util::vector::Vector;
+use devotee::visual::canvas::Canvas;
+use devotee::visual::{paint, Paint, PaintTarget};
+use devotee_backend_terminal::{Error, TermBackend, TermMiddleware};
+
+fn main() -> Result<(), Error> {
+    let backend = TermBackend::try_new("terminal")?;
+    backend.run(
+        App::new(Terminal::new()),
+        TermMiddleware::new(Canvas::with_resolution(false, 128, 64), Keyboard::new())
+            .with_background_color(0xff000000),
+        Duration::from_secs_f32(1.0 / 30.0),
+    )
+}
+
+pub struct Terminal {
+    position: Vector<f32>,
+    velocity: Vector<f32>,
+}
+
+impl Terminal {
+    pub fn new() -> Self {
+        Self {
+            position: Vector::new(64.0, 32.0),
+            velocity: Vector::new(24.0, 16.0),
+        }
+    }
+}
+
+impl Default for Terminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Init, Context> Root<Init, Context> for Terminal
+where
+    Context: devotee_backend::Context<'a, Keyboard>,
+{
+    type Converter = TwoColors;
+    type RenderSurface = Canvas<bool>;
+
+    fn init(&mut self, _: &mut Init) {}
+
+    fn update(&mut self, context: &mut Context) {
+        let keyboard = context.input();
+        let delta = context.delta().as_secs_f32();
+
+        let mut push = Vector::new(0.0, 0.0);
+        if keyboard.is_pressed(KeyCode::Left) {
+            push -= (1.0, 0.0);
+        }
+        if keyboard.is_pressed(KeyCode::Right) {
+            push += (1.0, 0.0);
+        }
+        if keyboard.is_pressed(KeyCode::Up) {
+            push -= (0.0, 1.0);
+        }
+        if keyboard.is_pressed(KeyCode::Down) {
+            push += (0.0, 1.0);
+        }
+        self.velocity += push * 64.0 * delta;
+        self.position += self.velocity * delta;
+
+        if !(8.0..=120.0).contains(&self.position.x()) {
+            *self.velocity.x_mut() = -self.velocity.x();
+            *self.position.x_mut() = self.position.x().clamp(8.0, 120.0);
+        }
+        if !(8.0..=56.0).contains(&self.position.y()) {
+            *self.velocity.y_mut() = -self.velocity.y();
+            *self.position.y_mut() = self.position.y().clamp(8.0, 56.0);
+        }
+
+        if keyboard.just_pressed(KeyCode::Esc) || keyboard.just_pressed(KeyCode::Char('q')) {
+            context.shutdown();
+        }
+    }
+
+    fn render(&mut self, render: &mut Self::RenderSurface) {
+        let mut render = render.painter();
+        render.clear(false);
+        render.rect_b(((0.0, 0.0).into(), (128.0, 64.0).into()), paint(true));
+        render.circle_f(self.position, 8.0, paint(true));
+    }
+
+    fn converter(&self) -> Self::Converter {
+        TwoColors::new(0xff101020, 0xffc0b0a0)
+    }
+}
@@ -1,3 +1,4 @@
+use devotee_backend::window::WindowToken;
 use devotee_backend::Application;
 
 /// Application root specification.
@@ -36,6 +37,14 @@ where
         self.root.render(render_surface);
     }
 
+    fn render_window(&mut self, window: WindowToken, render_surface: &mut RenderSurface) {
+        self.root.render_window(window, render_surface);
+    }
+
+    fn interpolate(&mut self, alpha: f32) {
+        self.root.interpolate(alpha);
+    }
+
     fn converter(&self) -> Converter {
         self.root.converter()
     }
@@ -47,4 +56,12 @@ where
     fn resume(&mut self) {
         self.root.resume();
     }
+
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        self.root.save_state()
+    }
+
+    fn load_state(&mut self, data: &[u8]) -> bool {
+        self.root.load_state(data)
+    }
 }
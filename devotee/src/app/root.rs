@@ -1,3 +1,4 @@
+use devotee_backend::window::WindowToken;
 use devotee_backend::Converter;
 
 /// App's root trait.
@@ -17,6 +18,17 @@ pub trait Root<Init, Context> {
     /// Handle rendering on the surface.
     fn render(&mut self, surface: &mut Self::RenderSurface);
 
+    /// Handle rendering on the surface of the additional `window`.
+    fn render_window(&mut self, window: WindowToken, surface: &mut Self::RenderSurface) {
+        let _ = (window, surface);
+    }
+
+    /// Handle interpolation factor in `[0.0, 1.0]` range between the last two fixed timestep updates
+    /// to be used by the following render.
+    fn interpolate(&mut self, alpha: f32) {
+        let _ = alpha;
+    }
+
     /// Get converter to convert Render Surface pixels into `u32` values.
     fn converter(&self) -> Self::Converter;
 
@@ -26,6 +38,17 @@ pub trait Root<Init, Context> {
     /// Handle resume event.
     fn resume(&mut self) {}
 
+    /// Serialize the state for a savestate, `None` if savestates are not supported.
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Restore the state from the savestate `data`, `false` if it was not restored.
+    fn load_state(&mut self, data: &[u8]) -> bool {
+        let _ = data;
+        false
+    }
+
     /// Handle exit request and give optional permission to shut down the App.
     fn handle_exit_request(&mut self) -> ExitPermission {
         ExitPermission::Allow
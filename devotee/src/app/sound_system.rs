@@ -1,3 +1,11 @@
 /// `Rodio`-based sound system.
 #[cfg(feature = "rodio-sound-system")]
 pub mod rodio_sound_system;
+
+/// Sound mixer with channels, volume and panning control.
+#[cfg(feature = "rodio-sound-system")]
+pub mod mixer;
+
+/// Decoded sound assets played without per-play decoding.
+#[cfg(feature = "rodio-sound-system")]
+pub mod assets;
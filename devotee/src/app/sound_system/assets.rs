@@ -0,0 +1,249 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, BufReader, Read, Seek};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rodio::decoder::DecoderError;
+use rodio::source::Source;
+use rodio::Decoder;
+
+use super::mixer::{Mixer, SoundHandle};
+use super::rodio_sound_system::{Sound, SoundSystem};
+
+/// Sound loading error enumeration.
+#[derive(Debug)]
+pub enum SoundLoadError {
+    /// File reading error.
+    IoError(io::Error),
+    /// Decoding error, e.g. the format decoder is not enabled with the `wav-sound` or `ogg-sound` feature.
+    DecoderError(DecoderError),
+}
+
+impl From<io::Error> for SoundLoadError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl From<DecoderError> for SoundLoadError {
+    fn from(value: DecoderError) -> Self {
+        Self::DecoderError(value)
+    }
+}
+
+/// Sound decoded into memory.
+/// Clones share the samples.
+#[derive(Clone, Debug)]
+pub struct SoundData {
+    channels: u16,
+    sample_rate: u32,
+    samples: Arc<[f32]>,
+}
+
+impl SoundData {
+    /// Create new sound data from the interleaved `samples`.
+    pub fn from_samples(channels: u16, sample_rate: u32, samples: Vec<f32>) -> Self {
+        Self {
+            channels: channels.max(1),
+            sample_rate,
+            samples: samples.into(),
+        }
+    }
+
+    /// Decode the whole encoded sound provided by the `reader`.
+    pub fn decode<R>(reader: R) -> Result<Self, SoundLoadError>
+    where
+        R: Read + Seek + Send + Sync + 'static,
+    {
+        let decoder = Decoder::new(reader)?;
+        let channels = decoder.channels();
+        let sample_rate = decoder.sample_rate();
+        let samples = decoder.convert_samples().collect();
+        Ok(Self::from_samples(channels, sample_rate, samples))
+    }
+
+    /// Load and decode the sound file at the `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, SoundLoadError> {
+        let file = File::open(path)?;
+        Self::decode(BufReader::new(file))
+    }
+
+    /// Get amount of channels.
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Get sample rate in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Get duration of the sound.
+    pub fn duration(&self) -> Duration {
+        let frames = (self.samples.len() / self.channels as usize) as u64;
+        Duration::from_secs(frames) / self.sample_rate.max(1)
+    }
+
+    /// Get source playing the sound without copying or decoding the samples.
+    pub fn source(&self) -> SoundSource {
+        SoundSource {
+            data: self.clone(),
+            position: 0,
+        }
+    }
+}
+
+/// Source playing the in-memory `SoundData`.
+#[derive(Clone, Debug)]
+pub struct SoundSource {
+    data: SoundData,
+    position: usize,
+}
+
+impl Iterator for SoundSource {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.data.samples.get(self.position).copied()?;
+        self.position += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.data.samples.len() - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl Source for SoundSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some(self.data.samples.len() - self.position)
+    }
+
+    fn channels(&self) -> u16 {
+        self.data.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.data.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(self.data.duration())
+    }
+}
+
+/// Collection of decoded sounds played by key.
+/// Sounds are decoded once on loading, playing them involves no decoding.
+pub struct SoundBank<Key> {
+    sounds: HashMap<Key, SoundData>,
+}
+
+impl<Key> SoundBank<Key>
+where
+    Key: Eq + Hash,
+{
+    /// Create new empty sound bank.
+    pub fn new() -> Self {
+        Self {
+            sounds: HashMap::new(),
+        }
+    }
+
+    /// Load and decode the sound file at the `path`, storing it with the `key`.
+    pub fn load<P: AsRef<Path>>(&mut self, key: Key, path: P) -> Result<(), SoundLoadError> {
+        let data = SoundData::load(path)?;
+        self.sounds.insert(key, data);
+        Ok(())
+    }
+
+    /// Store the decoded sound `data` with the `key`, returning the replaced one.
+    pub fn insert(&mut self, key: Key, data: SoundData) -> Option<SoundData> {
+        self.sounds.insert(key, data)
+    }
+
+    /// Remove the sound with the `key`.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<SoundData>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.sounds.remove(key)
+    }
+
+    /// Get the sound with the `key`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&SoundData>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.sounds.get(key)
+    }
+
+    /// Check if there is a sound with the `key`.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.sounds.contains_key(key)
+    }
+
+    /// Get amount of stored sounds.
+    pub fn len(&self) -> usize {
+        self.sounds.len()
+    }
+
+    /// Check if there are no stored sounds.
+    pub fn is_empty(&self) -> bool {
+        self.sounds.is_empty()
+    }
+
+    /// Play the sound with the `key` with the `sound_system`.
+    pub fn play<Q>(&self, key: &Q, sound_system: &mut SoundSystem) -> Option<Sound>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let source = self.get(key)?.source();
+        sound_system.play(Box::new(source))
+    }
+
+    /// Play the sound with the `key` on the `mixer` `channel`.
+    pub fn play_on<Q>(&self, key: &Q, mixer: &mut Mixer, channel: &str) -> Option<SoundHandle>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let source = self.get(key)?.source();
+        mixer.play(channel, Box::new(source))
+    }
+
+    /// Play the sound with the `key` on the `mixer` `channel` repeatedly until stopped.
+    pub fn play_looped_on<Q>(
+        &self,
+        key: &Q,
+        mixer: &mut Mixer,
+        channel: &str,
+    ) -> Option<SoundHandle>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let source = self.get(key)?.source();
+        mixer.play_looped(channel, Box::new(source))
+    }
+}
+
+impl<Key> Default for SoundBank<Key>
+where
+    Key: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rodio::source::{Source, UniformSourceIterator};
+use rodio::{OutputStreamHandle, Sink};
+
+use super::rodio_sound_system::SoundSystem;
+
+/// Name of the music channel present in every mixer.
+pub const MUSIC: &str = "music";
+
+/// Name of the sound effects channel present in every mixer.
+pub const SFX: &str = "sfx";
+
+/// Handle of the sound played by the `Mixer`.
+/// Handles of finished sounds are never reused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SoundHandle(u64);
+
+struct Fade {
+    from: f32,
+    to: f32,
+    elapsed: Duration,
+    duration: Duration,
+    stop: bool,
+}
+
+impl Fade {
+    fn level(&self) -> f32 {
+        if self.is_done() {
+            self.to
+        } else {
+            let progress = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+            self.from + (self.to - self.from) * progress
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+struct Playing {
+    sink: Sink,
+    channel: String,
+    volume: f32,
+    level: f32,
+    fade: Option<Fade>,
+    pan: Arc<AtomicU32>,
+}
+
+/// Sound mixer with named channels, master volume and per-sound control.
+///
+/// Call `update` on every update with the context delta to advance fades and release finished sounds.
+pub struct Mixer {
+    handle: OutputStreamHandle,
+    master: f32,
+    channels: HashMap<String, f32>,
+    sounds: HashMap<SoundHandle, Playing>,
+    next_handle: u64,
+    paused: bool,
+}
+
+impl Mixer {
+    /// Create new mixer playing through the `sound_system` output with `MUSIC` and `SFX` channels.
+    pub fn new(sound_system: &SoundSystem) -> Self {
+        let channels = HashMap::from([(MUSIC.to_owned(), 1.0), (SFX.to_owned(), 1.0)]);
+        Self {
+            handle: sound_system.output_handle().clone(),
+            master: 1.0,
+            channels,
+            sounds: HashMap::new(),
+            next_handle: 0,
+            paused: false,
+        }
+    }
+
+    /// Add channel with the `name` and `volume`.
+    pub fn with_channel(mut self, name: &str, volume: f32) -> Self {
+        self.channels.insert(name.to_owned(), volume);
+        self
+    }
+
+    /// Get master volume.
+    pub fn master_volume(&self) -> f32 {
+        self.master
+    }
+
+    /// Set master volume applied to all the channels.
+    pub fn set_master_volume(&mut self, volume: f32) -> &mut Self {
+        self.master = volume;
+        self.apply_all();
+        self
+    }
+
+    /// Get volume of the channel with the `name`, if there is one.
+    pub fn channel_volume(&self, name: &str) -> Option<f32> {
+        self.channels.get(name).copied()
+    }
+
+    /// Set volume of the channel with the `name`, adding the channel if there is none.
+    pub fn set_channel_volume(&mut self, name: &str, volume: f32) -> &mut Self {
+        self.channels.insert(name.to_owned(), volume);
+        self.apply_all();
+        self
+    }
+
+    /// Play the `source` once on the `channel`.
+    /// Returns `None` if there is no such channel or the playback could not be started.
+    pub fn play(
+        &mut self,
+        channel: &str,
+        source: Box<dyn Source<Item = f32> + Send>,
+    ) -> Option<SoundHandle> {
+        let pan = Arc::new(AtomicU32::new(0.0_f32.to_bits()));
+        let source = Panned::new(source, pan.clone());
+        self.start(channel, source, pan)
+    }
+
+    /// Play the `source` on the `channel` repeatedly until stopped.
+    /// Returns `None` if there is no such channel or the playback could not be started.
+    pub fn play_looped(
+        &mut self,
+        channel: &str,
+        source: Box<dyn Source<Item = f32> + Send>,
+    ) -> Option<SoundHandle> {
+        let pan = Arc::new(AtomicU32::new(0.0_f32.to_bits()));
+        let source = Panned::new(source.buffered().repeat_infinite(), pan.clone());
+        self.start(channel, source, pan)
+    }
+
+    fn start<S>(&mut self, channel: &str, source: S, pan: Arc<AtomicU32>) -> Option<SoundHandle>
+    where
+        S: Source<Item = f32> + Send + 'static,
+    {
+        if !self.channels.contains_key(channel) {
+            return None;
+        }
+        let sink = Sink::try_new(&self.handle).ok()?;
+        if self.paused {
+            sink.pause();
+        }
+        sink.append(source);
+        let handle = SoundHandle(self.next_handle);
+        self.next_handle += 1;
+        let playing = Playing {
+            sink,
+            channel: channel.to_owned(),
+            volume: 1.0,
+            level: 1.0,
+            fade: None,
+            pan,
+        };
+        self.apply(&playing);
+        self.sounds.insert(handle, playing);
+        Some(handle)
+    }
+
+    /// Check if the sound is still playing.
+    pub fn is_playing(&self, handle: SoundHandle) -> bool {
+        self.sounds
+            .get(&handle)
+            .is_some_and(|playing| !playing.sink.empty())
+    }
+
+    /// Stop the sound immediately.
+    pub fn stop(&mut self, handle: SoundHandle) {
+        if let Some(playing) = self.sounds.remove(&handle) {
+            playing.sink.stop();
+        }
+    }
+
+    /// Stop all the sounds of the `channel` immediately.
+    pub fn stop_channel(&mut self, channel: &str) {
+        self.sounds.retain(|_, playing| {
+            let keep = playing.channel != channel;
+            if !keep {
+                playing.sink.stop();
+            }
+            keep
+        });
+    }
+
+    /// Set volume of the individual sound.
+    pub fn set_volume(&mut self, handle: SoundHandle, volume: f32) {
+        if let Some(playing) = self.sounds.get_mut(&handle) {
+            playing.volume = volume;
+        }
+        self.apply_sound(handle);
+    }
+
+    /// Set stereo panning of the sound from `-1.0` (left) to `1.0` (right).
+    pub fn set_pan(&mut self, handle: SoundHandle, pan: f32) {
+        if let Some(playing) = self.sounds.get(&handle) {
+            playing
+                .pan
+                .store(pan.clamp(-1.0, 1.0).to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Fade the sound in from silence over the `duration`.
+    pub fn fade_in(&mut self, handle: SoundHandle, duration: Duration) {
+        self.fade(handle, 0.0, 1.0, duration, false);
+    }
+
+    /// Fade the sound out over the `duration` and stop it.
+    pub fn fade_out(&mut self, handle: SoundHandle, duration: Duration) {
+        let from = self
+            .sounds
+            .get(&handle)
+            .map_or(1.0, |playing| playing.level);
+        self.fade(handle, from, 0.0, duration, true);
+    }
+
+    /// Fade out all the sounds of the `channel` over the `duration` and stop them.
+    pub fn fade_out_channel(&mut self, channel: &str, duration: Duration) {
+        let handles: Vec<_> = self
+            .sounds
+            .iter()
+            .filter(|(_, playing)| playing.channel == channel)
+            .map(|(handle, _)| *handle)
+            .collect();
+        for handle in handles {
+            self.fade_out(handle, duration);
+        }
+    }
+
+    fn fade(&mut self, handle: SoundHandle, from: f32, to: f32, duration: Duration, stop: bool) {
+        if let Some(playing) = self.sounds.get_mut(&handle) {
+            playing.level = from;
+            playing.fade = Some(Fade {
+                from,
+                to,
+                elapsed: Duration::ZERO,
+                duration,
+                stop,
+            });
+        }
+        self.apply_sound(handle);
+    }
+
+    /// Get amount of sounds being played.
+    pub fn playing_count(&self) -> usize {
+        self.sounds.len()
+    }
+
+    /// Advance fades by the `delta` and release finished sounds.
+    pub fn update(&mut self, delta: Duration) {
+        if !self.paused {
+            for playing in self.sounds.values_mut() {
+                if let Some(fade) = &mut playing.fade {
+                    fade.elapsed += delta;
+                    playing.level = fade.level();
+                    if fade.is_done() {
+                        if fade.stop {
+                            playing.sink.stop();
+                        }
+                        playing.fade = None;
+                    }
+                }
+            }
+        }
+        self.sounds.retain(|_, playing| !playing.sink.empty());
+        self.apply_all();
+    }
+
+    /// Pause playback of all the sounds.
+    pub fn pause(&mut self) {
+        self.paused = true;
+        for playing in self.sounds.values() {
+            playing.sink.pause();
+        }
+    }
+
+    /// Resume playback of all the sounds.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        for playing in self.sounds.values() {
+            playing.sink.play();
+        }
+    }
+
+    fn apply(&self, playing: &Playing) {
+        let channel = self.channels.get(&playing.channel).copied().unwrap_or(1.0);
+        playing
+            .sink
+            .set_volume(self.master * channel * playing.volume * playing.level);
+    }
+
+    fn apply_sound(&self, handle: SoundHandle) {
+        if let Some(playing) = self.sounds.get(&handle) {
+            self.apply(playing);
+        }
+    }
+
+    fn apply_all(&self) {
+        for playing in self.sounds.values() {
+            self.apply(playing);
+        }
+    }
+}
+
+/// Stereo source with gains of the channels controlled by the shared pan value.
+struct Panned<S>
+where
+    S: Source<Item = f32>,
+{
+    input: UniformSourceIterator<S, f32>,
+    pan: Arc<AtomicU32>,
+    right: bool,
+}
+
+impl<S> Panned<S>
+where
+    S: Source<Item = f32>,
+{
+    fn new(input: S, pan: Arc<AtomicU32>) -> Self {
+        let sample_rate = input.sample_rate();
+        Self {
+            input: UniformSourceIterator::new(input, 2, sample_rate),
+            pan,
+            right: false,
+        }
+    }
+}
+
+impl<S> Iterator for Panned<S>
+where
+    S: Source<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.input.next()?;
+        let pan = f32::from_bits(self.pan.load(Ordering::Relaxed));
+        let gain = if self.right { 1.0 + pan } else { 1.0 - pan };
+        self.right = !self.right;
+        Some(sample * gain.min(1.0))
+    }
+}
+
+impl<S> Source for Panned<S>
+where
+    S: Source<Item = f32>,
+{
+    fn current_frame_len(&self) -> Option<usize> {
+        self.input.current_frame_len()
+    }
+
+    fn channels(&self) -> u16 {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.input.total_duration()
+    }
+}
@@ -1,13 +1,31 @@
+use std::fs;
+use std::io;
+use std::path::Path;
 use std::rc::Rc;
 
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::cpal::SupportedBufferSize;
 use rodio::source::Source;
-use rodio::{OutputStream, OutputStreamHandle, Sink, StreamError};
+use rodio::{Device, OutputStream, OutputStreamHandle, Sink, StreamError};
 
 pub use rodio;
 
 /// Reference-counted `rodio` sink.
 pub type Sound = Rc<Sink>;
 
+/// Output device information.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceInfo {
+    /// Device name.
+    pub name: String,
+    /// Default sample rate in Hz.
+    pub sample_rate: u32,
+    /// Default amount of channels.
+    pub channels: u16,
+    /// Supported buffer size range in frames, if known.
+    pub buffer_size: Option<(u32, u32)>,
+}
+
 /// Simple sound system implementation.
 pub struct SoundSystem {
     // We are storing `OutputStream` instance to save it from being dropped and thus stopping sound.
@@ -15,17 +33,76 @@ pub struct SoundSystem {
     output_stream: OutputStream,
     handle: OutputStreamHandle,
     sinks: Vec<Rc<Sink>>,
+    device: Option<Device>,
 }
 
 impl SoundSystem {
     /// Try creating new Sound System instance.
     pub fn try_new() -> Result<Self, StreamError> {
+        let device = rodio::cpal::default_host().default_output_device();
         let (output_stream, handle) = OutputStream::try_default()?;
         let sinks = Vec::new();
         Ok(Self {
             output_stream,
             handle,
             sinks,
+            device,
+        })
+    }
+
+    /// Try creating new Sound System instance playing on the output device with given name.
+    pub fn try_with_device(name: &str) -> Result<Self, StreamError> {
+        let device = rodio::cpal::default_host()
+            .output_devices()
+            .ok()
+            .and_then(|mut devices| {
+                devices.find(|device| device.name().ok().as_deref() == Some(name))
+            })
+            .ok_or(StreamError::NoDevice)?;
+        let (output_stream, handle) = OutputStream::try_from_device(&device)?;
+        let sinks = Vec::new();
+        Ok(Self {
+            output_stream,
+            handle,
+            sinks,
+            device: Some(device),
+        })
+    }
+
+    /// Try creating new Sound System instance playing on the device stored in the preference file.
+    /// Falls back to the default device if the preferred one is unavailable.
+    pub fn try_preferred<P: AsRef<Path>>(preference: P) -> Result<Self, StreamError> {
+        load_device_preference(preference)
+            .and_then(|name| Self::try_with_device(&name).ok())
+            .map_or_else(Self::try_new, Ok)
+    }
+
+    /// Get names of available output devices.
+    pub fn output_devices() -> Vec<String> {
+        rodio::cpal::default_host()
+            .output_devices()
+            .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get name of the device this sound system plays on.
+    pub fn device_name(&self) -> Option<String> {
+        self.device.as_ref().and_then(|device| device.name().ok())
+    }
+
+    /// Get information on the device this sound system plays on.
+    pub fn device_info(&self) -> Option<DeviceInfo> {
+        let device = self.device.as_ref()?;
+        let config = device.default_output_config().ok()?;
+        let buffer_size = match config.buffer_size() {
+            SupportedBufferSize::Range { min, max } => Some((*min, *max)),
+            SupportedBufferSize::Unknown => None,
+        };
+        Some(DeviceInfo {
+            name: device.name().ok()?,
+            sample_rate: config.sample_rate().0,
+            channels: config.channels(),
+            buffer_size,
         })
     }
 
@@ -48,6 +125,16 @@ impl SoundSystem {
         }
     }
 
+    /// Get handle of the output stream, e.g. to create sinks directly.
+    pub fn output_handle(&self) -> &OutputStreamHandle {
+        &self.handle
+    }
+
+    /// Get amount of sinks tracked by this sound system.
+    pub fn sink_count(&self) -> usize {
+        self.sinks.len()
+    }
+
     /// Pause playback.
     pub fn pause(&self) {
         for sink in self.sinks.iter() {
@@ -62,3 +149,15 @@ impl SoundSystem {
         }
     }
 }
+
+/// Load the preferred output device name from the `path`.
+pub fn load_device_preference<P: AsRef<Path>>(path: P) -> Option<String> {
+    let name = fs::read_to_string(path).ok()?;
+    let name = name.trim();
+    (!name.is_empty()).then(|| name.to_owned())
+}
+
+/// Store the preferred output device name into the `path`.
+pub fn store_device_preference<P: AsRef<Path>>(path: P, name: &str) -> io::Result<()> {
+    fs::write(path, name)
+}
@@ -0,0 +1,24 @@
+/// Audio device selection and channel test screen.
+#[cfg(all(
+    feature = "rodio-sound-system",
+    feature = "winit-input",
+    not(target_arch = "wasm32")
+))]
+pub mod audio;
+/// Frame hashing and determinism checking.
+pub mod determinism;
+/// World-space debug draw overlay.
+pub mod gizmos;
+/// Golden frame storage and comparison for visual regression checks.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod golden;
+/// Bug report dumps of frames and state.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod inspector;
+/// Down-scaled image previews printed to the terminal.
+pub mod preview;
+/// Long-running resource usage monitoring.
+pub mod soak;
+
+pub use determinism::frame_hash;
+pub use preview::{dump_ansi, dump_ascii};
@@ -0,0 +1,239 @@
+use std::f32::consts::TAU;
+use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::app::sound_system::rodio_sound_system::rodio::Source;
+use crate::app::sound_system::rodio_sound_system::{store_device_preference, SoundSystem};
+use crate::input::winit_input::{KeyCode, Keyboard};
+use crate::util::getter::Getter;
+use crate::util::vector::Vector;
+use crate::visual::image::{DesignatorMut, DesignatorRef};
+use crate::visual::prelude::*;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+/// Channel the test tone is played on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ToneChannel {
+    /// Left channel only.
+    Left,
+    /// Right channel only.
+    Right,
+    /// Both channels.
+    Both,
+}
+
+/// Stereo sine test tone.
+#[derive(Clone, Debug)]
+pub struct TestTone {
+    frequency: f32,
+    channel: ToneChannel,
+    sample: u64,
+    length: u64,
+}
+
+impl TestTone {
+    /// Create new test tone of given frequency and duration.
+    pub fn new(frequency: f32, channel: ToneChannel, duration: Duration) -> Self {
+        let length = (duration.as_secs_f64() * SAMPLE_RATE as f64) as u64 * 2;
+        Self {
+            frequency,
+            channel,
+            sample: 0,
+            length,
+        }
+    }
+}
+
+impl Iterator for TestTone {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.length {
+            return None;
+        }
+        let (frame, right) = (self.sample / 2, self.sample % 2 == 1);
+        self.sample += 1;
+        let audible = match self.channel {
+            ToneChannel::Left => !right,
+            ToneChannel::Right => right,
+            ToneChannel::Both => true,
+        };
+        if audible {
+            let time = frame as f32 / SAMPLE_RATE as f32;
+            Some((time * self.frequency * TAU).sin() * 0.25)
+        } else {
+            Some(0.0)
+        }
+    }
+}
+
+impl Source for TestTone {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some((self.length - self.sample) as usize)
+    }
+
+    fn channels(&self) -> u16 {
+        2
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f64(
+            self.length as f64 / 2.0 / SAMPLE_RATE as f64,
+        ))
+    }
+}
+
+/// Audio diagnostics screen.
+/// It lists output devices, switches between them and plays channel test tones.
+///
+/// Controls: up and down arrows select the device, enter switches to it,
+/// `L`, `R` and `C` play the test tone on the left, right or both channels.
+#[derive(Clone, Debug)]
+pub struct AudioDiagnostics {
+    devices: Vec<String>,
+    selected: usize,
+    preference: Option<PathBuf>,
+    status: String,
+}
+
+impl AudioDiagnostics {
+    /// Create new diagnostics screen with enumerated output devices.
+    pub fn new() -> Self {
+        Self {
+            devices: SoundSystem::output_devices(),
+            selected: 0,
+            preference: None,
+            status: String::new(),
+        }
+    }
+
+    /// Store the chosen device into the preference file at `path`.
+    pub fn with_preference<P: Into<PathBuf>>(self, path: P) -> Self {
+        let preference = Some(path.into());
+        Self { preference, ..self }
+    }
+
+    /// Enumerate output devices again.
+    pub fn refresh(&mut self) {
+        self.devices = SoundSystem::output_devices();
+        self.selected = self.selected.min(self.devices.len().saturating_sub(1));
+    }
+
+    /// Get names of the enumerated devices.
+    pub fn devices(&self) -> &[String] {
+        &self.devices
+    }
+
+    /// Handle the keyboard controls, switching the `sound_system` device on request.
+    pub fn update(&mut self, keyboard: &Keyboard, sound_system: &mut SoundSystem) {
+        if keyboard.just_pressed(KeyCode::ArrowUp) {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if keyboard.just_pressed(KeyCode::ArrowDown) {
+            self.selected = (self.selected + 1).min(self.devices.len().saturating_sub(1));
+        }
+        if keyboard.just_pressed(KeyCode::Enter) {
+            if let Some(name) = self.devices.get(self.selected) {
+                self.status = match SoundSystem::try_with_device(name) {
+                    Ok(system) => {
+                        *sound_system = system;
+                        match &self.preference {
+                            Some(path) => match store_device_preference(path, name) {
+                                Ok(()) => format!("Switched to {}", name),
+                                Err(error) => format!("Switched, not saved: {}", error),
+                            },
+                            None => format!("Switched to {}", name),
+                        }
+                    }
+                    Err(error) => format!("Failed: {}", error),
+                };
+            }
+        }
+        for (key, channel) in [
+            (KeyCode::KeyL, ToneChannel::Left),
+            (KeyCode::KeyR, ToneChannel::Right),
+            (KeyCode::KeyC, ToneChannel::Both),
+        ] {
+            if keyboard.just_pressed(key) {
+                let tone = TestTone::new(440.0, channel, Duration::from_millis(500));
+                if sound_system.play(Box::new(tone)).is_none() {
+                    self.status = "Failed to play the test tone".to_owned();
+                }
+            }
+        }
+    }
+
+    /// Get the screen text lines.
+    pub fn lines(&self, sound_system: &SoundSystem) -> Vec<String> {
+        let mut lines = vec!["AUDIO DIAGNOSTICS".to_owned()];
+        match sound_system.device_info() {
+            Some(info) => {
+                lines.push(format!("DEVICE: {}", info.name));
+                lines.push(format!(
+                    "RATE: {} HZ, CHANNELS: {}",
+                    info.sample_rate, info.channels
+                ));
+                lines.push(match info.buffer_size {
+                    Some((min, max)) => {
+                        let latency = min as f32 * 1000.0 / info.sample_rate as f32;
+                        format!("BUFFER: {}..{} ({:.1} MS MIN)", min, max, latency)
+                    }
+                    None => "BUFFER: UNKNOWN".to_owned(),
+                });
+            }
+            None => lines.push("DEVICE: UNKNOWN".to_owned()),
+        }
+        lines.push(String::new());
+        for (index, device) in self.devices.iter().enumerate() {
+            let marker = if index == self.selected { '>' } else { ' ' };
+            lines.push(format!("{} {}", marker, device));
+        }
+        lines.push(String::new());
+        lines.push("L/R/C: TEST TONE, ENTER: SELECT".to_owned());
+        if !self.status.is_empty() {
+            lines.push(self.status.clone());
+        }
+        lines
+    }
+
+    /// Draw the screen text with the `font`, advancing lines by `line_height`.
+    /// Glyph pixels with `true` value are painted with `color`.
+    pub fn render<T, U>(
+        &self,
+        target: &mut T,
+        sound_system: &SoundSystem,
+        font: &dyn Getter<Index = char, Item = U>,
+        line_height: i32,
+        color: T::Pixel,
+    ) where
+        T: ImageMut,
+        T::Pixel: Clone,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+        U: Image<Pixel = bool>,
+        for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = bool>,
+    {
+        let mut painter = target.painter::<i32>();
+        for (index, line) in self.lines(sound_system).iter().enumerate() {
+            painter.text(
+                Vector::new(0, index as i32 * line_height),
+                printer(),
+                font,
+                line,
+                |_, _, pixel, _, _, glyph| if glyph { color.clone() } else { pixel },
+            );
+        }
+    }
+}
+
+impl Default for AudioDiagnostics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,156 @@
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+use crate::util::vector::Vector;
+use crate::visual::image::{DesignatorRef, Image};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Stable FNV-1a hasher producing the same values across runs and platforms.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameHasher {
+    state: u64,
+}
+
+impl FrameHasher {
+    /// Create new hasher.
+    pub fn new() -> Self {
+        Self { state: FNV_OFFSET }
+    }
+}
+
+impl Default for FrameHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hasher for FrameHasher {
+    fn finish(&self) -> u64 {
+        self.state
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= *byte as u64;
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+/// Calculate 64-bit hash of the image dimensions and pixels.
+pub fn frame_hash<I>(image: &I) -> u64
+where
+    I: Image + ?Sized,
+    I::Pixel: Hash,
+    for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+{
+    let mut hasher = FrameHasher::new();
+    let (width, height) = (image.width(), image.height());
+    width.hash(&mut hasher);
+    height.hash(&mut hasher);
+    for y in 0..height {
+        for x in 0..width {
+            // SAFETY: the position lies within the image bounds.
+            unsafe { image.unsafe_pixel(Vector::new(x, y)).hash(&mut hasher) };
+        }
+    }
+    hasher.finish()
+}
+
+/// First tick the hashes diverged at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    tick: usize,
+    expected: u64,
+    actual: u64,
+}
+
+impl Divergence {
+    /// Get index of the divergent tick.
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    /// Get hash recorded during the reference run.
+    pub fn expected(&self) -> u64 {
+        self.expected
+    }
+
+    /// Get hash calculated during the current run.
+    pub fn actual(&self) -> u64 {
+        self.actual
+    }
+}
+
+/// Per-tick hash recorder comparing the run against the reference one.
+#[derive(Clone, Debug, Default)]
+pub struct DeterminismChecker {
+    reference: Option<Vec<u64>>,
+    hashes: Vec<u64>,
+    divergence: Option<Divergence>,
+}
+
+impl DeterminismChecker {
+    /// Create new checker only recording hashes.
+    pub fn recording() -> Self {
+        Self::default()
+    }
+
+    /// Create new checker comparing hashes with the `reference` ones.
+    pub fn verifying(reference: Vec<u64>) -> Self {
+        Self {
+            reference: Some(reference),
+            ..Default::default()
+        }
+    }
+
+    /// Register hash of the current tick.
+    /// Returns the divergence if this is the first tick differing from the reference.
+    pub fn register(&mut self, hash: u64) -> Option<Divergence> {
+        let tick = self.hashes.len();
+        self.hashes.push(hash);
+        let expected = self.reference.as_ref()?.get(tick).copied()?;
+        if self.divergence.is_none() && expected != hash {
+            let divergence = Divergence {
+                tick,
+                expected,
+                actual: hash,
+            };
+            self.divergence = Some(divergence);
+            return Some(divergence);
+        }
+        None
+    }
+
+    /// Hash the image and register the hash of the current tick.
+    pub fn register_image<I>(&mut self, image: &I) -> Option<Divergence>
+    where
+        I: Image + ?Sized,
+        I::Pixel: Hash,
+        for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+    {
+        self.register(frame_hash(image))
+    }
+
+    /// Get the first divergence found so far.
+    pub fn divergence(&self) -> Option<Divergence> {
+        self.divergence
+    }
+
+    /// Get the amount of registered ticks.
+    pub fn ticks(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Get hashes recorded so far.
+    pub fn hashes(&self) -> &[u64] {
+        &self.hashes
+    }
+
+    /// Consume the checker and get the recorded hashes to be used as a reference.
+    pub fn into_hashes(self) -> Vec<u64> {
+        self.hashes
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashSet;
+use std::ops::{Deref, DerefMut};
+
+use crate::util::getter::Getter;
+use crate::util::rect::Rect;
+use crate::util::vector::Vector;
+use crate::visual::canvas::Canvas;
+use crate::visual::image::{DesignatorMut, DesignatorRef};
+use crate::visual::prelude::*;
+
+/// Category the gizmos are put into unless another one is selected.
+pub const DEFAULT_CATEGORY: &str = "default";
+
+#[derive(Clone, Debug)]
+enum Shape {
+    Line(Vector<f32>, Vector<f32>),
+    Circle(Vector<f32>, f32),
+    Aabb(Vector<f32>, Vector<f32>),
+    Text(Vector<f32>, String),
+}
+
+/// Collection of world-space debug shapes.
+/// Shapes are collected during update and drawn over the rendered frame on flush.
+#[derive(Clone, Debug)]
+pub struct Gizmos<P> {
+    shapes: Vec<(&'static str, Shape, P)>,
+    category: &'static str,
+    disabled: HashSet<&'static str>,
+}
+
+impl<P> Gizmos<P>
+where
+    P: Clone,
+{
+    /// Create new empty gizmos collection with all the categories enabled.
+    pub fn new() -> Self {
+        Self {
+            shapes: Vec::new(),
+            category: DEFAULT_CATEGORY,
+            disabled: HashSet::new(),
+        }
+    }
+
+    /// Select category for the following gizmos.
+    pub fn set_category(&mut self, category: &'static str) -> &mut Self {
+        self.category = category;
+        self
+    }
+
+    /// Get currently selected category.
+    pub fn category(&self) -> &'static str {
+        self.category
+    }
+
+    /// Enable or disable drawing of the category.
+    pub fn set_enabled(&mut self, category: &'static str, enabled: bool) -> &mut Self {
+        if enabled {
+            self.disabled.remove(category);
+        } else {
+            self.disabled.insert(category);
+        }
+        self
+    }
+
+    /// Toggle drawing of the category.
+    pub fn toggle(&mut self, category: &'static str) -> &mut Self {
+        let enabled = self.is_enabled(category);
+        self.set_enabled(category, !enabled)
+    }
+
+    /// Check if the category is drawn.
+    pub fn is_enabled(&self, category: &'static str) -> bool {
+        !self.disabled.contains(category)
+    }
+
+    fn push(&mut self, shape: Shape, color: P) -> &mut Self {
+        if self.is_enabled(self.category) {
+            self.shapes.push((self.category, shape, color));
+        }
+        self
+    }
+
+    /// Draw line between `a` and `b`.
+    pub fn line(&mut self, a: Vector<f32>, b: Vector<f32>, color: P) -> &mut Self {
+        self.push(Shape::Line(a, b), color)
+    }
+
+    /// Draw circle outline.
+    pub fn circle(&mut self, center: Vector<f32>, radius: f32, color: P) -> &mut Self {
+        self.push(Shape::Circle(center, radius), color)
+    }
+
+    /// Draw axis-aligned bounding box outline between `min` and `max` corners.
+    pub fn aabb(&mut self, min: Vector<f32>, max: Vector<f32>, color: P) -> &mut Self {
+        self.push(Shape::Aabb(min, max), color)
+    }
+
+    /// Draw text with its top left corner at `at`.
+    /// Text is drawn only when flushed with a font.
+    pub fn text<S: Into<String>>(&mut self, at: Vector<f32>, text: S, color: P) -> &mut Self {
+        self.push(Shape::Text(at, text.into()), color)
+    }
+
+    /// Get the amount of collected gizmos.
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Check if there are no collected gizmos.
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    /// Drop all the collected gizmos.
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    /// Draw collected gizmos of enabled categories and clear the collection.
+    /// The `camera` is the world-space position of the target's top left corner.
+    pub fn flush<T>(&mut self, target: &mut T, camera: Vector<f32>)
+    where
+        T: ImageMut<Pixel = P>,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = P>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = P>,
+    {
+        self.flush_shapes(
+            target,
+            camera,
+            None::<&dyn Getter<Index = char, Item = Canvas<bool>>>,
+        );
+    }
+
+    /// Draw collected gizmos of enabled categories using `font` for text and clear the collection.
+    /// The `camera` is the world-space position of the target's top left corner.
+    /// Glyph pixels with `true` value are painted.
+    pub fn flush_with_font<T, U>(
+        &mut self,
+        target: &mut T,
+        camera: Vector<f32>,
+        font: &dyn Getter<Index = char, Item = U>,
+    ) where
+        T: ImageMut<Pixel = P>,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = P>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = P>,
+        U: Image<Pixel = bool>,
+        for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = bool>,
+    {
+        self.flush_shapes(target, camera, Some(font));
+    }
+
+    fn flush_shapes<T, U>(
+        &mut self,
+        target: &mut T,
+        camera: Vector<f32>,
+        font: Option<&dyn Getter<Index = char, Item = U>>,
+    ) where
+        T: ImageMut<Pixel = P>,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = P>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = P>,
+        U: Image<Pixel = bool>,
+        for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = bool>,
+    {
+        let offset = camera * -1.0;
+        for (category, shape, color) in self.shapes.drain(..) {
+            if self.disabled.contains(category) {
+                continue;
+            }
+            match shape {
+                Shape::Line(a, b) => {
+                    target
+                        .painter::<f32>()
+                        .with_offset(offset)
+                        .line(a, b, paint(color));
+                }
+                Shape::Circle(center, radius) => {
+                    target.painter::<f32>().with_offset(offset).circle_b(
+                        center,
+                        radius,
+                        paint(color),
+                    );
+                }
+                Shape::Aabb(min, max) => {
+                    target
+                        .painter::<f32>()
+                        .with_offset(offset)
+                        .rect_b(Rect::from_corners(min, max), paint(color));
+                }
+                Shape::Text(at, text) => {
+                    if let Some(font) = font {
+                        let at = (at + offset).map(|value| value.round() as i32);
+                        target.painter::<i32>().text(
+                            at,
+                            printer(),
+                            font,
+                            &text,
+                            move |_, _, pixel, _, _, glyph| {
+                                if glyph {
+                                    color.clone()
+                                } else {
+                                    pixel
+                                }
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<P> Default for Gizmos<P>
+where
+    P: Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
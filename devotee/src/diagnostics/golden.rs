@@ -0,0 +1,201 @@
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+use devotee_backend::Converter;
+
+use crate::util::vector::Vector;
+use crate::visual::image::{DesignatorRef, Image};
+
+/// Environment variable enabling golden frames overwrite.
+pub const BLESS_VARIABLE: &str = "DEVOTEE_BLESS";
+
+/// Golden frame comparison error enumeration.
+#[derive(Debug)]
+pub enum GoldenError {
+    /// Golden frame reading or writing error.
+    IoError(io::Error),
+
+    /// Stored golden frame is not a binary PPM image.
+    FormatError(PathBuf),
+
+    /// Rendered frame differs from the golden one.
+    Mismatch(Mismatch),
+}
+
+impl From<io::Error> for GoldenError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+/// Description of the golden frame mismatch.
+#[derive(Clone, Debug)]
+pub struct Mismatch {
+    expected: (usize, usize),
+    actual: (usize, usize),
+    differing: usize,
+    first: Option<(usize, usize)>,
+    output: PathBuf,
+}
+
+impl Mismatch {
+    /// Get dimensions of the golden frame.
+    pub fn expected_dimensions(&self) -> (usize, usize) {
+        self.expected
+    }
+
+    /// Get dimensions of the rendered frame.
+    pub fn actual_dimensions(&self) -> (usize, usize) {
+        self.actual
+    }
+
+    /// Get the amount of differing pixels.
+    pub fn differing(&self) -> usize {
+        self.differing
+    }
+
+    /// Get position of the first differing pixel.
+    pub fn first(&self) -> Option<(usize, usize)> {
+        self.first
+    }
+
+    /// Get path the rendered frame was saved to for inspection.
+    pub fn output(&self) -> &Path {
+        &self.output
+    }
+}
+
+pub(super) struct Frame {
+    width: usize,
+    height: usize,
+    rgb: Vec<u8>,
+}
+
+impl Frame {
+    pub(super) fn capture<I, C>(image: &I, converter: &C) -> Self
+    where
+        I: Image + ?Sized,
+        I::Pixel: Clone,
+        for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+        C: Converter<Data = I::Pixel>,
+    {
+        let (width, height) = (
+            image.width().max(0) as usize,
+            image.height().max(0) as usize,
+        );
+        let mut rgb = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                // SAFETY: the position lies within the image bounds.
+                let pixel = unsafe { image.unsafe_pixel(Vector::new(x as i32, y as i32)).clone() };
+                let color = converter.convert(x, y, pixel);
+                rgb.extend_from_slice(&color.to_be_bytes()[1..]);
+            }
+        }
+        Self { width, height, rgb }
+    }
+
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        data.extend_from_slice(&self.rgb);
+        data
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        let mut fields = Vec::new();
+        let mut position = 0;
+        while fields.len() < 4 {
+            while data.get(position)?.is_ascii_whitespace() {
+                position += 1;
+            }
+            let start = position;
+            while !data.get(position)?.is_ascii_whitespace() {
+                position += 1;
+            }
+            fields.push(std::str::from_utf8(&data[start..position]).ok()?);
+        }
+        let [magic, width, height, depth] = fields[..] else {
+            return None;
+        };
+        let (width, height) = (width.parse().ok()?, height.parse::<usize>().ok()?);
+        if magic != "P6" || depth != "255" {
+            return None;
+        }
+        let rgb = data.get(position + 1..)?.to_vec();
+        (rgb.len() == width * height * 3).then_some(Self { width, height, rgb })
+    }
+}
+
+/// Directory of golden frames to compare rendered frames against.
+/// Frames are stored as binary PPM images named after the checks.
+#[derive(Clone, Debug)]
+pub struct GoldenFrames {
+    directory: PathBuf,
+    bless: bool,
+}
+
+impl GoldenFrames {
+    /// Create new golden frames storage in the `directory`.
+    /// Golden frames are overwritten if the `DEVOTEE_BLESS` environment variable is set.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        let directory = directory.into();
+        let bless = std::env::var_os(BLESS_VARIABLE).is_some();
+        Self { directory, bless }
+    }
+
+    /// Set whether golden frames are overwritten instead of being compared.
+    pub fn with_bless(self, bless: bool) -> Self {
+        Self { bless, ..self }
+    }
+
+    /// Compare the `image` converted with the `converter` against the golden frame named `name`.
+    /// Missing golden frames are recorded.
+    /// On mismatch the rendered frame is saved alongside with `.actual.ppm` extension.
+    pub fn check<I, C>(&self, name: &str, image: &I, converter: &C) -> Result<(), GoldenError>
+    where
+        I: Image + ?Sized,
+        I::Pixel: Clone,
+        for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+        C: Converter<Data = I::Pixel>,
+    {
+        let actual = Frame::capture(image, converter);
+        let (width, height) = (actual.width, actual.height);
+
+        let path = self.directory.join(format!("{}.ppm", name));
+        if self.bless || !path.exists() {
+            fs::create_dir_all(&self.directory)?;
+            fs::write(&path, actual.encode())?;
+            return Ok(());
+        }
+
+        let Some(expected) = Frame::decode(&fs::read(&path)?) else {
+            return Err(GoldenError::FormatError(path));
+        };
+        let differing: Vec<usize> = if (expected.width, expected.height) == (width, height) {
+            (0..width * height)
+                .filter(|index| {
+                    expected.rgb[index * 3..index * 3 + 3] != actual.rgb[index * 3..index * 3 + 3]
+                })
+                .collect()
+        } else {
+            (0..width * height).collect()
+        };
+        if differing.is_empty() {
+            return Ok(());
+        }
+
+        let output = self.directory.join(format!("{}.actual.ppm", name));
+        fs::write(&output, actual.encode())?;
+        Err(GoldenError::Mismatch(Mismatch {
+            expected: (expected.width, expected.height),
+            actual: (width, height),
+            differing: differing.len(),
+            first: differing
+                .first()
+                .map(|index| (index % width, index / width)),
+            output,
+        }))
+    }
+}
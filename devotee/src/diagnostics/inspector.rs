@@ -0,0 +1,129 @@
+use std::fmt::{Debug, Display, Write};
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use devotee_backend::Converter;
+
+#[cfg(feature = "winit-input")]
+use crate::input::winit_input::{KeyCode, Keyboard};
+use crate::visual::image::{DesignatorRef, Image};
+
+use super::golden::Frame;
+
+/// State that can be dumped into the bug report.
+pub trait Inspect {
+    /// Describe the current state in human-readable form.
+    fn inspect(&self) -> String;
+}
+
+/// Contents of a single bug report.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    frame: Option<Vec<u8>>,
+    sections: Vec<(String, String)>,
+}
+
+impl Report {
+    /// Create new empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the `image` converted with the `converter` as the report frame.
+    pub fn with_frame<I, C>(self, image: &I, converter: &C) -> Self
+    where
+        I: Image + ?Sized,
+        I::Pixel: Clone,
+        for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+        C: Converter<Data = I::Pixel>,
+    {
+        let frame = Some(Frame::capture(image, converter).encode());
+        Self { frame, ..self }
+    }
+
+    /// Add text section, e.g. frame statistics.
+    pub fn with_section<S: Into<String>, D: Display>(self, name: S, content: D) -> Self {
+        let mut sections = self.sections;
+        sections.push((name.into(), content.to_string()));
+        Self { sections, ..self }
+    }
+
+    /// Add section with debug representation of the value, e.g. input snapshot.
+    pub fn with_debug<S: Into<String>, D: Debug>(self, name: S, value: &D) -> Self {
+        self.with_section(name, format!("{:#?}", value))
+    }
+
+    /// Add section with the inspected user state.
+    pub fn with_state<S: Into<String>>(self, name: S, state: &dyn Inspect) -> Self {
+        self.with_section(name, state.inspect())
+    }
+}
+
+/// Writer of bug reports into timestamped folders.
+#[derive(Clone, Debug)]
+pub struct Inspector {
+    directory: PathBuf,
+    #[cfg(feature = "winit-input")]
+    hotkey: KeyCode,
+}
+
+impl Inspector {
+    /// Create new inspector writing reports into the `directory`.
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        Self {
+            directory: directory.into(),
+            #[cfg(feature = "winit-input")]
+            hotkey: KeyCode::F12,
+        }
+    }
+
+    /// Get the directory reports are written to.
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Set the hotkey requesting the report, `F12` by default.
+    #[cfg(feature = "winit-input")]
+    pub fn with_hotkey(self, hotkey: KeyCode) -> Self {
+        Self { hotkey, ..self }
+    }
+
+    /// Check if the report was requested with the hotkey during the previous tick.
+    #[cfg(feature = "winit-input")]
+    pub fn requested(&self, keyboard: &Keyboard) -> bool {
+        keyboard.just_pressed(self.hotkey)
+    }
+
+    /// Write the `report` into a new timestamped folder.
+    /// Returns path to the created folder.
+    pub fn dump(&self, report: &Report) -> io::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let base = format!(
+            "report-{}-{:03}",
+            timestamp.as_secs(),
+            timestamp.subsec_millis()
+        );
+        let mut folder = self.directory.join(&base);
+        let mut attempt = 1;
+        while folder.exists() {
+            folder = self.directory.join(format!("{}-{}", base, attempt));
+            attempt += 1;
+        }
+        fs::create_dir_all(&folder)?;
+
+        if let Some(frame) = &report.frame {
+            fs::write(folder.join("frame.ppm"), frame)?;
+        }
+        let mut text = String::new();
+        for (name, content) in &report.sections {
+            let _ = writeln!(text, "## {}\n{}\n", name, content);
+        }
+        fs::write(folder.join("report.txt"), text)?;
+        Ok(folder)
+    }
+}
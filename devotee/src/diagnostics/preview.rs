@@ -0,0 +1,135 @@
+use std::env;
+use std::fmt::Write;
+use std::ops::Deref;
+
+use devotee_backend::Converter;
+
+use crate::util::vector::Vector;
+use crate::visual::image::{DesignatorRef, Image};
+
+/// Number of columns previews are fitted into if the `COLUMNS` variable is not set.
+pub const DEFAULT_COLUMNS: usize = 80;
+
+/// Common ASCII ramp from the darkest to the lightest.
+pub const ASCII_RAMP: &str = " .:-=+*#%@";
+
+fn terminal_columns() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_COLUMNS)
+}
+
+/// Get integer down-scale factor for the `width` to fit into `columns`.
+fn scale(width: usize, columns: usize) -> usize {
+    width.div_ceil(columns.max(1)).max(1)
+}
+
+fn dimensions<I: Image + ?Sized>(image: &I) -> (usize, usize) {
+    (
+        image.width().max(0) as usize,
+        image.height().max(0) as usize,
+    )
+}
+
+/// Render text preview of the `image` no wider than `columns` characters.
+/// Pixel values are used as indices into the `charset`, wrapping around its length.
+/// Each character represents a block of pixels twice as high as wide, sampled at its top left corner.
+///
+/// # Panics
+/// Panics if the `charset` is empty.
+pub fn ascii_preview<I>(image: &I, charset: &str, columns: usize) -> String
+where
+    I: Image + ?Sized,
+    I::Pixel: Clone + Into<u32>,
+    for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+{
+    let charset: Vec<char> = charset.chars().collect();
+    assert!(!charset.is_empty(), "Charset can't be empty");
+    let (width, height) = dimensions(image);
+    let scale = scale(width, columns);
+    let mut preview = String::new();
+    for y in (0..height).step_by(scale * 2) {
+        for x in (0..width).step_by(scale) {
+            // SAFETY: the position lies within the image bounds.
+            let pixel = unsafe { image.unsafe_pixel(Vector::new(x as i32, y as i32)).clone() };
+            preview.push(charset[pixel.into() as usize % charset.len()]);
+        }
+        preview.push('\n');
+    }
+    preview
+}
+
+/// Render colored preview of the `image` no wider than `columns` characters.
+/// Each character cell holds two vertically stacked blocks of pixels with averaged colors,
+/// drawn with ANSI 24-bit color escape sequences.
+pub fn ansi_preview<I, C>(image: &I, converter: &C, columns: usize) -> String
+where
+    I: Image + ?Sized,
+    I::Pixel: Clone,
+    for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+    C: Converter<Data = I::Pixel>,
+{
+    let (width, height) = dimensions(image);
+    let scale = scale(width, columns);
+    let average = |x: usize, y: usize| {
+        let mut sum = [0_u32; 3];
+        let mut count = 0;
+        for y in y..(y + scale).min(height) {
+            for x in x..(x + scale).min(width) {
+                // SAFETY: the position lies within the image bounds.
+                let pixel = unsafe { image.unsafe_pixel(Vector::new(x as i32, y as i32)).clone() };
+                let [_, r, g, b] = converter.convert(x, y, pixel).to_be_bytes();
+                sum[0] += r as u32;
+                sum[1] += g as u32;
+                sum[2] += b as u32;
+                count += 1;
+            }
+        }
+        (count > 0).then(|| sum.map(|channel| channel / count))
+    };
+    let mut preview = String::new();
+    for y in (0..height).step_by(scale * 2) {
+        for x in (0..width).step_by(scale) {
+            if let Some([r, g, b]) = average(x, y) {
+                let _ = write!(preview, "\x1b[38;2;{};{};{}m", r, g, b);
+            }
+            match average(x, y + scale) {
+                Some([r, g, b]) => {
+                    let _ = write!(preview, "\x1b[48;2;{};{};{}m", r, g, b);
+                }
+                None => preview.push_str("\x1b[49m"),
+            }
+            preview.push('▀');
+        }
+        preview.push_str("\x1b[0m\n");
+    }
+    preview
+}
+
+/// Print text preview of the `image` fitted into the terminal width to stdout.
+/// See `ascii_preview` for details.
+///
+/// # Panics
+/// Panics if the `charset` is empty.
+pub fn dump_ascii<I>(image: &I, charset: &str)
+where
+    I: Image + ?Sized,
+    I::Pixel: Clone + Into<u32>,
+    for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+{
+    print!("{}", ascii_preview(image, charset, terminal_columns()));
+}
+
+/// Print colored preview of the `image` fitted into the terminal width to stdout.
+/// See `ansi_preview` for details.
+pub fn dump_ansi<I, C>(image: &I, converter: &C)
+where
+    I: Image + ?Sized,
+    I::Pixel: Clone,
+    for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+    C: Converter<Data = I::Pixel>,
+{
+    print!("{}", ansi_preview(image, converter, terminal_columns()));
+}
@@ -0,0 +1,255 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use backend::window::WindowToken;
+use backend::Application;
+
+type Sampler = Box<dyn FnMut() -> Option<u64>>;
+
+struct Probe {
+    name: String,
+    sampler: Sampler,
+    samples: Vec<u64>,
+}
+
+/// Long-running resource usage monitor detecting leaks.
+///
+/// Probes are sampled every `interval` ticks.
+/// A probe is considered leaking if it did not decrease over the last `window` samples
+/// and grew by more than the tolerance during them.
+pub struct SoakMonitor {
+    interval: usize,
+    window: usize,
+    tolerance: u64,
+    tick: usize,
+    probes: Vec<Probe>,
+}
+
+impl SoakMonitor {
+    /// Create new monitor sampling probes every `interval` ticks.
+    ///
+    /// # Panics
+    /// Panics if `interval` is zero.
+    pub fn new(interval: usize) -> Self {
+        assert_ne!(interval, 0, "Soak sampling interval can't be zero");
+        Self {
+            interval,
+            window: 8,
+            tolerance: 0,
+            tick: 0,
+            probes: Vec::new(),
+        }
+    }
+
+    /// Set amount of consecutive samples the growth has to persist over to be reported.
+    ///
+    /// # Panics
+    /// Panics if `window` is less than 2.
+    pub fn with_window(self, window: usize) -> Self {
+        assert!(window >= 2, "Soak window has to cover at least 2 samples");
+        Self { window, ..self }
+    }
+
+    /// Set growth over the window not considered a leak.
+    pub fn with_tolerance(self, tolerance: u64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    /// Add probe with the `name` sampling some resource usage.
+    /// Samples of `None` are skipped.
+    pub fn with_probe<F>(mut self, name: &str, sampler: F) -> Self
+    where
+        F: FnMut() -> Option<u64> + 'static,
+    {
+        self.probes.push(Probe {
+            name: name.to_owned(),
+            sampler: Box::new(sampler),
+            samples: Vec::new(),
+        });
+        self
+    }
+
+    /// Add probes of the process resident memory and open handles if these are available on the platform.
+    pub fn with_process_probes(self) -> Self {
+        self.with_probe("resident memory, KiB", resident_memory)
+            .with_probe("open handles", open_handles)
+    }
+
+    /// Register the tick, sampling probes on every `interval` tick.
+    pub fn tick(&mut self) {
+        if self.tick.is_multiple_of(self.interval) {
+            self.sample();
+        }
+        self.tick += 1;
+    }
+
+    /// Sample all the probes immediately.
+    pub fn sample(&mut self) {
+        for probe in self.probes.iter_mut() {
+            if let Some(value) = (probe.sampler)() {
+                probe.samples.push(value);
+            }
+        }
+    }
+
+    /// Get amount of registered ticks.
+    pub fn ticks(&self) -> usize {
+        self.tick
+    }
+
+    /// Get samples of the probe with the `name`.
+    pub fn samples(&self, name: &str) -> Option<&[u64]> {
+        self.probes
+            .iter()
+            .find(|probe| probe.name == name)
+            .map(|probe| probe.samples.as_slice())
+    }
+
+    /// Get probes growing monotonically over the last window.
+    pub fn leaks(&self) -> Vec<Leak> {
+        self.probes
+            .iter()
+            .filter_map(|probe| {
+                let start = probe.samples.len().checked_sub(self.window)?;
+                let window = &probe.samples[start..];
+                let (first, last) = (window[0], window[window.len() - 1]);
+                let monotonic = window.windows(2).all(|pair| pair[1] >= pair[0]);
+                (monotonic && last > first.saturating_add(self.tolerance)).then(|| Leak {
+                    name: probe.name.clone(),
+                    first,
+                    last,
+                })
+            })
+            .collect()
+    }
+
+    /// Check that no probe is leaking.
+    pub fn check(&self) -> Result<(), Vec<Leak>> {
+        let leaks = self.leaks();
+        if leaks.is_empty() {
+            Ok(())
+        } else {
+            Err(leaks)
+        }
+    }
+}
+
+/// Resource growing monotonically during the soak run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leak {
+    name: String,
+    first: u64,
+    last: u64,
+}
+
+impl Leak {
+    /// Get name of the leaking probe.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the first sample of the window.
+    pub fn first(&self) -> u64 {
+        self.first
+    }
+
+    /// Get the last sample of the window.
+    pub fn last(&self) -> u64 {
+        self.last
+    }
+}
+
+/// Application wrapper ticking the shared monitor on every update.
+///
+/// Run it with the headless backend for the desired amount of frames
+/// and check the monitor once the backend finishes.
+pub struct Soak<App> {
+    app: App,
+    monitor: Rc<RefCell<SoakMonitor>>,
+}
+
+impl<App> Soak<App> {
+    /// Wrap the `app` with the `monitor`.
+    pub fn new(app: App, monitor: Rc<RefCell<SoakMonitor>>) -> Self {
+        Self { app, monitor }
+    }
+}
+
+impl<'a, App, Init, Context, RenderSurface, Converter>
+    Application<'a, Init, Context, RenderSurface, Converter> for Soak<App>
+where
+    App: Application<'a, Init, Context, RenderSurface, Converter>,
+{
+    fn init(&mut self, init: Init) {
+        self.app.init(init);
+    }
+
+    fn update(&mut self, context: Context) {
+        self.app.update(context);
+        self.monitor.borrow_mut().tick();
+    }
+
+    fn render(&mut self, render_surface: &mut RenderSurface) {
+        self.app.render(render_surface);
+    }
+
+    fn render_window(&mut self, window: WindowToken, render_surface: &mut RenderSurface) {
+        self.app.render_window(window, render_surface);
+    }
+
+    fn interpolate(&mut self, alpha: f32) {
+        self.app.interpolate(alpha);
+    }
+
+    fn converter(&self) -> Converter {
+        self.app.converter()
+    }
+
+    fn pause(&mut self) {
+        self.app.pause();
+    }
+
+    fn resume(&mut self) {
+        self.app.resume();
+    }
+
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        self.app.save_state()
+    }
+
+    fn load_state(&mut self, data: &[u8]) -> bool {
+        self.app.load_state(data)
+    }
+}
+
+/// Get resident memory size of the current process in KiB.
+/// Returns `None` if it is not available on the platform.
+pub fn resident_memory() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Get amount of handles opened by the current process.
+/// Returns `None` if it is not available on the platform.
+pub fn open_handles() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_dir("/proc/self/fd")
+            .ok()
+            .map(|entries| entries.count() as u64)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
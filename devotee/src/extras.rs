@@ -0,0 +1,85 @@
+use backend::Converter;
+
+use crate::visual::palette::Palette;
+pub use crate::visual::palette::PICO8;
+
+/// Built-in tiny bitmap font.
+pub mod font;
+
+/// Create mutable palette converter with the PICO-8 colors.
+pub fn pico8_palette() -> Palette {
+    Palette::new(PICO8.to_vec())
+}
+
+/// Converter mapping the lower four bits of the value into the PICO-8 colors.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FourBits;
+
+impl Converter for FourBits {
+    type Data = u8;
+
+    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
+        0xff_00_00_00 | PICO8[(data & 0x0f) as usize]
+    }
+}
+
+/// Converter mapping `bool` values into two colors.
+#[derive(Clone, Copy, Debug)]
+pub struct TwoColors {
+    off: u32,
+    on: u32,
+}
+
+impl TwoColors {
+    /// Create new converter with `off` color for `false` and `on` color for `true` in `0xff_rr_gg_bb` format.
+    pub const fn new(off: u32, on: u32) -> Self {
+        Self { off, on }
+    }
+
+    /// Create new converter mapping `false` to black and `true` to white.
+    pub const fn black_white() -> Self {
+        Self::new(0xff_00_00_00, 0xff_ff_ff_ff)
+    }
+}
+
+impl Default for TwoColors {
+    fn default() -> Self {
+        Self::black_white()
+    }
+}
+
+impl Converter for TwoColors {
+    type Data = bool;
+
+    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
+        if data {
+            self.on
+        } else {
+            self.off
+        }
+    }
+}
+
+/// Converter passing `0xrr_gg_bb` values through with the alpha channel set.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XrgbConverter;
+
+impl Converter for XrgbConverter {
+    type Data = u32;
+
+    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
+        0xff_00_00_00 | data
+    }
+}
+
+/// Converter packing `[r, g, b, a]` texels into `0xff_rr_gg_bb` values, ignoring the alpha channel.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RgbaConverter;
+
+impl Converter for RgbaConverter {
+    type Data = [u8; 4];
+
+    fn convert(&self, _: usize, _: usize, [r, g, b, _]: Self::Data) -> u32 {
+        0xff_00_00_00 | ((r as u32) << 16) | ((g as u32) << 8) | b as u32
+    }
+}
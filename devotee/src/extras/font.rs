@@ -0,0 +1,127 @@
+use crate::util::vector::Vector;
+use crate::visual::canvas::Canvas;
+use crate::visual::font::BitmapFont;
+use crate::visual::image::ImageMut;
+
+const COLUMNS: usize = 16;
+
+/// Glyphs of the printable ASCII characters starting with space.
+/// Each glyph is 3 pixels wide and 5 pixels tall, rows are stored top to bottom.
+const GLYPHS: [[u8; 5]; 95] = [
+    [0b000, 0b000, 0b000, 0b000, 0b000], // ' '
+    [0b010, 0b010, 0b010, 0b000, 0b010], // '!'
+    [0b101, 0b101, 0b000, 0b000, 0b000], // '"'
+    [0b101, 0b111, 0b101, 0b111, 0b101], // '#'
+    [0b011, 0b110, 0b010, 0b011, 0b110], // '$'
+    [0b100, 0b001, 0b010, 0b100, 0b001], // '%'
+    [0b010, 0b101, 0b010, 0b101, 0b011], // '&'
+    [0b010, 0b010, 0b000, 0b000, 0b000], // '\''
+    [0b001, 0b010, 0b010, 0b010, 0b001], // '('
+    [0b100, 0b010, 0b010, 0b010, 0b100], // ')'
+    [0b000, 0b101, 0b010, 0b101, 0b000], // '*'
+    [0b000, 0b010, 0b111, 0b010, 0b000], // '+'
+    [0b000, 0b000, 0b000, 0b010, 0b100], // ','
+    [0b000, 0b000, 0b111, 0b000, 0b000], // '-'
+    [0b000, 0b000, 0b000, 0b000, 0b010], // '.'
+    [0b001, 0b001, 0b010, 0b100, 0b100], // '/'
+    [0b111, 0b101, 0b101, 0b101, 0b111], // '0'
+    [0b010, 0b110, 0b010, 0b010, 0b111], // '1'
+    [0b110, 0b001, 0b010, 0b100, 0b111], // '2'
+    [0b110, 0b001, 0b010, 0b001, 0b110], // '3'
+    [0b101, 0b101, 0b111, 0b001, 0b001], // '4'
+    [0b111, 0b100, 0b110, 0b001, 0b110], // '5'
+    [0b011, 0b100, 0b111, 0b101, 0b111], // '6'
+    [0b111, 0b001, 0b010, 0b100, 0b100], // '7'
+    [0b111, 0b101, 0b111, 0b101, 0b111], // '8'
+    [0b111, 0b101, 0b111, 0b001, 0b110], // '9'
+    [0b000, 0b010, 0b000, 0b010, 0b000], // ':'
+    [0b000, 0b010, 0b000, 0b010, 0b100], // ';'
+    [0b001, 0b010, 0b100, 0b010, 0b001], // '<'
+    [0b000, 0b111, 0b000, 0b111, 0b000], // '='
+    [0b100, 0b010, 0b001, 0b010, 0b100], // '>'
+    [0b111, 0b001, 0b010, 0b000, 0b010], // '?'
+    [0b010, 0b101, 0b101, 0b100, 0b011], // '@'
+    [0b010, 0b101, 0b111, 0b101, 0b101], // 'A'
+    [0b110, 0b101, 0b110, 0b101, 0b110], // 'B'
+    [0b011, 0b100, 0b100, 0b100, 0b011], // 'C'
+    [0b110, 0b101, 0b101, 0b101, 0b110], // 'D'
+    [0b111, 0b100, 0b110, 0b100, 0b111], // 'E'
+    [0b111, 0b100, 0b110, 0b100, 0b100], // 'F'
+    [0b011, 0b100, 0b101, 0b101, 0b011], // 'G'
+    [0b101, 0b101, 0b111, 0b101, 0b101], // 'H'
+    [0b111, 0b010, 0b010, 0b010, 0b111], // 'I'
+    [0b001, 0b001, 0b001, 0b101, 0b010], // 'J'
+    [0b101, 0b101, 0b110, 0b101, 0b101], // 'K'
+    [0b100, 0b100, 0b100, 0b100, 0b111], // 'L'
+    [0b101, 0b111, 0b111, 0b101, 0b101], // 'M'
+    [0b101, 0b111, 0b111, 0b111, 0b101], // 'N'
+    [0b010, 0b101, 0b101, 0b101, 0b010], // 'O'
+    [0b110, 0b101, 0b110, 0b100, 0b100], // 'P'
+    [0b010, 0b101, 0b101, 0b111, 0b011], // 'Q'
+    [0b110, 0b101, 0b110, 0b101, 0b101], // 'R'
+    [0b011, 0b100, 0b010, 0b001, 0b110], // 'S'
+    [0b111, 0b010, 0b010, 0b010, 0b010], // 'T'
+    [0b101, 0b101, 0b101, 0b101, 0b111], // 'U'
+    [0b101, 0b101, 0b101, 0b010, 0b010], // 'V'
+    [0b101, 0b101, 0b111, 0b111, 0b101], // 'W'
+    [0b101, 0b101, 0b010, 0b101, 0b101], // 'X'
+    [0b101, 0b101, 0b010, 0b010, 0b010], // 'Y'
+    [0b111, 0b001, 0b010, 0b100, 0b111], // 'Z'
+    [0b110, 0b100, 0b100, 0b100, 0b110], // '['
+    [0b100, 0b100, 0b010, 0b001, 0b001], // '\\'
+    [0b011, 0b001, 0b001, 0b001, 0b011], // ']'
+    [0b010, 0b101, 0b000, 0b000, 0b000], // '^'
+    [0b000, 0b000, 0b000, 0b000, 0b111], // '_'
+    [0b100, 0b010, 0b000, 0b000, 0b000], // '`'
+    [0b000, 0b110, 0b011, 0b101, 0b111], // 'a'
+    [0b100, 0b110, 0b101, 0b101, 0b110], // 'b'
+    [0b000, 0b011, 0b100, 0b100, 0b011], // 'c'
+    [0b001, 0b011, 0b101, 0b101, 0b011], // 'd'
+    [0b000, 0b011, 0b101, 0b110, 0b011], // 'e'
+    [0b001, 0b010, 0b111, 0b010, 0b010], // 'f'
+    [0b000, 0b011, 0b101, 0b011, 0b110], // 'g'
+    [0b100, 0b110, 0b101, 0b101, 0b101], // 'h'
+    [0b010, 0b000, 0b010, 0b010, 0b010], // 'i'
+    [0b001, 0b000, 0b001, 0b101, 0b010], // 'j'
+    [0b100, 0b101, 0b110, 0b110, 0b101], // 'k'
+    [0b110, 0b010, 0b010, 0b010, 0b111], // 'l'
+    [0b000, 0b111, 0b111, 0b111, 0b101], // 'm'
+    [0b000, 0b110, 0b101, 0b101, 0b101], // 'n'
+    [0b000, 0b010, 0b101, 0b101, 0b010], // 'o'
+    [0b000, 0b110, 0b101, 0b110, 0b100], // 'p'
+    [0b000, 0b011, 0b101, 0b011, 0b001], // 'q'
+    [0b000, 0b011, 0b100, 0b100, 0b100], // 'r'
+    [0b000, 0b011, 0b110, 0b011, 0b110], // 's'
+    [0b010, 0b111, 0b010, 0b010, 0b011], // 't'
+    [0b000, 0b101, 0b101, 0b101, 0b011], // 'u'
+    [0b000, 0b101, 0b101, 0b111, 0b010], // 'v'
+    [0b000, 0b101, 0b111, 0b111, 0b111], // 'w'
+    [0b000, 0b101, 0b010, 0b010, 0b101], // 'x'
+    [0b000, 0b101, 0b101, 0b010, 0b100], // 'y'
+    [0b000, 0b111, 0b011, 0b110, 0b111], // 'z'
+    [0b011, 0b010, 0b110, 0b010, 0b011], // '{'
+    [0b010, 0b010, 0b010, 0b010, 0b010], // '|'
+    [0b110, 0b010, 0b011, 0b010, 0b110], // '}'
+    [0b000, 0b011, 0b110, 0b000, 0b000], // '~'
+];
+
+/// Create monospace 4x6 font covering printable ASCII characters.
+/// Each glyph is 3x5 pixels with a single pixel of spacing to the right and below it.
+pub fn font_4x6() -> BitmapFont<bool> {
+    let rows = GLYPHS.len().div_ceil(COLUMNS);
+    let mut image = Canvas::with_resolution(false, COLUMNS * 4, rows * 6);
+    for (index, glyph) in GLYPHS.iter().enumerate() {
+        let origin = Vector::new((index % COLUMNS) as i32 * 4, (index / COLUMNS) as i32 * 6);
+        for (y, row) in glyph.iter().enumerate() {
+            for x in 0..3 {
+                if row & (0b100 >> x) != 0 {
+                    if let Some(pixel) = image.pixel_mut(origin + Vector::new(x, y as i32)) {
+                        *pixel = true;
+                    }
+                }
+            }
+        }
+    }
+    let characters: String = (' '..='~').collect();
+    BitmapFont::from_grid(image, Vector::new(4, 6), &characters)
+}
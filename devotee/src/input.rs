@@ -1,3 +1,14 @@
+/// Logical action mapping over physical input bindings.
+#[cfg(feature = "winit-input")]
+pub mod action;
+/// Set of crossterm-based terminal input implementations.
+#[cfg(feature = "crossterm-input")]
+pub mod crossterm_input;
+/// Gilrs-based gamepad input implementation.
+#[cfg(feature = "gilrs-input")]
+pub mod gamepad;
+/// Force-feedback effect scheduling.
+pub mod rumble;
 /// Set of winit-based input implementations.
 #[cfg(feature = "winit-input")]
 pub mod winit_input;
@@ -0,0 +1,246 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use devotee_backend::Input;
+use winit::event::WindowEvent;
+
+#[cfg(feature = "gilrs-input")]
+use super::gamepad::{self, Gamepad};
+use super::winit_input::{KeyCode, KeyboardMouse, MouseButton};
+use crate::ui::virtual_gamepad::{VirtualButton, VirtualGamepad};
+
+/// Physical binding of a logical action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Binding {
+    /// Keyboard key.
+    Key(KeyCode),
+
+    /// Mouse button.
+    Mouse(MouseButton),
+
+    /// Button of the controller in the specific gamepad slot.
+    #[cfg(feature = "gilrs-input")]
+    Gamepad(usize, gamepad::Button),
+
+    /// Button of the on-screen virtual gamepad.
+    Virtual(VirtualButton),
+}
+
+impl From<KeyCode> for Binding {
+    fn from(value: KeyCode) -> Self {
+        Self::Key(value)
+    }
+}
+
+impl From<MouseButton> for Binding {
+    fn from(value: MouseButton) -> Self {
+        Self::Mouse(value)
+    }
+}
+
+impl From<VirtualButton> for Binding {
+    fn from(value: VirtualButton) -> Self {
+        Self::Virtual(value)
+    }
+}
+
+/// Input system mapping logical actions to sets of physical bindings.
+///
+/// An action is active while any of its bindings is pressed.
+/// Bindings can be changed at runtime.
+pub struct ActionMap<Action> {
+    keyboard_mouse: KeyboardMouse,
+    #[cfg(feature = "gilrs-input")]
+    gamepad: Option<Gamepad<WindowEvent>>,
+    virtual_gamepad: Option<VirtualGamepad>,
+    bindings: HashMap<Action, HashSet<Binding>>,
+}
+
+impl<Action> ActionMap<Action>
+where
+    Action: Eq + Hash,
+{
+    /// Create new action map without any bindings.
+    pub fn new() -> Self {
+        Self {
+            keyboard_mouse: KeyboardMouse::new(),
+            #[cfg(feature = "gilrs-input")]
+            gamepad: None,
+            virtual_gamepad: None,
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Bind the `action` to the `binding` in addition to its existing bindings.
+    pub fn with_binding<B: Into<Binding>>(mut self, action: Action, binding: B) -> Self {
+        self.bind(action, binding);
+        self
+    }
+
+    /// Use the gamepad input system for the `Binding::Gamepad` bindings.
+    #[cfg(feature = "gilrs-input")]
+    pub fn with_gamepad(self, gamepad: Gamepad<WindowEvent>) -> Self {
+        Self {
+            gamepad: Some(gamepad),
+            ..self
+        }
+    }
+
+    /// Use the on-screen virtual gamepad for the `Binding::Virtual` bindings.
+    /// Touches on its controls are consumed before other input systems.
+    pub fn with_virtual_gamepad(self, virtual_gamepad: VirtualGamepad) -> Self {
+        Self {
+            virtual_gamepad: Some(virtual_gamepad),
+            ..self
+        }
+    }
+
+    /// Bind the `action` to the `binding` in addition to its existing bindings.
+    pub fn bind<B: Into<Binding>>(&mut self, action: Action, binding: B) -> &mut Self {
+        self.bindings
+            .entry(action)
+            .or_default()
+            .insert(binding.into());
+        self
+    }
+
+    /// Remove the `binding` from the `action` bindings.
+    pub fn unbind<B: Into<Binding>>(&mut self, action: &Action, binding: B) -> &mut Self {
+        if let Some(bindings) = self.bindings.get_mut(action) {
+            bindings.remove(&binding.into());
+        }
+        self
+    }
+
+    /// Replace all the `action` bindings with the single `binding`.
+    pub fn rebind<B: Into<Binding>>(&mut self, action: Action, binding: B) -> &mut Self {
+        self.bindings
+            .insert(action, HashSet::from([binding.into()]));
+        self
+    }
+
+    /// Remove all the `action` bindings.
+    pub fn clear_bindings(&mut self, action: &Action) -> &mut Self {
+        self.bindings.remove(action);
+        self
+    }
+
+    /// Iterate over the `action` bindings.
+    pub fn bindings(&self, action: &Action) -> impl Iterator<Item = &Binding> {
+        self.bindings.get(action).into_iter().flatten()
+    }
+
+    /// Check if any binding of the `action` is pressed.
+    pub fn is_active(&self, action: &Action) -> bool {
+        self.bindings(action)
+            .any(|binding| self.is_pressed(binding))
+    }
+
+    /// Check if the `action` became active during the previous tick.
+    pub fn just_activated(&self, action: &Action) -> bool {
+        self.is_active(action) && !self.was_active(action)
+    }
+
+    /// Check if the `action` stopped being active during the previous tick.
+    pub fn just_deactivated(&self, action: &Action) -> bool {
+        !self.is_active(action) && self.was_active(action)
+    }
+
+    /// Get keyboard and mouse subsystem instance reference.
+    pub fn keyboard_mouse(&self) -> &KeyboardMouse {
+        &self.keyboard_mouse
+    }
+
+    /// Get gamepad subsystem instance reference if there is one.
+    #[cfg(feature = "gilrs-input")]
+    pub fn gamepad(&self) -> Option<&Gamepad<WindowEvent>> {
+        self.gamepad.as_ref()
+    }
+
+    /// Get virtual gamepad reference if there is one, e.g. to render it.
+    pub fn virtual_gamepad(&self) -> Option<&VirtualGamepad> {
+        self.virtual_gamepad.as_ref()
+    }
+
+    fn was_active(&self, action: &Action) -> bool {
+        self.bindings(action)
+            .any(|binding| self.was_pressed(binding))
+    }
+
+    fn is_pressed(&self, binding: &Binding) -> bool {
+        match *binding {
+            Binding::Key(key) => self.keyboard_mouse.keyboard().is_pressed(key),
+            Binding::Mouse(button) => self.keyboard_mouse.mouse().is_pressed(button),
+            #[cfg(feature = "gilrs-input")]
+            Binding::Gamepad(slot, button) => self
+                .gamepad
+                .as_ref()
+                .is_some_and(|gamepad| gamepad.is_pressed(slot, button)),
+            Binding::Virtual(button) => self
+                .virtual_gamepad
+                .as_ref()
+                .is_some_and(|gamepad| gamepad.is_pressed(button)),
+        }
+    }
+
+    fn was_pressed(&self, binding: &Binding) -> bool {
+        let changed = match *binding {
+            Binding::Key(key) => {
+                let keyboard = self.keyboard_mouse.keyboard();
+                keyboard.just_pressed(key) || keyboard.just_released(key)
+            }
+            Binding::Mouse(button) => {
+                let mouse = self.keyboard_mouse.mouse();
+                mouse.just_pressed(button) || mouse.just_released(button)
+            }
+            #[cfg(feature = "gilrs-input")]
+            Binding::Gamepad(slot, button) => self.gamepad.as_ref().is_some_and(|gamepad| {
+                gamepad.just_pressed(slot, button) || gamepad.just_released(slot, button)
+            }),
+            Binding::Virtual(button) => self.virtual_gamepad.as_ref().is_some_and(|gamepad| {
+                gamepad.just_pressed(button) || gamepad.just_released(button)
+            }),
+        };
+        self.is_pressed(binding) != changed
+    }
+}
+
+impl<Action> Default for ActionMap<Action>
+where
+    Action: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<EventContext, Action> Input<'_, EventContext> for ActionMap<Action>
+where
+    EventContext: backend::EventContext,
+{
+    type Event = WindowEvent;
+
+    fn handle_event(&mut self, event: Self::Event, context: &EventContext) -> Option<Self::Event> {
+        let event = match &mut self.virtual_gamepad {
+            Some(virtual_gamepad) => virtual_gamepad.handle_event(event, context)?,
+            None => event,
+        };
+        let event = self.keyboard_mouse.handle_event(event, context)?;
+        #[cfg(feature = "gilrs-input")]
+        if let Some(gamepad) = &mut self.gamepad {
+            return gamepad.handle_event(event, context);
+        }
+        Some(event)
+    }
+
+    fn tick(&mut self) {
+        Input::<'_, EventContext>::tick(&mut self.keyboard_mouse);
+        if let Some(virtual_gamepad) = &mut self.virtual_gamepad {
+            Input::<'_, EventContext>::tick(virtual_gamepad);
+        }
+        #[cfg(feature = "gilrs-input")]
+        if let Some(gamepad) = &mut self.gamepad {
+            Input::<'_, EventContext>::tick(gamepad);
+        }
+    }
+}
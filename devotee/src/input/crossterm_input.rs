@@ -0,0 +1,116 @@
+use std::collections::{HashMap, HashSet};
+
+use crossterm::event::{Event, KeyEvent, KeyEventKind};
+use devotee_backend::Input;
+
+pub use crossterm::event::KeyCode;
+
+/// Keyboard input system over terminal key events.
+///
+/// Most terminals do not report key releases,
+/// so keys are considered released after `hold_ticks` ticks without press or repeat events.
+/// Once the terminal reports a release event, releases are tracked exactly.
+#[derive(Clone, Debug)]
+pub struct Keyboard {
+    pressed: HashMap<KeyCode, u32>,
+    was_pressed: HashSet<KeyCode>,
+    hold_ticks: u32,
+    reports_releases: bool,
+}
+
+impl Keyboard {
+    /// Create new Keyboard input system instance.
+    pub fn new() -> Self {
+        let pressed = HashMap::new();
+        let was_pressed = HashSet::new();
+        let hold_ticks = 30;
+        let reports_releases = false;
+        Self {
+            pressed,
+            was_pressed,
+            hold_ticks,
+            reports_releases,
+        }
+    }
+
+    /// Set number of ticks a key is held after its last press or repeat event
+    /// if the terminal does not report releases.
+    pub fn with_hold_ticks(self, hold_ticks: u32) -> Self {
+        Self { hold_ticks, ..self }
+    }
+
+    /// Check if the key is pressed.
+    pub fn is_pressed(&self, key: KeyCode) -> bool {
+        self.pressed.contains_key(&normalize(key))
+    }
+
+    /// Check if the key was pressed during the previous tick and not before.
+    pub fn just_pressed(&self, key: KeyCode) -> bool {
+        let key = normalize(key);
+        self.pressed.contains_key(&key) && !self.was_pressed.contains(&key)
+    }
+
+    /// Check if the key was released during the previous tick.
+    pub fn just_released(&self, key: KeyCode) -> bool {
+        let key = normalize(key);
+        !self.pressed.contains_key(&key) && self.was_pressed.contains(&key)
+    }
+
+    /// Check if any key is pressed.
+    pub fn is_pressed_any(&self) -> bool {
+        !self.pressed.is_empty()
+    }
+
+    /// Check if any key was pressed during the previous tick.
+    pub fn just_pressed_any(&self) -> bool {
+        self.pressed
+            .keys()
+            .any(|key| !self.was_pressed.contains(key))
+    }
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<EventContext> Input<'_, EventContext> for Keyboard {
+    type Event = Event;
+
+    fn handle_event(&mut self, event: Self::Event, _context: &EventContext) -> Option<Self::Event> {
+        if let Event::Key(KeyEvent { code, kind, .. }) = event {
+            let code = normalize(code);
+            match kind {
+                KeyEventKind::Press | KeyEventKind::Repeat => {
+                    self.pressed.insert(code, self.hold_ticks);
+                }
+                KeyEventKind::Release => {
+                    self.reports_releases = true;
+                    self.pressed.remove(&code);
+                }
+            }
+            None
+        } else {
+            Some(event)
+        }
+    }
+
+    fn tick(&mut self) {
+        self.was_pressed = self.pressed.keys().copied().collect();
+        if !self.reports_releases {
+            self.pressed.retain(|_, ticks| {
+                *ticks = ticks.saturating_sub(1);
+                *ticks > 0
+            });
+        }
+    }
+}
+
+/// Terminals report characters typed with shift in upper case.
+fn normalize(key: KeyCode) -> KeyCode {
+    match key {
+        KeyCode::Char(c) => KeyCode::Char(c.to_ascii_lowercase()),
+        key => key,
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use devotee_backend::Input;
+use gilrs::{EventType, GamepadId, Gilrs};
+
+pub use gilrs::{Axis, Button};
+
+/// Gamepad input initialization error enumeration.
+#[derive(Debug)]
+pub enum GamepadError {
+    /// Gilrs initialization error.
+    GilrsError(Box<gilrs::Error>),
+}
+
+impl From<gilrs::Error> for GamepadError {
+    fn from(value: gilrs::Error) -> Self {
+        Self::GilrsError(Box::new(value))
+    }
+}
+
+#[derive(Debug)]
+struct Slot {
+    id: GamepadId,
+    name: String,
+    pressed: HashSet<Button>,
+    was_pressed: HashSet<Button>,
+    axes: HashMap<Axis, f32>,
+}
+
+impl Slot {
+    fn new(id: GamepadId, name: String) -> Self {
+        Self {
+            id,
+            name,
+            pressed: HashSet::new(),
+            was_pressed: HashSet::new(),
+            axes: HashMap::new(),
+        }
+    }
+}
+
+/// Gamepad input system with multiple controller slots.
+///
+/// Controllers are assigned to the first free slot on connection and keep it until disconnected.
+/// It passes all the backend events through, so it works with any backend event type.
+pub struct Gamepad<Event> {
+    gilrs: Gilrs,
+    slots: Vec<Option<Slot>>,
+    deadzone: f32,
+    deadzones: HashMap<Axis, f32>,
+    _event: PhantomData<fn(Event) -> Event>,
+}
+
+impl<Event> Gamepad<Event> {
+    /// Create new Gamepad input system instance with 4 controller slots.
+    /// Already connected controllers are assigned to the slots in order.
+    pub fn try_new() -> Result<Self, GamepadError> {
+        let gilrs = Gilrs::new()?;
+        let mut gamepad = Self {
+            gilrs,
+            slots: Vec::new(),
+            deadzone: 0.1,
+            deadzones: HashMap::new(),
+            _event: PhantomData,
+        };
+        gamepad.set_slots(4);
+        Ok(gamepad)
+    }
+
+    /// Set number of controller slots.
+    /// Controllers not fitting into the slots are ignored until some slot is freed.
+    pub fn with_slots(mut self, slots: usize) -> Self {
+        self.set_slots(slots);
+        self
+    }
+
+    /// Set deadzone applied to all the axes without specific deadzone.
+    pub fn with_deadzone(self, deadzone: f32) -> Self {
+        Self {
+            deadzone: deadzone.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Set deadzone for the specific `axis`.
+    pub fn with_axis_deadzone(mut self, axis: Axis, deadzone: f32) -> Self {
+        self.deadzones.insert(axis, deadzone.clamp(0.0, 1.0));
+        self
+    }
+
+    fn set_slots(&mut self, slots: usize) {
+        self.slots.resize_with(slots, || None);
+        let connected: Vec<_> = self
+            .gilrs
+            .gamepads()
+            .map(|(id, gamepad)| (id, gamepad.name().to_owned()))
+            .collect();
+        for (id, name) in connected {
+            self.connect(id, name);
+        }
+    }
+
+    fn connect(&mut self, id: GamepadId, name: String) {
+        if self.slot_index(id).is_some() {
+            return;
+        }
+        if let Some(slot) = self.slots.iter_mut().find(|slot| slot.is_none()) {
+            *slot = Some(Slot::new(id, name));
+        }
+    }
+
+    fn slot_index(&self, id: GamepadId) -> Option<usize> {
+        self.slots
+            .iter()
+            .position(|slot| slot.as_ref().is_some_and(|slot| slot.id == id))
+    }
+
+    fn slot(&self, slot: usize) -> Option<&Slot> {
+        self.slots.get(slot)?.as_ref()
+    }
+
+    /// Get number of controller slots.
+    pub fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Iterate over indices of the slots with connected controllers.
+    pub fn connected(&self) -> impl Iterator<Item = usize> + '_ {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|_| index))
+    }
+
+    /// Check if there is a controller connected to the `slot`.
+    pub fn is_connected(&self, slot: usize) -> bool {
+        self.slot(slot).is_some()
+    }
+
+    /// Get name of the controller connected to the `slot`.
+    pub fn name(&self, slot: usize) -> Option<&str> {
+        self.slot(slot).map(|slot| slot.name.as_str())
+    }
+
+    /// Check if the button is pressed on the controller in the `slot`.
+    pub fn is_pressed(&self, slot: usize, button: Button) -> bool {
+        self.slot(slot)
+            .is_some_and(|slot| slot.pressed.contains(&button))
+    }
+
+    /// Check if the button was pressed on the controller in the `slot` during the previous tick and not before.
+    pub fn just_pressed(&self, slot: usize, button: Button) -> bool {
+        self.slot(slot).is_some_and(|slot| {
+            slot.pressed.contains(&button) && !slot.was_pressed.contains(&button)
+        })
+    }
+
+    /// Check if the button was released on the controller in the `slot` during the previous tick.
+    pub fn just_released(&self, slot: usize, button: Button) -> bool {
+        self.slot(slot).is_some_and(|slot| {
+            !slot.pressed.contains(&button) && slot.was_pressed.contains(&button)
+        })
+    }
+
+    /// Check if any button is pressed on the controller in the `slot`.
+    pub fn is_pressed_any(&self, slot: usize) -> bool {
+        self.slot(slot).is_some_and(|slot| !slot.pressed.is_empty())
+    }
+
+    /// Get the raw `axis` value in `[-1.0, 1.0]` range of the controller in the `slot`.
+    pub fn raw_axis(&self, slot: usize, axis: Axis) -> f32 {
+        self.slot(slot)
+            .and_then(|slot| slot.axes.get(&axis).copied())
+            .unwrap_or(0.0)
+    }
+
+    /// Get the `axis` value with the deadzone applied.
+    /// Values within the deadzone are zero, the rest is rescaled to cover the whole `[-1.0, 1.0]` range.
+    pub fn axis(&self, slot: usize, axis: Axis) -> f32 {
+        let deadzone = self.deadzone(axis);
+        let value = self.raw_axis(slot, axis);
+        if value.abs() <= deadzone {
+            0.0
+        } else {
+            value.signum() * ((value.abs() - deadzone) / (1.0 - deadzone)).min(1.0)
+        }
+    }
+
+    /// Get the left stick position with radial deadzone of the `LeftStickX` axis applied.
+    pub fn left_stick(&self, slot: usize) -> (f32, f32) {
+        self.stick(slot, Axis::LeftStickX, Axis::LeftStickY)
+    }
+
+    /// Get the right stick position with radial deadzone of the `RightStickX` axis applied.
+    pub fn right_stick(&self, slot: usize) -> (f32, f32) {
+        self.stick(slot, Axis::RightStickX, Axis::RightStickY)
+    }
+
+    fn stick(&self, slot: usize, x: Axis, y: Axis) -> (f32, f32) {
+        let deadzone = self.deadzone(x);
+        let (x, y) = (self.raw_axis(slot, x), self.raw_axis(slot, y));
+        let length = (x * x + y * y).sqrt();
+        if length <= deadzone {
+            (0.0, 0.0)
+        } else {
+            let scale = ((length - deadzone) / (1.0 - deadzone)).min(1.0) / length;
+            (x * scale, y * scale)
+        }
+    }
+
+    fn deadzone(&self, axis: Axis) -> f32 {
+        self.deadzones.get(&axis).copied().unwrap_or(self.deadzone)
+    }
+
+    fn poll(&mut self) {
+        while let Some(event) = self.gilrs.next_event() {
+            if let EventType::Connected = event.event {
+                let name = self.gilrs.gamepad(event.id).name().to_owned();
+                self.connect(event.id, name);
+                continue;
+            }
+            let Some(index) = self.slot_index(event.id) else {
+                continue;
+            };
+            match event.event {
+                EventType::Disconnected => {
+                    self.slots[index] = None;
+                }
+                EventType::ButtonPressed(button, _) => {
+                    if let Some(slot) = &mut self.slots[index] {
+                        slot.pressed.insert(button);
+                    }
+                }
+                EventType::ButtonReleased(button, _) => {
+                    if let Some(slot) = &mut self.slots[index] {
+                        slot.pressed.remove(&button);
+                    }
+                }
+                EventType::AxisChanged(axis, value, _) => {
+                    if let Some(slot) = &mut self.slots[index] {
+                        slot.axes.insert(axis, value);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl<EventContext, Event> Input<'_, EventContext> for Gamepad<Event> {
+    type Event = Event;
+
+    fn handle_event(&mut self, event: Self::Event, _: &EventContext) -> Option<Self::Event> {
+        Some(event)
+    }
+
+    fn tick(&mut self) {
+        for slot in self.slots.iter_mut().flatten() {
+            slot.was_pressed.clone_from(&slot.pressed);
+        }
+        self.poll();
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+/// Device capable of force-feedback.
+pub trait Haptics {
+    /// Set the vibration strength in `[0.0, 1.0]` range, `0.0` stops the vibration.
+    /// Return `false` if the device does not support force-feedback.
+    fn set_vibration(&mut self, strength: f32) -> bool;
+}
+
+/// Rumble effect state of a single device.
+/// The effect is stopped on its own once its duration elapses, even if the game does not stop it explicitly.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rumble {
+    strength: f32,
+    remaining: Duration,
+    applied: f32,
+    supported: Option<bool>,
+}
+
+impl Rumble {
+    /// Create new idle rumble state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start rumble effect with `strength` in `[0.0, 1.0]` range lasting for `duration`.
+    /// The new effect replaces the current one.
+    pub fn rumble(&mut self, strength: f32, duration: Duration) -> &mut Self {
+        self.strength = if strength.is_nan() {
+            0.0
+        } else {
+            strength.clamp(0.0, 1.0)
+        };
+        self.remaining = duration;
+        self
+    }
+
+    /// Stop the current effect.
+    pub fn stop(&mut self) -> &mut Self {
+        self.rumble(0.0, Duration::ZERO)
+    }
+
+    /// Get the current effect strength.
+    pub fn strength(&self) -> f32 {
+        if self.remaining.is_zero() {
+            0.0
+        } else {
+            self.strength
+        }
+    }
+
+    /// Get the remaining duration of the current effect.
+    pub fn remaining(&self) -> Duration {
+        self.remaining
+    }
+
+    /// Check if there is an active effect.
+    pub fn is_active(&self) -> bool {
+        self.strength() > 0.0
+    }
+
+    /// Check if the device is known to support force-feedback.
+    /// Returns `None` if it was not applied to any device yet.
+    pub fn is_supported(&self) -> Option<bool> {
+        self.supported
+    }
+
+    /// Advance the effect by the `delta` time.
+    pub fn tick(&mut self, delta: Duration) {
+        self.remaining = self.remaining.saturating_sub(delta);
+    }
+
+    /// Apply the current effect strength to the `device`.
+    /// The device is updated only when the strength changes; unsupported devices are not touched again.
+    pub fn apply(&mut self, device: &mut dyn Haptics) {
+        if self.supported == Some(false) {
+            return;
+        }
+        let strength = self.strength();
+        if self.supported.is_none() || strength != self.applied {
+            self.supported = Some(device.set_vibration(strength));
+            self.applied = strength;
+        }
+    }
+}
@@ -1,8 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use devotee_backend::Input;
-use winit::event::{ElementState, WindowEvent};
-use winit::keyboard::PhysicalKey;
+use winit::event::{
+    ElementState, Ime, MouseScrollDelta, Touch as TouchEvent, TouchPhase, WindowEvent,
+};
+use winit::keyboard::{Key, NamedKey, PhysicalKey};
 
 use crate::util::vector::Vector;
 
@@ -70,6 +72,107 @@ impl<EventContext> Input<'_, EventContext> for Keyboard {
     }
 }
 
+/// Text input system accumulating typed characters per tick.
+///
+/// Typed text respects the keyboard layout and input method.
+/// IME events are only reported if IME is allowed on the window.
+/// It does not consume keyboard events, so it can be combined with the `Keyboard` input system.
+#[derive(Clone, Debug, Default)]
+pub struct TextInput {
+    text: String,
+    backspaces: usize,
+    preedit: Option<String>,
+}
+
+impl TextInput {
+    /// Create new TextInput system instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get text typed during the previous tick.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get amount of backspaces pressed during the previous tick not erasing the text typed during the same tick.
+    pub fn backspaces(&self) -> usize {
+        self.backspaces
+    }
+
+    /// Get text being composed with the input method, if any.
+    pub fn preedit(&self) -> Option<&str> {
+        self.preedit.as_deref()
+    }
+
+    /// Apply backspaces and the text typed during the previous tick to the `target` string.
+    pub fn apply(&self, target: &mut String) {
+        for _ in 0..self.backspaces {
+            target.pop();
+        }
+        target.push_str(&self.text);
+    }
+
+    /// Take the text typed during the previous tick, leaving it empty.
+    pub fn drain_text(&mut self) -> String {
+        std::mem::take(&mut self.text)
+    }
+
+    fn push_str(&mut self, text: &str) {
+        self.text
+            .extend(text.chars().filter(|character| !character.is_control()));
+    }
+
+    fn backspace(&mut self) {
+        if self.text.pop().is_none() {
+            self.backspaces += 1;
+        }
+    }
+}
+
+impl<EventContext> Input<'_, EventContext> for TextInput {
+    type Event = WindowEvent;
+
+    fn handle_event(&mut self, event: Self::Event, _context: &EventContext) -> Option<Self::Event> {
+        match event {
+            WindowEvent::KeyboardInput {
+                event: ref key_event,
+                ..
+            } => {
+                if key_event.state == ElementState::Pressed {
+                    if key_event.logical_key == Key::Named(NamedKey::Backspace) {
+                        self.backspace();
+                    } else if let Some(text) = &key_event.text {
+                        self.push_str(text);
+                    }
+                }
+                Some(event)
+            }
+            WindowEvent::Ime(ime) => {
+                match ime {
+                    Ime::Preedit(text, _) => {
+                        self.preedit = (!text.is_empty()).then_some(text);
+                    }
+                    Ime::Commit(text) => {
+                        self.preedit = None;
+                        self.push_str(&text);
+                    }
+                    Ime::Enabled | Ime::Disabled => {
+                        self.preedit = None;
+                    }
+                }
+                None
+            }
+            _ => Some(event),
+        }
+    }
+
+    fn tick(&mut self) {
+        self.text.clear();
+        self.backspaces = 0;
+    }
+}
+
 /// Mouse position representation.
 #[derive(Clone, Copy, Debug)]
 pub enum MousePosition {
@@ -90,12 +193,41 @@ impl MousePosition {
     }
 }
 
+/// Scroll wheel delta accumulated during a tick.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollDelta {
+    lines: (f32, f32),
+    pixels: (f64, f64),
+}
+
+impl ScrollDelta {
+    /// Get scrolled amount of lines or rows, reported by most of the mouse wheels.
+    /// Positive values mean scrolling right and up.
+    pub fn lines(&self) -> (f32, f32) {
+        self.lines
+    }
+
+    /// Get scrolled amount of pixels, reported by touchpads.
+    /// Positive values mean scrolling right and up.
+    pub fn pixels(&self) -> (f64, f64) {
+        self.pixels
+    }
+
+    /// Check if there was no scrolling.
+    pub fn is_zero(&self) -> bool {
+        self.lines == (0.0, 0.0) && self.pixels == (0.0, 0.0)
+    }
+}
+
 /// Mouse-related input system.
 #[derive(Clone, Debug)]
 pub struct Mouse {
     position: MousePosition,
     pressed: HashSet<MouseButton>,
     was_pressed: HashSet<MouseButton>,
+    scroll_delta: ScrollDelta,
+    window_position: Option<(f64, f64)>,
+    motion_delta: (f64, f64),
 }
 
 impl Mouse {
@@ -104,10 +236,16 @@ impl Mouse {
         let position = MousePosition::Inside((0, 0).into());
         let pressed = Default::default();
         let was_pressed = Default::default();
+        let scroll_delta = ScrollDelta::default();
+        let window_position = None;
+        let motion_delta = (0.0, 0.0);
         Self {
             position,
             pressed,
             was_pressed,
+            scroll_delta,
+            window_position,
+            motion_delta,
         }
     }
 
@@ -130,6 +268,19 @@ impl Mouse {
     pub fn position(&self) -> MousePosition {
         self.position
     }
+
+    /// Get scroll wheel delta accumulated during the previous tick.
+    pub fn scroll_delta(&self) -> ScrollDelta {
+        self.scroll_delta
+    }
+
+    /// Get cursor motion in physical window pixels accumulated during the previous tick.
+    ///
+    /// The motion stops once the cursor reaches the screen edge;
+    /// raw device motion is available from the backend control `cursor_motion`.
+    pub fn motion_delta(&self) -> (f64, f64) {
+        self.motion_delta
+    }
 }
 
 impl<EventContext> Input<'_, EventContext> for Mouse
@@ -147,7 +298,29 @@ where
                 };
                 None
             }
+            WindowEvent::MouseWheel { delta, .. } => {
+                match delta {
+                    MouseScrollDelta::LineDelta(x, y) => {
+                        self.scroll_delta.lines.0 += x;
+                        self.scroll_delta.lines.1 += y;
+                    }
+                    MouseScrollDelta::PixelDelta(position) => {
+                        self.scroll_delta.pixels.0 += position.x;
+                        self.scroll_delta.pixels.1 += position.y;
+                    }
+                }
+                None
+            }
+            WindowEvent::CursorEntered { .. } => {
+                self.window_position = None;
+                Some(event)
+            }
             WindowEvent::CursorMoved { position, .. } => {
+                if let Some((x, y)) = self.window_position {
+                    self.motion_delta.0 += position.x - x;
+                    self.motion_delta.1 += position.y - y;
+                }
+                self.window_position = Some((position.x, position.y));
                 match context
                     .position_into_render_surface_space((position.x as f32, position.y as f32))
                 {
@@ -165,7 +338,9 @@ where
     }
 
     fn tick(&mut self) {
-        self.was_pressed.clone_from(&self.pressed)
+        self.was_pressed.clone_from(&self.pressed);
+        self.scroll_delta = ScrollDelta::default();
+        self.motion_delta = (0.0, 0.0);
     }
 }
 
@@ -175,11 +350,214 @@ impl Default for Mouse {
     }
 }
 
-/// Keyboard and mouse input systems union.
+/// Touch point gesture state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TouchState {
+    /// The point is just pressed and may become a tap, a hold or a drag.
+    Pressed,
+
+    /// The point is held in place for the hold duration.
+    Hold,
+
+    /// The point moved further than the drag threshold from its start position.
+    Drag,
+}
+
+/// Single touch point representation.
+#[derive(Clone, Copy, Debug)]
+pub struct TouchPoint {
+    id: u64,
+    start: MousePosition,
+    position: MousePosition,
+    state: TouchState,
+    ticks: u32,
+}
+
+impl TouchPoint {
+    /// Get the touch point identifier unique among the active touch points.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Get position the touch started at.
+    pub fn start(&self) -> MousePosition {
+        self.start
+    }
+
+    /// Get current touch position.
+    pub fn position(&self) -> MousePosition {
+        self.position
+    }
+
+    /// Get touch gesture state.
+    pub fn state(&self) -> TouchState {
+        self.state
+    }
+
+    /// Get offset of the current position from the start position.
+    pub fn offset(&self) -> Vector<i32> {
+        self.position.any() - self.start.any()
+    }
+}
+
+/// Touch-related input system tracking multiple touch points.
+#[derive(Clone, Debug)]
+pub struct Touch {
+    points: HashMap<u64, TouchPoint>,
+    started: HashSet<u64>,
+    ended: Vec<TouchPoint>,
+    hold_ticks: u32,
+    drag_threshold: i32,
+}
+
+impl Touch {
+    /// Create new Touch input system instance.
+    pub fn new() -> Self {
+        let points = HashMap::new();
+        let started = HashSet::new();
+        let ended = Vec::new();
+        let hold_ticks = 30;
+        let drag_threshold = 4;
+        Self {
+            points,
+            started,
+            ended,
+            hold_ticks,
+            drag_threshold,
+        }
+    }
+
+    /// Set number of ticks a touch point has to stay in place to be considered held.
+    pub fn with_hold_ticks(self, hold_ticks: u32) -> Self {
+        Self { hold_ticks, ..self }
+    }
+
+    /// Set distance in render surface pixels a touch point has to move to be considered dragged.
+    pub fn with_drag_threshold(self, drag_threshold: i32) -> Self {
+        Self {
+            drag_threshold,
+            ..self
+        }
+    }
+
+    /// Iterate over the active touch points.
+    pub fn points(&self) -> impl Iterator<Item = &TouchPoint> {
+        self.points.values()
+    }
+
+    /// Get the active touch point by its identifier.
+    pub fn point(&self, id: u64) -> Option<&TouchPoint> {
+        self.points.get(&id)
+    }
+
+    /// Check if any touch point is active.
+    pub fn is_touched(&self) -> bool {
+        !self.points.is_empty()
+    }
+
+    /// Iterate over the touch points started during the previous tick.
+    pub fn just_started(&self) -> impl Iterator<Item = &TouchPoint> {
+        self.started.iter().filter_map(|id| self.points.get(id))
+    }
+
+    /// Iterate over the touch points ended or cancelled during the previous tick.
+    pub fn just_ended(&self) -> impl Iterator<Item = &TouchPoint> {
+        self.ended.iter()
+    }
+
+    /// Iterate over the touch points ended during the previous tick without being held or dragged.
+    pub fn taps(&self) -> impl Iterator<Item = &TouchPoint> {
+        self.ended
+            .iter()
+            .filter(|point| point.state == TouchState::Pressed)
+    }
+
+    fn handle_touch<EventContext>(&mut self, touch: TouchEvent, context: &EventContext)
+    where
+        EventContext: backend::EventContext,
+    {
+        let position = match context
+            .position_into_render_surface_space((touch.location.x as f32, touch.location.y as f32))
+        {
+            Ok(inside) => MousePosition::Inside(inside.into()),
+            Err(outside) => MousePosition::Outside(outside.into()),
+        };
+        match touch.phase {
+            TouchPhase::Started => {
+                self.started.insert(touch.id);
+                self.points.insert(
+                    touch.id,
+                    TouchPoint {
+                        id: touch.id,
+                        start: position,
+                        position,
+                        state: TouchState::Pressed,
+                        ticks: 0,
+                    },
+                );
+            }
+            TouchPhase::Moved => {
+                if let Some(point) = self.points.get_mut(&touch.id) {
+                    point.position = position;
+                    let offset = point.offset();
+                    if offset.x().abs().max(offset.y().abs()) >= self.drag_threshold {
+                        point.state = TouchState::Drag;
+                    }
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                if let Some(mut point) = self.points.remove(&touch.id) {
+                    point.position = position;
+                    if touch.phase == TouchPhase::Cancelled && point.state == TouchState::Pressed {
+                        // Cancelled touches are never reported as taps.
+                        point.state = TouchState::Hold;
+                    }
+                    self.ended.push(point);
+                }
+            }
+        }
+    }
+}
+
+impl Default for Touch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<EventContext> Input<'_, EventContext> for Touch
+where
+    EventContext: backend::EventContext,
+{
+    type Event = WindowEvent;
+
+    fn handle_event(&mut self, event: Self::Event, context: &EventContext) -> Option<Self::Event> {
+        if let WindowEvent::Touch(touch) = event {
+            self.handle_touch(touch, context);
+            None
+        } else {
+            Some(event)
+        }
+    }
+
+    fn tick(&mut self) {
+        self.started.clear();
+        self.ended.clear();
+        for point in self.points.values_mut() {
+            point.ticks = point.ticks.saturating_add(1);
+            if point.state == TouchState::Pressed && point.ticks >= self.hold_ticks {
+                point.state = TouchState::Hold;
+            }
+        }
+    }
+}
+
+/// Keyboard, mouse and touch input systems union.
 #[derive(Clone, Debug, Default)]
 pub struct KeyboardMouse {
     keyboard: Keyboard,
     mouse: Mouse,
+    touch: Touch,
 }
 
 impl KeyboardMouse {
@@ -197,6 +575,11 @@ impl KeyboardMouse {
     pub fn mouse(&self) -> &Mouse {
         &self.mouse
     }
+
+    /// Get touch subsystem instance reference.
+    pub fn touch(&self) -> &Touch {
+        &self.touch
+    }
 }
 
 impl<EventContext> Input<'_, EventContext> for KeyboardMouse
@@ -207,12 +590,14 @@ where
 
     fn handle_event(&mut self, event: Self::Event, context: &EventContext) -> Option<Self::Event> {
         let event = self.keyboard.handle_event(event, context)?;
-        self.mouse.handle_event(event, context)
+        let event = self.mouse.handle_event(event, context)?;
+        self.touch.handle_event(event, context)
     }
 
     fn tick(&mut self) {
         Input::<'_, EventContext>::tick(&mut self.keyboard);
         Input::<'_, EventContext>::tick(&mut self.mouse);
+        Input::<'_, EventContext>::tick(&mut self.touch);
     }
 }
 
@@ -7,9 +7,18 @@ extern crate devotee_backend as backend;
 /// Default application implementation for the devotee project.
 pub mod app;
 
+/// Debugging and diagnostics helpers.
+pub mod diagnostics;
+
+/// Ready-made palettes, converters and fonts.
+pub mod extras;
+
 /// Input implementations.
 pub mod input;
 
+/// User interface helpers.
+pub mod ui;
+
 /// Various utility.
 pub mod util;
 
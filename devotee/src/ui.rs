@@ -0,0 +1,11 @@
+/// Runtime palette editing overlay.
+#[cfg(feature = "winit-input")]
+pub mod palette_editor;
+/// On-screen gamepad overlay.
+#[cfg(feature = "winit-input")]
+pub mod virtual_gamepad;
+
+#[cfg(feature = "winit-input")]
+pub use palette_editor::{Channel, PaletteEditor, PaletteElement};
+#[cfg(feature = "winit-input")]
+pub use virtual_gamepad::{VirtualButton, VirtualGamepad};
@@ -0,0 +1,330 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+use std::ops::{Deref, DerefMut};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use winit::event::MouseButton;
+
+use crate::input::winit_input::Mouse;
+use crate::util::vector::Vector;
+use crate::visual::image::{DesignatorMut, DesignatorRef};
+use crate::visual::palette::Palette;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::visual::palette::{load_palette, store_palette};
+use crate::visual::prelude::*;
+
+const GAP: i32 = 2;
+
+/// Color channel adjusted by a slider.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Channel {
+    /// Red channel.
+    Red,
+    /// Green channel.
+    Green,
+    /// Blue channel.
+    Blue,
+}
+
+impl Channel {
+    const ALL: [Channel; 3] = [Channel::Red, Channel::Green, Channel::Blue];
+
+    fn shift(self) -> u32 {
+        match self {
+            Channel::Red => 16,
+            Channel::Green => 8,
+            Channel::Blue => 0,
+        }
+    }
+
+    /// Get channel value of the `0xff_rr_gg_bb` color.
+    pub fn get(self, color: u32) -> u8 {
+        (color >> self.shift()) as u8
+    }
+
+    /// Replace channel value of the `0xff_rr_gg_bb` color.
+    pub fn set(self, color: u32, value: u8) -> u32 {
+        color & !(0xff << self.shift()) | (value as u32) << self.shift()
+    }
+}
+
+/// Part of the palette editor a pixel belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PaletteElement {
+    /// Editor background.
+    Background,
+    /// Swatch of the palette entry with the index.
+    Swatch(usize),
+    /// Outline of the selected swatch.
+    Selection,
+    /// Unfilled part of the channel slider.
+    Track(Channel),
+    /// Filled part of the channel slider.
+    Fill(Channel),
+}
+
+/// Overlay displaying the runtime palette and adjusting its entries with RGB sliders.
+/// Any adjustment goes through the palette, emitting its invalidation signal.
+#[derive(Clone, Debug)]
+pub struct PaletteEditor {
+    position: Vector<i32>,
+    swatch: i32,
+    columns: usize,
+    selected: usize,
+    dragging: Option<Channel>,
+    visible: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    path: Option<PathBuf>,
+}
+
+impl PaletteEditor {
+    /// Create new visible editor with 8 columns of 8 pixel swatches.
+    pub fn new() -> Self {
+        Self {
+            position: Vector::new(0, 0),
+            swatch: 8,
+            columns: 8,
+            selected: 0,
+            dragging: None,
+            visible: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            path: None,
+        }
+    }
+
+    /// Set top left corner of the editor in render surface space.
+    pub fn with_position(self, position: Vector<i32>) -> Self {
+        Self { position, ..self }
+    }
+
+    /// Set swatch size in pixels.
+    ///
+    /// # Panics
+    /// Panics if `swatch` is not positive.
+    pub fn with_swatch_size(self, swatch: i32) -> Self {
+        assert!(swatch > 0, "Swatch size must be positive");
+        Self { swatch, ..self }
+    }
+
+    /// Set amount of swatches per row.
+    ///
+    /// # Panics
+    /// Panics if `columns` is zero.
+    pub fn with_columns(self, columns: usize) -> Self {
+        assert_ne!(columns, 0, "Columns amount can't be zero");
+        Self { columns, ..self }
+    }
+
+    /// Set file the palette is saved into and loaded from.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_path<P: Into<PathBuf>>(self, path: P) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Check if the editor is visible.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show or hide the editor.
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        self.dragging = None;
+    }
+
+    /// Get index of the selected palette entry.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select palette entry by its index.
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+    }
+
+    /// Get editor dimensions for the palette of `len` colors.
+    pub fn dimensions(&self, len: usize) -> Vector<i32> {
+        let sliders = Channel::ALL.len() as i32;
+        Vector::new(
+            self.columns as i32 * self.swatch,
+            self.rows(len) * self.swatch + sliders * (self.slider_height() + GAP) + GAP,
+        )
+    }
+
+    fn rows(&self, len: usize) -> i32 {
+        len.div_ceil(self.columns) as i32
+    }
+
+    fn slider_height(&self) -> i32 {
+        (self.swatch / 2).max(2)
+    }
+
+    fn swatch_origin(&self, index: usize) -> Vector<i32> {
+        let (column, row) = (index % self.columns, index / self.columns);
+        self.position + (column as i32 * self.swatch, row as i32 * self.swatch)
+    }
+
+    fn slider_origin(&self, len: usize, channel: Channel) -> Vector<i32> {
+        let index = Channel::ALL.iter().position(|c| *c == channel).unwrap_or(0) as i32;
+        self.position
+            + (
+                0,
+                self.rows(len) * self.swatch + GAP + index * (self.slider_height() + GAP),
+            )
+    }
+
+    fn slider_at(&self, len: usize, position: Vector<i32>) -> Option<Channel> {
+        let width = self.columns as i32 * self.swatch;
+        Channel::ALL.into_iter().find(|channel| {
+            let (x, y) = (position - self.slider_origin(len, *channel)).split();
+            x >= 0 && x < width && y >= 0 && y < self.slider_height()
+        })
+    }
+
+    fn swatch_at(&self, len: usize, position: Vector<i32>) -> Option<usize> {
+        let (x, y) = (position - self.position).split();
+        if x < 0 || y < 0 || x >= self.columns as i32 * self.swatch {
+            return None;
+        }
+        let index = (y / self.swatch) as usize * self.columns + (x / self.swatch) as usize;
+        (index < len).then_some(index)
+    }
+
+    /// Handle the mouse: clicking a swatch selects it, dragging along a slider adjusts the selected color.
+    /// Returns `true` if the palette was changed.
+    pub fn update(&mut self, mouse: &Mouse, palette: &mut Palette) -> bool {
+        if !self.visible {
+            return false;
+        }
+        let len = palette.colors().len();
+        let position = mouse.position().any();
+        if mouse.just_pressed(MouseButton::Left) {
+            if let Some(index) = self.swatch_at(len, position) {
+                self.selected = index;
+            }
+            self.dragging = self.slider_at(len, position);
+        }
+        if !mouse.is_pressed(MouseButton::Left) {
+            self.dragging = None;
+        }
+        let (Some(channel), Some(color)) = (self.dragging, palette.color(self.selected)) else {
+            return false;
+        };
+        let width = self.columns as i32 * self.swatch;
+        let x = (position - self.slider_origin(len, channel))
+            .x()
+            .clamp(0, width - 1);
+        let value = (x * 255 / (width - 1).max(1)) as u8;
+        let adjusted = channel.set(color, value);
+        palette.set_color(self.selected, adjusted);
+        adjusted != color
+    }
+
+    /// Save the palette into the editor file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, palette: &Palette) -> io::Result<()> {
+        store_palette(self.path()?, palette.colors())
+    }
+
+    /// Replace the palette colors with ones loaded from the editor file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(&mut self, palette: &mut Palette) -> io::Result<()> {
+        let colors = load_palette(self.path()?)?;
+        self.selected = self.selected.min(colors.len().saturating_sub(1));
+        palette.swap(colors);
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn path(&self) -> io::Result<&PathBuf> {
+        self.path
+            .as_ref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Palette path is not set"))
+    }
+
+    /// Render the editor with the provided function.
+    /// The function receives pixel position, its original value and the editor element there.
+    pub fn render<T, F>(&self, target: &mut T, palette: &Palette, function: F)
+    where
+        T: ImageMut,
+        T::Pixel: Clone,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+        F: FnMut(i32, i32, T::Pixel, PaletteElement) -> T::Pixel,
+    {
+        if !self.visible {
+            return;
+        }
+        let mut function = function;
+        let mut painter = target.painter::<i32>();
+        let len = palette.colors().len();
+        painter.rect_f((self.position, self.dimensions(len)), |x, y, pixel| {
+            function(x, y, pixel, PaletteElement::Background)
+        });
+        for index in 0..len {
+            painter.rect_f(
+                (self.swatch_origin(index), (self.swatch, self.swatch).into()),
+                |x, y, pixel| function(x, y, pixel, PaletteElement::Swatch(index)),
+            );
+        }
+        if self.selected < len {
+            painter.rect_b(
+                (
+                    self.swatch_origin(self.selected),
+                    (self.swatch, self.swatch).into(),
+                ),
+                |x, y, pixel| function(x, y, pixel, PaletteElement::Selection),
+            );
+        }
+        let color = palette.color(self.selected).unwrap_or(0);
+        let width = self.columns as i32 * self.swatch;
+        for channel in Channel::ALL {
+            let origin = self.slider_origin(len, channel);
+            let filled = (channel.get(color) as i32 * width / 255).max(1);
+            let height = self.slider_height();
+            painter.rect_f((origin, (width, height).into()), |x, y, pixel| {
+                function(x, y, pixel, PaletteElement::Track(channel))
+            });
+            painter.rect_f((origin, (filled, height).into()), |x, y, pixel| {
+                function(x, y, pixel, PaletteElement::Fill(channel))
+            });
+        }
+    }
+
+    /// Get render function for indexed targets drawing swatches with their own indices.
+    pub fn indexed(
+        background: u8,
+        foreground: u8,
+    ) -> impl FnMut(i32, i32, u8, PaletteElement) -> u8 {
+        move |_, _, _, element| match element {
+            PaletteElement::Swatch(index) => index as u8,
+            PaletteElement::Selection | PaletteElement::Fill(_) => foreground,
+            PaletteElement::Background | PaletteElement::Track(_) => background,
+        }
+    }
+
+    /// Get render function for `0xff_rr_gg_bb` targets drawing swatches with the palette colors.
+    pub fn colored(
+        palette: &Palette,
+        background: u32,
+        foreground: u32,
+    ) -> impl FnMut(i32, i32, u32, PaletteElement) -> u32 {
+        let colors = palette.colors().to_vec();
+        move |_, _, _, element| match element {
+            PaletteElement::Swatch(index) => 0xff_00_00_00 | colors[index],
+            PaletteElement::Selection => foreground,
+            PaletteElement::Fill(channel) => channel.set(0xff_00_00_00, 0xff),
+            PaletteElement::Background | PaletteElement::Track(_) => background,
+        }
+    }
+}
+
+impl Default for PaletteEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
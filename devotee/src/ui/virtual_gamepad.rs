@@ -0,0 +1,275 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+
+use backend::Input;
+use winit::event::{ElementState, MouseButton, TouchPhase, WindowEvent};
+
+use crate::util::vector::Vector;
+use crate::visual::blend::{self, BlendSpace};
+use crate::visual::image::{DesignatorMut, DesignatorRef};
+use crate::visual::prelude::*;
+
+const MOUSE_POINTER: u64 = u64::MAX;
+
+/// Buttons of the virtual gamepad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VirtualButton {
+    /// D-pad up direction.
+    Up,
+    /// D-pad down direction.
+    Down,
+    /// D-pad left direction.
+    Left,
+    /// D-pad right direction.
+    Right,
+    /// The `A` button.
+    A,
+    /// The `B` button.
+    B,
+    /// The `X` button.
+    X,
+    /// The `Y` button.
+    Y,
+    /// The `Start` button.
+    Start,
+    /// The `Select` button.
+    Select,
+}
+
+/// On-screen gamepad overlay controlled by touches and the left mouse button.
+/// Touches starting outside of the controls are passed through.
+#[derive(Clone, Debug)]
+pub struct VirtualGamepad {
+    dpad: Option<(Vector<i32>, i32)>,
+    buttons: Vec<(VirtualButton, Vector<i32>, i32)>,
+    opacity: u8,
+    cursor: Vector<i32>,
+    pointers: HashMap<u64, Vector<i32>>,
+    pressed: HashSet<VirtualButton>,
+    was_pressed: HashSet<VirtualButton>,
+}
+
+impl VirtualGamepad {
+    /// Create new virtual gamepad without any controls.
+    pub fn new() -> Self {
+        Self {
+            dpad: None,
+            buttons: Vec::new(),
+            opacity: 0x80,
+            cursor: Vector::new(0, 0),
+            pointers: HashMap::new(),
+            pressed: HashSet::new(),
+            was_pressed: HashSet::new(),
+        }
+    }
+
+    /// Place the D-pad at `center` with desired `radius` in render surface space.
+    pub fn with_dpad(self, center: Vector<i32>, radius: i32) -> Self {
+        Self {
+            dpad: Some((center, radius)),
+            ..self
+        }
+    }
+
+    /// Place round `button` at `center` with desired `radius` in render surface space.
+    pub fn with_button(self, button: VirtualButton, center: Vector<i32>, radius: i32) -> Self {
+        let mut buttons = self.buttons;
+        buttons.push((button, center, radius));
+        Self { buttons, ..self }
+    }
+
+    /// Set overlay opacity.
+    pub fn with_opacity(self, opacity: u8) -> Self {
+        Self { opacity, ..self }
+    }
+
+    /// Get overlay opacity.
+    pub fn opacity(&self) -> u8 {
+        self.opacity
+    }
+
+    /// Check if the button is pressed.
+    pub fn is_pressed(&self, button: VirtualButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Check if the button was pressed during the previous tick and not before.
+    pub fn just_pressed(&self, button: VirtualButton) -> bool {
+        self.pressed.contains(&button) && !self.was_pressed.contains(&button)
+    }
+
+    /// Check if the button was released during the previous tick.
+    pub fn just_released(&self, button: VirtualButton) -> bool {
+        !self.pressed.contains(&button) && self.was_pressed.contains(&button)
+    }
+
+    fn buttons_at(&self, position: Vector<i32>) -> impl Iterator<Item = VirtualButton> + '_ {
+        let dpad = self.dpad.into_iter().flat_map(move |(center, radius)| {
+            let (x, y) = (position - center).split();
+            let inside = x * x + y * y <= radius * radius;
+            let dead_zone = radius / 4;
+            [
+                (VirtualButton::Up, y < -dead_zone && y.abs() >= x.abs() / 2),
+                (VirtualButton::Down, y > dead_zone && y.abs() >= x.abs() / 2),
+                (
+                    VirtualButton::Left,
+                    x < -dead_zone && x.abs() >= y.abs() / 2,
+                ),
+                (
+                    VirtualButton::Right,
+                    x > dead_zone && x.abs() >= y.abs() / 2,
+                ),
+            ]
+            .into_iter()
+            .filter_map(move |(button, hit)| (inside && hit).then_some(button))
+        });
+        let buttons = self
+            .buttons
+            .iter()
+            .filter(move |(_, center, radius)| {
+                let (x, y) = (position - *center).split();
+                x * x + y * y <= radius * radius
+            })
+            .map(|(button, _, _)| *button);
+        dpad.chain(buttons)
+    }
+
+    fn refresh(&mut self) {
+        let pressed = self
+            .pointers
+            .values()
+            .flat_map(|position| self.buttons_at(*position))
+            .collect();
+        self.pressed = pressed;
+    }
+
+    /// Render the overlay with the provided function.
+    /// The function receives pixel position, its original value and pressed state of the control.
+    pub fn render<T, F>(&self, target: &mut T, function: F)
+    where
+        T: ImageMut,
+        T::Pixel: Clone,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+        F: FnMut(i32, i32, T::Pixel, bool) -> T::Pixel,
+    {
+        let mut function = function;
+        let mut painter = target.painter::<i32>();
+        if let Some((center, radius)) = self.dpad {
+            let arm = (radius * 2 / 3).max(1);
+            let half = arm / 2;
+            let arms = [
+                (VirtualButton::Up, (-half, -radius), (arm, radius - half)),
+                (VirtualButton::Down, (-half, half), (arm, radius - half)),
+                (VirtualButton::Left, (-radius, -half), (radius - half, arm)),
+                (VirtualButton::Right, (half, -half), (radius - half, arm)),
+            ];
+            for (button, origin, dimensions) in arms {
+                let pressed = self.is_pressed(button);
+                painter.rect_f((center + origin, dimensions.into()), |x, y, pixel| {
+                    function(x, y, pixel, pressed)
+                });
+            }
+            painter.rect_f((center - (half, half), (arm, arm).into()), |x, y, pixel| {
+                function(x, y, pixel, false)
+            });
+        }
+        for (button, center, radius) in self.buttons.iter() {
+            let pressed = self.is_pressed(*button);
+            painter.circle_f(*center, *radius, |x, y, pixel| {
+                function(x, y, pixel, pressed)
+            });
+        }
+    }
+
+    /// Get render function blending `0xaa_rr_gg_bb` colors with the overlay opacity.
+    pub fn blend_u32(
+        &self,
+        color: u32,
+        pressed_color: u32,
+    ) -> impl FnMut(i32, i32, u32, bool) -> u32 {
+        let opacity = (self.opacity as u32) << 24;
+        let mut released = blend::alpha(opacity | (color & 0x00_ff_ff_ff), BlendSpace::Srgb);
+        let mut pressed = blend::alpha(opacity | (pressed_color & 0x00_ff_ff_ff), BlendSpace::Srgb);
+        move |x, y, pixel, is_pressed| {
+            if is_pressed {
+                pressed(x, y, pixel)
+            } else {
+                released(x, y, pixel)
+            }
+        }
+    }
+}
+
+impl Default for VirtualGamepad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<EventContext> Input<'_, EventContext> for VirtualGamepad
+where
+    EventContext: backend::EventContext,
+{
+    type Event = WindowEvent;
+
+    fn handle_event(&mut self, event: Self::Event, context: &EventContext) -> Option<Self::Event> {
+        match event {
+            WindowEvent::Touch(touch) => {
+                let position = context
+                    .position_into_render_surface_space((
+                        touch.location.x as f32,
+                        touch.location.y as f32,
+                    ))
+                    .unwrap_or_else(|outside| outside);
+                let position = position.into();
+                let tracked = self.pointers.contains_key(&touch.id);
+                match touch.phase {
+                    TouchPhase::Started if self.buttons_at(position).next().is_none() => {
+                        return Some(event);
+                    }
+                    TouchPhase::Started => {
+                        self.pointers.insert(touch.id, position);
+                    }
+                    _ if !tracked => return Some(event),
+                    TouchPhase::Moved => {
+                        self.pointers.insert(touch.id, position);
+                    }
+                    TouchPhase::Ended | TouchPhase::Cancelled => {
+                        self.pointers.remove(&touch.id);
+                    }
+                }
+                self.refresh();
+                None
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                self.cursor = context
+                    .position_into_render_surface_space((position.x as f32, position.y as f32))
+                    .unwrap_or_else(|outside| outside)
+                    .into();
+                if let Some(pointer) = self.pointers.get_mut(&MOUSE_POINTER) {
+                    *pointer = self.cursor;
+                    self.refresh();
+                }
+                Some(event)
+            }
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+                ..
+            } => {
+                match state {
+                    ElementState::Pressed => self.pointers.insert(MOUSE_POINTER, self.cursor),
+                    ElementState::Released => self.pointers.remove(&MOUSE_POINTER),
+                };
+                self.refresh();
+                Some(event)
+            }
+            _ => Some(event),
+        }
+    }
+
+    fn tick(&mut self) {
+        self.was_pressed.clone_from(&self.pressed)
+    }
+}
@@ -1,4 +1,47 @@
+/// Generational asset storage with reference counted handles.
+pub mod assets;
+/// Spreading incremental work across frames within time budget.
+pub mod budget;
+/// Collision tests of boxes, circles, polygons and segments.
+pub mod collision;
+/// Easing curves and tweening of values over time.
+pub mod ease;
+/// Field of view computation over occlusion grids.
+pub mod fov;
 /// Collection of elements with `get` and `get_mut` operations.
 pub mod getter;
+/// Interpolation of values between update ticks.
+pub mod interp;
+/// Invalidation signals for cached data.
+pub mod invalidation;
+/// Asset loading resolved between update ticks.
+pub mod loader;
+/// Deterministic value and gradient noise.
+pub mod noise;
+/// Seeded dungeon and cave generators.
+pub mod procgen;
+/// Axis aligned rectangles with overlap tests.
+pub mod rect;
+/// Seedable pseudo-random number generation.
+pub mod rng;
+
+/// Deterministic pseudo-random numbers and noise functions in a single module.
+pub mod random {
+    pub use super::noise::{fractal_2d, perlin_1d, perlin_2d, value_1d, value_2d};
+    pub use super::rng::Rng;
+}
+/// Single application instance lock.
+#[cfg(not(target_arch = "wasm32"))]
+pub mod single_instance;
+/// Persistent key-value storage.
+pub mod storage;
+/// Timers and scheduled actions driven by update deltas.
+pub mod timer;
+/// Affine transforms of two-dimensional points.
+pub mod transform;
+/// Polygon triangulation for concave shapes.
+pub mod triangulation;
+/// Energy based turn scheduling.
+pub mod turns;
 /// Vector represents two-dimensional point in space.
 pub mod vector;
@@ -0,0 +1,343 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use super::getter::{Getter, GetterMut};
+
+/// Group assets are put into unless another one is specified.
+pub const DEFAULT_GROUP: &str = "default";
+
+/// Strong reference counted handle to the stored asset.
+/// Assets without strong handles are removed on `Assets::collect`.
+pub struct Handle<T> {
+    id: WeakHandle<T>,
+    counter: Arc<()>,
+}
+
+impl<T> Handle<T> {
+    /// Get weak version of this handle.
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        self.id
+    }
+}
+
+impl<T> From<&Handle<T>> for WeakHandle<T> {
+    fn from(handle: &Handle<T>) -> Self {
+        handle.id
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            counter: self.counter.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("index", &self.id.index)
+            .field("generation", &self.id.generation)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Generational handle to the stored asset not keeping it alive.
+pub struct WeakHandle<T> {
+    index: usize,
+    generation: u32,
+    asset: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for WeakHandle<T> {}
+
+impl<T> fmt::Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakHandle")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+impl<T> PartialEq for WeakHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for WeakHandle<T> {}
+
+impl<T> Hash for WeakHandle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    asset: T,
+    group: String,
+    counter: Arc<()>,
+}
+
+#[derive(Debug)]
+struct Slot<T> {
+    generation: u32,
+    entry: Option<Entry<T>>,
+}
+
+/// Summary of the live assets in the group.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GroupReport {
+    /// Amount of live assets.
+    pub count: usize,
+    /// Estimated memory usage in bytes.
+    pub bytes: usize,
+    /// Amount of assets without strong handles pending collection.
+    pub unreferenced: usize,
+}
+
+/// Summary of the live assets by group.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssetsReport {
+    /// Summary of all the live assets.
+    pub total: GroupReport,
+    /// Summary of live assets per group.
+    pub groups: BTreeMap<String, GroupReport>,
+}
+
+impl fmt::Display for AssetsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (group, report) in self
+            .groups
+            .iter()
+            .chain([(&"total".to_owned(), &self.total)])
+        {
+            writeln!(
+                f,
+                "{}: {} assets, {} bytes, {} unreferenced",
+                group, report.count, report.bytes, report.unreferenced
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Storage of assets accessed with generational handles.
+/// Handles to unloaded assets stay invalid even if their slot is reused.
+#[derive(Debug)]
+pub struct Assets<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    measure: fn(&T) -> usize,
+}
+
+impl<T> Assets<T> {
+    /// Create new empty storage estimating asset memory usage by its type size.
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            measure: |_| std::mem::size_of::<T>(),
+        }
+    }
+
+    /// Set function estimating memory usage of the asset in bytes for reports.
+    pub fn with_measure(self, measure: fn(&T) -> usize) -> Self {
+        Self { measure, ..self }
+    }
+
+    /// Store the asset in the default group.
+    pub fn insert(&mut self, asset: T) -> Handle<T> {
+        self.insert_in_group(DEFAULT_GROUP, asset)
+    }
+
+    /// Store the asset in the `group`.
+    pub fn insert_in_group(&mut self, group: &str, asset: T) -> Handle<T> {
+        let counter = Arc::new(());
+        let entry = Entry {
+            asset,
+            group: group.to_owned(),
+            counter: counter.clone(),
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].entry = Some(entry);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                self.slots.len() - 1
+            }
+        };
+        let id = WeakHandle {
+            index,
+            generation: self.slots[index].generation,
+            asset: PhantomData,
+        };
+        Handle { id, counter }
+    }
+
+    fn entry(&self, id: WeakHandle<T>) -> Option<&Entry<T>> {
+        self.slots
+            .get(id.index)
+            .filter(|slot| slot.generation == id.generation)?
+            .entry
+            .as_ref()
+    }
+
+    fn entry_mut(&mut self, id: WeakHandle<T>) -> Option<&mut Entry<T>> {
+        self.slots
+            .get_mut(id.index)
+            .filter(|slot| slot.generation == id.generation)?
+            .entry
+            .as_mut()
+    }
+
+    /// Try getting a strong handle to the asset if it is still loaded.
+    pub fn upgrade(&self, id: WeakHandle<T>) -> Option<Handle<T>> {
+        let counter = self.entry(id)?.counter.clone();
+        Some(Handle { id, counter })
+    }
+
+    /// Check if the asset is still loaded.
+    pub fn contains(&self, id: impl Into<WeakHandle<T>>) -> bool {
+        let id = id.into();
+        self.entry(id).is_some()
+    }
+
+    /// Get reference to the asset if it is still loaded.
+    pub fn get(&self, id: impl Into<WeakHandle<T>>) -> Option<&T> {
+        let id = id.into();
+        self.entry(id).map(|entry| &entry.asset)
+    }
+
+    /// Get mutable reference to the asset if it is still loaded.
+    pub fn get_mut(&mut self, id: impl Into<WeakHandle<T>>) -> Option<&mut T> {
+        let id = id.into();
+        self.entry_mut(id).map(|entry| &mut entry.asset)
+    }
+
+    /// Get group of the asset if it is still loaded.
+    pub fn group(&self, id: impl Into<WeakHandle<T>>) -> Option<&str> {
+        let id = id.into();
+        self.entry(id).map(|entry| entry.group.as_str())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let slot = &mut self.slots[index];
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(index);
+        Some(entry.asset)
+    }
+
+    /// Unload the asset regardless of its handles, invalidating them.
+    pub fn unload(&mut self, id: impl Into<WeakHandle<T>>) -> Option<T> {
+        let id = id.into();
+        self.entry(id)?;
+        self.remove(id.index)
+    }
+
+    fn unload_where<F>(&mut self, predicate: F) -> usize
+    where
+        F: Fn(&Entry<T>) -> bool,
+    {
+        let mut count = 0;
+        for index in 0..self.slots.len() {
+            if self.slots[index].entry.as_ref().is_some_and(&predicate) {
+                self.remove(index);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Unload all the assets of the `group`, invalidating their handles.
+    /// Returns the amount of unloaded assets.
+    pub fn unload_group(&mut self, group: &str) -> usize {
+        self.unload_where(|entry| entry.group == group)
+    }
+
+    /// Unload all the assets without strong handles.
+    /// Returns the amount of unloaded assets.
+    pub fn collect(&mut self) -> usize {
+        self.unload_where(|entry| Arc::strong_count(&entry.counter) == 1)
+    }
+
+    /// Get the amount of loaded assets.
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Check if there are no loaded assets.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Summarize live assets and their estimated memory usage.
+    pub fn report(&self) -> AssetsReport {
+        let mut report = AssetsReport::default();
+        for entry in self.slots.iter().filter_map(|slot| slot.entry.as_ref()) {
+            let bytes = (self.measure)(&entry.asset);
+            let unreferenced = (Arc::strong_count(&entry.counter) == 1) as usize;
+            let group = report.groups.entry(entry.group.clone()).or_default();
+            for summary in [group, &mut report.total] {
+                summary.count += 1;
+                summary.bytes += bytes;
+                summary.unreferenced += unreferenced;
+            }
+        }
+        report
+    }
+}
+
+impl<T> Default for Assets<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Getter for Assets<T> {
+    type Index = WeakHandle<T>;
+    type Item = T;
+    fn get(&self, index: &Self::Index) -> Option<&Self::Item> {
+        Assets::get(self, *index)
+    }
+}
+
+impl<T> GetterMut for Assets<T> {
+    fn get_mut(&mut self, index: &Self::Index) -> Option<&mut Self::Item> {
+        Assets::get_mut(self, *index)
+    }
+}
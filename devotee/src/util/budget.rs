@@ -0,0 +1,162 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+use devotee_backend::platform::Instant;
+
+/// Result of a single incremental work step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// There is more work to do.
+    Continue,
+    /// The work is finished.
+    Done,
+}
+
+/// Time budget for the incremental work performed during a single frame.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameBudget {
+    budget: Duration,
+    fraction: Option<f32>,
+    started: Instant,
+}
+
+impl FrameBudget {
+    /// Create new budget starting now.
+    pub fn new(budget: Duration) -> Self {
+        let started = Instant::now();
+        Self {
+            budget,
+            fraction: None,
+            started,
+        }
+    }
+
+    /// Create new budget starting now as a `fraction` of the frame duration, e.g. the update `delta`.
+    /// The fraction is kept for the following `restart_with_delta` calls.
+    pub fn fraction_of(frame: Duration, fraction: f32) -> Self {
+        let fraction = fraction.clamp(0.0, 1.0);
+        Self {
+            fraction: Some(fraction),
+            ..Self::new(frame.mul_f32(fraction))
+        }
+    }
+
+    /// Get the total budget.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Set the total budget for the following frames.
+    /// The budget stops following the update delta.
+    pub fn set_budget(&mut self, budget: Duration) -> &mut Self {
+        self.budget = budget;
+        self.fraction = None;
+        self
+    }
+
+    /// Restart the budget at the beginning of the frame.
+    pub fn restart(&mut self) -> &mut Self {
+        self.started = Instant::now();
+        self
+    }
+
+    /// Restart the budget at the beginning of the update lasting for the `delta`,
+    /// e.g. the one reported by the backend context.
+    /// Budgets created with `fraction_of` take the same fraction of the `delta`.
+    pub fn restart_with_delta(&mut self, delta: Duration) -> &mut Self {
+        if let Some(fraction) = self.fraction {
+            self.budget = delta.mul_f32(fraction);
+        }
+        self.restart()
+    }
+
+    /// Get time spent since the budget start.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Get time left in the budget.
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.elapsed())
+    }
+
+    /// Check if the budget is spent.
+    pub fn is_exhausted(&self) -> bool {
+        self.elapsed() >= self.budget
+    }
+
+    /// Repeatedly run the `step` until it is done or the budget is spent.
+    /// The step is run at least once so the work progresses even with tiny budgets.
+    pub fn run<F>(&self, step: F) -> Step
+    where
+        F: FnMut() -> Step,
+    {
+        let mut step = step;
+        loop {
+            if step() == Step::Done {
+                return Step::Done;
+            }
+            if self.is_exhausted() {
+                return Step::Continue;
+            }
+        }
+    }
+}
+
+/// Queue of incremental jobs, e.g. asset loading or procedural generation, spread across frames.
+#[derive(Default)]
+pub struct BackgroundWork {
+    jobs: VecDeque<Box<dyn FnMut() -> Step>>,
+}
+
+impl fmt::Debug for BackgroundWork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BackgroundWork")
+            .field("jobs", &self.jobs.len())
+            .finish()
+    }
+}
+
+impl BackgroundWork {
+    /// Create new empty work queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enqueue the job; it is called step by step until it reports `Step::Done`.
+    pub fn push<F>(&mut self, job: F) -> &mut Self
+    where
+        F: FnMut() -> Step + 'static,
+    {
+        self.jobs.push_back(Box::new(job));
+        self
+    }
+
+    /// Get the amount of unfinished jobs.
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Check if all the jobs are finished.
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Progress the jobs in order within the `budget`.
+    /// Returns the amount of jobs finished.
+    pub fn run(&mut self, budget: &FrameBudget) -> usize {
+        let mut finished = 0;
+        while let Some(job) = self.jobs.front_mut() {
+            if budget.run(job) == Step::Continue {
+                break;
+            }
+            self.jobs.pop_front();
+            finished += 1;
+            if budget.is_exhausted() {
+                break;
+            }
+        }
+        finished
+    }
+}
@@ -0,0 +1,261 @@
+use super::rect::Rect;
+use super::vector::Vector;
+
+/// Overlap of two static shapes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Contact {
+    /// Point of the second shape surface closest to the first shape.
+    pub position: Vector<f32>,
+    /// Unit direction to push the first shape out of the second one.
+    pub normal: Vector<f32>,
+    /// Distance to push the first shape along the normal to resolve the overlap.
+    pub depth: f32,
+}
+
+/// Intersection of the moving shape or the segment with an obstacle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hit {
+    /// Position at the moment of the hit.
+    pub position: Vector<f32>,
+    /// Unit normal of the obstacle surface at the hit.
+    pub normal: Vector<f32>,
+    /// Fraction of the motion or the segment passed before the hit in the `[0.0, 1.0]` range.
+    pub time: f32,
+}
+
+fn length(vector: Vector<f32>) -> f32 {
+    vector.dot::<f32, f32>(vector).sqrt()
+}
+
+/// Check the overlap of the `a` and `b` boxes.
+/// Touching boxes do not overlap.
+pub fn aabb(a: &Rect<f32>, b: &Rect<f32>) -> Option<Contact> {
+    let overlap = a.intersection(b)?;
+    let (a_center, b_center) = (
+        a.origin() + a.dimensions() / 2.0,
+        b.origin() + b.dimensions() / 2.0,
+    );
+    let delta = a_center - b_center;
+    let (end, overlap_end) = (b.end(), overlap.end());
+    if overlap.dimensions().x() < overlap.dimensions().y() {
+        let sign = if delta.x() < 0.0 { -1.0 } else { 1.0 };
+        let x = if sign < 0.0 { b.origin().x() } else { end.x() };
+        Some(Contact {
+            position: Vector::new(x, (overlap.origin().y() + overlap_end.y()) / 2.0),
+            normal: Vector::new(sign, 0.0),
+            depth: overlap.dimensions().x(),
+        })
+    } else {
+        let sign = if delta.y() < 0.0 { -1.0 } else { 1.0 };
+        let y = if sign < 0.0 { b.origin().y() } else { end.y() };
+        Some(Contact {
+            position: Vector::new((overlap.origin().x() + overlap_end.x()) / 2.0, y),
+            normal: Vector::new(0.0, sign),
+            depth: overlap.dimensions().y(),
+        })
+    }
+}
+
+/// Find the first moment the `moving` box shifted by the `velocity` during the step touches the `target` box.
+/// The hit position is the origin of the `moving` box at that moment.
+/// Boxes overlapping from the start hit at zero time with the normal of their static contact.
+pub fn sweep_aabb(moving: &Rect<f32>, velocity: Vector<f32>, target: &Rect<f32>) -> Option<Hit> {
+    if let Some(contact) = aabb(moving, target) {
+        return Some(Hit {
+            position: moving.origin(),
+            normal: contact.normal,
+            time: 0.0,
+        });
+    }
+    let expanded = Rect::new(
+        target.origin() - moving.dimensions(),
+        target.dimensions() + moving.dimensions(),
+    );
+    let (start, end) = expanded.corners();
+    let origin = moving.origin();
+    let axis = |origin: f32, velocity: f32, start: f32, end: f32| {
+        if velocity == 0.0 {
+            if origin > start && origin < end {
+                Some((f32::NEG_INFINITY, f32::INFINITY))
+            } else {
+                None
+            }
+        } else {
+            let (near, far) = ((start - origin) / velocity, (end - origin) / velocity);
+            Some((near.min(far), near.max(far)))
+        }
+    };
+    let (near_x, far_x) = axis(origin.x(), velocity.x(), start.x(), end.x())?;
+    let (near_y, far_y) = axis(origin.y(), velocity.y(), start.y(), end.y())?;
+    let (near, far) = (near_x.max(near_y), far_x.min(far_y));
+    if near >= far || !(0.0..=1.0).contains(&near) {
+        return None;
+    }
+    let normal = if near_x > near_y {
+        Vector::new(-velocity.x().signum(), 0.0)
+    } else {
+        Vector::new(0.0, -velocity.y().signum())
+    };
+    Some(Hit {
+        position: origin + velocity * near,
+        normal,
+        time: near,
+    })
+}
+
+/// Check the overlap of two circles with centers and radii.
+/// Circles with the same center are pushed apart upwards.
+pub fn circles(
+    a_center: Vector<f32>,
+    a_radius: f32,
+    b_center: Vector<f32>,
+    b_radius: f32,
+) -> Option<Contact> {
+    let delta = a_center - b_center;
+    let distance = length(delta);
+    let depth = a_radius + b_radius - distance;
+    if depth <= 0.0 {
+        return None;
+    }
+    let normal = if distance > 0.0 {
+        delta / distance
+    } else {
+        Vector::new(0.0, -1.0)
+    };
+    Some(Contact {
+        position: b_center + normal * b_radius,
+        normal,
+        depth,
+    })
+}
+
+/// Check if the `point` is inside of the polygon with `vertices` using the even-odd rule.
+pub fn point_in_polygon(point: Vector<f32>, vertices: &[Vector<f32>]) -> bool {
+    let mut inside = false;
+    for (index, a) in vertices.iter().enumerate() {
+        let b = vertices[(index + 1) % vertices.len()];
+        if (a.y() > point.y()) != (b.y() > point.y()) {
+            let x = a.x() + (point.y() - a.y()) * (b.x() - a.x()) / (b.y() - a.y());
+            if point.x() < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Find intersection of the segment `a` with the segment `b`, both given by their ends.
+/// The hit time is measured along the segment `a`, the normal of `b` faces the start of `a`.
+/// Parallel segments do not intersect.
+pub fn segments(a: (Vector<f32>, Vector<f32>), b: (Vector<f32>, Vector<f32>)) -> Option<Hit> {
+    let (direction, other) = (a.1 - a.0, b.1 - b.0);
+    let denominator: f32 = direction.cross_2d(other);
+    if denominator == 0.0 {
+        return None;
+    }
+    let offset = b.0 - a.0;
+    let time = offset.cross_2d::<f32, f32>(other) / denominator;
+    let along = offset.cross_2d::<f32, f32>(direction) / denominator;
+    if !(0.0..=1.0).contains(&time) || !(0.0..=1.0).contains(&along) {
+        return None;
+    }
+    let mut normal = Vector::new(-other.y(), other.x()) / length(other);
+    if normal.dot::<f32, f32>(direction) > 0.0 {
+        normal = -normal;
+    }
+    Some(Hit {
+        position: a.0 + direction * time,
+        normal,
+        time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect<f32> {
+        Rect::new(Vector::new(x, y), Vector::new(width, height))
+    }
+
+    #[test]
+    fn sweep_hits_box_on_the_way() {
+        let moving = rect(0.0, 0.0, 2.0, 2.0);
+        let target = rect(5.0, 0.0, 2.0, 2.0);
+        let hit = sweep_aabb(&moving, Vector::new(6.0, 0.0), &target).unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.position, Vector::new(3.0, 0.0));
+        assert_eq!(hit.normal, Vector::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn sweep_hits_from_above() {
+        let moving = rect(0.0, 0.0, 2.0, 2.0);
+        let target = rect(-4.0, 6.0, 10.0, 1.0);
+        let hit = sweep_aabb(&moving, Vector::new(1.0, 8.0), &target).unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.position, Vector::new(0.5, 4.0));
+        assert_eq!(hit.normal, Vector::new(0.0, -1.0));
+    }
+
+    #[test]
+    fn sweep_misses_short_and_side_motion() {
+        let moving = rect(0.0, 0.0, 2.0, 2.0);
+        let target = rect(5.0, 0.0, 2.0, 2.0);
+        assert_eq!(sweep_aabb(&moving, Vector::new(2.0, 0.0), &target), None);
+        assert_eq!(sweep_aabb(&moving, Vector::new(-6.0, 0.0), &target), None);
+        assert_eq!(
+            sweep_aabb(&moving, Vector::new(6.0, 0.0), &rect(5.0, 3.0, 2.0, 2.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn sweep_of_overlapping_boxes_hits_at_start() {
+        let moving = rect(0.0, 0.0, 2.0, 2.0);
+        let hit = sweep_aabb(&moving, Vector::new(1.0, 0.0), &rect(1.5, 0.0, 2.0, 2.0)).unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.position, moving.origin());
+        assert_eq!(hit.normal, Vector::new(-1.0, 0.0));
+    }
+
+    #[test]
+    fn crossing_segments_intersect() {
+        let hit = segments(
+            (Vector::new(0.0, 0.0), Vector::new(4.0, 4.0)),
+            (Vector::new(0.0, 4.0), Vector::new(4.0, 0.0)),
+        )
+        .unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.position, Vector::new(2.0, 2.0));
+        assert!(hit.normal.dot::<f32, f32>(Vector::new(1.0, 1.0)) < 0.0);
+    }
+
+    #[test]
+    fn segment_normal_faces_segment_start() {
+        let hit = segments(
+            (Vector::new(2.0, 4.0), Vector::new(2.0, -4.0)),
+            (Vector::new(0.0, 0.0), Vector::new(4.0, 0.0)),
+        )
+        .unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, Vector::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn separate_and_parallel_segments_do_not_intersect() {
+        let a = (Vector::new(0.0, 0.0), Vector::new(4.0, 0.0));
+        assert_eq!(
+            segments(a, (Vector::new(0.0, 1.0), Vector::new(4.0, 1.0))),
+            None
+        );
+        assert_eq!(
+            segments(a, (Vector::new(5.0, -1.0), Vector::new(5.0, 1.0))),
+            None
+        );
+        assert_eq!(
+            segments(a, (Vector::new(2.0, 1.0), Vector::new(2.0, 3.0))),
+            None
+        );
+    }
+}
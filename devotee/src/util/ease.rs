@@ -0,0 +1,289 @@
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+use super::interp::Interpolate;
+
+const BACK: f32 = 1.70158;
+const BACK_IN_OUT: f32 = BACK * 1.525;
+
+/// Easing curve mapping linear progress into eased one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Easing {
+    /// Constant speed.
+    #[default]
+    Linear,
+    /// Quadratic acceleration.
+    QuadIn,
+    /// Quadratic deceleration.
+    QuadOut,
+    /// Quadratic acceleration and deceleration.
+    QuadInOut,
+    /// Cubic acceleration.
+    CubicIn,
+    /// Cubic deceleration.
+    CubicOut,
+    /// Cubic acceleration and deceleration.
+    CubicInOut,
+    /// Exponential acceleration.
+    ExpoIn,
+    /// Exponential deceleration.
+    ExpoOut,
+    /// Exponential acceleration and deceleration.
+    ExpoInOut,
+    /// Pulling back before acceleration.
+    BackIn,
+    /// Overshooting the end before settling.
+    BackOut,
+    /// Pulling back at the start and overshooting at the end.
+    BackInOut,
+    /// Growing oscillation before the end.
+    ElasticIn,
+    /// Decaying oscillation around the end.
+    ElasticOut,
+    /// Growing and then decaying oscillation.
+    ElasticInOut,
+}
+
+impl Easing {
+    /// Map the `progress` clamped to the `[0.0, 1.0]` range.
+    /// The result is `0.0` at the start and `1.0` at the end,
+    /// back and elastic curves leave the range in between.
+    pub fn apply(self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        if t == 0.0 || t == 1.0 {
+            return t;
+        }
+        let in_out = |ease_in: fn(f32) -> f32| {
+            if t < 0.5 {
+                ease_in(2.0 * t) / 2.0
+            } else {
+                1.0 - ease_in(2.0 - 2.0 * t) / 2.0
+            }
+        };
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => quad(t),
+            Easing::QuadOut => 1.0 - quad(1.0 - t),
+            Easing::QuadInOut => in_out(quad),
+            Easing::CubicIn => cubic(t),
+            Easing::CubicOut => 1.0 - cubic(1.0 - t),
+            Easing::CubicInOut => in_out(cubic),
+            Easing::ExpoIn => expo(t),
+            Easing::ExpoOut => 1.0 - expo(1.0 - t),
+            Easing::ExpoInOut => in_out(expo),
+            Easing::BackIn => back(t, BACK),
+            Easing::BackOut => 1.0 - back(1.0 - t, BACK),
+            Easing::BackInOut => in_out(|t| back(t, BACK_IN_OUT)),
+            Easing::ElasticIn => elastic(t, 0.3),
+            Easing::ElasticOut => 1.0 - elastic(1.0 - t, 0.3),
+            Easing::ElasticInOut => in_out(|t| elastic(t, 0.45)),
+        }
+    }
+}
+
+fn quad(t: f32) -> f32 {
+    t * t
+}
+
+fn cubic(t: f32) -> f32 {
+    t * t * t
+}
+
+fn expo(t: f32) -> f32 {
+    2.0f32.powf(10.0 * (t - 1.0))
+}
+
+fn back(t: f32, overshoot: f32) -> f32 {
+    t * t * ((overshoot + 1.0) * t - overshoot)
+}
+
+fn elastic(t: f32, period: f32) -> f32 {
+    let shift = period / 4.0;
+    -(2.0f32.powf(10.0 * (t - 1.0)) * ((t - 1.0 - shift) * TAU / period).sin())
+}
+
+/// Value changing from the start to the end over the duration along the easing curve.
+#[derive(Clone, Debug)]
+pub struct Tween<T> {
+    from: T,
+    to: T,
+    duration: Duration,
+    elapsed: Duration,
+    easing: Easing,
+}
+
+impl<T> Tween<T>
+where
+    T: Interpolate + Clone,
+{
+    /// Create new linear tween from `from` to `to` lasting for `duration`.
+    pub fn new(from: T, to: T, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            elapsed: Duration::ZERO,
+            easing: Easing::Linear,
+        }
+    }
+
+    /// Set the easing curve.
+    pub fn with_easing(self, easing: Easing) -> Self {
+        Self { easing, ..self }
+    }
+
+    /// Advance the tween by `delta` time.
+    pub fn update(&mut self, delta: Duration) {
+        self.elapsed = (self.elapsed + delta).min(self.duration);
+    }
+
+    /// Get current value.
+    pub fn value(&self) -> T {
+        self.from
+            .interpolate(&self.to, self.easing.apply(self.progress()))
+    }
+
+    /// Get linear progress in the `[0.0, 1.0]` range.
+    pub fn progress(&self) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+        self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+    }
+
+    /// Check if the tween has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Get time passed since the start.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Get time left until the end.
+    pub fn remaining(&self) -> Duration {
+        self.duration - self.elapsed
+    }
+
+    /// Get total duration.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Get the easing curve.
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Restart the tween from the beginning.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Start new tween from current value to `to` lasting for `duration`, keeping the easing curve.
+    pub fn retarget(&mut self, to: T, duration: Duration) {
+        self.from = self.value();
+        self.to = to;
+        self.duration = duration;
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 16] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ExpoIn,
+        Easing::ExpoOut,
+        Easing::ExpoInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+    ];
+
+    #[test]
+    fn easings_keep_ends() {
+        for easing in ALL {
+            assert_eq!(easing.apply(0.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+            assert_eq!(easing.apply(-1.0), 0.0, "{easing:?}");
+            assert_eq!(easing.apply(2.0), 1.0, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn in_and_out_easings_are_symmetric() {
+        let pairs = [
+            (Easing::QuadIn, Easing::QuadOut),
+            (Easing::CubicIn, Easing::CubicOut),
+            (Easing::ExpoIn, Easing::ExpoOut),
+            (Easing::BackIn, Easing::BackOut),
+            (Easing::ElasticIn, Easing::ElasticOut),
+        ];
+        for (ease_in, ease_out) in pairs {
+            for step in 0..=20 {
+                let t = step as f32 / 20.0;
+                let mirrored = 1.0 - ease_out.apply(1.0 - t);
+                assert!(
+                    (ease_in.apply(t) - mirrored).abs() < 1e-5,
+                    "{ease_in:?} and {ease_out:?} at {t}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn in_out_easings_are_symmetric_around_middle() {
+        let in_outs = [
+            Easing::QuadInOut,
+            Easing::CubicInOut,
+            Easing::ExpoInOut,
+            Easing::BackInOut,
+            Easing::ElasticInOut,
+        ];
+        for easing in in_outs {
+            for step in 0..=20 {
+                let t = step as f32 / 20.0;
+                let mirrored = 1.0 - easing.apply(1.0 - t);
+                assert!(
+                    (easing.apply(t) - mirrored).abs() < 1e-5,
+                    "{easing:?} at {t}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tween_stops_at_duration() {
+        let mut tween = Tween::new(0.0f32, 10.0, Duration::from_millis(100));
+        tween.update(Duration::from_millis(40));
+        assert!(!tween.is_finished());
+        assert!((tween.value() - 4.0).abs() < 1e-5);
+
+        tween.update(Duration::from_millis(100));
+        assert!(tween.is_finished());
+        assert_eq!(tween.elapsed(), Duration::from_millis(100));
+        assert_eq!(tween.remaining(), Duration::ZERO);
+        assert_eq!(tween.progress(), 1.0);
+        assert_eq!(tween.value(), 10.0);
+    }
+
+    #[test]
+    fn zero_duration_tween_is_finished() {
+        let tween = Tween::new(1.0f32, 3.0, Duration::ZERO);
+        assert!(tween.is_finished());
+        assert_eq!(tween.value(), 3.0);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::visual::canvas::Canvas;
+use crate::visual::image::ImageMut;
+
+use super::vector::Vector;
+
+const OCTANTS: [[i32; 4]; 8] = [
+    [1, 0, 0, 1],
+    [0, 1, 1, 0],
+    [0, -1, 1, 0],
+    [-1, 0, 0, 1],
+    [-1, 0, 0, -1],
+    [0, -1, -1, 0],
+    [0, 1, -1, 0],
+    [1, 0, 0, -1],
+];
+
+/// Area of the grid the visibility is computed for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+}
+
+impl Viewport {
+    /// Create new viewport with top left grid cell at `origin`.
+    pub fn new(origin: Vector<i32>, dimensions: Vector<i32>) -> Self {
+        let dimensions = dimensions.individual_max((0, 0));
+        Self { origin, dimensions }
+    }
+
+    /// Get grid position of the top left cell.
+    pub fn origin(&self) -> Vector<i32> {
+        self.origin
+    }
+
+    /// Get dimensions of the viewport in cells.
+    pub fn dimensions(&self) -> Vector<i32> {
+        self.dimensions
+    }
+
+    fn mask(&self) -> Canvas<bool> {
+        Canvas::with_resolution(
+            false,
+            self.dimensions.x() as usize,
+            self.dimensions.y() as usize,
+        )
+    }
+
+    fn mark(&self, mask: &mut Canvas<bool>, cell: Vector<i32>) {
+        if let Some(pixel) = mask.pixel_mut(cell - self.origin) {
+            *pixel = true;
+        }
+    }
+}
+
+struct Shadowcaster<'a, F> {
+    viewport: Viewport,
+    origin: Vector<i32>,
+    radius: i32,
+    opaque: &'a mut F,
+    mask: Canvas<bool>,
+}
+
+impl<F> Shadowcaster<'_, F>
+where
+    F: FnMut(Vector<i32>) -> bool,
+{
+    fn cast(&mut self, row: i32, start: f64, end: f64, octant: [i32; 4]) {
+        if start < end {
+            return;
+        }
+        let [xx, xy, yx, yy] = octant;
+        let radius_squared = self.radius as i64 * self.radius as i64;
+        let mut start = start;
+        let mut next_start = start;
+        for distance in row..=self.radius {
+            let dy = -distance;
+            let mut blocked = false;
+            for dx in -distance..=0 {
+                let cell = self.origin + Vector::new(dx * xx + dy * xy, dx * yx + dy * yy);
+                let left_slope = (dx as f64 - 0.5) / (dy as f64 + 0.5);
+                let right_slope = (dx as f64 + 0.5) / (dy as f64 - 0.5);
+                if start < right_slope {
+                    continue;
+                }
+                if end > left_slope {
+                    break;
+                }
+                if (dx as i64 * dx as i64 + dy as i64 * dy as i64) <= radius_squared {
+                    self.viewport.mark(&mut self.mask, cell);
+                }
+                let opaque = (self.opaque)(cell);
+                if blocked {
+                    if opaque {
+                        next_start = right_slope;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if opaque && distance < self.radius {
+                    blocked = true;
+                    self.cast(distance + 1, start, left_slope, octant);
+                    next_start = right_slope;
+                }
+            }
+            if blocked {
+                break;
+            }
+        }
+    }
+}
+
+/// Compute cells visible from the `origin` within the `radius` with recursive shadowcasting.
+/// The `opaque` callback tells if the grid cell blocks the sight; opaque cells are visible themselves.
+/// Returns visibility mask of the `viewport` cells.
+pub fn shadowcast<F>(
+    origin: Vector<i32>,
+    radius: i32,
+    viewport: Viewport,
+    opaque: F,
+) -> Canvas<bool>
+where
+    F: FnMut(Vector<i32>) -> bool,
+{
+    let mut opaque = opaque;
+    let mut caster = Shadowcaster {
+        viewport,
+        origin,
+        radius: radius.max(0),
+        opaque: &mut opaque,
+        mask: viewport.mask(),
+    };
+    viewport.mark(&mut caster.mask, origin);
+    for octant in OCTANTS {
+        caster.cast(1, 1.0, 0.0, octant);
+    }
+    caster.mask
+}
+
+/// Check if the ray from `from` to `to` passes only transparent cells between the end cells.
+fn clear_ray<F>(from: (f64, f64), to: (f64, f64), opaque: &mut F) -> bool
+where
+    F: FnMut(Vector<i32>) -> bool,
+{
+    let target = Vector::new(to.0.floor() as i32, to.1.floor() as i32);
+    let mut cell = Vector::new(from.0.floor() as i32, from.1.floor() as i32);
+    let (delta_x, delta_y) = (to.0 - from.0, to.1 - from.1);
+    let step = Vector::new(delta_x.signum() as i32, delta_y.signum() as i32);
+    let crossing = |position: f64, cell: i32, delta: f64| {
+        if delta > 0.0 {
+            (cell as f64 + 1.0 - position) / delta
+        } else if delta < 0.0 {
+            (cell as f64 - position) / delta
+        } else {
+            f64::INFINITY
+        }
+    };
+    let mut next_x = crossing(from.0, cell.x(), delta_x);
+    let mut next_y = crossing(from.1, cell.y(), delta_y);
+    let advance_x = (1.0 / delta_x).abs();
+    let advance_y = (1.0 / delta_y).abs();
+    loop {
+        if next_x < next_y {
+            *cell.x_mut() += step.x();
+            next_x += advance_x;
+        } else {
+            *cell.y_mut() += step.y();
+            next_y += advance_y;
+        }
+        if cell == target || next_x.min(next_y) > 1.0 {
+            return true;
+        }
+        if opaque(cell) {
+            return false;
+        }
+    }
+}
+
+/// Compute cells visible from the `origin` within the `radius` permissively.
+/// The cell is visible if any ray from the origin cell center to the target cell center or its corners is not blocked.
+/// The `opaque` callback tells if the grid cell blocks the sight; opaque cells are visible themselves.
+/// Returns visibility mask of the `viewport` cells.
+pub fn permissive<F>(
+    origin: Vector<i32>,
+    radius: i32,
+    viewport: Viewport,
+    opaque: F,
+) -> Canvas<bool>
+where
+    F: FnMut(Vector<i32>) -> bool,
+{
+    const INSET: f64 = 0.01;
+    let mut opaque = opaque;
+    let mut mask = viewport.mask();
+    let radius = radius.max(0);
+    let radius_squared = radius as i64 * radius as i64;
+    let from = (origin.x() as f64 + 0.5, origin.y() as f64 + 0.5);
+    let start = viewport
+        .origin
+        .individual_max(origin - Vector::new(radius, radius));
+    let end = (viewport.origin + viewport.dimensions)
+        .individual_min(origin + Vector::new(radius + 1, radius + 1));
+    for y in start.y()..end.y() {
+        for x in start.x()..end.x() {
+            let (dx, dy) = ((x - origin.x()) as i64, (y - origin.y()) as i64);
+            if dx * dx + dy * dy > radius_squared {
+                continue;
+            }
+            let cell = Vector::new(x, y);
+            let (left, top) = (x as f64, y as f64);
+            let targets = [
+                (left + 0.5, top + 0.5),
+                (left + INSET, top + INSET),
+                (left + 1.0 - INSET, top + INSET),
+                (left + INSET, top + 1.0 - INSET),
+                (left + 1.0 - INSET, top + 1.0 - INSET),
+            ];
+            if cell == origin
+                || targets
+                    .into_iter()
+                    .any(|to| clear_ray(from, to, &mut opaque))
+            {
+                viewport.mark(&mut mask, cell);
+            }
+        }
+    }
+    mask
+}
@@ -0,0 +1,114 @@
+use std::f32::consts::{PI, TAU};
+
+use super::vector::Vector;
+
+/// Values that can be blended between two states.
+pub trait Interpolate {
+    /// Blend between `self` at `alpha` equal to `0.0` and `other` at `alpha` equal to `1.0`.
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        self + (other - self) * alpha
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        self + (other - self) * alpha as f64
+    }
+}
+
+impl Interpolate for Vector<f32> {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        Vector::new(
+            self.x().interpolate(&other.x(), alpha),
+            self.y().interpolate(&other.y(), alpha),
+        )
+    }
+}
+
+impl<const N: usize> Interpolate for [f32; N] {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        std::array::from_fn(|i| self[i].interpolate(&other[i], alpha))
+    }
+}
+
+/// Angle in radians interpolated along the shortest arc.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Angle(pub f32);
+
+impl Interpolate for Angle {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        let delta = (other.0 - self.0 + PI).rem_euclid(TAU) - PI;
+        Angle(self.0 + delta * alpha)
+    }
+}
+
+/// Color in `0xaa_rr_gg_bb` format interpolated channel by channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Color(pub u32);
+
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        let mut result = 0;
+        for shift in [24, 16, 8, 0] {
+            let from = ((self.0 >> shift) & 0xff) as f32;
+            let to = ((other.0 >> shift) & 0xff) as f32;
+            result |= (from.interpolate(&to, alpha).round().clamp(0.0, 255.0) as u32) << shift;
+        }
+        Color(result)
+    }
+}
+
+/// Double-buffered value set during update ticks and blended during render.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Interpolated<T> {
+    previous: T,
+    current: T,
+}
+
+impl<T> Interpolated<T>
+where
+    T: Clone,
+{
+    /// Create new interpolated value with both states set to `value`.
+    pub fn new(value: T) -> Self {
+        Self {
+            previous: value.clone(),
+            current: value,
+        }
+    }
+
+    /// Set new current value, the old one becomes the previous.
+    pub fn set(&mut self, value: T) {
+        self.previous = std::mem::replace(&mut self.current, value);
+    }
+
+    /// Set both states to `value` so no blending happens, e.g. on teleportation.
+    pub fn reset(&mut self, value: T) {
+        self.previous = value.clone();
+        self.current = value;
+    }
+
+    /// Get value set during the latest update.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Get value set during the update before the latest one.
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+}
+
+impl<T> Interpolated<T>
+where
+    T: Interpolate,
+{
+    /// Get value blended between the previous and the current states by the render `alpha`.
+    pub fn get(&self, alpha: f32) -> T {
+        self.previous.interpolate(&self.current, alpha)
+    }
+}
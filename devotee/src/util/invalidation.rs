@@ -0,0 +1,51 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Source of invalidation signals for caches depending on mutable data.
+#[derive(Clone, Debug, Default)]
+pub struct Invalidator {
+    generation: Arc<AtomicU64>,
+}
+
+impl Invalidator {
+    /// Create new invalidator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Signal all the subscribers that dependent caches are stale.
+    pub fn invalidate(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Create new subscription considering the current state up to date.
+    pub fn subscribe(&self) -> Subscription {
+        Subscription {
+            seen: self.generation.load(Ordering::Relaxed),
+            generation: self.generation.clone(),
+        }
+    }
+}
+
+/// Subscription to the invalidation signals.
+#[derive(Clone, Debug)]
+pub struct Subscription {
+    generation: Arc<AtomicU64>,
+    seen: u64,
+}
+
+impl Subscription {
+    /// Check if there were invalidations since the last acknowledgement.
+    pub fn is_stale(&self) -> bool {
+        self.generation.load(Ordering::Relaxed) != self.seen
+    }
+
+    /// Acknowledge all the invalidations.
+    /// Returns `true` if there were any since the last acknowledgement.
+    pub fn acknowledge(&mut self) -> bool {
+        let generation = self.generation.load(Ordering::Relaxed);
+        let stale = generation != self.seen;
+        self.seen = generation;
+        stale
+    }
+}
@@ -0,0 +1,208 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use super::assets::{Assets, Handle, DEFAULT_GROUP};
+use super::budget::FrameBudget;
+
+/// Asset loading error enumeration.
+#[derive(Debug)]
+pub enum LoadError {
+    /// File system error.
+    #[cfg(not(target_arch = "wasm32"))]
+    IoError(io::Error),
+
+    /// Fetch request error description.
+    #[cfg(target_arch = "wasm32")]
+    FetchError(String),
+
+    /// Loaded data was rejected by the decoder.
+    DecodeError,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<io::Error> for LoadError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+enum State<T> {
+    Pending,
+    Fetched(Result<Vec<u8>, LoadError>),
+    Loaded(Handle<T>),
+    Failed(LoadError),
+    Taken,
+}
+
+/// Handle to the asset being loaded.
+/// It resolves on `Loader::update` once the data is available.
+pub struct Loading<T> {
+    state: Rc<RefCell<State<T>>>,
+}
+
+impl<T> Clone for Loading<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T> Loading<T> {
+    /// Check if the asset is not resolved yet.
+    pub fn is_pending(&self) -> bool {
+        matches!(*self.state.borrow(), State::Pending | State::Fetched(_))
+    }
+
+    /// Get handle to the loaded asset.
+    pub fn handle(&self) -> Option<Handle<T>> {
+        match &*self.state.borrow() {
+            State::Loaded(handle) => Some(handle.clone()),
+            _ => None,
+        }
+    }
+
+    /// Take the loading error if the loading has failed.
+    pub fn take_error(&self) -> Option<LoadError> {
+        let mut state = self.state.borrow_mut();
+        if matches!(*state, State::Failed(_)) {
+            if let State::Failed(error) = std::mem::replace(&mut *state, State::Taken) {
+                return Some(error);
+            }
+        }
+        None
+    }
+}
+
+/// Asset loader reading files on desktop targets and fetching them on wasm targets.
+/// Loaded assets are decoded and put into the `Assets` storage on `update`.
+pub struct Loader<T> {
+    root: String,
+    decode: fn(Vec<u8>) -> Option<T>,
+    requests: Vec<(String, Loading<T>)>,
+}
+
+impl<T: 'static> Loader<T> {
+    /// Create new loader resolving paths relative to the `root` and decoding data with `decode`.
+    pub fn new<S: Into<String>>(root: S, decode: fn(Vec<u8>) -> Option<T>) -> Self {
+        Self {
+            root: root.into(),
+            decode,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Start loading the asset at `path` into the default group.
+    pub fn load(&mut self, path: &str) -> Loading<T> {
+        self.load_in_group(DEFAULT_GROUP, path)
+    }
+
+    /// Start loading the asset at `path` into the specified `group`.
+    pub fn load_in_group(&mut self, group: &str, path: &str) -> Loading<T> {
+        let loading = Loading {
+            state: Rc::new(RefCell::new(State::Pending)),
+        };
+        self.fetch(path, loading.state.clone());
+        self.requests.push((group.to_owned(), loading.clone()));
+        loading
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fetch(&self, path: &str, state: Rc<RefCell<State<T>>>) {
+        let path: PathBuf = [self.root.as_str(), path].iter().collect();
+        *state.borrow_mut() = State::Fetched(fs::read(path).map_err(LoadError::from));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn fetch(&self, path: &str, state: Rc<RefCell<State<T>>>) {
+        let url = if self.root.is_empty() {
+            path.to_owned()
+        } else {
+            format!("{}/{}", self.root.trim_end_matches('/'), path)
+        };
+        wasm_bindgen_futures::spawn_local(async move {
+            *state.borrow_mut() = State::Fetched(request(&url).await);
+        });
+    }
+
+    /// Get number of unresolved loadings.
+    pub fn pending(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Decode fetched data and put it into the `assets`.
+    /// Returns number of resolved loadings.
+    pub fn update(&mut self, assets: &mut Assets<T>) -> usize {
+        self.resolve(assets, || false)
+    }
+
+    /// Decode fetched data and put it into the `assets` until the `budget` is spent.
+    /// At least one loading is resolved so the loading progresses even with tiny budgets.
+    /// Returns number of resolved loadings.
+    pub fn update_within(&mut self, assets: &mut Assets<T>, budget: &FrameBudget) -> usize {
+        self.resolve(assets, || budget.is_exhausted())
+    }
+
+    fn resolve<F: Fn() -> bool>(&mut self, assets: &mut Assets<T>, exhausted: F) -> usize {
+        let decode = self.decode;
+        let mut resolved = 0;
+        let mut index = 0;
+        while index < self.requests.len() {
+            if resolved > 0 && exhausted() {
+                break;
+            }
+            let (group, loading) = &self.requests[index];
+            let mut state = loading.state.borrow_mut();
+            match std::mem::replace(&mut *state, State::Pending) {
+                State::Pending => {
+                    index += 1;
+                    continue;
+                }
+                State::Fetched(result) => {
+                    *state =
+                        match result.and_then(|data| decode(data).ok_or(LoadError::DecodeError)) {
+                            Ok(asset) => State::Loaded(assets.insert_in_group(group, asset)),
+                            Err(error) => State::Failed(error),
+                        };
+                }
+                other => *state = other,
+            }
+            drop(state);
+            self.requests.remove(index);
+            resolved += 1;
+        }
+        resolved
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn request(url: &str) -> Result<Vec<u8>, LoadError> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let describe = |value: wasm_bindgen::JsValue| LoadError::FetchError(format!("{:?}", value));
+    let window = web_sys::window().ok_or_else(|| LoadError::FetchError("No window".to_owned()))?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(describe)?
+        .dyn_into()
+        .map_err(describe)?;
+    if !response.ok() {
+        return Err(LoadError::FetchError(format!(
+            "{} responded with {}",
+            url,
+            response.status()
+        )));
+    }
+    let buffer = JsFuture::from(response.array_buffer().map_err(describe)?)
+        .await
+        .map_err(describe)?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
@@ -0,0 +1,173 @@
+use std::f32::consts::FRAC_1_SQRT_2;
+
+use super::rng::mix;
+
+/// Gradient directions of the two-dimensional gradient noise.
+const GRADIENTS: [(f32, f32); 8] = [
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+    (FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+    (FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    (-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+];
+
+fn lattice(seed: u64, x: i32, y: i32) -> u64 {
+    mix(seed ^ mix((x as u32 as u64) | ((y as u32 as u64) << 32)))
+}
+
+/// Map the `hash` into the `[-1.0, 1.0)` range.
+fn signed(hash: u64) -> f32 {
+    (hash >> 40) as f32 / (1 << 23) as f32 - 1.0
+}
+
+fn lerp(from: f32, to: f32, alpha: f32) -> f32 {
+    from + (to - from) * alpha
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn quintic(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn split(value: f32) -> (i32, f32) {
+    let floor = value.floor();
+    (floor as i32, value - floor)
+}
+
+/// Get one-dimensional value noise with the `seed` at `x` in the `[-1.0, 1.0)` range.
+/// Random values at integer coordinates are smoothly interpolated.
+pub fn value_1d(seed: u64, x: f32) -> f32 {
+    let (cell, t) = split(x);
+    let (from, to) = (
+        lattice(seed, cell, 0),
+        lattice(seed, cell.wrapping_add(1), 0),
+    );
+    lerp(signed(from), signed(to), smoothstep(t))
+}
+
+/// Get two-dimensional value noise with the `seed` at `x` and `y` in the `[-1.0, 1.0)` range.
+/// Random values at integer coordinates are smoothly interpolated.
+pub fn value_2d(seed: u64, x: f32, y: f32) -> f32 {
+    let ((cell_x, t_x), (cell_y, t_y)) = (split(x), split(y));
+    let (next_x, next_y) = (cell_x.wrapping_add(1), cell_y.wrapping_add(1));
+    let value = |x, y| signed(lattice(seed, x, y));
+    let (alpha_x, alpha_y) = (smoothstep(t_x), smoothstep(t_y));
+    let top = lerp(value(cell_x, cell_y), value(next_x, cell_y), alpha_x);
+    let bottom = lerp(value(cell_x, next_y), value(next_x, next_y), alpha_x);
+    lerp(top, bottom, alpha_y)
+}
+
+/// Get one-dimensional Perlin-style gradient noise with the `seed` at `x` in the `[-1.0, 1.0]` range.
+/// The noise is zero at integer coordinates.
+pub fn perlin_1d(seed: u64, x: f32) -> f32 {
+    let (cell, t) = split(x);
+    let gradient = |cell| signed(lattice(seed, cell, 0));
+    let from = gradient(cell) * t;
+    let to = gradient(cell.wrapping_add(1)) * (t - 1.0);
+    (2.0 * lerp(from, to, quintic(t))).clamp(-1.0, 1.0)
+}
+
+/// Get two-dimensional Perlin-style gradient noise with the `seed` at `x` and `y` in the `[-1.0, 1.0]` range.
+/// The noise is zero at integer coordinates.
+pub fn perlin_2d(seed: u64, x: f32, y: f32) -> f32 {
+    let ((cell_x, t_x), (cell_y, t_y)) = (split(x), split(y));
+    let (next_x, next_y) = (cell_x.wrapping_add(1), cell_y.wrapping_add(1));
+    let contribution = |x, y, dx: f32, dy: f32| {
+        let (gx, gy) = GRADIENTS[(lattice(seed, x, y) & 7) as usize];
+        gx * dx + gy * dy
+    };
+    let (alpha_x, alpha_y) = (quintic(t_x), quintic(t_y));
+    let top = lerp(
+        contribution(cell_x, cell_y, t_x, t_y),
+        contribution(next_x, cell_y, t_x - 1.0, t_y),
+        alpha_x,
+    );
+    let bottom = lerp(
+        contribution(cell_x, next_y, t_x, t_y - 1.0),
+        contribution(next_x, next_y, t_x - 1.0, t_y - 1.0),
+        alpha_x,
+    );
+    (lerp(top, bottom, alpha_y) * std::f32::consts::SQRT_2).clamp(-1.0, 1.0)
+}
+
+/// Sum `octaves` of the two-dimensional `noise` with the `seed` at `x` and `y`,
+/// doubling the frequency and halving the amplitude of each next octave.
+/// The result is normalized to the range of the `noise`.
+pub fn fractal_2d<F>(noise: F, seed: u64, x: f32, y: f32, octaves: u32) -> f32
+where
+    F: Fn(u64, f32, f32) -> f32,
+{
+    let (mut sum, mut total) = (0.0, 0.0);
+    let (mut frequency, mut amplitude) = (1.0, 1.0);
+    for octave in 0..octaves.max(1) {
+        let seed = seed.wrapping_add(octave as u64);
+        sum += noise(seed, x * frequency, y * frequency) * amplitude;
+        total += amplitude;
+        frequency *= 2.0;
+        amplitude *= 0.5;
+    }
+    sum / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> impl Iterator<Item = (f32, f32)> {
+        (-20..20).flat_map(|y| (-20..20).map(move |x| (x as f32 * 0.37, y as f32 * 0.29)))
+    }
+
+    #[test]
+    fn noise_is_deterministic() {
+        for (x, y) in samples() {
+            assert_eq!(value_2d(1, x, y), value_2d(1, x, y));
+            assert_eq!(perlin_2d(1, x, y), perlin_2d(1, x, y));
+        }
+        assert_ne!(value_1d(1, 0.5), value_1d(2, 0.5));
+    }
+
+    #[test]
+    fn noise_stays_in_range() {
+        for (x, y) in samples() {
+            for value in [
+                value_1d(3, x),
+                value_2d(3, x, y),
+                perlin_1d(3, x),
+                perlin_2d(3, x, y),
+                fractal_2d(perlin_2d, 3, x, y, 4),
+            ] {
+                assert!((-1.0..=1.0).contains(&value), "{value} at {x}, {y}");
+            }
+        }
+    }
+
+    #[test]
+    fn perlin_is_zero_at_integer_coordinates() {
+        for (x, y) in [(0, 0), (1, -3), (-7, 12)] {
+            assert_eq!(perlin_1d(4, x as f32), 0.0);
+            assert_eq!(perlin_2d(4, x as f32, y as f32), 0.0);
+        }
+    }
+
+    #[test]
+    fn value_noise_is_continuous() {
+        for (x, y) in samples() {
+            let step = 1e-3;
+            assert!((value_1d(6, x) - value_1d(6, x + step)).abs() < 0.01);
+            assert!((value_2d(6, x, y) - value_2d(6, x + step, y + step)).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn single_octave_fractal_matches_noise() {
+        for (x, y) in samples() {
+            assert_eq!(fractal_2d(value_2d, 8, x, y, 1), value_2d(8, x, y));
+        }
+    }
+}
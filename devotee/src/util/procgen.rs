@@ -0,0 +1,465 @@
+use crate::visual::canvas::Canvas;
+use crate::visual::image::{Image, ImageMut};
+
+use super::budget::{FrameBudget, Step};
+use super::rng::Rng;
+use super::vector::Vector;
+
+/// Generated grid cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Terrain {
+    /// Impassable cell.
+    #[default]
+    Wall,
+    /// Walkable cell.
+    Floor,
+}
+
+impl Terrain {
+    /// Check if the cell is walkable.
+    pub fn is_floor(self) -> bool {
+        self == Terrain::Floor
+    }
+}
+
+fn grid(dimensions: Vector<i32>) -> Canvas<Terrain> {
+    let dimensions = dimensions.individual_max((0, 0));
+    Canvas::with_resolution(
+        Terrain::Wall,
+        dimensions.x() as usize,
+        dimensions.y() as usize,
+    )
+}
+
+fn carve(terrain: &mut Canvas<Terrain>, cell: Vector<i32>) {
+    if let Some(pixel) = terrain.pixel_mut(cell) {
+        *pixel = Terrain::Floor;
+    }
+}
+
+/// Rectangular room of the generated dungeon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Room {
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+}
+
+impl Room {
+    /// Get top left cell of the room.
+    pub fn origin(&self) -> Vector<i32> {
+        self.origin
+    }
+
+    /// Get dimensions of the room in cells.
+    pub fn dimensions(&self) -> Vector<i32> {
+        self.dimensions
+    }
+
+    /// Get center cell of the room.
+    pub fn center(&self) -> Vector<i32> {
+        self.origin + self.dimensions / 2
+    }
+
+    /// Check if the `cell` is inside of the room.
+    pub fn contains(&self, cell: Vector<i32>) -> bool {
+        let end = self.origin + self.dimensions;
+        cell.x() >= self.origin.x()
+            && cell.y() >= self.origin.y()
+            && cell.x() < end.x()
+            && cell.y() < end.y()
+    }
+}
+
+/// Generated rooms connected with corridors.
+#[derive(Clone, Debug)]
+pub struct Dungeon {
+    terrain: Canvas<Terrain>,
+    rooms: Vec<Room>,
+}
+
+impl Dungeon {
+    /// Get the generated grid.
+    pub fn terrain(&self) -> &Canvas<Terrain> {
+        &self.terrain
+    }
+
+    /// Get the generated rooms.
+    pub fn rooms(&self) -> &[Room] {
+        &self.rooms
+    }
+
+    /// Take the generated grid and rooms.
+    pub fn into_parts(self) -> (Canvas<Terrain>, Vec<Room>) {
+        (self.terrain, self.rooms)
+    }
+}
+
+/// Binary space partitioning generator of rooms connected with corridors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bsp {
+    dimensions: Vector<i32>,
+    min_leaf: i32,
+    min_room: i32,
+    max_depth: u32,
+}
+
+impl Bsp {
+    /// Create new generator of the grid with the `dimensions`.
+    /// Partitions are at least 8 cells wide, rooms are at least 4 cells wide, splitting goes 6 levels deep.
+    pub fn new(dimensions: Vector<i32>) -> Self {
+        Self {
+            dimensions,
+            min_leaf: 8,
+            min_room: 4,
+            max_depth: 6,
+        }
+    }
+
+    /// Set minimal partition size.
+    pub fn with_min_leaf(self, min_leaf: i32) -> Self {
+        Self {
+            min_leaf: min_leaf.max(3),
+            ..self
+        }
+    }
+
+    /// Set minimal room size.
+    pub fn with_min_room(self, min_room: i32) -> Self {
+        Self {
+            min_room: min_room.max(1),
+            ..self
+        }
+    }
+
+    /// Set maximal depth of partitioning.
+    pub fn with_max_depth(self, max_depth: u32) -> Self {
+        Self { max_depth, ..self }
+    }
+
+    /// Generate the dungeon.
+    pub fn generate(&self, rng: &mut Rng) -> Dungeon {
+        let mut dungeon = Dungeon {
+            terrain: grid(self.dimensions),
+            rooms: Vec::new(),
+        };
+        let dimensions = dungeon.terrain.dimensions();
+        self.split(rng, &mut dungeon, Vector::new(0, 0), dimensions, 0);
+        dungeon
+    }
+
+    /// Partition the area, returning a cell of one of its rooms to connect to.
+    fn split(
+        &self,
+        rng: &mut Rng,
+        dungeon: &mut Dungeon,
+        origin: Vector<i32>,
+        dimensions: Vector<i32>,
+        depth: u32,
+    ) -> Option<Vector<i32>> {
+        let (width, height) = dimensions.split();
+        let can_split_x = width >= self.min_leaf * 2;
+        let can_split_y = height >= self.min_leaf * 2;
+        let vertical = match (can_split_x, can_split_y) {
+            _ if depth >= self.max_depth => None,
+            (true, true) if width * 4 > height * 5 => Some(true),
+            (true, true) if height * 4 > width * 5 => Some(false),
+            (true, true) => Some(rng.chance(0.5)),
+            (true, false) => Some(true),
+            (false, true) => Some(false),
+            (false, false) => None,
+        };
+        let Some(vertical) = vertical else {
+            return self.room(rng, dungeon, origin, dimensions);
+        };
+
+        let (first, second) = if vertical {
+            let split = rng.range(self.min_leaf..width - self.min_leaf + 1);
+            (
+                (origin, Vector::new(split, height)),
+                (origin + (split, 0), Vector::new(width - split, height)),
+            )
+        } else {
+            let split = rng.range(self.min_leaf..height - self.min_leaf + 1);
+            (
+                (origin, Vector::new(width, split)),
+                (origin + (0, split), Vector::new(width, height - split)),
+            )
+        };
+        let first = self.split(rng, dungeon, first.0, first.1, depth + 1);
+        let second = self.split(rng, dungeon, second.0, second.1, depth + 1);
+        match (first, second) {
+            (Some(first), Some(second)) => {
+                corridor(rng, &mut dungeon.terrain, first, second);
+                Some(if rng.chance(0.5) { first } else { second })
+            }
+            (first, second) => first.or(second),
+        }
+    }
+
+    fn room(
+        &self,
+        rng: &mut Rng,
+        dungeon: &mut Dungeon,
+        origin: Vector<i32>,
+        dimensions: Vector<i32>,
+    ) -> Option<Vector<i32>> {
+        // Keep a wall margin around the room so neighbouring rooms do not merge.
+        let available = dimensions - (2, 2);
+        if available.x() < 1 || available.y() < 1 {
+            return None;
+        }
+        let min = available.individual_min((self.min_room, self.min_room));
+        let size = Vector::new(
+            rng.range(min.x()..available.x() + 1),
+            rng.range(min.y()..available.y() + 1),
+        );
+        let offset = Vector::new(
+            rng.range(0..available.x() - size.x() + 1),
+            rng.range(0..available.y() - size.y() + 1),
+        );
+        let room = Room {
+            origin: origin + (1, 1) + offset,
+            dimensions: size,
+        };
+        for y in 0..size.y() {
+            for x in 0..size.x() {
+                carve(&mut dungeon.terrain, room.origin + (x, y));
+            }
+        }
+        dungeon.rooms.push(room);
+        Some(room.center())
+    }
+}
+
+/// Carve L-shaped corridor between the cells.
+fn corridor(rng: &mut Rng, terrain: &mut Canvas<Terrain>, from: Vector<i32>, to: Vector<i32>) {
+    let corner = if rng.chance(0.5) {
+        Vector::new(to.x(), from.y())
+    } else {
+        Vector::new(from.x(), to.y())
+    };
+    for (start, end) in [(from, corner), (corner, to)] {
+        let step = (end - start).map(i32::signum);
+        let mut cell = start;
+        carve(terrain, cell);
+        while cell != end {
+            cell += step;
+            carve(terrain, cell);
+        }
+    }
+}
+
+/// Cellular automaton cave generator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cave {
+    dimensions: Vector<i32>,
+    fill: f32,
+    iterations: u32,
+    birth: u32,
+    survival: u32,
+}
+
+impl Cave {
+    /// Create new generator of the grid with the `dimensions`.
+    /// Initially 45% of cells are walls, the 4-5 rule is applied 4 times.
+    pub fn new(dimensions: Vector<i32>) -> Self {
+        Self {
+            dimensions,
+            fill: 0.45,
+            iterations: 4,
+            birth: 5,
+            survival: 4,
+        }
+    }
+
+    /// Set initial share of the wall cells.
+    pub fn with_fill(self, fill: f32) -> Self {
+        Self { fill, ..self }
+    }
+
+    /// Set amount of smoothing iterations.
+    pub fn with_iterations(self, iterations: u32) -> Self {
+        Self { iterations, ..self }
+    }
+
+    /// Set amounts of neighbouring walls turning a floor cell into a wall (`birth`)
+    /// and keeping a wall cell a wall (`survival`).
+    pub fn with_rule(self, birth: u32, survival: u32) -> Self {
+        Self {
+            birth,
+            survival,
+            ..self
+        }
+    }
+
+    /// Generate the cave.
+    /// The outer border is always a wall.
+    pub fn generate(&self, rng: &mut Rng) -> Canvas<Terrain> {
+        let mut generation = self.start(rng);
+        while generation.step() == Step::Continue {}
+        generation.into_terrain()
+    }
+
+    /// Start the cave generation to be smoothed step by step, e.g. within a `FrameBudget`.
+    pub fn start(&self, rng: &mut Rng) -> CaveGeneration {
+        let mut terrain = grid(self.dimensions);
+        let (width, height) = terrain.dimensions().split();
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                if !rng.chance(self.fill) {
+                    carve(&mut terrain, Vector::new(x, y));
+                }
+            }
+        }
+        CaveGeneration {
+            cave: *self,
+            previous: terrain.clone(),
+            terrain,
+            iteration: 0,
+            row: 1,
+        }
+    }
+}
+
+/// Cave generation in progress, smoothing a single row per step.
+#[derive(Clone, Debug)]
+pub struct CaveGeneration {
+    cave: Cave,
+    terrain: Canvas<Terrain>,
+    previous: Canvas<Terrain>,
+    iteration: u32,
+    row: i32,
+}
+
+impl CaveGeneration {
+    /// Smooth the next row of the cave.
+    pub fn step(&mut self) -> Step {
+        let (width, height) = self.terrain.dimensions().split();
+        if self.iteration >= self.cave.iterations || height < 3 {
+            return Step::Done;
+        }
+        if self.row == 1 {
+            self.previous.clone_from(&self.terrain);
+        }
+        let y = self.row;
+        for x in 1..width - 1 {
+            let cell = Vector::new(x, y);
+            let walls = wall_neighbours(&self.previous, cell);
+            let wall = match self.previous.pixel(cell) {
+                Some(Terrain::Wall) => walls >= self.cave.survival,
+                _ => walls >= self.cave.birth,
+            };
+            if let Some(pixel) = self.terrain.pixel_mut(cell) {
+                *pixel = if wall { Terrain::Wall } else { Terrain::Floor };
+            }
+        }
+        self.row += 1;
+        if self.row >= height - 1 {
+            self.row = 1;
+            self.iteration += 1;
+        }
+        if self.iteration >= self.cave.iterations {
+            Step::Done
+        } else {
+            Step::Continue
+        }
+    }
+
+    /// Smooth the rows until the cave is done or the `budget` is spent.
+    pub fn run(&mut self, budget: &FrameBudget) -> Step {
+        budget.run(|| self.step())
+    }
+
+    /// Get the terrain generated so far.
+    pub fn terrain(&self) -> &Canvas<Terrain> {
+        &self.terrain
+    }
+
+    /// Get the generated terrain.
+    pub fn into_terrain(self) -> Canvas<Terrain> {
+        self.terrain
+    }
+}
+
+fn wall_neighbours(terrain: &Canvas<Terrain>, cell: Vector<i32>) -> u32 {
+    let mut walls = 0;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            if (dx, dy) != (0, 0) && terrain.pixel(cell + (dx, dy)) != Some(&Terrain::Floor) {
+                walls += 1;
+            }
+        }
+    }
+    walls
+}
+
+/// Drunkard's walk generator carving a single connected area.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DrunkardWalk {
+    dimensions: Vector<i32>,
+    coverage: f32,
+    max_steps: Option<usize>,
+}
+
+impl DrunkardWalk {
+    /// Create new generator of the grid with the `dimensions`.
+    /// Walking stops once 40% of the cells are carved.
+    pub fn new(dimensions: Vector<i32>) -> Self {
+        Self {
+            dimensions,
+            coverage: 0.4,
+            max_steps: None,
+        }
+    }
+
+    /// Set share of the inner cells to carve.
+    pub fn with_coverage(self, coverage: f32) -> Self {
+        Self { coverage, ..self }
+    }
+
+    /// Set maximal amount of steps, ten steps per cell by default.
+    pub fn with_max_steps(self, max_steps: usize) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+            ..self
+        }
+    }
+
+    /// Generate the area starting the walk at the grid center.
+    /// The outer border is always a wall.
+    pub fn generate(&self, rng: &mut Rng) -> Canvas<Terrain> {
+        let mut terrain = grid(self.dimensions);
+        let (width, height) = terrain.dimensions().split();
+        if width < 3 || height < 3 {
+            return terrain;
+        }
+        let inner = ((width - 2) * (height - 2)) as usize;
+        let target = ((inner as f32 * self.coverage.clamp(0.0, 1.0)) as usize).max(1);
+        let max_steps = self.max_steps.unwrap_or((width * height) as usize * 10);
+
+        let mut cell = Vector::new(width / 2, height / 2);
+        carve(&mut terrain, cell);
+        let mut carved = 1;
+        for _ in 0..max_steps {
+            if carved >= target {
+                break;
+            }
+            let step = match rng.range(0..4) {
+                0 => Vector::new(1, 0),
+                1 => Vector::new(-1, 0),
+                2 => Vector::new(0, 1),
+                _ => Vector::new(0, -1),
+            };
+            let next = cell + step;
+            if next.x() < 1 || next.y() < 1 || next.x() >= width - 1 || next.y() >= height - 1 {
+                continue;
+            }
+            cell = next;
+            if terrain.pixel(cell) == Some(&Terrain::Wall) {
+                carve(&mut terrain, cell);
+                carved += 1;
+            }
+        }
+        terrain
+    }
+}
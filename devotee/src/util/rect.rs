@@ -0,0 +1,213 @@
+use std::ops::{Add, Sub};
+
+use super::vector::Vector;
+
+fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Axis aligned rectangle defined by its top left corner and dimensions.
+/// The bottom right corner is exclusive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rect<T> {
+    origin: Vector<T>,
+    dimensions: Vector<T>,
+}
+
+impl<T> Rect<T> {
+    /// Create new rectangle with the `origin` and `dimensions`.
+    pub const fn new(origin: Vector<T>, dimensions: Vector<T>) -> Self {
+        Self { origin, dimensions }
+    }
+}
+
+impl<T> Rect<T>
+where
+    T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// Create new rectangle between the `start` corner and the exclusive `end` corner.
+    /// Dimensions of the inverted corners are negative.
+    pub fn from_corners(start: Vector<T>, end: Vector<T>) -> Self {
+        Self::new(start, end - start)
+    }
+
+    /// Get the top left corner.
+    pub fn origin(&self) -> Vector<T> {
+        self.origin
+    }
+
+    /// Get the dimensions.
+    pub fn dimensions(&self) -> Vector<T> {
+        self.dimensions
+    }
+
+    /// Get the exclusive bottom right corner.
+    pub fn end(&self) -> Vector<T> {
+        self.origin + self.dimensions
+    }
+
+    /// Get the top left and the exclusive bottom right corners.
+    pub fn corners(&self) -> (Vector<T>, Vector<T>) {
+        (self.origin, self.end())
+    }
+
+    /// Check if the rectangle covers no area.
+    pub fn is_empty(&self) -> bool {
+        !(self.dimensions.x() > T::default() && self.dimensions.y() > T::default())
+    }
+
+    /// Check if the `point` is inside of the rectangle.
+    pub fn contains(&self, point: Vector<T>) -> bool {
+        let end = self.end();
+        point.x() >= self.origin.x()
+            && point.y() >= self.origin.y()
+            && point.x() < end.x()
+            && point.y() < end.y()
+    }
+
+    /// Check if the `other` non-empty rectangle is completely inside of this one.
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        let (end, other_end) = (self.end(), other.end());
+        !other.is_empty()
+            && other.origin.x() >= self.origin.x()
+            && other.origin.y() >= self.origin.y()
+            && other_end.x() <= end.x()
+            && other_end.y() <= end.y()
+    }
+
+    /// Check if the rectangles share any area.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Get the area shared by both rectangles.
+    /// Returns `None` if there is no such area.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (end, other_end) = (self.end(), other.end());
+        let start = Vector::new(
+            max(self.origin.x(), other.origin.x()),
+            max(self.origin.y(), other.origin.y()),
+        );
+        let end = Vector::new(min(end.x(), other_end.x()), min(end.y(), other_end.y()));
+        let intersection = Self::from_corners(start, end);
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Get the smallest rectangle covering both rectangles.
+    /// Empty rectangles are ignored.
+    pub fn union(&self, other: &Self) -> Self {
+        if other.is_empty() {
+            return *self;
+        }
+        if self.is_empty() {
+            return *other;
+        }
+        let (end, other_end) = (self.end(), other.end());
+        Self::from_corners(
+            Vector::new(
+                min(self.origin.x(), other.origin.x()),
+                min(self.origin.y(), other.origin.y()),
+            ),
+            Vector::new(max(end.x(), other_end.x()), max(end.y(), other_end.y())),
+        )
+    }
+
+    /// Clamp the `point` to the rectangle, including its exclusive edges.
+    pub fn clamp(&self, point: Vector<T>) -> Vector<T> {
+        let end = self.end();
+        Vector::new(
+            min(max(point.x(), self.origin.x()), end.x()),
+            min(max(point.y(), self.origin.y()), end.y()),
+        )
+    }
+
+    /// Move the rectangle by the `offset`.
+    pub fn translated(&self, offset: Vector<T>) -> Self {
+        Self::new(self.origin + offset, self.dimensions)
+    }
+}
+
+impl<T> From<(Vector<T>, Vector<T>)> for Rect<T> {
+    /// Create new rectangle from the `origin` and `dimensions` pair.
+    fn from((origin, dimensions): (Vector<T>, Vector<T>)) -> Self {
+        Self::new(origin, dimensions)
+    }
+}
+
+impl<T> From<Rect<T>> for (Vector<T>, Vector<T>) {
+    /// Split the rectangle into the `origin` and `dimensions` pair.
+    fn from(rect: Rect<T>) -> Self {
+        (rect.origin, rect.dimensions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> Rect<i32> {
+        Rect::new(Vector::new(x, y), Vector::new(width, height))
+    }
+
+    #[test]
+    fn intersection_of_overlapping_rects() {
+        let (a, b) = (rect(0, 0, 10, 10), rect(5, -2, 10, 4));
+        assert_eq!(a.intersection(&b), Some(rect(5, 0, 5, 2)));
+        assert_eq!(b.intersection(&a), a.intersection(&b));
+        assert!(a.intersects(&b));
+    }
+
+    #[test]
+    fn intersection_of_nested_rects() {
+        let (outer, inner) = (rect(0, 0, 10, 10), rect(2, 3, 4, 5));
+        assert_eq!(outer.intersection(&inner), Some(inner));
+        assert!(outer.contains_rect(&inner));
+        assert!(!inner.contains_rect(&outer));
+    }
+
+    #[test]
+    fn touching_and_empty_rects_do_not_intersect() {
+        let a = rect(0, 0, 10, 10);
+        assert_eq!(a.intersection(&rect(10, 0, 5, 5)), None);
+        assert_eq!(a.intersection(&rect(0, -5, 5, 5)), None);
+        assert_eq!(a.intersection(&rect(2, 2, 0, 5)), None);
+        assert!(!a.intersects(&rect(20, 20, 1, 1)));
+    }
+
+    #[test]
+    fn union_covers_both_rects() {
+        let (a, b) = (rect(0, 0, 4, 4), rect(6, -2, 2, 3));
+        let union = a.union(&b);
+        assert_eq!(union, rect(0, -2, 8, 6));
+        assert_eq!(b.union(&a), union);
+        assert!(union.contains_rect(&a) && union.contains_rect(&b));
+    }
+
+    #[test]
+    fn union_ignores_empty_rects() {
+        let (a, empty) = (rect(1, 1, 2, 2), rect(-10, -10, 0, 3));
+        assert_eq!(a.union(&empty), a);
+        assert_eq!(empty.union(&a), a);
+    }
+
+    #[test]
+    fn contains_excludes_end_corner() {
+        let a = rect(1, 1, 2, 2);
+        assert!(a.contains(Vector::new(1, 1)));
+        assert!(a.contains(Vector::new(2, 2)));
+        assert!(!a.contains(Vector::new(3, 2)));
+        assert_eq!(a.clamp(Vector::new(10, -10)), Vector::new(3, 1));
+    }
+}
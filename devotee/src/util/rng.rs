@@ -0,0 +1,176 @@
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const INCREMENT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Scramble bits of the `value` with the SplitMix64 finalizer.
+pub(crate) fn mix(value: u64) -> u64 {
+    let value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    let value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+/// Seedable pseudo-random number generator based on SplitMix64.
+/// The same seed produces the same sequence on every platform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create new generator with the `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Get the next 64-bit value.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(INCREMENT);
+        mix(self.state)
+    }
+
+    /// Get the next 32-bit value.
+    pub fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Get the next value in the `[0.0, 1.0)` range.
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+
+    /// Get the next value in the `range`.
+    /// Empty ranges produce their start.
+    pub fn range(&mut self, range: Range<i32>) -> i32 {
+        if range.end <= range.start {
+            return range.start;
+        }
+        let span = (range.end as i64 - range.start as i64) as u64;
+        let offset = (self.next_u32() as u64 * span) >> 32;
+        (range.start as i64 + offset as i64) as i32
+    }
+
+    /// Get the next value in the `range` of floats.
+    /// Empty ranges produce their start.
+    pub fn range_f32(&mut self, range: Range<f32>) -> f32 {
+        if range.end <= range.start {
+            return range.start;
+        }
+        let value = range.start + (range.end - range.start) * self.next_f32();
+        value.min(range.end.next_down())
+    }
+
+    /// Get `true` with the `probability`.
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Get random element of the `items`.
+    /// Returns `None` if there are no items.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        let last = i32::try_from(items.len()).unwrap_or(i32::MAX);
+        items.get(self.range(0..last) as usize)
+    }
+
+    /// Get index of the random element with probability proportional to its weight in the `weights`.
+    /// Returns `None` if the weights sum is not positive.
+    pub fn weighted(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().map(|weight| weight.max(0.0)).sum();
+        if total <= 0.0 || !total.is_finite() {
+            return None;
+        }
+        let mut threshold = self.next_f32() * total;
+        let mut chosen = None;
+        for (index, weight) in weights.iter().enumerate() {
+            let weight = weight.max(0.0);
+            if weight == 0.0 {
+                continue;
+            }
+            chosen = Some(index);
+            if threshold < weight {
+                break;
+            }
+            threshold -= weight;
+        }
+        chosen
+    }
+
+    /// Shuffle the `items` in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            let other = (self.next_u64() % (index as u64 + 1)) as usize;
+            items.swap(index, other);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn values_stay_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((0.0..1.0).contains(&rng.next_f32()));
+            assert!((-3..5).contains(&rng.range(-3..5)));
+            assert!((i32::MIN..i32::MAX).contains(&rng.range(i32::MIN..i32::MAX)));
+            assert!((0.5..0.75).contains(&rng.range_f32(0.5..0.75)));
+        }
+        assert_eq!(rng.range(4..4), 4);
+        assert_eq!(rng.range_f32(2.0..1.0), 2.0);
+    }
+
+    #[test]
+    fn range_covers_all_values() {
+        let mut rng = Rng::new(3);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            seen[rng.range(0..6) as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn choose_picks_existing_element() {
+        let mut rng = Rng::new(11);
+        let items = [1, 2, 3];
+        for _ in 0..100 {
+            assert!(items.contains(rng.choose(&items).unwrap()));
+        }
+        assert_eq!(rng.choose::<i32>(&[]), None);
+    }
+
+    #[test]
+    fn weighted_skips_non_positive_weights() {
+        let mut rng = Rng::new(5);
+        for _ in 0..1000 {
+            let index = rng.weighted(&[0.0, 1.0, -2.0, 3.0]).unwrap();
+            assert!(index == 1 || index == 3);
+        }
+        assert_eq!(rng.weighted(&[]), None);
+        assert_eq!(rng.weighted(&[0.0, -1.0]), None);
+    }
+
+    #[test]
+    fn shuffle_keeps_elements() {
+        let mut rng = Rng::new(9);
+        let mut items: Vec<_> = (0..32).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..32).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..32).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(250);
+const HANDSHAKE_ATTEMPTS: u32 = 8;
+
+type Waker = Box<dyn Fn() + Send>;
+
+/// Result of the single instance lock acquisition.
+#[derive(Debug)]
+pub enum Instance {
+    /// This is the first running instance; keep the guard alive while the application runs.
+    Primary(InstanceGuard),
+    /// Another instance is already running and confirmed the activation request.
+    Secondary,
+}
+
+#[derive(Default)]
+struct Activation {
+    requested: AtomicBool,
+    closed: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// Lock held by the primary application instance.
+/// Activation requests are received on a separate thread.
+/// The lock is released on drop.
+pub struct InstanceGuard {
+    port_path: PathBuf,
+    port: u16,
+    activation: Arc<Activation>,
+    _lock: File,
+}
+
+impl InstanceGuard {
+    /// Set `waker` to be called from the listener thread on each activation request,
+    /// e.g. sending `BackendRequest::Activate` with the event loop proxy of the backend
+    /// to focus the window.
+    pub fn with_waker<F>(self, waker: F) -> Self
+    where
+        F: Fn() + Send + 'static,
+    {
+        if let Ok(mut slot) = self.activation.waker.lock() {
+            *slot = Some(Box::new(waker));
+        }
+        self
+    }
+
+    /// Check if any other instance asked this one to activate since the last check.
+    /// Without the waker focusing the window is left to the application,
+    /// e.g. with `window_ref().focus_window()` of the backend control.
+    pub fn activation_requested(&self) -> bool {
+        self.activation.requested.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl fmt::Debug for InstanceGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstanceGuard")
+            .field("port_path", &self.port_path)
+            .field("port", &self.port)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        self.activation.closed.store(true, Ordering::Release);
+        // Unblock the listener thread so it can notice the guard is gone.
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, self.port));
+        let _ = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT);
+        let _ = fs::remove_file(&self.port_path);
+    }
+}
+
+/// Try to acquire the single instance lock for the application with `app_id`.
+/// If another instance holds the lock it is asked to activate and `Instance::Secondary` is returned
+/// once it confirms the request.
+pub fn acquire(app_id: &str) -> io::Result<Instance> {
+    let directory = runtime_directory();
+    fs::create_dir_all(&directory)?;
+    let file_name = file_name(app_id);
+    let lock_path = directory.join(format!("{}.lock", file_name));
+    let port_path = directory.join(format!("{}.instance", file_name));
+
+    // The lock file is never removed: the OS releases the lock even if the instance crashes.
+    let lock = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(lock_path)?;
+    match lock.try_lock() {
+        Ok(()) => (),
+        Err(TryLockError::WouldBlock) => return activate(&port_path, app_id),
+        Err(TryLockError::Error(error)) => return Err(error),
+    }
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    let port = listener.local_addr()?.port();
+    fs::write(&port_path, port.to_string())?;
+
+    let activation = Arc::new(Activation::default());
+    let app_id = app_id.to_owned();
+    let shared = Arc::clone(&activation);
+    thread::Builder::new()
+        .name("single instance listener".to_owned())
+        .spawn(move || listen(listener, &app_id, &shared))?;
+
+    Ok(Instance::Primary(InstanceGuard {
+        port_path,
+        port,
+        activation,
+        _lock: lock,
+    }))
+}
+
+/// Get directory private to the current user, so instances of other users do not interfere.
+fn runtime_directory() -> PathBuf {
+    if let Some(directory) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(directory);
+    }
+    match std::env::var("USER").or_else(|_| std::env::var("USERNAME")) {
+        Ok(user) if !user.is_empty() => {
+            std::env::temp_dir().join(format!("devotee-{}", file_name(&user)))
+        }
+        _ => std::env::temp_dir().join("devotee"),
+    }
+}
+
+/// Escape everything except ASCII alphanumerics, so distinct ids get distinct file names.
+fn file_name(id: &str) -> String {
+    let mut name = String::with_capacity(id.len());
+    for byte in id.bytes() {
+        if byte.is_ascii_alphanumeric() {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("_{:02x}", byte));
+        }
+    }
+    name
+}
+
+/// Ask the running instance to activate, retrying while it is still starting up.
+fn activate(port_path: &Path, app_id: &str) -> io::Result<Instance> {
+    for _ in 0..HANDSHAKE_ATTEMPTS {
+        if handshake(port_path, app_id).unwrap_or(false) {
+            return Ok(Instance::Secondary);
+        }
+        thread::sleep(CONNECT_TIMEOUT);
+    }
+    Err(io::Error::new(
+        io::ErrorKind::TimedOut,
+        "running instance did not confirm the activation request",
+    ))
+}
+
+fn handshake(port_path: &Path, app_id: &str) -> io::Result<bool> {
+    let port = fs::read_to_string(port_path)?
+        .trim()
+        .parse::<u16>()
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let mut stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    writeln!(stream, "{}", app_id)?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(reply.trim_end() == app_id)
+}
+
+fn listen(listener: TcpListener, app_id: &str, activation: &Activation) {
+    for stream in listener.incoming() {
+        if activation.closed.load(Ordering::Acquire) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if confirm(stream, app_id).unwrap_or(false) {
+            activation.requested.store(true, Ordering::Release);
+            if let Ok(waker) = activation.waker.lock() {
+                if let Some(waker) = waker.as_ref() {
+                    waker();
+                }
+            }
+        }
+    }
+}
+
+fn confirm(stream: TcpStream, app_id: &str) -> io::Result<bool> {
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    if line.trim_end() != app_id {
+        return Ok(false);
+    }
+    writeln!(reader.get_mut(), "{}", app_id)?;
+    Ok(true)
+}
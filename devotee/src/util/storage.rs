@@ -0,0 +1,164 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+/// IndexedDB persistence for wasm targets.
+#[cfg(target_arch = "wasm32")]
+mod indexed_db;
+
+/// Storage error enumeration.
+#[derive(Debug)]
+pub enum StorageError {
+    /// Key is empty or contains characters other than ASCII alphanumerics, `-`, `_` and `.`.
+    InvalidKey,
+
+    /// File system error.
+    #[cfg(not(target_arch = "wasm32"))]
+    IoError(io::Error),
+
+    /// IndexedDB error description.
+    #[cfg(target_arch = "wasm32")]
+    IndexedDbError(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<io::Error> for StorageError {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    entries: BTreeMap<String, Vec<u8>>,
+    ready: bool,
+    error: Option<StorageError>,
+}
+
+/// Persistent key-value storage with synchronous access.
+///
+/// On desktop targets entries are files in the storage directory.
+/// On wasm targets entries live in memory and are mirrored into the IndexedDB database
+/// in background; previously stored entries become available once the storage `is_ready`.
+pub struct Storage {
+    shared: Rc<RefCell<Shared>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    directory: PathBuf,
+    #[cfg(target_arch = "wasm32")]
+    database: indexed_db::Database,
+}
+
+impl Storage {
+    /// Open storage in the `name` directory.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open<P: Into<PathBuf>>(name: P) -> Self {
+        let shared = Rc::new(RefCell::new(Shared {
+            ready: true,
+            ..Default::default()
+        }));
+        Self {
+            shared,
+            directory: name.into(),
+        }
+    }
+
+    /// Open storage in the `name` IndexedDB database, loading its entries in background.
+    #[cfg(target_arch = "wasm32")]
+    pub fn open(name: &str) -> Self {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        let database = indexed_db::Database::open(name, shared.clone());
+        Self { shared, database }
+    }
+
+    /// Check if previously stored entries are available.
+    pub fn is_ready(&self) -> bool {
+        self.shared.borrow().ready
+    }
+
+    /// Take the last background operation error if any.
+    pub fn take_error(&mut self) -> Option<StorageError> {
+        self.shared.borrow_mut().error.take()
+    }
+
+    fn validate(key: &str) -> Result<(), StorageError> {
+        let valid = !key.is_empty()
+            && !key.starts_with('.')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if valid {
+            Ok(())
+        } else {
+            Err(StorageError::InvalidKey)
+        }
+    }
+
+    /// Get entry value by its `key`.
+    pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+        Self::validate(key).ok()?;
+        if let Some(value) = self.shared.borrow().entries.get(key) {
+            return Some(value.clone());
+        }
+        let value = self.read(key)?;
+        self.shared
+            .borrow_mut()
+            .entries
+            .insert(key.to_owned(), value.clone());
+        Some(value)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn read(&self, key: &str) -> Option<Vec<u8>> {
+        fs::read(self.directory.join(key)).ok()
+    }
+
+    /// All the persisted entries are loaded into memory when the storage becomes ready.
+    #[cfg(target_arch = "wasm32")]
+    fn read(&self, _: &str) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Get entry value by its `key` as UTF-8 string.
+    pub fn get_string(&self, key: &str) -> Option<String> {
+        String::from_utf8(self.get(key)?).ok()
+    }
+
+    /// Store the `value` under the `key`.
+    /// On wasm targets the value is persisted in background.
+    pub fn set(&mut self, key: &str, value: Vec<u8>) -> Result<(), StorageError> {
+        Self::validate(key)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            fs::create_dir_all(&self.directory)?;
+            fs::write(self.directory.join(key), &value)?;
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.database.put(key, &value);
+        self.shared
+            .borrow_mut()
+            .entries
+            .insert(key.to_owned(), value);
+        Ok(())
+    }
+
+    /// Remove entry by its `key`.
+    pub fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        Self::validate(key)?;
+        #[cfg(not(target_arch = "wasm32"))]
+        match fs::remove_file(self.directory.join(key)) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error.into()),
+            _ => (),
+        }
+        #[cfg(target_arch = "wasm32")]
+        self.database.delete(key);
+        self.shared.borrow_mut().entries.remove(key);
+        Ok(())
+    }
+}
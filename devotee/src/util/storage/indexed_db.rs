@@ -0,0 +1,159 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::{Array, Promise, Uint8Array};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransactionMode};
+
+use super::{Shared, StorageError};
+
+const STORE: &str = "entries";
+
+type Operation = (String, Option<Vec<u8>>);
+
+/// Lazily opened IndexedDB database mirroring the storage entries.
+/// Operations issued before the database is opened are queued.
+pub(super) struct Database {
+    database: Rc<RefCell<Option<IdbDatabase>>>,
+    pending: Rc<RefCell<Vec<Operation>>>,
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl Database {
+    /// Open the database and load its entries into the `shared` state in background.
+    pub(super) fn open(name: &str, shared: Rc<RefCell<Shared>>) -> Self {
+        let database = Rc::new(RefCell::new(None));
+        let pending: Rc<RefCell<Vec<Operation>>> = Rc::new(RefCell::new(Vec::new()));
+        let name = name.to_owned();
+        let (slot, queue, state) = (database.clone(), pending.clone(), shared.clone());
+        spawn_local(async move {
+            match load(&name).await {
+                Ok((database, entries)) => {
+                    let operations = std::mem::take(&mut *queue.borrow_mut());
+                    {
+                        let mut state = state.borrow_mut();
+                        for (key, value) in entries {
+                            // Entries set before loading finished are newer.
+                            state.entries.entry(key).or_insert(value);
+                        }
+                        for (key, value) in operations.iter() {
+                            if value.is_none() {
+                                state.entries.remove(key);
+                            }
+                        }
+                    }
+                    for (key, value) in operations {
+                        execute(&database, &key, value.as_deref(), &state);
+                    }
+                    *slot.borrow_mut() = Some(database);
+                }
+                Err(error) => state.borrow_mut().error = Some(error),
+            }
+            state.borrow_mut().ready = true;
+        });
+        Self {
+            database,
+            pending,
+            shared,
+        }
+    }
+
+    fn run(&self, key: &str, value: Option<&[u8]>) {
+        match self.database.borrow().as_ref() {
+            Some(database) => execute(database, key, value, &self.shared),
+            None => self
+                .pending
+                .borrow_mut()
+                .push((key.to_owned(), value.map(<[u8]>::to_vec))),
+        }
+    }
+
+    pub(super) fn put(&self, key: &str, value: &[u8]) {
+        self.run(key, Some(value));
+    }
+
+    pub(super) fn delete(&self, key: &str) {
+        self.run(key, None);
+    }
+}
+
+/// Put the `value` under the `key` or delete the entry if there is no value.
+fn execute(database: &IdbDatabase, key: &str, value: Option<&[u8]>, shared: &Rc<RefCell<Shared>>) {
+    let key = JsValue::from_str(key);
+    let request = database
+        .transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite)
+        .and_then(|transaction| transaction.object_store(STORE))
+        .and_then(|store| match value {
+            Some(value) => store.put_with_key(&Uint8Array::from(value), &key),
+            None => store.delete(&key),
+        });
+    let shared = shared.clone();
+    match request {
+        Ok(request) => spawn_local(async move {
+            if let Err(error) = wait(&request).await {
+                shared.borrow_mut().error = Some(error);
+            }
+        }),
+        Err(error) => shared.borrow_mut().error = Some(describe(error)),
+    }
+}
+
+fn describe(value: JsValue) -> StorageError {
+    StorageError::IndexedDbError(format!("{:?}", value))
+}
+
+async fn wait(request: &IdbRequest) -> Result<JsValue, StorageError> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await.map_err(describe)?;
+    request.result().map_err(describe)
+}
+
+async fn load(name: &str) -> Result<(IdbDatabase, Vec<(String, Vec<u8>)>), StorageError> {
+    let factory = web_sys::window()
+        .ok_or_else(|| StorageError::IndexedDbError("No window".to_owned()))?
+        .indexed_db()
+        .map_err(describe)?
+        .ok_or_else(|| StorageError::IndexedDbError("IndexedDB is unavailable".to_owned()))?;
+    let request: IdbOpenDbRequest = factory.open_with_u32(name, 1).map_err(describe)?;
+    let upgrade_request = request.clone();
+    let upgrade = Closure::once(move |_: web_sys::Event| {
+        if let Ok(database) = upgrade_request
+            .result()
+            .and_then(|database| database.dyn_into::<IdbDatabase>())
+        {
+            let _ = database.create_object_store(STORE);
+        }
+    });
+    request.set_onupgradeneeded(Some(upgrade.as_ref().unchecked_ref()));
+    let database = wait(&request).await?;
+    request.set_onupgradeneeded(None);
+    drop(upgrade);
+    let database: IdbDatabase = database.dyn_into().map_err(describe)?;
+
+    let store = database
+        .transaction_with_str(STORE)
+        .and_then(|transaction| transaction.object_store(STORE))
+        .map_err(describe)?;
+    let keys: Array = wait(&store.get_all_keys().map_err(describe)?)
+        .await?
+        .dyn_into()
+        .map_err(describe)?;
+    let values: Array = wait(&store.get_all().map_err(describe)?)
+        .await?
+        .dyn_into()
+        .map_err(describe)?;
+    let entries = keys
+        .iter()
+        .zip(values.iter())
+        .filter_map(|(key, value)| {
+            let value = value.dyn_into::<Uint8Array>().ok()?;
+            Some((key.as_string()?, value.to_vec()))
+        })
+        .collect();
+    Ok((database, entries))
+}
@@ -0,0 +1,411 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Timer repetition mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimerMode {
+    /// Fire once and stay finished.
+    #[default]
+    Once,
+    /// Fire each period.
+    Repeating,
+}
+
+/// Timer driven by the update deltas.
+#[derive(Clone, Debug)]
+pub struct Timer {
+    period: Duration,
+    elapsed: Duration,
+    mode: TimerMode,
+    finished: bool,
+    paused: bool,
+}
+
+impl Timer {
+    /// Create new timer firing after the `period` in given `mode`.
+    pub fn new(period: Duration, mode: TimerMode) -> Self {
+        Self {
+            period,
+            elapsed: Duration::ZERO,
+            mode,
+            finished: false,
+            paused: false,
+        }
+    }
+
+    /// Create new timer firing once after the `period`.
+    pub fn once(period: Duration) -> Self {
+        Self::new(period, TimerMode::Once)
+    }
+
+    /// Create new timer firing each `period`.
+    pub fn repeating(period: Duration) -> Self {
+        Self::new(period, TimerMode::Repeating)
+    }
+
+    /// Advance the timer by `delta` time.
+    /// Returns how many times the timer fired during this update.
+    /// Repeating timers with zero period fire once per update.
+    pub fn update(&mut self, delta: Duration) -> u32 {
+        if self.paused || self.finished {
+            return 0;
+        }
+        self.elapsed += delta;
+        if self.elapsed < self.period {
+            return 0;
+        }
+        match self.mode {
+            TimerMode::Once => {
+                self.elapsed = self.period;
+                self.finished = true;
+                1
+            }
+            TimerMode::Repeating if self.period.is_zero() => {
+                self.elapsed = Duration::ZERO;
+                1
+            }
+            TimerMode::Repeating => {
+                let period = self.period.as_nanos();
+                let fired = self.elapsed.as_nanos() / period;
+                self.elapsed = Duration::from_nanos((self.elapsed.as_nanos() % period) as u64);
+                fired.try_into().unwrap_or(u32::MAX)
+            }
+        }
+    }
+
+    /// Check if the one-shot timer has fired.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Get the timer mode.
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    /// Get the timer period.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Get time passed since the start or the last firing of the repeating timer.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Get time left until the next firing.
+    pub fn remaining(&self) -> Duration {
+        self.period.saturating_sub(self.elapsed)
+    }
+
+    /// Get progress towards the next firing in the `[0.0, 1.0]` range.
+    pub fn progress(&self) -> f32 {
+        if self.period.is_zero() {
+            return 1.0;
+        }
+        (self.elapsed.as_secs_f32() / self.period.as_secs_f32()).min(1.0)
+    }
+
+    /// Pause the timer.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume the paused timer.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Check if the timer is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Restart the timer from zero.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.finished = false;
+    }
+}
+
+/// Identifier of the task in the scheduler.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+enum Trigger {
+    Time(Timer),
+    Ticks {
+        period: u64,
+        left: u64,
+        mode: TimerMode,
+    },
+}
+
+impl Trigger {
+    fn update(&mut self, delta: Duration) -> u32 {
+        match self {
+            Trigger::Time(timer) => timer.update(delta),
+            Trigger::Ticks { period, left, mode } => {
+                *left = left.saturating_sub(1);
+                if *left > 0 {
+                    return 0;
+                }
+                if *mode == TimerMode::Repeating {
+                    *left = (*period).max(1);
+                }
+                1
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        match self {
+            Trigger::Time(timer) => timer.is_finished(),
+            Trigger::Ticks { left, mode, .. } => *mode == TimerMode::Once && *left == 0,
+        }
+    }
+}
+
+struct Task<S> {
+    id: TaskId,
+    trigger: Trigger,
+    action: Box<dyn FnMut(&mut S)>,
+}
+
+/// Queue of delayed and periodic actions over the `S` state, driven from the update.
+pub struct Scheduler<S> {
+    tasks: Vec<Task<S>>,
+    next_id: u64,
+}
+
+impl<S> Scheduler<S> {
+    /// Create new empty scheduler.
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Run the `action` once after the `delay`.
+    pub fn after<F>(&mut self, delay: Duration, action: F) -> TaskId
+    where
+        F: FnOnce(&mut S) + 'static,
+    {
+        self.push(Trigger::Time(Timer::once(delay)), once(action))
+    }
+
+    /// Run the `action` each `period`.
+    pub fn every<F>(&mut self, period: Duration, action: F) -> TaskId
+    where
+        F: FnMut(&mut S) + 'static,
+    {
+        self.push(Trigger::Time(Timer::repeating(period)), Box::new(action))
+    }
+
+    /// Run the `action` once after `ticks` updates.
+    pub fn after_ticks<F>(&mut self, ticks: u64, action: F) -> TaskId
+    where
+        F: FnOnce(&mut S) + 'static,
+    {
+        let trigger = Trigger::Ticks {
+            period: ticks,
+            left: ticks.max(1),
+            mode: TimerMode::Once,
+        };
+        self.push(trigger, once(action))
+    }
+
+    /// Run the `action` each `ticks` updates.
+    pub fn every_ticks<F>(&mut self, ticks: u64, action: F) -> TaskId
+    where
+        F: FnMut(&mut S) + 'static,
+    {
+        let trigger = Trigger::Ticks {
+            period: ticks,
+            left: ticks.max(1),
+            mode: TimerMode::Repeating,
+        };
+        self.push(trigger, Box::new(action))
+    }
+
+    /// Cancel the task.
+    /// Returns `false` if there is no such task, e.g. it has already finished.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        let count = self.tasks.len();
+        self.tasks.retain(|task| task.id != id);
+        self.tasks.len() != count
+    }
+
+    /// Check if the task is still scheduled.
+    pub fn contains(&self, id: TaskId) -> bool {
+        self.tasks.iter().any(|task| task.id == id)
+    }
+
+    /// Get amount of scheduled tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Check if there are no scheduled tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Cancel all tasks.
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+    }
+
+    /// Advance all tasks by one update lasting for `delta` and run the due ones over the `state`
+    /// in the order they were scheduled.
+    /// Periodic tasks run as many times as their period passed.
+    pub fn update(&mut self, delta: Duration, state: &mut S) {
+        for task in self.tasks.iter_mut() {
+            for _ in 0..task.trigger.update(delta) {
+                (task.action)(state);
+            }
+        }
+        self.tasks.retain(|task| !task.trigger.is_finished());
+    }
+
+    fn push(&mut self, trigger: Trigger, action: Box<dyn FnMut(&mut S)>) -> TaskId {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+        self.tasks.push(Task {
+            id,
+            trigger,
+            action,
+        });
+        id
+    }
+}
+
+impl<S> Default for Scheduler<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> fmt::Debug for Scheduler<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("tasks", &self.tasks.len())
+            .finish()
+    }
+}
+
+fn once<S, F>(action: F) -> Box<dyn FnMut(&mut S)>
+where
+    F: FnOnce(&mut S) + 'static,
+{
+    let mut action = Some(action);
+    Box::new(move |state| {
+        if let Some(action) = action.take() {
+            action(state);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn once_timer_fires_once() {
+        let mut timer = Timer::once(ms(100));
+        assert_eq!(timer.update(ms(60)), 0);
+        assert!(!timer.is_finished());
+        assert_eq!(timer.update(ms(60)), 1);
+        assert!(timer.is_finished());
+        assert_eq!(timer.elapsed(), ms(100));
+        assert_eq!(timer.update(ms(500)), 0);
+    }
+
+    #[test]
+    fn repeating_timer_counts_firings() {
+        let mut timer = Timer::repeating(ms(100));
+        assert_eq!(timer.update(ms(50)), 0);
+        assert_eq!(timer.update(ms(50)), 1);
+        assert_eq!(timer.update(ms(350)), 3);
+        assert_eq!(timer.elapsed(), ms(50));
+        assert_eq!(timer.remaining(), ms(50));
+        assert!(!timer.is_finished());
+    }
+
+    #[test]
+    fn zero_period_repeating_timer_fires_each_update() {
+        let mut timer = Timer::repeating(Duration::ZERO);
+        assert_eq!(timer.update(ms(10)), 1);
+        assert_eq!(timer.update(Duration::ZERO), 1);
+    }
+
+    #[test]
+    fn paused_timer_does_not_advance() {
+        let mut timer = Timer::repeating(ms(100));
+        timer.pause();
+        assert_eq!(timer.update(ms(500)), 0);
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+        timer.resume();
+        assert_eq!(timer.update(ms(100)), 1);
+    }
+
+    #[test]
+    fn reset_restarts_finished_timer() {
+        let mut timer = Timer::once(ms(10));
+        assert_eq!(timer.update(ms(10)), 1);
+        timer.reset();
+        assert!(!timer.is_finished());
+        assert_eq!(timer.update(ms(10)), 1);
+    }
+
+    #[test]
+    fn scheduler_runs_due_tasks() {
+        let mut scheduler = Scheduler::new();
+        let mut count = 0;
+        let once = scheduler.after(ms(100), |count: &mut i32| *count += 1);
+        scheduler.every(ms(30), |count: &mut i32| *count += 10);
+
+        scheduler.update(ms(50), &mut count);
+        assert_eq!(count, 10);
+        assert!(scheduler.contains(once));
+
+        scheduler.update(ms(50), &mut count);
+        assert_eq!(count, 31);
+        assert!(!scheduler.contains(once));
+        assert_eq!(scheduler.len(), 1);
+    }
+
+    #[test]
+    fn scheduler_counts_ticks() {
+        let mut scheduler = Scheduler::new();
+        let fired = Rc::new(Cell::new(0));
+        let counter = fired.clone();
+        scheduler.every_ticks(2, move |_: &mut ()| counter.set(counter.get() + 1));
+        scheduler.after_ticks(3, |_: &mut ()| {});
+        for _ in 0..6 {
+            scheduler.update(Duration::ZERO, &mut ());
+        }
+        assert_eq!(fired.get(), 3);
+        assert_eq!(scheduler.len(), 1);
+    }
+
+    #[test]
+    fn cancelled_task_does_not_run() {
+        let mut scheduler = Scheduler::new();
+        let mut count = 0;
+        let id = scheduler.every(ms(10), |count: &mut i32| *count += 1);
+        assert!(scheduler.cancel(id));
+        assert!(!scheduler.cancel(id));
+        scheduler.update(ms(100), &mut count);
+        assert_eq!(count, 0);
+        assert!(scheduler.is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+use std::ops::{Mul, MulAssign};
+
+use super::vector::Vector;
+
+/// Two-dimensional affine transform stored as the upper two rows of 3x3 matrix.
+///
+/// The point `(x, y)` is mapped to `(a * x + b * y + c, d * x + e * y + f)`
+/// with the matrix `[[a, b, c], [d, e, f]]`.
+/// Product `first * second` applies `second` first, like the matrix product does.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2<T> {
+    matrix: [[T; 3]; 2],
+}
+
+impl<T> Transform2<T> {
+    /// Create new transform from the matrix rows.
+    pub const fn new(matrix: [[T; 3]; 2]) -> Self {
+        Self { matrix }
+    }
+
+    /// Get the matrix rows.
+    pub fn matrix(&self) -> &[[T; 3]; 2] {
+        &self.matrix
+    }
+}
+
+impl Transform2<f32> {
+    /// Create the transform leaving points in place.
+    pub const fn identity() -> Self {
+        Self::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]])
+    }
+
+    /// Create the transform moving points by `offset`.
+    pub fn translation(offset: Vector<f32>) -> Self {
+        Self::new([[1.0, 0.0, offset.x()], [0.0, 1.0, offset.y()]])
+    }
+
+    /// Create the transform rotating points around the origin by `angle` in radians.
+    /// Positive angle rotates from the positive `x` direction towards the positive `y` one.
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new([[cos, -sin, 0.0], [sin, cos, 0.0]])
+    }
+
+    /// Create the transform scaling points relative to the origin by `factors` along each axis.
+    pub fn scale(factors: Vector<f32>) -> Self {
+        Self::new([[factors.x(), 0.0, 0.0], [0.0, factors.y(), 0.0]])
+    }
+
+    /// Create the transform shifting `x` by `factors.x() * y` and `y` by `factors.y() * x`.
+    pub fn shear(factors: Vector<f32>) -> Self {
+        Self::new([[1.0, factors.x(), 0.0], [factors.y(), 1.0, 0.0]])
+    }
+
+    /// Create the transform scaling points by `scale` first and then rotating them by `angle` in radians.
+    pub fn from_angle_scale(angle: f32, scale: Vector<f32>) -> Self {
+        Self::rotation(angle) * Self::scale(scale)
+    }
+
+    /// Apply `other` transform after this one.
+    pub fn then(self, other: Self) -> Self {
+        other * self
+    }
+
+    /// Move points by `offset` after this transform.
+    pub fn translated(self, offset: Vector<f32>) -> Self {
+        self.then(Self::translation(offset))
+    }
+
+    /// Rotate points around the origin by `angle` in radians after this transform.
+    pub fn rotated(self, angle: f32) -> Self {
+        self.then(Self::rotation(angle))
+    }
+
+    /// Scale points relative to the origin by `factors` after this transform.
+    pub fn scaled(self, factors: Vector<f32>) -> Self {
+        self.then(Self::scale(factors))
+    }
+
+    /// Get the translation part of the transform.
+    pub fn offset(&self) -> Vector<f32> {
+        Vector::new(self.matrix[0][2], self.matrix[1][2])
+    }
+
+    /// Apply the transform to the `vector` ignoring the translation part.
+    pub fn apply_vector(&self, vector: Vector<f32>) -> Vector<f32> {
+        self.apply(vector) - self.offset()
+    }
+
+    /// Apply the transform to the `point`.
+    pub fn apply(&self, point: Vector<f32>) -> Vector<f32> {
+        let [[a, b, c], [d, e, f]] = self.matrix;
+        Vector::new(
+            a * point.x() + b * point.y() + c,
+            d * point.x() + e * point.y() + f,
+        )
+    }
+
+    /// Calculate the transform reverting this one.
+    /// Returns `None` if the transform collapses the plane and can not be reverted.
+    pub fn inverse(&self) -> Option<Self> {
+        let [[a, b, c], [d, e, f]] = self.matrix;
+        let determinant = a * e - b * d;
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+        let (a, b, d, e) = (
+            e / determinant,
+            -b / determinant,
+            -d / determinant,
+            a / determinant,
+        );
+        Some(Self::new([
+            [a, b, -(a * c + b * f)],
+            [d, e, -(d * c + e * f)],
+        ]))
+    }
+}
+
+impl Default for Transform2<f32> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul for Transform2<f32> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let [[a, b, c], [d, e, f]] = self.matrix;
+        let [[g, h, i], [j, k, l]] = rhs.matrix;
+        Self::new([
+            [a * g + b * j, a * h + b * k, a * i + b * l + c],
+            [d * g + e * j, d * h + e * k, d * i + e * l + f],
+        ])
+    }
+}
+
+impl MulAssign for Transform2<f32> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Vector<f32>> for Transform2<f32> {
+    type Output = Vector<f32>;
+
+    fn mul(self, rhs: Vector<f32>) -> Self::Output {
+        self.apply(rhs)
+    }
+}
@@ -0,0 +1,102 @@
+use super::vector::Vector;
+
+fn point<T: Copy + Into<f64>>(vertex: Vector<T>) -> (f64, f64) {
+    (vertex.x().into(), vertex.y().into())
+}
+
+fn cross(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+fn contains(triangle: [(f64, f64); 3], point: (f64, f64)) -> bool {
+    let [a, b, c] = triangle;
+    cross(a, b, point) >= 0.0 && cross(b, c, point) >= 0.0 && cross(c, a, point) >= 0.0
+}
+
+/// Triangulate simple (non-self-intersecting) polygon outline with the ear clipping method.
+/// Returns triangles as indices into the `outline` in its winding order.
+/// Collinear vertices may be left out of the result.
+/// Returns `None` if the outline has less than 3 vertices or no ear can be clipped.
+/// The result for self-intersecting outlines is unspecified.
+pub fn triangulate<T>(outline: &[Vector<T>]) -> Option<Vec<[usize; 3]>>
+where
+    T: Copy + Into<f64>,
+{
+    if outline.len() < 3 {
+        return None;
+    }
+    let points: Vec<(f64, f64)> = outline.iter().copied().map(point).collect();
+    let doubled_area: f64 = (0..points.len())
+        .map(|index| {
+            let (a, b) = (points[index], points[(index + 1) % points.len()]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum();
+    // Work in the counter-clockwise order, where convex corners have positive cross product.
+    let orientation = if doubled_area < 0.0 { -1.0 } else { 1.0 };
+    let turn = |a: usize, b: usize, c: usize| cross(points[a], points[b], points[c]) * orientation;
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let corner = |index: usize| {
+            (
+                remaining[(index + count - 1) % count],
+                remaining[index],
+                remaining[(index + 1) % count],
+            )
+        };
+        let ear = (0..count).find(|&index| {
+            let (a, b, c) = corner(index);
+            if turn(a, b, c) <= 0.0 {
+                return false;
+            }
+            let triangle = if orientation > 0.0 {
+                [points[a], points[b], points[c]]
+            } else {
+                [points[a], points[c], points[b]]
+            };
+            remaining
+                .iter()
+                .filter(|&&other| ![a, b, c].contains(&other))
+                .map(|&other| points[other])
+                .filter(|other| ![points[a], points[b], points[c]].contains(other))
+                .all(|other| !contains(triangle, other))
+        });
+        match ear {
+            Some(index) => {
+                let (a, b, c) = corner(index);
+                triangles.push([a, b, c]);
+                remaining.remove(index);
+            }
+            None => {
+                let degenerate = (0..count).find(|&index| {
+                    let (a, b, c) = corner(index);
+                    turn(a, b, c) == 0.0
+                })?;
+                remaining.remove(degenerate);
+            }
+        }
+    }
+    let (a, b, c) = (remaining[0], remaining[1], remaining[2]);
+    if turn(a, b, c) != 0.0 {
+        triangles.push([a, b, c]);
+    }
+    Some(triangles)
+}
+
+/// Triangulate simple polygon outline into triangles of its vertices.
+/// See [`triangulate`] for details.
+pub fn triangles<T>(outline: &[Vector<T>]) -> Option<Vec<[Vector<T>; 3]>>
+where
+    T: Copy + Into<f64>,
+{
+    let indices = triangulate(outline)?;
+    Some(
+        indices
+            .into_iter()
+            .map(|triangle| triangle.map(|index| outline[index]))
+            .collect(),
+    )
+}
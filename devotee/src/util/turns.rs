@@ -0,0 +1,166 @@
+/// Energy an actor needs to accumulate to take a turn unless configured otherwise.
+pub const DEFAULT_THRESHOLD: u32 = 100;
+
+/// Outcome of the actor turn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// The actor performed an action costing given energy.
+    Performed(u32),
+    /// The actor needs external input, e.g. the player has not chosen an action yet.
+    AwaitInput,
+}
+
+#[derive(Clone, Debug)]
+struct Actor<K> {
+    key: K,
+    speed: u32,
+    energy: u64,
+}
+
+/// Energy based turn scheduler.
+/// Each time unit actors gain energy equal to their speed and act once it reaches the threshold.
+#[derive(Clone, Debug)]
+pub struct TurnScheduler<K> {
+    actors: Vec<Actor<K>>,
+    threshold: u32,
+    time: u64,
+}
+
+impl<K> TurnScheduler<K>
+where
+    K: Clone + PartialEq,
+{
+    /// Create new empty scheduler with the default threshold.
+    pub fn new() -> Self {
+        Self {
+            actors: Vec::new(),
+            threshold: DEFAULT_THRESHOLD,
+            time: 0,
+        }
+    }
+
+    /// Set the energy needed to take a turn.
+    pub fn with_threshold(self, threshold: u32) -> Self {
+        let threshold = threshold.max(1);
+        Self { threshold, ..self }
+    }
+
+    /// Add new actor with given speed.
+    /// Actor with the same key is replaced.
+    pub fn add(&mut self, key: K, speed: u32) -> &mut Self {
+        self.remove(&key);
+        self.actors.push(Actor {
+            key,
+            speed,
+            energy: 0,
+        });
+        self
+    }
+
+    /// Remove the actor, returning `true` if it was scheduled.
+    pub fn remove(&mut self, key: &K) -> bool {
+        let length = self.actors.len();
+        self.actors.retain(|actor| actor.key != *key);
+        self.actors.len() != length
+    }
+
+    fn actor_mut(&mut self, key: &K) -> Option<&mut Actor<K>> {
+        self.actors.iter_mut().find(|actor| actor.key == *key)
+    }
+
+    /// Get speed of the actor.
+    pub fn speed(&self, key: &K) -> Option<u32> {
+        self.actors
+            .iter()
+            .find(|actor| actor.key == *key)
+            .map(|actor| actor.speed)
+    }
+
+    /// Set speed of the actor.
+    pub fn set_speed(&mut self, key: &K, speed: u32) -> &mut Self {
+        if let Some(actor) = self.actor_mut(key) {
+            actor.speed = speed;
+        }
+        self
+    }
+
+    /// Get the amount of scheduled actors.
+    pub fn len(&self) -> usize {
+        self.actors.len()
+    }
+
+    /// Check if there are no scheduled actors.
+    pub fn is_empty(&self) -> bool {
+        self.actors.is_empty()
+    }
+
+    /// Get the amount of time units passed.
+    pub fn time(&self) -> u64 {
+        self.time
+    }
+
+    /// Get the actor to act next, advancing the time if nobody is ready.
+    /// The actor with the most energy goes first, ties are resolved in the order actors were added.
+    /// Returns `None` if no actor can ever act.
+    pub fn next_actor(&mut self) -> Option<K> {
+        let threshold = self.threshold as u64;
+        let ready = |actor: &Actor<K>| actor.energy >= threshold;
+        if !self.actors.iter().any(ready) {
+            let wait = self
+                .actors
+                .iter()
+                .filter(|actor| actor.speed > 0)
+                .map(|actor| (threshold - actor.energy).div_ceil(actor.speed as u64))
+                .min()?;
+            for actor in self.actors.iter_mut() {
+                actor.energy = actor.energy.saturating_add(wait * actor.speed as u64);
+            }
+            self.time += wait;
+        }
+        let mut best: Option<&Actor<K>> = None;
+        for actor in self.actors.iter().filter(|actor| ready(actor)) {
+            if best.is_none_or(|best| actor.energy > best.energy) {
+                best = Some(actor);
+            }
+        }
+        best.map(|actor| actor.key.clone())
+    }
+
+    /// Spend the actor energy on the performed action.
+    pub fn spend(&mut self, key: &K, cost: u32) -> &mut Self {
+        if let Some(actor) = self.actor_mut(key) {
+            actor.energy = actor.energy.saturating_sub(cost as u64);
+        }
+        self
+    }
+
+    /// Let actors act in order until one of them awaits input.
+    /// Returns the actor awaiting input or `None` if no actor can ever act.
+    ///
+    /// Actions costing no energy are treated as costing the whole threshold to guarantee progress.
+    pub fn process<F>(&mut self, act: F) -> Option<K>
+    where
+        F: FnMut(&K) -> Action,
+    {
+        let mut act = act;
+        loop {
+            let key = self.next_actor()?;
+            match act(&key) {
+                Action::Performed(cost) => {
+                    let cost = if cost == 0 { self.threshold } else { cost };
+                    self.spend(&key, cost);
+                }
+                Action::AwaitInput => return Some(key),
+            }
+        }
+    }
+}
+
+impl<K> Default for TurnScheduler<K>
+where
+    K: Clone + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
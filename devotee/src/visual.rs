@@ -2,6 +2,7 @@ use std::ops::{Deref, DerefMut, RangeInclusive};
 
 use image::{DesignatorMut, DesignatorRef, Image, ImageMut, PixelMut, PixelRef};
 
+use crate::util::rect::Rect;
 use crate::util::vector::Vector;
 
 /// General image-related traits.
@@ -20,13 +21,90 @@ pub mod pixel;
 /// Subpixel-perfect operations implementation.
 pub mod subpixel;
 
+/// Frame sequences with timing.
+pub mod animation;
+
+/// Runtime packing of small images into a single canvas.
+pub mod atlas;
+
+/// Blending helpers with optional linear light support.
+pub mod blend;
+
+/// Recording painter operations into display lists.
+pub mod display_list;
+
+/// World-to-surface camera with zoom and viewport.
+pub mod camera;
+
+/// Capturing of the screen and other windows pixels on X11.
+#[cfg(all(
+    feature = "x11-capture",
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android")),
+    not(target_arch = "wasm32")
+))]
+pub mod capture;
+
+/// RGBA color type with HSV conversion and color converters.
+pub mod color;
+
+/// Cached layer composition with dirty regions tracking.
+pub mod compositor;
+
+/// Canvas with tracking of the regions changed between presentations.
+pub mod damage;
+
+/// Ordered and blue-noise dithering paint functions.
+pub mod dither;
+
+/// Screen shake and displacement effects.
+pub mod effects;
+
+/// Bitmap fonts and styled text layout.
+pub mod font;
+
+/// Importing of encoded images.
+#[cfg(any(feature = "gif-import", feature = "png-import"))]
+pub mod import;
+
+/// Scalable images with fixed borders for UI panels.
+pub mod nine_slice;
+
+/// Canvas of palette indices with the palette stored alongside.
+pub mod indexed;
+
+/// Indexed color palette converter.
+pub mod palette;
+
+/// Converter adapters with per-frame mutable state.
+pub mod adapter;
+
+/// Nearest neighbor scaling of images.
+pub mod scale;
+
+/// Signed distance field shape and text rendering.
+pub mod sdf;
+
+/// Caching of rasterized text.
+pub mod text_cache;
+
+/// Full-screen transition effects.
+pub mod transition;
+
+/// Layered tile grids with parallax scrolling.
+pub mod tilemap;
+
+mod stroke;
 mod util;
 
 /// Collection of drawing traits and functions in a single prelude.
 pub mod prelude {
-    pub use super::image::{Image, ImageMut};
+    pub use super::image::{Image, ImageMut, OwnedImage};
     pub use super::view::View;
+    pub use super::Anchor;
+    pub use super::JoinStyle;
     pub use super::Paint;
+    pub use super::Sampling;
     pub use super::{paint, printer, stamp};
     pub use super::{PaintTarget, Painter};
 }
@@ -69,6 +147,72 @@ pub fn stamp<P>() -> impl FnMut(i32, i32, P, i32, i32, P) -> P {
     move |_, _, _original, _, _, other| other
 }
 
+/// Anchor point of the drawn image relative to its dimensions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Anchor {
+    /// Top left corner.
+    #[default]
+    TopLeft,
+    /// Center of the top edge.
+    TopCenter,
+    /// Top right corner.
+    TopRight,
+    /// Center of the left edge.
+    CenterLeft,
+    /// Center of the image.
+    Center,
+    /// Center of the right edge.
+    CenterRight,
+    /// Bottom left corner.
+    BottomLeft,
+    /// Center of the bottom edge.
+    BottomCenter,
+    /// Bottom right corner.
+    BottomRight,
+    /// Custom offset from the top left corner.
+    Custom(Vector<i32>),
+}
+
+impl Anchor {
+    /// Get offset of the anchor point from the top left corner of the image with given dimensions.
+    pub fn offset(self, dimensions: Vector<i32>) -> Vector<i32> {
+        let (width, height) = (dimensions.x(), dimensions.y());
+        match self {
+            Anchor::TopLeft => Vector::new(0, 0),
+            Anchor::TopCenter => Vector::new(width / 2, 0),
+            Anchor::TopRight => Vector::new(width, 0),
+            Anchor::CenterLeft => Vector::new(0, height / 2),
+            Anchor::Center => Vector::new(width / 2, height / 2),
+            Anchor::CenterRight => Vector::new(width, height / 2),
+            Anchor::BottomLeft => Vector::new(0, height),
+            Anchor::BottomCenter => Vector::new(width / 2, height),
+            Anchor::BottomRight => Vector::new(width, height),
+            Anchor::Custom(offset) => offset,
+        }
+    }
+}
+
+/// Shape of the joint between consecutive segments of the thick polyline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JoinStyle {
+    /// Outer corners are cut off with a straight edge.
+    Bevel,
+    /// Outer edges are extended to the sharp corner.
+    /// Too sharp corners fall back to bevel.
+    #[default]
+    Miter,
+    /// Outer corners are rounded.
+    Round,
+}
+
+/// Way of picking source pixels when drawing transformed images.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Sampling {
+    /// Source pixel nearest to the sampled position.
+    #[default]
+    Nearest,
+}
+
 #[derive(Clone, Copy, Debug)]
 enum Scan<T> {
     None,
@@ -119,6 +263,17 @@ where
     }
 }
 
+impl Scan<i32> {
+    fn clamped(self, low: i32, high: i32) -> Self {
+        match self {
+            Scan::Single(a) if a < low || a > high => Scan::None,
+            Scan::Inclusive(a, b) if a.max(b) < low || a.min(b) > high => Scan::None,
+            Scan::Inclusive(a, b) => Scan::Inclusive(a.clamp(low, high), b.clamp(low, high)),
+            scan => scan,
+        }
+    }
+}
+
 impl IntoIterator for Scan<i32> {
     type Item = i32;
     type IntoIter = ScanIterator<i32>;
@@ -169,7 +324,7 @@ impl Iterator for ScanIterator<i32> {
                     self.exhausted = true;
                 }
                 Scan::Inclusive(a, b) => {
-                    self.current += (b - a).signum();
+                    self.current += b.cmp(&a) as i32;
                     self.exhausted = result == b;
                 }
             }
@@ -181,13 +336,13 @@ impl Iterator for ScanIterator<i32> {
 /// Something that can be painted on.
 pub trait PaintTarget<T> {
     /// Get painter for painting.
-    fn painter<C>(&mut self) -> Painter<T, C>
+    fn painter<C>(&mut self) -> Painter<'_, T, C>
     where
         C: Clone + Default;
 }
 
 impl<T> PaintTarget<T> for T {
-    fn painter<C>(&mut self) -> Painter<T, C>
+    fn painter<C>(&mut self) -> Painter<'_, T, C>
     where
         C: Clone + Default,
     {
@@ -199,6 +354,7 @@ impl<T> PaintTarget<T> for T {
 pub struct Painter<'image, I, C> {
     target: &'image mut I,
     offset: Vector<C>,
+    wrap: bool,
 }
 
 impl<'image, I, C> Painter<'image, I, C>
@@ -209,9 +365,28 @@ where
         Self {
             target,
             offset: Default::default(),
+            wrap: false,
         }
     }
 
+    /// Get new painter with wrap-around mode enabled or disabled.
+    /// In wrap-around mode drawing exiting the target edge continues at the opposite edge.
+    /// Parts of the geometry more than one target size away from the target are clipped.
+    pub fn with_wrap(self, wrap: bool) -> Self {
+        Self { wrap, ..self }
+    }
+
+    /// Enable or disable wrap-around mode for this particular painter.
+    pub fn set_wrap(&mut self, wrap: bool) -> &mut Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Check if the painter wraps drawing around the target edges.
+    pub fn wraps(&self) -> bool {
+        self.wrap
+    }
+
     /// Get new painter with desired offset.
     pub fn with_offset(self, offset: Vector<C>) -> Self {
         Self { offset, ..self }
@@ -254,6 +429,29 @@ where
         ImageMut::clear(self.target, clear_color)
     }
 
+    /// Get the area primitives are clipped to as top left and bottom right exclusive corners.
+    fn bounds(&self) -> (Vector<i32>, Vector<i32>) {
+        let (width, height) = (Image::width(self.target), Image::height(self.target));
+        if self.wrap {
+            (
+                Vector::new(width.saturating_neg(), height.saturating_neg()),
+                Vector::new(width.saturating_mul(2), height.saturating_mul(2)),
+            )
+        } else {
+            (Vector::new(0, 0), Vector::new(width, height))
+        }
+    }
+
+    /// Map the point into the target in wrap-around mode.
+    fn wrapped(&self, point: Vector<i32>) -> Vector<i32> {
+        let (width, height) = (Image::width(self.target), Image::height(self.target));
+        if self.wrap && width > 0 && height > 0 {
+            Vector::new(point.x().rem_euclid(width), point.y().rem_euclid(height))
+        } else {
+            point
+        }
+    }
+
     fn map_on_pixel_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
         &mut self,
         point: Vector<i32>,
@@ -261,6 +459,7 @@ where
     ) where
         for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
     {
+        let point = self.wrapped(point);
         if let Some(mut pixel) = self.target.pixel_mut(point) {
             *pixel = function(point.x(), point.y(), pixel.clone());
         }
@@ -276,12 +475,24 @@ where
     ) where
         for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
     {
-        if x < 0 || x >= self.target.width() {
+        let (low, high) = self.bounds();
+        if x < low.x() || x >= high.x() || high.y() <= low.y() {
             return;
         }
 
-        let mut iter = from_y..=to_y;
-        let mut iter_rev = (to_y..=from_y).rev();
+        // Pixels to skip are counted from `from_y`, which is invisible when clamped.
+        let skip = if (low.y()..high.y()).contains(&from_y) {
+            skip
+        } else {
+            0
+        };
+        let (from_y, to_y) = (
+            from_y.clamp(low.y() - 1, high.y()),
+            to_y.clamp(low.y() - 1, high.y()),
+        );
+
+        let mut iter = from_y.max(low.y())..=to_y.min(high.y() - 1);
+        let mut iter_rev = (to_y.max(low.y())..=from_y.min(high.y() - 1)).rev();
 
         let iter_ref: &mut dyn Iterator<Item = i32> = if from_y < to_y {
             &mut iter
@@ -303,6 +514,45 @@ where
         function: &mut F,
     ) where
         for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+    {
+        if !self.wrap {
+            self.map_fast_horizontal_span_raw(from_x, to_x, y, function);
+            return;
+        }
+
+        let width = Image::width(self.target);
+        let (low, high) = self.bounds();
+        if width <= 0 || y < low.y() || y >= high.y() {
+            return;
+        }
+        let y = self.wrapped(Vector::new(0, y)).y();
+        let from = from_x.min(to_x).max(low.x());
+        let to = from_x.max(to_x).min(high.x() - 1);
+        // Each pixel of the wrapped span is visited once even if the span is wider than the target.
+        let to = to.min(from.saturating_add(width - 1));
+
+        let mut start = from;
+        while start <= to {
+            let wrapped_start = start.rem_euclid(width);
+            let end = to.min(start + (width - 1 - wrapped_start));
+            self.map_fast_horizontal_span_raw(
+                wrapped_start,
+                wrapped_start + (end - start),
+                y,
+                function,
+            );
+            start = end + 1;
+        }
+    }
+
+    fn map_fast_horizontal_span_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        from_x: i32,
+        to_x: i32,
+        y: i32,
+        function: &mut F,
+    ) where
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
     {
         if self
             .target
@@ -314,6 +564,34 @@ where
         }
     }
 
+    /// Map pixels of the horizontal line within the circle sector from `start` to `end` angle around `center`.
+    fn map_sector_horizontal_line_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        from_x: i32,
+        to_x: i32,
+        y: i32,
+        (center, start, end): (Vector<f32>, f32, f32),
+        function: &mut F,
+    ) where
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+    {
+        let (low, high) = self.bounds();
+        if y < low.y() || y >= high.y() {
+            return;
+        }
+        let from = from_x.min(to_x).max(low.x());
+        let mut to = from_x.max(to_x).min(high.x() - 1);
+        if self.wrap {
+            to = to.min(from.saturating_add(Image::width(self.target) - 1));
+        }
+        let delta_y = y as f32 - center.y();
+        for x in from..=to {
+            if util::in_sector((x as f32 - center.x(), delta_y), start, end) {
+                self.map_on_pixel_raw(Vector::new(x, y), function);
+            }
+        }
+    }
+
     fn map_horizontal_line_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
         &mut self,
         from_x: i32,
@@ -324,12 +602,24 @@ where
     ) where
         for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
     {
-        if y < 0 || y >= self.target.height() {
+        let (low, high) = self.bounds();
+        if y < low.y() || y >= high.y() || high.x() <= low.x() {
             return;
         }
 
-        let mut iter = from_x..=to_x;
-        let mut iter_rev = (to_x..=from_x).rev();
+        // Pixels to skip are counted from `from_x`, which is invisible when clamped.
+        let skip = if (low.x()..high.x()).contains(&from_x) {
+            skip
+        } else {
+            0
+        };
+        let (from_x, to_x) = (
+            from_x.clamp(low.x() - 1, high.x()),
+            to_x.clamp(low.x() - 1, high.x()),
+        );
+
+        let mut iter = from_x.max(low.x())..=to_x.min(high.x() - 1);
+        let mut iter_rev = (to_x.max(low.x())..=from_x.min(high.x() - 1)).rev();
 
         let iter_ref: &mut dyn Iterator<Item = i32> = if from_x < to_x {
             &mut iter
@@ -352,17 +642,23 @@ where
         for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
         for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
     {
-        let start_x = from.x().max(0);
-        let start_y = from.y().max(0);
-        let end_x = (to.x()).min(self.target.width());
-        let end_y = (to.y()).min(self.target.height());
+        let (low, high) = self.bounds();
+        let start_x = from.x().max(low.x());
+        let start_y = from.y().max(low.y());
+        let mut end_x = (to.x()).min(high.x());
+        let mut end_y = (to.y()).min(high.y());
+        if self.wrap {
+            end_x = end_x.min(start_x.saturating_add(self.target.width()));
+            end_y = end_y.min(start_y.saturating_add(self.target.height()));
+        }
 
         for x in start_x..end_x {
             for y in start_y..end_y {
-                let step = (x, y).into();
+                let step = self.wrapped((x, y).into());
                 // SAFETY: we believe that start and end values are in proper bounds.
                 unsafe {
-                    let pixel = function(x, y, self.target.unsafe_pixel(step).clone());
+                    let pixel =
+                        function(step.x(), step.y(), self.target.unsafe_pixel(step).clone());
                     *self.target.unsafe_pixel_mut(step) = pixel;
                 }
             }
@@ -371,6 +667,9 @@ where
 }
 
 /// Painter trait to generalize pixel-perfect and subpixel paint routines.
+///
+/// Primitives accept any input without panicking: the parts outside of the target are clipped,
+/// coordinates close to the integer limits saturate instead of overflowing.
 pub trait Paint<T, C>
 where
     T: ImageMut,
@@ -392,15 +691,17 @@ where
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
 
     /// Use passed function on each pixel in filled rectangle.
-    /// The `dimensions` determine size of the rectangle, zero or negative value produces no rectangle.
-    fn rect_f<F>(&mut self, from: Vector<C>, dimensions: Vector<C>, function: F)
+    /// Zero or negative dimensions of the `rect` produce no rectangle.
+    fn rect_f<R, F>(&mut self, rect: R, function: F)
     where
+        R: Into<Rect<C>>,
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
 
     /// Use passed function on each pixel of rectangle bounds.
-    /// The `dimensions` determine size of the rectangle, zero or negative value produces no rectangle.
-    fn rect_b<F>(&mut self, from: Vector<C>, dimensions: Vector<C>, function: F)
+    /// Zero or negative dimensions of the `rect` produce no rectangle.
+    fn rect_b<R, F>(&mut self, rect: R, function: F)
     where
+        R: Into<Rect<C>>,
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
 
     /// Use passed function on each pixel in triangle.
@@ -424,14 +725,55 @@ where
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
 
     /// Use passed function on each pixel in circle.
+    /// Negative radius produces no circle.
     fn circle_f<F>(&mut self, center: Vector<C>, radius: C, function: F)
     where
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
 
     /// Use passed function on each pixel of circle bounds.
+    /// Negative radius produces no circle.
     fn circle_b<F>(&mut self, center: Vector<C>, radius: C, function: F)
     where
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
+
+    /// Use passed function on each pixel of line with given `width` and flat ends.
+    /// Width below one is treated as one.
+    fn line_thick<F>(&mut self, from: Vector<C>, to: Vector<C>, width: C, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
+
+    /// Use passed function on each pixel of line segments connecting `points` with given `width`.
+    /// Segments are connected according to the `join` style, each pixel is processed once.
+    /// Width below one is treated as one.
+    fn polyline<F>(&mut self, points: &[Vector<C>], width: C, join: JoinStyle, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
+
+    /// Use passed function on each pixel in axis-aligned ellipse with horizontal and vertical `radii`.
+    /// Negative radius produces no ellipse.
+    fn ellipse_f<F>(&mut self, center: Vector<C>, radii: Vector<C>, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
+
+    /// Use passed function on each pixel of axis-aligned ellipse bounds with horizontal and vertical `radii`.
+    /// Negative radius produces no ellipse.
+    fn ellipse_b<F>(&mut self, center: Vector<C>, radii: Vector<C>, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
+
+    /// Use passed function on each pixel of circle arc swept from `start` to `end` angle.
+    /// Angles are in radians, growing clockwise from the positive `x` direction.
+    /// Negative radius produces no arc.
+    fn arc_b<F>(&mut self, center: Vector<C>, radius: C, start: f32, end: f32, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
+
+    /// Use passed function on each pixel in circle sector swept from `start` to `end` angle.
+    /// Angles are in radians, growing clockwise from the positive `x` direction.
+    /// Negative radius produces no sector.
+    fn sector_f<F>(&mut self, center: Vector<C>, radius: C, start: f32, end: f32, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel;
 }
 
 /// A helper utility for writing horizontal lines faster.
@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use backend::Converter;
+
+use crate::util::invalidation::{Invalidator, Subscription};
+
+/// Converter adapter looking up the texel of the inner converter by index in the mutable table.
+/// Rotating and swapping the table entries each frame produces palette cycling effects
+/// without touching the render surface.
+/// Any table mutation emits invalidation signal to the subscribed caches.
+#[derive(Clone, Debug)]
+pub struct PalettedConverter<C>
+where
+    C: Converter,
+{
+    inner: C,
+    table: Vec<C::Data>,
+    original: Vec<C::Data>,
+    fallback: u32,
+    invalidator: Invalidator,
+}
+
+impl<C> PalettedConverter<C>
+where
+    C: Converter,
+    C::Data: Clone,
+{
+    /// Create new paletted converter with the `inner` converter and the index to texel `table`.
+    pub fn new(inner: C, table: Vec<C::Data>) -> Self {
+        Self {
+            inner,
+            original: table.clone(),
+            table,
+            fallback: 0xff_00_00_00,
+            invalidator: Invalidator::new(),
+        }
+    }
+
+    /// Set color used for indices outside of the table.
+    pub fn with_fallback(self, fallback: u32) -> Self {
+        Self { fallback, ..self }
+    }
+
+    /// Get reference to the inner converter.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Get current table entries.
+    pub fn table(&self) -> &[C::Data] {
+        &self.table
+    }
+
+    /// Get texel by its index.
+    pub fn texel(&self, index: usize) -> Option<&C::Data> {
+        self.table.get(index)
+    }
+
+    /// Remap single index of the table to the `texel`.
+    pub fn set_texel(&mut self, index: usize, texel: C::Data) {
+        if let Some(entry) = self.table.get_mut(index) {
+            *entry = texel;
+            self.invalidator.invalidate();
+        }
+    }
+
+    /// Swap two entries of the table.
+    /// Does nothing if any of the indices is outside of the table.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if a != b && a < self.table.len() && b < self.table.len() {
+            self.table.swap(a, b);
+            self.invalidator.invalidate();
+        }
+    }
+
+    /// Rotate entries in the `range` of the table by `steps` towards higher indices.
+    /// Negative `steps` rotate towards lower indices.
+    /// The range is clipped to the table.
+    pub fn rotate(&mut self, range: Range<usize>, steps: isize) {
+        let end = range.end.min(self.table.len());
+        let Some(slice) = self.table.get_mut(range.start..end) else {
+            return;
+        };
+        if slice.len() < 2 {
+            return;
+        }
+        let shift = steps.rem_euclid(slice.len() as isize) as usize;
+        if shift != 0 {
+            slice.rotate_right(shift);
+            self.invalidator.invalidate();
+        }
+    }
+
+    /// Restore the table entries passed on creation.
+    pub fn reset(&mut self) {
+        self.table.clone_from(&self.original);
+        self.invalidator.invalidate();
+    }
+
+    /// Subscribe to the table changes.
+    pub fn subscribe(&self) -> Subscription {
+        self.invalidator.subscribe()
+    }
+}
+
+impl<C> Converter for PalettedConverter<C>
+where
+    C: Converter,
+    C::Data: Clone,
+{
+    type Data = u8;
+
+    fn convert(&self, x: usize, y: usize, data: Self::Data) -> u32 {
+        self.table
+            .get(data as usize)
+            .map_or(self.fallback, |texel| {
+                self.inner.convert(x, y, texel.clone())
+            })
+    }
+}
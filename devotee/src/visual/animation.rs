@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use super::canvas::Canvas;
+
+/// Animation repetition mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Looping {
+    /// Repeat forever.
+    #[default]
+    Infinite,
+    /// Play given amount of times and stop on the last frame.
+    Finite(u32),
+}
+
+/// Sequence of frames with individual delays.
+#[derive(Clone, Debug)]
+pub struct Animation<P> {
+    frames: Vec<(Canvas<P>, Duration)>,
+    looping: Looping,
+}
+
+impl<P> Animation<P> {
+    /// Create new infinitely looping animation from frames and their delays.
+    pub fn new(frames: Vec<(Canvas<P>, Duration)>) -> Self {
+        Self {
+            frames,
+            looping: Looping::Infinite,
+        }
+    }
+
+    /// Set the animation repetition mode.
+    pub fn with_looping(self, looping: Looping) -> Self {
+        Self { looping, ..self }
+    }
+
+    /// Get the animation repetition mode.
+    pub fn looping(&self) -> Looping {
+        self.looping
+    }
+
+    /// Get frames with their delays.
+    pub fn frames(&self) -> &[(Canvas<P>, Duration)] {
+        &self.frames
+    }
+
+    /// Get the amount of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Check if there are no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Get duration of a single animation cycle.
+    pub fn duration(&self) -> Duration {
+        self.frames.iter().map(|(_, delay)| *delay).sum()
+    }
+
+    /// Get index of the frame shown after `elapsed` time since the animation start.
+    pub fn frame_index_at(&self, elapsed: Duration) -> Option<usize> {
+        let last = self.frames.len().checked_sub(1)?;
+        let cycle = self.duration();
+        if cycle.is_zero() {
+            return Some(last);
+        }
+        let cycles = elapsed.as_nanos() / cycle.as_nanos();
+        if let Looping::Finite(count) = self.looping {
+            if cycles >= count as u128 {
+                return Some(last);
+            }
+        }
+        let mut remaining = Duration::from_nanos((elapsed.as_nanos() % cycle.as_nanos()) as u64);
+        for (index, (_, delay)) in self.frames.iter().enumerate() {
+            if remaining < *delay {
+                return Some(index);
+            }
+            remaining -= *delay;
+        }
+        Some(last)
+    }
+
+    /// Get the frame shown after `elapsed` time since the animation start.
+    pub fn frame_at(&self, elapsed: Duration) -> Option<&Canvas<P>> {
+        self.frame_index_at(elapsed)
+            .map(|index| &self.frames[index].0)
+    }
+}
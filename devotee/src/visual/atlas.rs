@@ -0,0 +1,309 @@
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use crate::util::vector::Vector;
+
+use super::canvas::Canvas;
+use super::image::DesignatorRef;
+use super::view::View;
+use super::{Image, ImageMut};
+
+#[derive(Clone, Copy, Debug)]
+struct Segment {
+    x: i32,
+    y: i32,
+    width: i32,
+}
+
+/// Skyline rectangle packer.
+/// Rectangles are placed at the lowest available position, left to right on ties.
+#[derive(Clone, Debug)]
+pub struct Packer {
+    width: i32,
+    height: i32,
+    skyline: Vec<Segment>,
+}
+
+impl Packer {
+    /// Create new empty packer of given dimensions.
+    pub fn new(width: i32, height: i32) -> Self {
+        let width = width.max(0);
+        let height = height.max(0);
+        let skyline = vec![Segment { x: 0, y: 0, width }];
+        Self {
+            width,
+            height,
+            skyline,
+        }
+    }
+
+    /// Get width of the packing area.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Get height of the packing area.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn fit(&self, index: usize, width: i32, height: i32) -> Option<i32> {
+        let x = self.skyline[index].x;
+        if x + width > self.width {
+            return None;
+        }
+        let mut remaining = width;
+        let mut y = 0;
+        for segment in &self.skyline[index..] {
+            if remaining <= 0 {
+                break;
+            }
+            y = y.max(segment.y);
+            remaining -= segment.width;
+        }
+        (y + height <= self.height).then_some(y)
+    }
+
+    /// Reserve space for the rectangle of given dimensions.
+    /// Returns top left corner of the reserved space or `None` if there is not enough space left.
+    pub fn insert(&mut self, dimensions: Vector<i32>) -> Option<Vector<i32>> {
+        let (width, height) = (dimensions.x(), dimensions.y());
+        if width <= 0 || height <= 0 {
+            return (width >= 0 && height >= 0).then_some(Vector::new(0, 0));
+        }
+        let (index, y) = (0..self.skyline.len())
+            .filter_map(|index| Some((index, self.fit(index, width, height)?)))
+            .min_by_key(|(index, y)| (*y, self.skyline[*index].x))?;
+
+        let x = self.skyline[index].x;
+        let placed = Segment {
+            x,
+            y: y + height,
+            width,
+        };
+        let end = x + width;
+        let mut rest: Vec<Segment> = self.skyline[index..]
+            .iter()
+            .filter(|segment| segment.x + segment.width > end)
+            .map(|segment| {
+                let start = segment.x.max(end);
+                Segment {
+                    x: start,
+                    y: segment.y,
+                    width: segment.x + segment.width - start,
+                }
+            })
+            .collect();
+        self.skyline.truncate(index);
+        self.skyline.push(placed);
+        self.skyline.append(&mut rest);
+        self.skyline.dedup_by(|next, previous| {
+            if next.y == previous.y {
+                previous.width += next.width;
+                true
+            } else {
+                false
+            }
+        });
+        Some(Vector::new(x, y))
+    }
+}
+
+/// Identifier of the image region in an atlas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasId(usize);
+
+impl AtlasId {
+    /// Get index of the region in order of addition.
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// Single canvas containing many small images packed together, e.g. a sprite sheet.
+/// Regions are identified by index and optionally by name.
+#[derive(Clone, Debug)]
+pub struct Atlas<P> {
+    canvas: Canvas<P>,
+    packer: Option<Packer>,
+    padding: i32,
+    regions: Vec<(Vector<i32>, Vector<i32>)>,
+    names: BTreeMap<String, AtlasId>,
+}
+
+impl<P> Atlas<P>
+where
+    P: Clone,
+{
+    /// Create new empty atlas with given resolution filled with `background` color.
+    pub fn with_resolution(background: P, width: usize, height: usize) -> Self {
+        let canvas = Canvas::with_resolution(background, width, height);
+        let packer = Some(Packer::new(width as i32, height as i32));
+        Self {
+            canvas,
+            packer,
+            padding: 0,
+            regions: Vec::new(),
+            names: BTreeMap::new(),
+        }
+    }
+
+    /// Create atlas over the existing sprite sheet `canvas` with no regions defined.
+    /// Images can not be inserted into such atlas, only regions added.
+    pub fn from_canvas(canvas: Canvas<P>) -> Self {
+        Self {
+            canvas,
+            packer: None,
+            padding: 0,
+            regions: Vec::new(),
+            names: BTreeMap::new(),
+        }
+    }
+
+    /// Create atlas over the sprite sheet `canvas` split into grid of `cell` sized regions.
+    /// Regions are indexed row by row; incomplete cells at the right and bottom edges are skipped.
+    pub fn from_grid(canvas: Canvas<P>, cell: Vector<i32>) -> Self {
+        let mut atlas = Self::from_canvas(canvas);
+        if cell.x() > 0 && cell.y() > 0 {
+            let (columns, rows) = (
+                atlas.canvas.width() / cell.x(),
+                atlas.canvas.height() / cell.y(),
+            );
+            for row in 0..rows {
+                for column in 0..columns {
+                    let origin = Vector::new(column * cell.x(), row * cell.y());
+                    atlas.regions.push((origin, cell));
+                }
+            }
+        }
+        atlas
+    }
+
+    /// Set padding between packed images.
+    pub fn with_padding(self, padding: i32) -> Self {
+        let padding = padding.max(0);
+        Self { padding, ..self }
+    }
+
+    /// Copy the `image` into the atlas.
+    /// Returns `None` if there is not enough space left.
+    pub fn insert<U>(&mut self, image: &U) -> Option<AtlasId>
+    where
+        U: Image<Pixel = P> + ?Sized,
+        for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = P>,
+    {
+        let dimensions = image.dimensions();
+        let origin = self
+            .packer
+            .as_mut()?
+            .insert(dimensions + Vector::new(self.padding, self.padding))?;
+        for y in 0..dimensions.y() {
+            for x in 0..dimensions.x() {
+                let position = Vector::new(x, y);
+                // SAFETY: the position lies within the image and the packed region.
+                unsafe {
+                    *self.canvas.unsafe_pixel_mut(origin + position) =
+                        image.unsafe_pixel(position).clone();
+                }
+            }
+        }
+        self.regions.push((origin, dimensions));
+        Some(AtlasId(self.regions.len() - 1))
+    }
+
+    /// Copy the `image` into the atlas and name its region.
+    /// Returns `None` if there is not enough space left.
+    pub fn insert_named<U, S>(&mut self, name: S, image: &U) -> Option<AtlasId>
+    where
+        U: Image<Pixel = P> + ?Sized,
+        for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = P>,
+        S: Into<String>,
+    {
+        let id = self.insert(image)?;
+        self.names.insert(name.into(), id);
+        Some(id)
+    }
+
+    /// Define region of the atlas canvas with given `origin` and `dimensions`.
+    /// Returns `None` if the region does not fit into the canvas.
+    pub fn add_region(&mut self, origin: Vector<i32>, dimensions: Vector<i32>) -> Option<AtlasId> {
+        let end = origin + dimensions;
+        let fits = origin.x() >= 0
+            && origin.y() >= 0
+            && dimensions.x() >= 0
+            && dimensions.y() >= 0
+            && end.x() <= self.canvas.width()
+            && end.y() <= self.canvas.height();
+        if !fits {
+            return None;
+        }
+        self.regions.push((origin, dimensions));
+        Some(AtlasId(self.regions.len() - 1))
+    }
+
+    /// Assign the `name` to the region, replacing region previously named so.
+    /// Returns `false` if there is no such region.
+    pub fn set_name<S: Into<String>>(&mut self, id: AtlasId, name: S) -> bool {
+        if id.0 >= self.regions.len() {
+            return false;
+        }
+        self.names.insert(name.into(), id);
+        true
+    }
+
+    /// Find region by its `name`.
+    pub fn find(&self, name: &str) -> Option<AtlasId> {
+        self.names.get(name).copied()
+    }
+
+    /// Get region by its `index` in order of addition.
+    pub fn nth(&self, index: usize) -> Option<AtlasId> {
+        (index < self.regions.len()).then_some(AtlasId(index))
+    }
+
+    /// Get origin and dimensions of the region.
+    pub fn region(&self, id: AtlasId) -> Option<(Vector<i32>, Vector<i32>)> {
+        self.regions.get(id.0).copied()
+    }
+
+    /// Get view into the region.
+    pub fn view(&self, id: AtlasId) -> Option<View<&Canvas<P>>> {
+        let (origin, dimensions) = self.region(id)?;
+        Some(self.canvas.view(origin, dimensions))
+    }
+
+    /// Get view into the region by its `name`.
+    pub fn view_named(&self, name: &str) -> Option<View<&Canvas<P>>> {
+        self.view(self.find(name)?)
+    }
+
+    /// Iterate over all the regions in order of addition.
+    pub fn iter(&self) -> impl Iterator<Item = (AtlasId, View<&Canvas<P>>)> {
+        self.regions
+            .iter()
+            .enumerate()
+            .map(|(index, (origin, dimensions))| {
+                (AtlasId(index), self.canvas.view(*origin, *dimensions))
+            })
+    }
+
+    /// Iterate over the named regions in order of their names.
+    pub fn names(&self) -> impl Iterator<Item = (&str, AtlasId)> {
+        self.names.iter().map(|(name, id)| (name.as_str(), *id))
+    }
+
+    /// Get the amount of regions.
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Check if there are no regions.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Get reference to the underlying canvas.
+    pub fn canvas(&self) -> &Canvas<P> {
+        &self.canvas
+    }
+}
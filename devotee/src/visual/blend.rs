@@ -0,0 +1,240 @@
+use std::sync::OnceLock;
+
+use backend::Converter;
+
+const ENCODE_STEPS: usize = 4096;
+
+fn decode_table() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0.0; 256];
+        for (value, entry) in table.iter_mut().enumerate() {
+            let value = value as f32 / 255.0;
+            *entry = if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            };
+        }
+        table
+    })
+}
+
+fn encode_table() -> &'static [u8; ENCODE_STEPS] {
+    static TABLE: OnceLock<[u8; ENCODE_STEPS]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0; ENCODE_STEPS];
+        for (value, entry) in table.iter_mut().enumerate() {
+            let value = value as f32 / (ENCODE_STEPS - 1) as f32;
+            let encoded = if value <= 0.0031308 {
+                value * 12.92
+            } else {
+                1.055 * value.powf(1.0 / 2.4) - 0.055
+            };
+            *entry = (encoded * 255.0).round() as u8;
+        }
+        table
+    })
+}
+
+/// Convert sRGB-encoded channel value into linear light value in `[0.0, 1.0]` range.
+pub fn srgb_to_linear(value: u8) -> f32 {
+    decode_table()[value as usize]
+}
+
+/// Convert linear light value into sRGB-encoded channel value.
+/// Values outside of `[0.0, 1.0]` range are clamped.
+pub fn linear_to_srgb(value: f32) -> u8 {
+    let index = (value.clamp(0.0, 1.0) * (ENCODE_STEPS - 1) as f32).round() as usize;
+    encode_table()[index]
+}
+
+/// Color space to perform blending in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendSpace {
+    /// Blend raw sRGB-encoded values.
+    #[default]
+    Srgb,
+    /// Convert values into linear light, blend and convert back.
+    Linear,
+}
+
+impl BlendSpace {
+    fn decode(self, value: u8) -> f32 {
+        match self {
+            BlendSpace::Srgb => value as f32 / 255.0,
+            BlendSpace::Linear => srgb_to_linear(value),
+        }
+    }
+
+    fn encode(self, value: f32) -> u8 {
+        match self {
+            BlendSpace::Srgb => (value.clamp(0.0, 1.0) * 255.0).round() as u8,
+            BlendSpace::Linear => linear_to_srgb(value),
+        }
+    }
+
+    /// Combine color channels of `0xaa_rr_gg_bb` values in this space.
+    /// The alpha channel of `destination` is preserved.
+    pub fn combine<F>(self, destination: u32, source: u32, function: F) -> u32
+    where
+        F: Fn(f32, f32) -> f32,
+    {
+        let mut result = destination & 0xff_00_00_00;
+        for shift in [16, 8, 0] {
+            let destination = self.decode((destination >> shift) as u8);
+            let source = self.decode((source >> shift) as u8);
+            result |= (self.encode(function(destination, source)) as u32) << shift;
+        }
+        result
+    }
+}
+
+/// Pixel values with color and alpha channels which can be blended.
+pub trait BlendPixel: Copy {
+    /// Get `0xaa_rr_gg_bb` representation of the pixel.
+    fn to_argb(self) -> u32;
+
+    /// Create pixel from its `0xaa_rr_gg_bb` representation.
+    fn from_argb(argb: u32) -> Self;
+}
+
+impl BlendPixel for u32 {
+    fn to_argb(self) -> u32 {
+        self
+    }
+
+    fn from_argb(argb: u32) -> Self {
+        argb
+    }
+}
+
+/// `[r, g, b, a]` texels.
+impl BlendPixel for [u8; 4] {
+    fn to_argb(self) -> u32 {
+        let [r, g, b, a] = self;
+        u32::from_be_bytes([a, r, g, b])
+    }
+
+    fn from_argb(argb: u32) -> Self {
+        let [a, r, g, b] = argb.to_be_bytes();
+        [r, g, b, a]
+    }
+}
+
+/// Standard way of combining the source color with the destination pixel.
+/// The effect of the source color is scaled by its alpha channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Mix the source color over the destination.
+    #[default]
+    Alpha,
+    /// Add the source color to the destination, lightening it.
+    Additive,
+    /// Multiply the destination by the source color, darkening it.
+    Multiply,
+    /// Inverse multiplication of inverted colors, lightening the destination.
+    Screen,
+}
+
+impl BlendMode {
+    /// Blend `0xaa_rr_gg_bb` `source` color onto the `destination` in the `space`.
+    /// The alpha channel of `destination` is preserved.
+    pub fn apply(self, destination: u32, source: u32, space: BlendSpace) -> u32 {
+        let opacity = (source >> 24) as f32 / 255.0;
+        space.combine(destination, source, |destination, source| {
+            let blended = match self {
+                BlendMode::Alpha => source,
+                BlendMode::Additive => destination + source,
+                BlendMode::Multiply => destination * source,
+                BlendMode::Screen => 1.0 - (1.0 - destination) * (1.0 - source),
+            };
+            destination + (blended - destination) * opacity
+        })
+    }
+}
+
+/// Blend `color` onto the original pixel value with the `mode`.
+pub fn blend<P>(mode: BlendMode, color: P, space: BlendSpace) -> impl FnMut(i32, i32, P) -> P
+where
+    P: BlendPixel,
+{
+    let color = color.to_argb();
+    move |_, _, pixel| P::from_argb(mode.apply(pixel.to_argb(), color, space))
+}
+
+/// Alpha blend `color` over the original `0xaa_rr_gg_bb` pixel value.
+/// The opacity is taken from the alpha channel of the `color`.
+pub fn alpha(color: u32, space: BlendSpace) -> impl FnMut(i32, i32, u32) -> u32 {
+    blend(BlendMode::Alpha, color, space)
+}
+
+/// Add `color` to the original `0xaa_rr_gg_bb` pixel value.
+/// The added value is scaled by the alpha channel of the `color`.
+pub fn additive(color: u32, space: BlendSpace) -> impl FnMut(i32, i32, u32) -> u32 {
+    blend(BlendMode::Additive, color, space)
+}
+
+/// Multiply the original `0xaa_rr_gg_bb` pixel value by `color`.
+/// The effect is scaled by the alpha channel of the `color`.
+pub fn multiply(color: u32, space: BlendSpace) -> impl FnMut(i32, i32, u32) -> u32 {
+    blend(BlendMode::Multiply, color, space)
+}
+
+/// Screen the original `0xaa_rr_gg_bb` pixel value with `color`.
+/// The effect is scaled by the alpha channel of the `color`.
+pub fn screen(color: u32, space: BlendSpace) -> impl FnMut(i32, i32, u32) -> u32 {
+    blend(BlendMode::Screen, color, space)
+}
+
+/// Image stamper blending pixels of the drawn image onto the original with the `mode`.
+pub fn blend_stamp<P>(
+    mode: BlendMode,
+    space: BlendSpace,
+) -> impl FnMut(i32, i32, P, i32, i32, P) -> P
+where
+    P: BlendPixel,
+{
+    move |_, _, original, _, _, other| {
+        P::from_argb(mode.apply(original.to_argb(), other.to_argb(), space))
+    }
+}
+
+/// Image stamper treating pixels of the drawn image equal to the `key` as transparent.
+pub fn keyed<P>(key: P) -> impl FnMut(i32, i32, P, i32, i32, P) -> P
+where
+    P: PartialEq,
+{
+    move |_, _, original, _, _, other| if other == key { original } else { other }
+}
+
+/// Converter adapter for render surfaces storing linear light values.
+/// Encodes the `0xff_rr_gg_bb` output of the inner converter into sRGB.
+#[derive(Clone, Copy, Debug)]
+pub struct LinearToSrgb<C> {
+    inner: C,
+}
+
+impl<C> LinearToSrgb<C> {
+    /// Wrap the converter producing linear light values.
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C> Converter for LinearToSrgb<C>
+where
+    C: Converter,
+{
+    type Data = C::Data;
+
+    fn convert(&self, x: usize, y: usize, data: Self::Data) -> u32 {
+        let linear = self.inner.convert(x, y, data);
+        let mut result = 0xff_00_00_00;
+        for shift in [16, 8, 0] {
+            let value = ((linear >> shift) & 0xff) as f32 / 255.0;
+            result |= (linear_to_srgb(value) as u32) << shift;
+        }
+        result
+    }
+}
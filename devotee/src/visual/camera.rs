@@ -0,0 +1,138 @@
+use crate::util::transform::Transform2;
+use crate::util::vector::Vector;
+
+use super::Painter;
+
+/// Camera looking at the world through the rectangular viewport of the render surface.
+///
+/// The camera `position` is the world point shown in the center of the viewport.
+/// World distances are multiplied by the `zoom` on the surface.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    position: Vector<f32>,
+    zoom: f32,
+    viewport_origin: Vector<i32>,
+    viewport_dimensions: Vector<i32>,
+}
+
+impl Camera {
+    /// Create new camera looking at the world origin through the viewport of given `dimensions`
+    /// placed in the top left corner of the surface.
+    pub fn new(dimensions: Vector<i32>) -> Self {
+        Self {
+            position: Vector::new(0.0, 0.0),
+            zoom: 1.0,
+            viewport_origin: Vector::new(0, 0),
+            viewport_dimensions: dimensions,
+        }
+    }
+
+    /// Get new camera looking at `position`.
+    pub fn with_position(self, position: Vector<f32>) -> Self {
+        Self { position, ..self }
+    }
+
+    /// Get new camera with desired `zoom`.
+    /// Non-positive zoom is ignored.
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.set_zoom(zoom);
+        self
+    }
+
+    /// Get new camera with the viewport moved to `origin` on the surface.
+    pub fn with_viewport_origin(self, origin: Vector<i32>) -> Self {
+        Self {
+            viewport_origin: origin,
+            ..self
+        }
+    }
+
+    /// Get the world point shown in the center of the viewport.
+    pub fn position(&self) -> Vector<f32> {
+        self.position
+    }
+
+    /// Look at the world `position`.
+    pub fn set_position(&mut self, position: Vector<f32>) -> &mut Self {
+        self.position = position;
+        self
+    }
+
+    /// Move the camera by `delta` in world units.
+    pub fn translate(&mut self, delta: Vector<f32>) -> &mut Self {
+        self.position += delta;
+        self
+    }
+
+    /// Get the zoom.
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /// Set the zoom.
+    /// Non-positive zoom is ignored.
+    pub fn set_zoom(&mut self, zoom: f32) -> &mut Self {
+        if zoom > 0.0 {
+            self.zoom = zoom;
+        }
+        self
+    }
+
+    /// Get top left corner of the viewport on the surface.
+    pub fn viewport_origin(&self) -> Vector<i32> {
+        self.viewport_origin
+    }
+
+    /// Get dimensions of the viewport on the surface.
+    pub fn viewport_dimensions(&self) -> Vector<i32> {
+        self.viewport_dimensions
+    }
+
+    /// Set the viewport rectangle on the surface.
+    pub fn set_viewport(&mut self, origin: Vector<i32>, dimensions: Vector<i32>) -> &mut Self {
+        self.viewport_origin = origin;
+        self.viewport_dimensions = dimensions;
+        self
+    }
+
+    fn viewport_center(&self) -> Vector<f32> {
+        self.viewport_origin.map(|v| v as f32) + self.viewport_dimensions.map(|v| v as f32) / 2.0
+    }
+
+    /// Convert world `point` to the surface coordinates.
+    pub fn world_to_surface(&self, point: Vector<f32>) -> Vector<f32> {
+        (point - self.position) * self.zoom + self.viewport_center()
+    }
+
+    /// Convert surface `point` to the world coordinates.
+    pub fn surface_to_world(&self, point: Vector<f32>) -> Vector<f32> {
+        (point - self.viewport_center()) / self.zoom + self.position
+    }
+
+    /// Get top left corner and dimensions of the world area visible through the viewport.
+    pub fn visible_area(&self) -> (Vector<f32>, Vector<f32>) {
+        let dimensions = self.viewport_dimensions.map(|v| v as f32) / self.zoom;
+        (self.position - dimensions / 2.0, dimensions)
+    }
+
+    /// Get the transform from the world coordinates to the surface ones.
+    pub fn transform(&self) -> Transform2<f32> {
+        Transform2::translation(-self.position)
+            .scaled(Vector::new(self.zoom, self.zoom))
+            .translated(self.viewport_center())
+    }
+
+    /// Set the `painter` offset so that world coordinates passed to it land on the surface.
+    /// Painters do not scale, so the zoom is expected to be applied to the drawn geometry separately.
+    /// The offset is rounded to whole pixels to avoid jitter of pixel-perfect drawing.
+    pub fn apply<T>(&self, painter: &mut Painter<T, i32>) {
+        let offset = self.world_to_surface(Vector::new(0.0, 0.0));
+        painter.set_offset(offset.map(|v| v.round() as i32));
+    }
+
+    /// Set the subpixel `painter` offset so that world coordinates passed to it land on the surface.
+    /// Painters do not scale, so the zoom is expected to be applied to the drawn geometry separately.
+    pub fn apply_subpixel<T>(&self, painter: &mut Painter<T, f32>) {
+        painter.set_offset(self.world_to_surface(Vector::new(0.0, 0.0)));
+    }
+}
@@ -1,18 +1,22 @@
-use std::ops::RangeInclusive;
+use std::ops::{Deref, DerefMut, RangeInclusive};
 
 use devotee_backend::RenderSurface;
 
-use super::image::{DesignatorMut, DesignatorRef};
+use super::image::{DesignatorMut, DesignatorRef, OwnedImage};
+use super::sprite::Sprite;
 use super::{FastHorizontalWriter, Image, ImageMut};
+use crate::util::rect::Rect;
 use crate::util::vector::Vector;
 
 /// Canvas based on box slice of pixel data.
 /// The canvas size is not known at compile time.
+/// Pixels exposed by resizing get the color the canvas was created or last cleared with.
 #[derive(Clone, Debug)]
 pub struct Canvas<P> {
     data: Box<[P]>,
     width: usize,
     height: usize,
+    fill: P,
 }
 
 impl<P> Canvas<P>
@@ -21,11 +25,142 @@ where
 {
     /// Create new canvas with given color and resolution.
     pub fn with_resolution(color: P, width: usize, height: usize) -> Self {
-        let data = vec![color; width * height].into_boxed_slice();
+        let data = vec![color.clone(); width * height].into_boxed_slice();
         Self {
             data,
             width,
             height,
+            fill: color,
+        }
+    }
+
+    /// Resize the canvas preserving its top left content.
+    /// Pixels exposed by the resize get the `fill` color.
+    pub fn resize_filled(&mut self, width: usize, height: usize, fill: P) {
+        self.reallocate(width, height, fill, true);
+    }
+
+    /// Create new canvas from the rectangle with `origin` and `dimensions` cropped to this canvas.
+    pub fn crop(&self, origin: Vector<i32>, dimensions: Vector<i32>) -> Self {
+        let area = Rect::new(origin, dimensions)
+            .intersection(&Rect::new(Vector::new(0, 0), self.dimensions()))
+            .unwrap_or_default();
+        let (width, height) = (
+            area.dimensions().x() as usize,
+            area.dimensions().y() as usize,
+        );
+        let (left, top) = (area.origin().x() as usize, area.origin().y() as usize);
+        let mut data = Vec::with_capacity(width * height);
+        for y in top..top + height {
+            let row = y * self.width + left;
+            data.extend_from_slice(&self.data[row..row + width]);
+        }
+        Self {
+            data: data.into_boxed_slice(),
+            width,
+            height,
+            fill: self.fill.clone(),
+        }
+    }
+
+    fn reallocate(&mut self, width: usize, height: usize, fill: P, preserve: bool) {
+        let mut data = vec![fill; width * height].into_boxed_slice();
+        if preserve {
+            let overlap = self.width.min(width);
+            for y in 0..self.height.min(height) {
+                data[y * width..y * width + overlap]
+                    .clone_from_slice(&self.data[y * self.width..y * self.width + overlap]);
+            }
+        }
+        self.data = data;
+        self.width = width;
+        self.height = height;
+    }
+}
+
+impl<P> OwnedImage for Canvas<P>
+where
+    P: Clone,
+{
+    /// Create new canvas getting each pixel by its position from the `function`.
+    /// The first pixel becomes the color exposed by resizing.
+    /// Returns `None` if any of the dimensions is zero.
+    fn from_fn<F>(width: usize, height: usize, function: F) -> Option<Self>
+    where
+        F: FnMut(Vector<i32>) -> P,
+    {
+        let data: Box<[P]> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Vector::new(x as i32, y as i32)))
+            .map(function)
+            .collect();
+        let fill = data.first()?.clone();
+        Some(Self {
+            data,
+            width,
+            height,
+            fill,
+        })
+    }
+}
+
+impl<P, const W: usize, const H: usize> From<Sprite<P, W, H>> for Canvas<P>
+where
+    P: Copy,
+{
+    /// Convert the `sprite` into the canvas.
+    /// The top left pixel becomes the color exposed by resizing.
+    /// Conversion of sprites with zero dimensions fails to compile.
+    fn from(sprite: Sprite<P, W, H>) -> Self {
+        const { assert!(W > 0 && H > 0, "Empty sprite can't be converted") };
+        let data: Box<[P]> = sprite.rows().flatten().copied().collect();
+        Self {
+            fill: data[0],
+            data,
+            width: W,
+            height: H,
+        }
+    }
+}
+
+/// Copy the rectangle with `origin` and `dimensions` of the `source` onto the `target` at `at`.
+/// Parts of the rectangle outside of any image are skipped.
+pub fn copy_region<S, T>(
+    source: &S,
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+    target: &mut T,
+    at: Vector<i32>,
+) where
+    S: Image + ?Sized,
+    T: ImageMut<Pixel = S::Pixel> + ?Sized,
+    S::Pixel: Clone,
+    for<'a> <S as DesignatorRef<'a>>::PixelRef: Deref<Target = S::Pixel>,
+    for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = S::Pixel>,
+{
+    let shift = at - origin;
+    let start = origin.individual_max((0, 0)).individual_max(-shift);
+    let end = (origin + dimensions)
+        .individual_min(source.dimensions())
+        .individual_min(target.dimensions() - shift);
+    if start.x() >= end.x() || start.y() >= end.y() {
+        return;
+    }
+    let (from, to) = (start.x() as usize, end.x() as usize);
+    let (target_from, target_to) = (
+        (start.x() + shift.x()) as usize,
+        (end.x() + shift.x()) as usize,
+    );
+    for y in start.y()..end.y() {
+        if let (Some(row), Some(target_row)) = (source.row(y), target.row_mut(y + shift.y())) {
+            target_row[target_from..target_to].clone_from_slice(&row[from..to]);
+            continue;
+        }
+        for x in start.x()..end.x() {
+            let position = Vector::new(x, y);
+            // SAFETY: the rectangle is clipped to both images dimensions.
+            unsafe {
+                *target.unsafe_pixel_mut(position + shift) = source.unsafe_pixel(position).clone();
+            }
         }
     }
 }
@@ -67,6 +202,22 @@ where
     fn height(&self) -> i32 {
         self.height as i32
     }
+
+    fn row(&self, y: i32) -> Option<&[P]> {
+        RenderSurface::row(self, usize::try_from(y).ok()?)
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[P]> {
+        self.data.chunks_exact(self.width.max(1))
+    }
+
+    fn pixels(&self) -> impl Iterator<Item = (Vector<i32>, &P)> {
+        let width = self.width.max(1);
+        self.data.iter().enumerate().map(move |(index, pixel)| {
+            let position = Vector::new((index % width) as i32, (index / width) as i32);
+            (position, pixel)
+        })
+    }
 }
 
 impl<'a, P> DesignatorMut<'a> for Canvas<P> {
@@ -98,7 +249,17 @@ where
     }
 
     fn clear(&mut self, color: P) {
-        self.data = vec![color; self.width * self.height].into_boxed_slice();
+        self.data.fill(color.clone());
+        self.fill = color;
+    }
+
+    fn row_mut(&mut self, y: i32) -> Option<&mut [P]> {
+        let y = usize::try_from(y).ok().filter(|&y| y < self.height)?;
+        self.data.get_mut(y * self.width..(y + 1) * self.width)
+    }
+
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut [P]> {
+        self.data.chunks_exact_mut(self.width.max(1))
     }
 
     fn fast_horizontal_writer(&mut self) -> Option<impl FastHorizontalWriter<Self>> {
@@ -123,6 +284,17 @@ where
     fn data(&self, x: usize, y: usize) -> P {
         unsafe { self.unsafe_pixel(Vector::new(x as i32, y as i32)).clone() }
     }
+
+    fn row(&self, y: usize) -> Option<&[P]> {
+        (y < self.height)
+            .then(|| self.data.get(y * self.width..(y + 1) * self.width))
+            .flatten()
+    }
+
+    fn resize(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        self.reallocate(width, height, self.fill.clone(), preserve);
+        true
+    }
 }
 
 struct CanvasFastHorizontalWriter<'a, P> {
@@ -143,13 +315,12 @@ where
             return;
         }
         let width = Image::width(self.canvas);
-        let start_x = (*x.start()).clamp(0, width - 1);
-        let end_x = (*x.end() + 1).clamp(0, width - 1);
-        let start = start_x + width * y;
-        let end = end_x + width * y;
+        let (left, right) = (*x.start().min(x.end()), *x.start().max(x.end()));
+        let start_x = left.clamp(0, width);
+        let end_x = right.saturating_add(1).clamp(0, width);
 
-        let s = start.min(end) as usize;
-        let e = start.max(end) as usize;
+        let s = (start_x + width * y) as usize;
+        let e = (end_x + width * y) as usize;
 
         self.canvas.data[s..e]
             .iter_mut()
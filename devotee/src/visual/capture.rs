@@ -0,0 +1,54 @@
+use crate::util::vector::Vector;
+
+use super::canvas::Canvas;
+
+mod x11;
+
+/// Source of the captured pixels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The whole screen.
+    Screen,
+    /// X11 window id.
+    Window(u64),
+}
+
+/// Screen capture error enumeration.
+#[derive(Debug)]
+pub enum CaptureError {
+    /// Captured image pixel format is not supported.
+    UnsupportedFormat,
+
+    /// X11 server connection error.
+    X11ConnectError(x11rb::errors::ConnectError),
+
+    /// X11 request error.
+    X11ReplyError(x11rb::errors::ReplyError),
+}
+
+/// Capture the region of the `source` with its top left corner at `origin` into `[r, g, b, a]` canvas.
+/// The pixels are read from the X11 server named by the `DISPLAY` environment variable.
+pub fn capture(
+    source: Source,
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+) -> Result<Canvas<[u8; 4]>, CaptureError> {
+    x11::capture(source, origin, dimensions)
+}
+
+/// Capture the screen region with its top left corner at `origin`.
+pub fn capture_screen(
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+) -> Result<Canvas<[u8; 4]>, CaptureError> {
+    capture(Source::Screen, origin, dimensions)
+}
+
+/// Capture the window region with its top left corner at `origin` relative to the window.
+pub fn capture_window(
+    window: u64,
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+) -> Result<Canvas<[u8; 4]>, CaptureError> {
+    capture(Source::Window(window), origin, dimensions)
+}
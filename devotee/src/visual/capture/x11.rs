@@ -0,0 +1,130 @@
+use x11rb::connection::Connection;
+use x11rb::errors::{ConnectError, ConnectionError, ReplyError};
+use x11rb::protocol::xproto::{ConnectionExt, ImageFormat, ImageOrder, Visualtype};
+
+use crate::util::vector::Vector;
+use crate::visual::canvas::Canvas;
+use crate::visual::image::ImageMut;
+
+use super::{CaptureError, Source};
+
+impl From<ConnectError> for CaptureError {
+    fn from(value: ConnectError) -> Self {
+        Self::X11ConnectError(value)
+    }
+}
+
+impl From<ReplyError> for CaptureError {
+    fn from(value: ReplyError) -> Self {
+        Self::X11ReplyError(value)
+    }
+}
+
+impl From<ConnectionError> for CaptureError {
+    fn from(value: ConnectionError) -> Self {
+        Self::X11ReplyError(value.into())
+    }
+}
+
+fn channel(value: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let maximum = (mask >> shift) as u64;
+    (((value & mask) >> shift) as u64 * 255 / maximum) as u8
+}
+
+pub(super) fn capture(
+    source: Source,
+    origin: Vector<i32>,
+    dimensions: Vector<i32>,
+) -> Result<Canvas<[u8; 4]>, CaptureError> {
+    let (connection, screen_index) = x11rb::connect(None)?;
+    let setup = connection.setup();
+    let screen = &setup.roots[screen_index];
+    let drawable = match source {
+        Source::Screen => screen.root,
+        Source::Window(window) => window as u32,
+    };
+    let clamp_position = |value: i32| value.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+    let clamp_length = |value: i32| value.clamp(0, u16::MAX as i32) as u16;
+    let (width, height) = (clamp_length(dimensions.x()), clamp_length(dimensions.y()));
+    if width == 0 || height == 0 {
+        return Ok(Canvas::with_resolution(
+            [0, 0, 0, 0xff],
+            width as usize,
+            height as usize,
+        ));
+    }
+
+    let reply = connection
+        .get_image(
+            ImageFormat::Z_PIXMAP,
+            drawable,
+            clamp_position(origin.x()),
+            clamp_position(origin.y()),
+            width,
+            height,
+            !0,
+        )?
+        .reply()?;
+
+    let format = setup
+        .pixmap_formats
+        .iter()
+        .find(|format| format.depth == reply.depth)
+        .ok_or(CaptureError::UnsupportedFormat)?;
+    let visual: &Visualtype = screen
+        .allowed_depths
+        .iter()
+        .flat_map(|depth| depth.visuals.iter())
+        .find(|visual| visual.visual_id == reply.visual)
+        .or_else(|| {
+            screen
+                .allowed_depths
+                .iter()
+                .flat_map(|depth| depth.visuals.iter())
+                .find(|visual| visual.visual_id == screen.root_visual)
+        })
+        .ok_or(CaptureError::UnsupportedFormat)?;
+
+    let bytes_per_pixel = match format.bits_per_pixel {
+        16 | 24 | 32 => format.bits_per_pixel as usize / 8,
+        _ => return Err(CaptureError::UnsupportedFormat),
+    };
+    let pad = format.scanline_pad.max(8) as usize;
+    let stride = (width as usize * format.bits_per_pixel as usize).div_ceil(pad) * pad / 8;
+    if reply.data.len() < stride * height as usize {
+        return Err(CaptureError::UnsupportedFormat);
+    }
+    let little_endian = setup.image_byte_order == ImageOrder::LSB_FIRST;
+
+    let mut canvas = Canvas::with_resolution([0, 0, 0, 0xff], width as usize, height as usize);
+    for y in 0..height as usize {
+        for x in 0..width as usize {
+            let start = y * stride + x * bytes_per_pixel;
+            let bytes = &reply.data[start..start + bytes_per_pixel];
+            let value = if little_endian {
+                bytes
+                    .iter()
+                    .rev()
+                    .fold(0u32, |value, byte| value << 8 | *byte as u32)
+            } else {
+                bytes
+                    .iter()
+                    .fold(0u32, |value, byte| value << 8 | *byte as u32)
+            };
+            let color = [
+                channel(value, visual.red_mask),
+                channel(value, visual.green_mask),
+                channel(value, visual.blue_mask),
+                0xff,
+            ];
+            if let Some(pixel) = canvas.pixel_mut(Vector::new(x as i32, y as i32)) {
+                *pixel = color;
+            }
+        }
+    }
+    Ok(canvas)
+}
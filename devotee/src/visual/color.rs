@@ -0,0 +1,236 @@
+use backend::texel::Texel;
+use backend::Converter;
+
+use super::blend::BlendPixel;
+use super::compositor::{LayerBlend, LayerPixel};
+use crate::util::interp::Interpolate;
+use crate::util::vector::Vector;
+
+/// Order of the color channels packed into `u32` value, from the most significant byte.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ChannelOrder {
+    /// `0xaa_rr_gg_bb` values.
+    #[default]
+    Argb,
+    /// `0xxx_rr_gg_bb` values with the upper byte ignored and considered opaque.
+    Xrgb,
+    /// `0xrr_gg_bb_aa` values.
+    Rgba,
+}
+
+/// Color with 8 bit red, green, blue and alpha channels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rgba8 {
+    /// Red channel.
+    pub r: u8,
+    /// Green channel.
+    pub g: u8,
+    /// Blue channel.
+    pub b: u8,
+    /// Alpha channel, `0` is transparent.
+    pub a: u8,
+}
+
+impl Rgba8 {
+    /// Transparent black color.
+    pub const TRANSPARENT: Self = Self::new(0, 0, 0, 0);
+    /// Opaque black color.
+    pub const BLACK: Self = Self::opaque(0, 0, 0);
+    /// Opaque white color.
+    pub const WHITE: Self = Self::opaque(0xff, 0xff, 0xff);
+
+    /// Create new color from channel values.
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Create new opaque color.
+    pub const fn opaque(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r, g, b, 0xff)
+    }
+
+    /// Unpack color from the `value` with channels in `order`.
+    pub const fn from_u32(value: u32, order: ChannelOrder) -> Self {
+        let [first, second, third, fourth] = value.to_be_bytes();
+        match order {
+            ChannelOrder::Argb => Self::new(second, third, fourth, first),
+            ChannelOrder::Xrgb => Self::opaque(second, third, fourth),
+            ChannelOrder::Rgba => Self::new(first, second, third, fourth),
+        }
+    }
+
+    /// Pack color into `u32` value with channels in `order`.
+    /// The `Xrgb` order sets the upper byte to `0xff`.
+    pub const fn to_u32(self, order: ChannelOrder) -> u32 {
+        let Self { r, g, b, a } = self;
+        u32::from_be_bytes(match order {
+            ChannelOrder::Argb => [a, r, g, b],
+            ChannelOrder::Xrgb => [0xff, r, g, b],
+            ChannelOrder::Rgba => [r, g, b, a],
+        })
+    }
+
+    /// Get `[r, g, b, a]` channel values.
+    pub const fn to_array(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// Parse hexadecimal color notation with optional leading `#`.
+    /// Supports `rgb`, `rgba`, `rrggbb` and `rrggbbaa` forms; colors without alpha are opaque.
+    pub fn from_hex(text: &str) -> Option<Self> {
+        let digits = text.strip_prefix('#').unwrap_or(text);
+        if !matches!(digits.len(), 3 | 4 | 6 | 8)
+            || !digits.bytes().all(|digit| digit.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        let short = |index: u32| (value >> (index * 4) & 0xf) as u8 * 0x11;
+        let long = |index: u32| (value >> (index * 8) & 0xff) as u8;
+        Some(match digits.len() {
+            3 => Self::opaque(short(2), short(1), short(0)),
+            4 => Self::new(short(3), short(2), short(1), short(0)),
+            6 => Self::opaque(long(2), long(1), long(0)),
+            8 => Self::new(long(3), long(2), long(1), long(0)),
+            _ => return None,
+        })
+    }
+
+    /// Create opaque color from `hue` in degrees, `saturation` and `value` in `[0.0, 1.0]` range.
+    /// Hue wraps around, saturation and value are clamped.
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let (saturation, value) = (saturation.clamp(0.0, 1.0), value.clamp(0.0, 1.0));
+        let sector = hue.rem_euclid(360.0) / 60.0;
+        let chroma = value * saturation;
+        let second = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let base = value - chroma;
+        let channel = |value: f32| ((value + base) * 255.0).round() as u8;
+        Self::opaque(channel(r), channel(g), channel(b))
+    }
+
+    /// Get hue in degrees, saturation and value in `[0.0, 1.0]` range, ignoring alpha.
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let [r, g, b, _] = self.to_array().map(|channel| channel as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let chroma = max - r.min(g).min(b);
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+        (hue, saturation, max)
+    }
+
+    /// Interpolate each channel between `self` at `alpha` equal to `0.0` and `other` at `alpha` equal to `1.0`.
+    pub fn lerp(self, other: Self, alpha: f32) -> Self {
+        let [from, to] = [self.to_array(), other.to_array()];
+        let [r, g, b, a] = std::array::from_fn(|index| {
+            let (from, to) = (from[index] as f32, to[index] as f32);
+            (from + (to - from) * alpha).round().clamp(0.0, 255.0) as u8
+        });
+        Self::new(r, g, b, a)
+    }
+
+    /// Multiply color channels by the alpha channel.
+    pub fn premultiply(self) -> Self {
+        let scale = |channel: u8| ((channel as u32 * self.a as u32 + 127) / 255) as u8;
+        Self::new(scale(self.r), scale(self.g), scale(self.b), self.a)
+    }
+}
+
+impl From<[u8; 4]> for Rgba8 {
+    fn from([r, g, b, a]: [u8; 4]) -> Self {
+        Self::new(r, g, b, a)
+    }
+}
+
+impl From<Rgba8> for [u8; 4] {
+    fn from(color: Rgba8) -> Self {
+        color.to_array()
+    }
+}
+
+impl Interpolate for Rgba8 {
+    fn interpolate(&self, other: &Self, alpha: f32) -> Self {
+        self.lerp(*other, alpha)
+    }
+}
+
+impl BlendPixel for Rgba8 {
+    fn to_argb(self) -> u32 {
+        self.to_u32(ChannelOrder::Argb)
+    }
+
+    fn from_argb(argb: u32) -> Self {
+        Self::from_u32(argb, ChannelOrder::Argb)
+    }
+}
+
+/// Alpha channel of the layer pixels scales their contribution.
+impl LayerPixel for Rgba8 {
+    fn blend(self, above: Self, mode: LayerBlend) -> Self {
+        Self::from_argb(self.to_argb().blend(above.to_argb(), mode))
+    }
+
+    fn fade(self, blended: Self, opacity: u8, position: Vector<i32>) -> Self {
+        Self::from_argb(self.to_argb().fade(blended.to_argb(), opacity, position))
+    }
+}
+
+/// Alpha channel is ignored.
+impl Texel for Rgba8 {
+    fn pack(self) -> u32 {
+        self.to_u32(ChannelOrder::Xrgb)
+    }
+}
+
+/// Converter presenting `Rgba8` colors, ignoring the alpha channel.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Rgba8Converter;
+
+impl Converter for Rgba8Converter {
+    type Data = Rgba8;
+
+    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
+        data.pack()
+    }
+}
+
+/// Converter presenting `u32` colors packed with channels in the given order, ignoring the alpha channel.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PackedColorConverter {
+    order: ChannelOrder,
+}
+
+impl PackedColorConverter {
+    /// Create new converter for values with channels in `order`.
+    pub const fn new(order: ChannelOrder) -> Self {
+        Self { order }
+    }
+
+    /// Get order of the channels.
+    pub fn order(&self) -> ChannelOrder {
+        self.order
+    }
+}
+
+impl Converter for PackedColorConverter {
+    type Data = u32;
+
+    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
+        Rgba8::from_u32(data, self.order).pack()
+    }
+}
@@ -0,0 +1,456 @@
+use std::ops::DerefMut;
+
+use super::blend::BlendSpace;
+use super::canvas::{copy_region, Canvas};
+use super::dither::bayer;
+use super::image::DesignatorMut;
+use super::{Image, ImageMut};
+use crate::util::invalidation::Subscription;
+use crate::util::rect::Rect;
+use crate::util::vector::Vector;
+
+/// Rectangular region as top left and bottom right exclusive corners.
+pub type Region = (Vector<i32>, Vector<i32>);
+
+fn union(region: Option<Region>, other: Region) -> Option<Region> {
+    Some(match region {
+        Some((start, end)) => Rect::from_corners(start, end)
+            .union(&Rect::from_corners(other.0, other.1))
+            .corners(),
+        None => other,
+    })
+}
+
+fn clip(region: Region, dimensions: Vector<i32>) -> Option<Region> {
+    Rect::from_corners(region.0, region.1)
+        .intersection(&Rect::new(Vector::new(0, 0), dimensions))
+        .map(|area| area.corners())
+}
+
+/// Pick either `below` or `above` with ordered dithering so that `opacity` share of pixels is `above`.
+fn dither<P>(below: P, above: P, opacity: u8, position: Vector<i32>) -> P {
+    if opacity as u32 * 16 / 255 > bayer(2, position) {
+        above
+    } else {
+        below
+    }
+}
+
+/// Mode of blending the layer over the layers below during composition.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayerBlend {
+    /// Layer is drawn over the layers below.
+    #[default]
+    Normal,
+    /// Layer is added to the layers below, e.g. for lighting and flashes.
+    Additive,
+    /// Layers below are multiplied by the layer, e.g. for fog and shadows.
+    Multiply,
+}
+
+/// Pixel type supporting layer blend modes and opacity.
+pub trait LayerPixel: Clone {
+    /// Blend the `above` pixel over this one with the `mode`.
+    fn blend(self, above: Self, mode: LayerBlend) -> Self;
+
+    /// Mix the `blended` pixel into this one with the layer `opacity` at the layer `position`.
+    /// Defaults to ordered dithering suitable for palette pixels.
+    fn fade(self, blended: Self, opacity: u8, position: Vector<i32>) -> Self {
+        dither(self, blended, opacity, position)
+    }
+}
+
+impl LayerPixel for bool {
+    fn blend(self, above: Self, mode: LayerBlend) -> Self {
+        match mode {
+            LayerBlend::Normal => above,
+            LayerBlend::Additive => self || above,
+            LayerBlend::Multiply => self && above,
+        }
+    }
+}
+
+/// `0xaa_rr_gg_bb` pixels, alpha channel of the layer pixels scales their contribution.
+impl LayerPixel for u32 {
+    fn blend(self, above: Self, mode: LayerBlend) -> Self {
+        let opacity = (above >> 24) as f32 / 255.0;
+        BlendSpace::Srgb.combine(self, above, |below, above| match mode {
+            LayerBlend::Normal => below + (above - below) * opacity,
+            LayerBlend::Additive => below + above * opacity,
+            LayerBlend::Multiply => below + (below * above - below) * opacity,
+        })
+    }
+
+    fn fade(self, blended: Self, opacity: u8, _: Vector<i32>) -> Self {
+        let opacity = opacity as f32 / 255.0;
+        BlendSpace::Srgb.combine(self, blended, |below, blended| {
+            below + (blended - below) * opacity
+        })
+    }
+}
+
+/// Scene layer with cached content and child layers drawn over it.
+/// Layer is re-composed only when its content or any of its children change.
+#[derive(Clone, Debug)]
+pub struct Layer<P> {
+    content: Canvas<P>,
+    composed: Canvas<P>,
+    offset: Vector<i32>,
+    composed_offset: Vector<i32>,
+    scale: i32,
+    composed_scale: i32,
+    visible: bool,
+    composed_visible: bool,
+    opacity: u8,
+    blend: LayerBlend,
+    dirty: bool,
+    children: Vec<Layer<P>>,
+}
+
+impl<P> Layer<P>
+where
+    P: Clone,
+{
+    /// Create new layer with the content canvas.
+    pub fn new(content: Canvas<P>) -> Self {
+        let composed = content.clone();
+        let offset = Vector::new(0, 0);
+        Self {
+            content,
+            composed,
+            offset,
+            composed_offset: offset,
+            scale: 1,
+            composed_scale: 1,
+            visible: true,
+            composed_visible: false,
+            opacity: u8::MAX,
+            blend: LayerBlend::Normal,
+            dirty: true,
+            children: Vec::new(),
+        }
+    }
+
+    /// Set offset of the layer relative to its parent.
+    pub fn with_offset(self, offset: Vector<i32>) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Set integer scale of the layer over its parent.
+    /// Values below one are treated as one.
+    pub fn with_scale(self, scale: i32) -> Self {
+        Self {
+            scale: scale.max(1),
+            ..self
+        }
+    }
+
+    /// Set opacity of the layer over its parent, `255` being fully opaque.
+    pub fn with_opacity(self, opacity: u8) -> Self {
+        Self { opacity, ..self }
+    }
+
+    /// Set blend mode of the layer over its parent.
+    pub fn with_blend(self, blend: LayerBlend) -> Self {
+        Self { blend, ..self }
+    }
+
+    /// Add child layer drawn over this one.
+    pub fn with_child(self, child: Layer<P>) -> Self {
+        let mut children = self.children;
+        children.push(child);
+        Self { children, ..self }
+    }
+
+    /// Get offset of the layer relative to its parent.
+    pub fn offset(&self) -> Vector<i32> {
+        self.offset
+    }
+
+    /// Set offset of the layer relative to its parent.
+    pub fn set_offset(&mut self, offset: Vector<i32>) -> &mut Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Get integer scale of the layer over its parent.
+    pub fn scale(&self) -> i32 {
+        self.scale
+    }
+
+    /// Set integer scale of the layer over its parent.
+    /// Values below one are treated as one.
+    pub fn set_scale(&mut self, scale: i32) -> &mut Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Check if the layer is visible.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show or hide the layer.
+    pub fn set_visible(&mut self, visible: bool) -> &mut Self {
+        self.visible = visible;
+        self
+    }
+
+    /// Get opacity of the layer over its parent.
+    pub fn opacity(&self) -> u8 {
+        self.opacity
+    }
+
+    /// Set opacity of the layer over its parent, `255` being fully opaque.
+    pub fn set_opacity(&mut self, opacity: u8) -> &mut Self {
+        self.dirty |= self.opacity != opacity;
+        self.opacity = opacity;
+        self
+    }
+
+    /// Get blend mode of the layer over its parent.
+    pub fn blend(&self) -> LayerBlend {
+        self.blend
+    }
+
+    /// Set blend mode of the layer over its parent.
+    pub fn set_blend(&mut self, blend: LayerBlend) -> &mut Self {
+        self.dirty |= self.blend != blend;
+        self.blend = blend;
+        self
+    }
+
+    /// Get reference to the layer content.
+    pub fn content(&self) -> &Canvas<P> {
+        &self.content
+    }
+
+    /// Get mutable reference to the layer content, marking the layer dirty.
+    pub fn content_mut(&mut self) -> &mut Canvas<P> {
+        self.dirty = true;
+        &mut self.content
+    }
+
+    /// Mark the layer dirty so its content is re-composed.
+    pub fn invalidate(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Mark the layer and all its descendants dirty.
+    pub fn invalidate_all(&mut self) {
+        self.dirty = true;
+        self.children.iter_mut().for_each(Layer::invalidate_all);
+    }
+
+    /// Check if the layer or any of its descendants need re-composition.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+            || self.offset != self.composed_offset
+            || self.scale != self.composed_scale
+            || self.visible != self.composed_visible
+            || self.children.iter().any(Layer::is_dirty)
+    }
+
+    /// Get reference to the child layers.
+    pub fn children(&self) -> &[Layer<P>] {
+        &self.children
+    }
+
+    /// Get mutable reference to the child layer.
+    pub fn child_mut(&mut self, index: usize) -> Option<&mut Layer<P>> {
+        self.children.get_mut(index)
+    }
+
+    /// Add child layer drawn over this and previously added children.
+    pub fn push_child(&mut self, child: Layer<P>) -> &mut Self {
+        self.dirty = true;
+        self.children.push(child);
+        self
+    }
+
+    /// Remove child layer.
+    pub fn remove_child(&mut self, index: usize) -> Layer<P> {
+        self.dirty = true;
+        self.children.remove(index)
+    }
+
+    /// Get the composed image of the layer with all its children.
+    pub fn composed(&self) -> &Canvas<P> {
+        &self.composed
+    }
+
+    fn bounds(&self, offset: Vector<i32>, scale: i32) -> Region {
+        (offset, offset + self.content.dimensions() * scale)
+    }
+
+    /// Re-compose the layer, blending children with the `blend` function accepting pixels below and above,
+    /// the child layer and the position within the child layer.
+    /// Returns the changed region of the layer in its local coordinates.
+    fn refresh<F>(&mut self, blend: &mut F) -> Option<Region>
+    where
+        F: FnMut(P, P, &Layer<P>, Vector<i32>) -> P,
+    {
+        let dimensions = self.content.dimensions();
+        let mut region = self.dirty.then_some((Vector::new(0, 0), dimensions));
+        for child in self.children.iter_mut() {
+            if let Some((start, end)) = child.refresh(blend) {
+                if child.visible {
+                    let (start, end) = (start * child.scale, end * child.scale);
+                    region = union(region, (start + child.offset, end + child.offset));
+                }
+            }
+            if child.offset != child.composed_offset
+                || child.scale != child.composed_scale
+                || child.visible != child.composed_visible
+            {
+                if child.composed_visible {
+                    region = union(
+                        region,
+                        child.bounds(child.composed_offset, child.composed_scale),
+                    );
+                }
+                if child.visible {
+                    region = union(region, child.bounds(child.offset, child.scale));
+                }
+                child.composed_offset = child.offset;
+                child.composed_scale = child.scale;
+                child.composed_visible = child.visible;
+            }
+        }
+        self.dirty = false;
+
+        let (start, end) = clip(region?, dimensions)?;
+        for y in start.y()..end.y() {
+            for x in start.x()..end.x() {
+                let position = Vector::new(x, y);
+                // SAFETY: the region is clipped to the layer dimensions.
+                let mut pixel = unsafe { self.content.unsafe_pixel(position).clone() };
+                for child in self.children.iter() {
+                    if !child.visible || child.opacity == 0 {
+                        continue;
+                    }
+                    let local = position - child.offset;
+                    if local.x() < 0 || local.y() < 0 {
+                        continue;
+                    }
+                    let local = local / child.scale;
+                    if let Some(above) = child.composed.pixel(local) {
+                        pixel = blend(pixel, above.clone(), child, local);
+                    }
+                }
+                // SAFETY: the region is clipped to the layer dimensions.
+                unsafe { *self.composed.unsafe_pixel_mut(position) = pixel };
+            }
+        }
+        Some((start, end))
+    }
+}
+
+/// Compositor of the layer hierarchy.
+/// Only regions affected by changed layers are re-blended on refresh.
+#[derive(Clone, Debug)]
+pub struct Compositor<P> {
+    root: Layer<P>,
+    subscriptions: Vec<Subscription>,
+}
+
+impl<P> Compositor<P>
+where
+    P: Clone,
+{
+    /// Create new compositor with the root layer defining the output dimensions.
+    pub fn new(root: Layer<P>) -> Self {
+        let subscriptions = Vec::new();
+        Self {
+            root,
+            subscriptions,
+        }
+    }
+
+    /// Invalidate all the layers whenever the subscription source signals, e.g. on palette swap.
+    pub fn with_subscription(self, subscription: Subscription) -> Self {
+        let mut subscriptions = self.subscriptions;
+        subscriptions.push(subscription);
+        Self {
+            subscriptions,
+            ..self
+        }
+    }
+
+    /// Get reference to the root layer.
+    pub fn root(&self) -> &Layer<P> {
+        &self.root
+    }
+
+    /// Get mutable reference to the root layer.
+    pub fn root_mut(&mut self) -> &mut Layer<P> {
+        &mut self.root
+    }
+
+    /// Re-blend changed regions using the `blend` function accepting pixels below and above.
+    /// Layer blend modes are left to the `blend` function, translucent layers are dithered.
+    /// Returns the changed region of the output, if any.
+    pub fn refresh<F>(&mut self, blend: F) -> Option<Region>
+    where
+        F: FnMut(P, P) -> P,
+    {
+        let mut blend = blend;
+        self.recompose(&mut |below: P, above, layer: &Layer<P>, position| {
+            if layer.opacity == u8::MAX {
+                blend(below, above)
+            } else {
+                dither(below.clone(), blend(below, above), layer.opacity, position)
+            }
+        })
+    }
+
+    /// Re-blend changed regions applying layer blend modes and opacity.
+    /// Returns the changed region of the output, if any.
+    pub fn compose(&mut self) -> Option<Region>
+    where
+        P: LayerPixel,
+    {
+        self.recompose(&mut |below: P, above, layer: &Layer<P>, position| {
+            let blended = below.clone().blend(above, layer.blend);
+            if layer.opacity == u8::MAX {
+                blended
+            } else {
+                below.fade(blended, layer.opacity, position)
+            }
+        })
+    }
+
+    fn recompose<F>(&mut self, blend: &mut F) -> Option<Region>
+    where
+        F: FnMut(P, P, &Layer<P>, Vector<i32>) -> P,
+    {
+        let mut stale = false;
+        for subscription in self.subscriptions.iter_mut() {
+            stale |= subscription.acknowledge();
+        }
+        if stale {
+            self.root.invalidate_all();
+        }
+        self.root.composed_visible = self.root.visible;
+        self.root.composed_offset = self.root.offset;
+        self.root.composed_scale = self.root.scale;
+        self.root.refresh(blend)
+    }
+
+    /// Get the composed output image.
+    pub fn output(&self) -> &Canvas<P> {
+        &self.root.composed
+    }
+
+    /// Copy the `region` of the output, e.g. returned by the refresh, onto the `target` at the same position.
+    /// The whole output is copied if the `region` is `None`.
+    pub fn copy_to<T>(&self, target: &mut T, region: Option<Region>)
+    where
+        T: ImageMut<Pixel = P> + ?Sized,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = P>,
+    {
+        let output = &self.root.composed;
+        let (start, end) = region.unwrap_or((Vector::new(0, 0), output.dimensions()));
+        copy_region(output, start, end - start, target, start);
+    }
+}
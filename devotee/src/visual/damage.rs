@@ -0,0 +1,198 @@
+use std::ops::RangeInclusive;
+
+use backend::damage::Damage;
+use backend::RenderSurface;
+
+use super::canvas::Canvas;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{FastHorizontalWriter, Image, ImageMut};
+use crate::util::vector::Vector;
+
+/// Canvas wrapper recording the bounding rectangle of the modified texels.
+/// Backends supporting damage tracking present only the changed region of it.
+#[derive(Clone, Debug)]
+pub struct Tracked<P> {
+    canvas: Canvas<P>,
+    damage: Damage,
+}
+
+impl<P> Tracked<P>
+where
+    P: Clone,
+{
+    /// Create new tracked canvas with given color and resolution.
+    pub fn with_resolution(color: P, width: usize, height: usize) -> Self {
+        Self::new(Canvas::with_resolution(color, width, height))
+    }
+
+    /// Wrap the `canvas`, considering it completely damaged.
+    pub fn new(canvas: Canvas<P>) -> Self {
+        Self {
+            canvas,
+            damage: Damage::Full,
+        }
+    }
+
+    /// Get reference to the underlying canvas.
+    pub fn canvas(&self) -> &Canvas<P> {
+        &self.canvas
+    }
+
+    /// Unwrap the underlying canvas.
+    pub fn into_inner(self) -> Canvas<P> {
+        self.canvas
+    }
+
+    /// Mark the rectangle with `origin` and `dimensions` as changed, e.g. after external modification.
+    pub fn invalidate(&mut self, origin: Vector<i32>, dimensions: Vector<i32>) {
+        let (width, height) = (Image::width(&self.canvas), Image::height(&self.canvas));
+        let start = (origin.x().clamp(0, width), origin.y().clamp(0, height));
+        let end = (
+            origin.x().saturating_add(dimensions.x()).clamp(0, width),
+            origin.y().saturating_add(dimensions.y()).clamp(0, height),
+        );
+        self.damage.add(
+            (start.0 as usize, start.1 as usize),
+            (end.0 as usize, end.1 as usize),
+        );
+    }
+
+    fn touch(&mut self, position: Vector<i32>) {
+        let (width, height) = (Image::width(&self.canvas), Image::height(&self.canvas));
+        if (0..width).contains(&position.x()) && (0..height).contains(&position.y()) {
+            self.damage
+                .add_texel(position.x() as usize, position.y() as usize);
+        }
+    }
+}
+
+impl<'a, P> DesignatorRef<'a> for Tracked<P> {
+    type PixelRef = &'a P;
+}
+
+impl<P> Image for Tracked<P>
+where
+    P: Clone,
+{
+    type Pixel = P;
+
+    fn pixel(&self, position: Vector<i32>) -> Option<&P> {
+        self.canvas.pixel(position)
+    }
+
+    unsafe fn unsafe_pixel(&self, position: Vector<i32>) -> &P {
+        self.canvas.unsafe_pixel(position)
+    }
+
+    fn width(&self) -> i32 {
+        Image::width(&self.canvas)
+    }
+
+    fn height(&self) -> i32 {
+        Image::height(&self.canvas)
+    }
+
+    fn row(&self, y: i32) -> Option<&[P]> {
+        Image::row(&self.canvas, y)
+    }
+}
+
+impl<'a, P> DesignatorMut<'a> for Tracked<P> {
+    type PixelMut = &'a mut P;
+}
+
+impl<P> ImageMut for Tracked<P>
+where
+    P: Clone,
+{
+    fn pixel_mut(&mut self, position: Vector<i32>) -> Option<&mut P> {
+        self.touch(position);
+        self.canvas.pixel_mut(position)
+    }
+
+    unsafe fn unsafe_pixel_mut(&mut self, position: Vector<i32>) -> &mut P {
+        self.touch(position);
+        self.canvas.unsafe_pixel_mut(position)
+    }
+
+    fn clear(&mut self, color: P) {
+        self.damage.add_all();
+        self.canvas.clear(color);
+    }
+
+    fn row_mut(&mut self, y: i32) -> Option<&mut [P]> {
+        let width = Image::width(&self.canvas);
+        self.invalidate(Vector::new(0, y), Vector::new(width, 1));
+        self.canvas.row_mut(y)
+    }
+
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut [P]> {
+        self.damage.add_all();
+        self.canvas.rows_mut()
+    }
+
+    fn fast_horizontal_writer(&mut self) -> Option<impl FastHorizontalWriter<Self>> {
+        Some(TrackedFastHorizontalWriter { tracked: self })
+    }
+}
+
+impl<P> RenderSurface for Tracked<P>
+where
+    P: Clone,
+{
+    type Data = P;
+
+    fn width(&self) -> usize {
+        RenderSurface::width(&self.canvas)
+    }
+
+    fn height(&self) -> usize {
+        RenderSurface::height(&self.canvas)
+    }
+
+    fn data(&self, x: usize, y: usize) -> P {
+        self.canvas.data(x, y)
+    }
+
+    fn row(&self, y: usize) -> Option<&[P]> {
+        RenderSurface::row(&self.canvas, y)
+    }
+
+    fn resize(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        self.damage.add_all();
+        self.canvas.resize(width, height, preserve)
+    }
+
+    fn damage(&self) -> Damage {
+        self.damage
+    }
+
+    fn clear_damage(&mut self) {
+        self.damage = Damage::None;
+    }
+}
+
+struct TrackedFastHorizontalWriter<'a, P> {
+    tracked: &'a mut Tracked<P>,
+}
+
+impl<P> FastHorizontalWriter<Tracked<P>> for TrackedFastHorizontalWriter<'_, P>
+where
+    P: Clone,
+{
+    fn write_line<F: FnMut(i32, i32, P) -> P>(
+        &mut self,
+        x: RangeInclusive<i32>,
+        y: i32,
+        function: &mut F,
+    ) {
+        let (start, end) = (*x.start().min(x.end()), *x.start().max(x.end()));
+        self.tracked.invalidate(
+            Vector::new(start, y),
+            Vector::new(end.saturating_sub(start).saturating_add(1), 1),
+        );
+        if let Some(mut writer) = self.tracked.canvas.fast_horizontal_writer() {
+            writer.write_line(x, y, function);
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::ops::RangeInclusive;
+
+use backend::RenderSurface;
+
+use super::canvas::Canvas;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{FastHorizontalWriter, Image, ImageMut};
+use crate::util::vector::Vector;
+
+/// Single display list command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command<P> {
+    /// Fill the whole target with the color.
+    Clear(P),
+    /// Write horizontal run of texels starting at the position.
+    Span {
+        /// Leftmost texel position.
+        start: Vector<i32>,
+        /// Texel values.
+        texels: Vec<P>,
+    },
+    /// Write rectangular block of texels.
+    Blit {
+        /// Top left texel position.
+        origin: Vector<i32>,
+        /// Block width.
+        width: i32,
+        /// Texel values, row by row.
+        texels: Vec<P>,
+    },
+}
+
+/// Recorded frame as a compact list of commands.
+#[derive(Clone, Debug)]
+pub struct DisplayList<P> {
+    dimensions: Vector<i32>,
+    commands: Vec<Command<P>>,
+}
+
+impl<P> DisplayList<P> {
+    /// Get dimensions of the recorded target.
+    pub fn dimensions(&self) -> Vector<i32> {
+        self.dimensions
+    }
+
+    /// Get recorded commands in the execution order.
+    pub fn commands(&self) -> &[Command<P>] {
+        &self.commands
+    }
+
+    /// Consume the display list and get recorded commands.
+    pub fn into_commands(self) -> Vec<Command<P>> {
+        self.commands
+    }
+}
+
+/// Image recording painter operations into display lists.
+/// All the operations are applied to the inner canvas too.
+#[derive(Clone, Debug)]
+pub struct Recorder<P> {
+    canvas: Canvas<P>,
+    touched: Vec<bool>,
+    cleared: Option<P>,
+}
+
+impl<P> Recorder<P>
+where
+    P: Clone,
+{
+    /// Create new recorder over the canvas.
+    pub fn new(canvas: Canvas<P>) -> Self {
+        let touched = vec![false; (Image::width(&canvas) * Image::height(&canvas)) as usize];
+        let cleared = None;
+        Self {
+            canvas,
+            touched,
+            cleared,
+        }
+    }
+
+    /// Get reference to the inner canvas.
+    pub fn canvas(&self) -> &Canvas<P> {
+        &self.canvas
+    }
+
+    /// Consume the recorder and get the inner canvas.
+    pub fn into_canvas(self) -> Canvas<P> {
+        self.canvas
+    }
+
+    fn touch(&mut self, position: Vector<i32>) {
+        if let Some(touched) = self.index(position).and_then(|i| self.touched.get_mut(i)) {
+            *touched = true;
+        }
+    }
+
+    fn index(&self, position: Vector<i32>) -> Option<usize> {
+        let (width, height) = (Image::width(&self.canvas), Image::height(&self.canvas));
+        if position.x() < 0 || position.y() < 0 || position.x() >= width || position.y() >= height {
+            None
+        } else {
+            Some((position.x() + width * position.y()) as usize)
+        }
+    }
+
+    /// Finish recording the frame and get its display list.
+    /// Consecutive rows of equally placed spans are merged into blits.
+    pub fn finish(&mut self) -> DisplayList<P> {
+        let (width, height) = (Image::width(&self.canvas), Image::height(&self.canvas));
+        let mut commands = Vec::new();
+        if let Some(color) = self.cleared.take() {
+            commands.push(Command::Clear(color));
+        }
+
+        // Blits ending at the previous row as `(start, length, command index)`, ordered by start.
+        let mut open: Vec<(i32, i32, usize)> = Vec::new();
+        for y in 0..height {
+            let touched = &self.touched[(width * y) as usize..(width * (y + 1)) as usize];
+            let mut row = Vec::new();
+            let mut previous = open.iter().peekable();
+            let mut x = 0;
+            while x < width {
+                if !touched[x as usize] {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < width && touched[x as usize] {
+                    x += 1;
+                }
+                let length = x - start;
+                let texels = (start..x)
+                    // SAFETY: the span lies within canvas bounds.
+                    .map(|x| unsafe { self.canvas.unsafe_pixel(Vector::new(x, y)).clone() });
+                while previous
+                    .next_if(|(open_start, ..)| *open_start < start)
+                    .is_some()
+                {}
+                let index = match previous.next_if(|&&(open_start, open_length, _)| {
+                    (open_start, open_length) == (start, length)
+                }) {
+                    Some(&(.., index)) => {
+                        if let Command::Blit { texels: data, .. } = &mut commands[index] {
+                            data.extend(texels);
+                        }
+                        index
+                    }
+                    None => {
+                        commands.push(Command::Blit {
+                            origin: Vector::new(start, y),
+                            width: length,
+                            texels: texels.collect(),
+                        });
+                        commands.len() - 1
+                    }
+                };
+                row.push((start, length, index));
+            }
+            open = row;
+        }
+
+        let commands = commands
+            .into_iter()
+            .map(|command| match command {
+                Command::Blit {
+                    origin,
+                    width,
+                    texels,
+                } if texels.len() as i32 == width => Command::Span {
+                    start: origin,
+                    texels,
+                },
+                command => command,
+            })
+            .collect();
+
+        self.touched.fill(false);
+
+        DisplayList {
+            dimensions: Vector::new(width, height),
+            commands,
+        }
+    }
+}
+
+impl<'a, P> DesignatorRef<'a> for Recorder<P> {
+    type PixelRef = &'a P;
+}
+
+impl<P> Image for Recorder<P>
+where
+    P: Clone,
+{
+    type Pixel = P;
+
+    fn pixel(&self, position: Vector<i32>) -> Option<&P> {
+        self.canvas.pixel(position)
+    }
+
+    unsafe fn unsafe_pixel(&self, position: Vector<i32>) -> &P {
+        self.canvas.unsafe_pixel(position)
+    }
+
+    fn width(&self) -> i32 {
+        Image::width(&self.canvas)
+    }
+
+    fn height(&self) -> i32 {
+        Image::height(&self.canvas)
+    }
+
+    fn row(&self, y: i32) -> Option<&[P]> {
+        Image::row(&self.canvas, y)
+    }
+}
+
+impl<'a, P> DesignatorMut<'a> for Recorder<P> {
+    type PixelMut = &'a mut P;
+}
+
+impl<P> ImageMut for Recorder<P>
+where
+    P: Clone,
+{
+    fn pixel_mut(&mut self, position: Vector<i32>) -> Option<&mut P> {
+        self.touch(position);
+        self.canvas.pixel_mut(position)
+    }
+
+    unsafe fn unsafe_pixel_mut(&mut self, position: Vector<i32>) -> &mut P {
+        self.touch(position);
+        self.canvas.unsafe_pixel_mut(position)
+    }
+
+    fn clear(&mut self, color: P) {
+        self.touched.fill(false);
+        self.cleared = Some(color.clone());
+        self.canvas.clear(color);
+    }
+
+    fn row_mut(&mut self, y: i32) -> Option<&mut [P]> {
+        for x in 0..Image::width(&self.canvas) {
+            self.touch(Vector::new(x, y));
+        }
+        self.canvas.row_mut(y)
+    }
+
+    fn fast_horizontal_writer(&mut self) -> Option<impl FastHorizontalWriter<Self>> {
+        Some(RecorderFastHorizontalWriter { recorder: self })
+    }
+}
+
+impl<P> RenderSurface for Recorder<P>
+where
+    P: Clone,
+{
+    type Data = P;
+
+    fn width(&self) -> usize {
+        RenderSurface::width(&self.canvas)
+    }
+
+    fn height(&self) -> usize {
+        RenderSurface::height(&self.canvas)
+    }
+
+    fn data(&self, x: usize, y: usize) -> P {
+        self.canvas.data(x, y)
+    }
+
+    fn row(&self, y: usize) -> Option<&[P]> {
+        RenderSurface::row(&self.canvas, y)
+    }
+}
+
+struct RecorderFastHorizontalWriter<'a, P> {
+    recorder: &'a mut Recorder<P>,
+}
+
+impl<P> FastHorizontalWriter<Recorder<P>> for RecorderFastHorizontalWriter<'_, P>
+where
+    P: Clone,
+{
+    fn write_line<F: FnMut(i32, i32, P) -> P>(
+        &mut self,
+        x: RangeInclusive<i32>,
+        y: i32,
+        function: &mut F,
+    ) {
+        let (start, end) = (*x.start().min(x.end()), *x.start().max(x.end()));
+        let start = start.max(0);
+        let end = end.min(Image::width(self.recorder) - 1);
+        for x in start..=end {
+            let position = Vector::new(x, y);
+            self.recorder.touch(position);
+            if let Some(pixel) = self.recorder.canvas.pixel_mut(position) {
+                *pixel = function(x, y, pixel.clone());
+            }
+        }
+    }
+}
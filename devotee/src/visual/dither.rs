@@ -0,0 +1,81 @@
+use crate::util::vector::Vector;
+
+/// Threshold pattern used to pick between two pixel values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Pattern {
+    /// Ordered dithering with 2x2 Bayer matrix.
+    Bayer2,
+    /// Ordered dithering with 4x4 Bayer matrix.
+    #[default]
+    Bayer4,
+    /// Ordered dithering with 8x8 Bayer matrix.
+    Bayer8,
+    /// Blue-noise-like thresholds from the R2 low-discrepancy sequence.
+    /// Avoids the regular cross-hatch look of the Bayer matrices.
+    BlueNoise,
+}
+
+impl Pattern {
+    /// Get threshold in the `[0.0, 1.0)` range for the pixel at `position`.
+    pub fn threshold(self, position: Vector<i32>) -> f32 {
+        match self {
+            Pattern::Bayer2 => bayer(1, position) as f32 / 4.0,
+            Pattern::Bayer4 => bayer(2, position) as f32 / 16.0,
+            Pattern::Bayer8 => bayer(3, position) as f32 / 64.0,
+            Pattern::BlueNoise => {
+                // Coefficients are reciprocals of the plastic number and its square.
+                let (x, y) = (position.x() as f64, position.y() as f64);
+                (0.5 + x * 0.754_877_666_246_692_7 + y * 0.569_840_290_998_053_2).rem_euclid(1.0)
+                    as f32
+            }
+        }
+    }
+
+    /// Check if the pixel at `position` is lit with the `level` share of lit pixels.
+    pub fn lit(self, level: f32, position: Vector<i32>) -> bool {
+        level > self.threshold(position)
+    }
+}
+
+/// Get value of the Bayer matrix with side of `2^order` at `position`, repeated over the plane.
+pub(super) fn bayer(order: u32, position: Vector<i32>) -> u32 {
+    const BASE: [[u32; 2]; 2] = [[0, 2], [3, 1]];
+    let (mut x, mut y) = (position.x() as u32, position.y() as u32);
+    let mut value = 0;
+    for _ in 0..order {
+        value = value * 4 + BASE[(y & 1) as usize][(x & 1) as usize];
+        (x, y) = (x >> 1, y >> 1);
+    }
+    value
+}
+
+/// Helper paint function choosing between `below` and `above` values,
+/// so that `level` share of the painted pixels is `above`.
+pub fn dither<P>(pattern: Pattern, level: f32, below: P, above: P) -> impl FnMut(i32, i32, P) -> P
+where
+    P: Clone,
+{
+    dither_by(pattern, below, above, move |_, _| level)
+}
+
+/// Helper paint function choosing between `below` and `above` values,
+/// with the share of `above` pixels determined by the `level` function of the pixel position.
+pub fn dither_by<P, L>(
+    pattern: Pattern,
+    below: P,
+    above: P,
+    level: L,
+) -> impl FnMut(i32, i32, P) -> P
+where
+    P: Clone,
+    L: FnMut(i32, i32) -> f32,
+{
+    let mut level = level;
+    move |x, y, _| {
+        if pattern.lit(level(x, y), Vector::new(x, y)) {
+            above.clone()
+        } else {
+            below.clone()
+        }
+    }
+}
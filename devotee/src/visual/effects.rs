@@ -0,0 +1,202 @@
+use std::f32::consts::TAU;
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
+
+use crate::util::rng::Rng;
+use crate::util::vector::Vector;
+
+use super::compositor::Layer;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{Image, ImageMut, Paint, Painter};
+
+/// Screen shake producing random offsets decaying over time.
+/// The shake strength is the square of the accumulated trauma, so small impacts barely move the screen.
+#[derive(Clone, Debug)]
+pub struct ScreenShake {
+    rng: Rng,
+    trauma: f32,
+    decay: f32,
+    amplitude: Vector<f32>,
+    offset: Vector<i32>,
+}
+
+impl ScreenShake {
+    /// Create new calm screen shake with random generator `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            trauma: 0.0,
+            decay: 1.0,
+            amplitude: Vector::new(8.0, 8.0),
+            offset: Vector::new(0, 0),
+        }
+    }
+
+    /// Set maximal offset along each axis at full trauma.
+    pub fn with_amplitude(self, amplitude: Vector<f32>) -> Self {
+        Self { amplitude, ..self }
+    }
+
+    /// Set trauma lost per second.
+    pub fn with_decay(self, decay: f32) -> Self {
+        Self {
+            decay: decay.max(0.0),
+            ..self
+        }
+    }
+
+    /// Add `amount` of trauma, clamping the total to `1.0`.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Get current trauma in the `[0.0, 1.0]` range.
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Check if the shake is active.
+    pub fn is_active(&self) -> bool {
+        self.trauma > 0.0
+    }
+
+    /// Stop the shake immediately.
+    pub fn stop(&mut self) {
+        self.trauma = 0.0;
+        self.offset = Vector::new(0, 0);
+    }
+
+    /// Decay the trauma by `delta` time and pick new offset.
+    pub fn update(&mut self, delta: Duration) {
+        self.trauma = (self.trauma - self.decay * delta.as_secs_f32()).max(0.0);
+        let strength = self.trauma * self.trauma;
+        let mut random = || self.rng.next_f32() * 2.0 - 1.0;
+        let (x, y) = (random(), random());
+        self.offset = Vector::new(
+            (self.amplitude.x() * strength * x).round() as i32,
+            (self.amplitude.y() * strength * y).round() as i32,
+        );
+    }
+
+    /// Get current shake offset.
+    pub fn offset(&self) -> Vector<i32> {
+        self.offset
+    }
+
+    /// Add current shake offset to the `painter` offset.
+    pub fn apply<T>(&self, painter: &mut Painter<T, i32>)
+    where
+        T: ImageMut,
+    {
+        painter.set_offset(painter.offset() + self.offset);
+    }
+
+    /// Place the compositor `layer` at `origin` displaced by current shake offset.
+    pub fn apply_layer<P>(&self, layer: &mut Layer<P>, origin: Vector<i32>)
+    where
+        P: Clone,
+    {
+        layer.set_offset(origin + self.offset);
+    }
+}
+
+/// Kind of the displacement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Distortion {
+    /// Shift rows horizontally along the sine wave.
+    #[default]
+    Wave,
+    /// Shift columns vertically along the irregular shimmer, as over the hot surface.
+    Heat,
+}
+
+/// Displacement remapping rows or columns of the image while blitting it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Displacement {
+    distortion: Distortion,
+    amplitude: f32,
+    wavelength: f32,
+    speed: f32,
+    phase: f32,
+}
+
+impl Displacement {
+    /// Create new displacement of the `distortion` kind.
+    pub fn new(distortion: Distortion) -> Self {
+        Self {
+            distortion,
+            amplitude: 2.0,
+            wavelength: 16.0,
+            speed: 1.0,
+            phase: 0.0,
+        }
+    }
+
+    /// Set maximal shift in pixels.
+    pub fn with_amplitude(self, amplitude: f32) -> Self {
+        Self { amplitude, ..self }
+    }
+
+    /// Set length of the single wave in pixels.
+    pub fn with_wavelength(self, wavelength: f32) -> Self {
+        Self {
+            wavelength: wavelength.max(f32::EPSILON),
+            ..self
+        }
+    }
+
+    /// Set amount of wave periods passed per second.
+    pub fn with_speed(self, speed: f32) -> Self {
+        Self { speed, ..self }
+    }
+
+    /// Get the displacement kind.
+    pub fn distortion(&self) -> Distortion {
+        self.distortion
+    }
+
+    /// Advance the wave by `delta` time.
+    pub fn update(&mut self, delta: Duration) {
+        self.phase = (self.phase + self.speed * delta.as_secs_f32()).rem_euclid(1.0);
+    }
+
+    /// Get shift of the row or the column at `line`.
+    pub fn shift(&self, line: i32) -> i32 {
+        let angle = TAU * (line as f32 / self.wavelength + self.phase);
+        let wave = match self.distortion {
+            Distortion::Wave => angle.sin(),
+            Distortion::Heat => (angle.sin() + 0.5 * (2.3 * angle + TAU * self.phase).sin()) / 1.5,
+        };
+        (self.amplitude * wave).round() as i32
+    }
+
+    /// Blit the `image` at `at` onto the `painter` with rows or columns displaced.
+    /// Samples outside of the image are clamped to its edges.
+    pub fn blit<T, U>(&self, painter: &mut Painter<T, i32>, at: Vector<i32>, image: &U)
+    where
+        T: ImageMut,
+        T::Pixel: Clone,
+        U: Image<Pixel = T::Pixel> + ?Sized,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+        for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+    {
+        let dimensions = image.dimensions();
+        if dimensions.x() <= 0 || dimensions.y() <= 0 {
+            return;
+        }
+        let origin = at + painter.offset();
+        painter.rect_f((at, dimensions), |x, y, pixel| {
+            let local = Vector::new(x, y) - origin;
+            let source = match self.distortion {
+                Distortion::Wave => Vector::new(local.x() - self.shift(local.y()), local.y()),
+                Distortion::Heat => Vector::new(local.x(), local.y() - self.shift(local.x())),
+            };
+            let source = Vector::new(
+                source.x().clamp(0, dimensions.x() - 1),
+                source.y().clamp(0, dimensions.y() - 1),
+            );
+            image.pixel(source).map_or(pixel, |value| value.clone())
+        });
+    }
+}
@@ -0,0 +1,389 @@
+use std::collections::HashMap;
+use std::ops::{Deref, DerefMut};
+
+use crate::util::vector::Vector;
+
+use super::canvas::Canvas;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::view::View;
+use super::{Image, ImageMut, Painter};
+
+#[derive(Clone, Copy, Debug)]
+struct Glyph {
+    origin: Vector<i32>,
+    advance: i32,
+}
+
+/// Monospace-grid bitmap font with per-glyph advance and kerning.
+#[derive(Clone, Debug)]
+pub struct BitmapFont<P> {
+    image: Canvas<P>,
+    cell: Vector<i32>,
+    glyphs: HashMap<char, Glyph>,
+    kerning: HashMap<(char, char), i32>,
+    line_height: i32,
+    spacing: i32,
+}
+
+impl<P> BitmapFont<P>
+where
+    P: Clone,
+{
+    /// Create new font from the `image` split into a grid of `cell` sized glyphs.
+    /// Glyphs are assigned to the `characters` row by row, left to right.
+    /// Extra characters not fitting into the grid are ignored.
+    /// Each glyph advances by the cell width and lines are spaced by the cell height by default.
+    pub fn from_grid(image: Canvas<P>, cell: Vector<i32>, characters: &str) -> Self {
+        let cell = cell.individual_max((1, 1));
+        let columns = image.width() / cell.x();
+        let rows = image.height() / cell.y();
+        let glyphs = characters
+            .chars()
+            .take((columns * rows).max(0) as usize)
+            .enumerate()
+            .map(|(index, code_point)| {
+                let index = index as i32;
+                let origin = Vector::new(index % columns * cell.x(), index / columns * cell.y());
+                let advance = cell.x();
+                (code_point, Glyph { origin, advance })
+            })
+            .collect();
+        let kerning = HashMap::new();
+        let line_height = cell.y();
+        let spacing = 0;
+        Self {
+            image,
+            cell,
+            glyphs,
+            kerning,
+            line_height,
+            spacing,
+        }
+    }
+
+    /// Get new font with the `advance` set for the `code_point` glyph.
+    pub fn with_advance(mut self, code_point: char, advance: i32) -> Self {
+        self.set_advance(code_point, advance);
+        self
+    }
+
+    /// Set the `advance` for the `code_point` glyph.
+    pub fn set_advance(&mut self, code_point: char, advance: i32) -> &mut Self {
+        if let Some(glyph) = self.glyphs.get_mut(&code_point) {
+            glyph.advance = advance;
+        }
+        self
+    }
+
+    /// Get new font with the kerning `adjustment` applied between `left` and `right` glyphs.
+    pub fn with_kerning(mut self, left: char, right: char, adjustment: i32) -> Self {
+        self.set_kerning(left, right, adjustment);
+        self
+    }
+
+    /// Set the kerning `adjustment` applied between `left` and `right` glyphs.
+    pub fn set_kerning(&mut self, left: char, right: char, adjustment: i32) -> &mut Self {
+        self.kerning.insert((left, right), adjustment);
+        self
+    }
+
+    /// Get new font with desired distance between consecutive baselines.
+    pub fn with_line_height(self, line_height: i32) -> Self {
+        Self {
+            line_height,
+            ..self
+        }
+    }
+
+    /// Get new font with extra `spacing` added after each glyph.
+    pub fn with_spacing(self, spacing: i32) -> Self {
+        Self { spacing, ..self }
+    }
+
+    /// Get the glyph cell dimensions.
+    pub fn cell(&self) -> Vector<i32> {
+        self.cell
+    }
+
+    /// Get distance between consecutive baselines.
+    pub fn line_height(&self) -> i32 {
+        self.line_height
+    }
+
+    /// Get extra spacing added after each glyph.
+    pub fn spacing(&self) -> i32 {
+        self.spacing
+    }
+
+    /// Check if the font has a glyph for the `code_point`.
+    pub fn contains(&self, code_point: char) -> bool {
+        self.glyphs.contains_key(&code_point)
+    }
+
+    /// Get view into the `code_point` glyph image.
+    pub fn glyph(&self, code_point: char) -> Option<View<&Canvas<P>>> {
+        let glyph = self.glyphs.get(&code_point)?;
+        Some(self.image.view(glyph.origin, self.cell))
+    }
+
+    /// Get horizontal advance of the `code_point` glyph, including spacing.
+    /// Unknown glyphs do not advance.
+    pub fn advance(&self, code_point: char) -> i32 {
+        self.glyphs
+            .get(&code_point)
+            .map_or(0, |glyph| glyph.advance + self.spacing)
+    }
+
+    /// Get kerning adjustment between `left` and `right` glyphs.
+    pub fn kerning(&self, left: char, right: char) -> i32 {
+        self.kerning.get(&(left, right)).copied().unwrap_or(0)
+    }
+
+    /// Measure dimensions of the `text` laid out with the `style`.
+    pub fn measure(&self, text: &str, style: TextStyle) -> Vector<i32> {
+        let lines = self.layout(text, style.wrap_width);
+        let width = lines.iter().map(|line| line.width).max().unwrap_or(0);
+        let width = style.wrap_width.map_or(width, |wrap| width.max(wrap));
+        Vector::new(width, self.lines_height(lines.len(), style))
+    }
+
+    fn lines_height(&self, lines: usize, style: TextStyle) -> i32 {
+        if lines == 0 {
+            0
+        } else {
+            (lines as i32 - 1) * (self.line_height + style.line_spacing) + self.cell.y()
+        }
+    }
+
+    fn width(&self, glyphs: &[(usize, char)]) -> i32 {
+        let mut width = 0;
+        let mut previous = None;
+        for &(_, code_point) in glyphs {
+            if let Some(previous) = previous {
+                width += self.kerning(previous, code_point);
+            }
+            width += self.advance(code_point);
+            previous = Some(code_point);
+        }
+        // Spacing after the last glyph does not contribute to the line width.
+        if previous.is_some() {
+            width -= self.spacing;
+        }
+        width
+    }
+
+    fn layout(&self, text: &str, wrap_width: Option<i32>) -> Vec<Line> {
+        let mut lines = Vec::new();
+        let text: Vec<(usize, char)> = text.chars().enumerate().collect();
+        for paragraph in text.split(|(_, code_point)| *code_point == '\n') {
+            let glyphs = paragraph.to_vec();
+            let Some(wrap_width) = wrap_width else {
+                lines.push(self.line(glyphs));
+                continue;
+            };
+            let mut current: Vec<(usize, char)> = Vec::new();
+            for word in glyphs.split_inclusive(|(_, code_point)| *code_point == ' ') {
+                let mut candidate = current.clone();
+                candidate.extend_from_slice(word);
+                if current.is_empty() || self.width(trim(&candidate)) <= wrap_width {
+                    current = candidate;
+                } else {
+                    lines.push(self.line(std::mem::take(&mut current)));
+                    current.extend_from_slice(word);
+                }
+                // Break words that do not fit on a line on their own.
+                while self.width(trim(&current)) > wrap_width && current.len() > 1 {
+                    let mut split = 1;
+                    while split < current.len() && self.width(&current[..=split]) <= wrap_width {
+                        split += 1;
+                    }
+                    let rest = current.split_off(split);
+                    lines.push(self.line(std::mem::replace(&mut current, rest)));
+                }
+            }
+            lines.push(self.line(current));
+        }
+        lines
+    }
+
+    fn line(&self, mut glyphs: Vec<(usize, char)>) -> Line {
+        let length = trim(&glyphs).len();
+        glyphs.truncate(length);
+        let width = self.width(&glyphs);
+        Line { glyphs, width }
+    }
+}
+
+fn trim(glyphs: &[(usize, char)]) -> &[(usize, char)] {
+    let length = glyphs
+        .iter()
+        .rposition(|(_, code_point)| *code_point != ' ')
+        .map_or(0, |position| position + 1);
+    &glyphs[..length]
+}
+
+struct Line {
+    glyphs: Vec<(usize, char)>,
+    width: i32,
+}
+
+/// Horizontal alignment of text lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Alignment {
+    /// Align lines to the left edge.
+    #[default]
+    Left,
+    /// Center lines.
+    Center,
+    /// Align lines to the right edge.
+    Right,
+}
+
+/// Layout options for the styled text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TextStyle {
+    alignment: Alignment,
+    wrap_width: Option<i32>,
+    line_spacing: i32,
+}
+
+impl TextStyle {
+    /// Create new style with left alignment and no wrapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get new style with desired alignment.
+    /// Lines are aligned within the wrap width if set, within the widest line otherwise.
+    pub fn with_alignment(self, alignment: Alignment) -> Self {
+        Self { alignment, ..self }
+    }
+
+    /// Get new style wrapping lines at word boundaries to fit into `width`.
+    /// Words wider than `width` are broken between glyphs.
+    pub fn with_wrap_width(self, width: i32) -> Self {
+        Self {
+            wrap_width: Some(width),
+            ..self
+        }
+    }
+
+    /// Get new style with extra spacing between lines.
+    pub fn with_line_spacing(self, line_spacing: i32) -> Self {
+        Self {
+            line_spacing,
+            ..self
+        }
+    }
+
+    /// Get the alignment.
+    pub fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// Get the wrap width, if any.
+    pub fn wrap_width(&self) -> Option<i32> {
+        self.wrap_width
+    }
+
+    /// Get extra spacing between lines.
+    pub fn line_spacing(&self) -> i32 {
+        self.line_spacing
+    }
+}
+
+/// Description of the glyph being drawn with `Painter::text_styled`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlyphInfo {
+    code_point: char,
+    index: usize,
+    line: usize,
+}
+
+impl GlyphInfo {
+    /// Get code point of the glyph.
+    pub fn code_point(&self) -> char {
+        self.code_point
+    }
+
+    /// Get index of the glyph code point in the source text.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get index of the laid out line containing the glyph.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+/// Helper glyph function for `bool` fonts.
+/// It replaces pixels covered by the glyph with `value`.
+pub fn ink<P>(value: P) -> impl FnMut(GlyphInfo, i32, i32, P, i32, i32, bool) -> P
+where
+    P: Clone,
+{
+    move |_, _, _, pixel, _, _, glyph| if glyph { value.clone() } else { pixel }
+}
+
+impl<T> Painter<'_, T, i32>
+where
+    T: ImageMut,
+    T::Pixel: Clone,
+    for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+    for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+{
+    /// Draw `text` with the bitmap `font` laid out according to the `style` with its top left corner at `at`.
+    /// The function receives the glyph description along with the pixel position, its original value,
+    /// position inside the glyph and the glyph pixel value.
+    pub fn text_styled<P, F>(
+        &mut self,
+        at: Vector<i32>,
+        font: &BitmapFont<P>,
+        text: &str,
+        style: TextStyle,
+        function: F,
+    ) where
+        P: Clone,
+        F: FnMut(GlyphInfo, i32, i32, T::Pixel, i32, i32, P) -> T::Pixel,
+    {
+        let mut function = function;
+        let lines = font.layout(text, style.wrap_width);
+        let width = style
+            .wrap_width
+            .unwrap_or_else(|| lines.iter().map(|line| line.width).max().unwrap_or(0));
+        let mut y = at.y();
+        for (line_index, line) in lines.iter().enumerate() {
+            let mut x = at.x()
+                + match style.alignment {
+                    Alignment::Left => 0,
+                    Alignment::Center => (width - line.width) / 2,
+                    Alignment::Right => width - line.width,
+                };
+            let mut previous = None;
+            for &(index, code_point) in &line.glyphs {
+                if let Some(previous) = previous {
+                    x += font.kerning(previous, code_point);
+                }
+                previous = Some(code_point);
+                let Some(glyph) = font.glyph(code_point) else {
+                    continue;
+                };
+                let info = GlyphInfo {
+                    code_point,
+                    index,
+                    line: line_index,
+                };
+                self.image(
+                    Vector::new(x, y),
+                    &glyph,
+                    |x, y, pixel, glyph_x, glyph_y, value| {
+                        function(info, x, y, pixel, glyph_x, glyph_y, value)
+                    },
+                );
+                x += font.advance(code_point);
+            }
+            y += font.line_height + style.line_spacing;
+        }
+    }
+}
@@ -43,6 +43,28 @@ pub trait Image: for<'a> DesignatorRef<'a> {
     /// Get height of this image.
     fn height(&self) -> i32;
 
+    /// Get contiguous row of pixels at `y` for bulk access.
+    /// Returns `None` if the row is out of bounds or the image does not store its rows contiguously.
+    fn row(&self, y: i32) -> Option<&[Self::Pixel]> {
+        let _ = y;
+        None
+    }
+
+    /// Get iterator over contiguous rows of pixels from top to bottom.
+    /// Stops at the first row not stored contiguously.
+    fn rows(&self) -> impl Iterator<Item = &[Self::Pixel]> {
+        (0..self.height()).map_while(|y| self.row(y))
+    }
+
+    /// Get iterator over pixels with their positions, row by row.
+    fn pixels(&self) -> impl Iterator<Item = (Vector<i32>, PixelRef<'_, Self>)> {
+        let width = self.width().max(0);
+        (0..self.height().max(0))
+            .flat_map(move |y| (0..width).map(move |x| Vector::new(x, y)))
+            // SAFETY: positions are within the image dimensions.
+            .map(|position| (position, unsafe { self.unsafe_pixel(position) }))
+    }
+
     /// Get dimensions of this image.
     fn dimensions(&self) -> Vector<i32> {
         Vector::new(self.width(), self.height())
@@ -69,6 +91,19 @@ pub trait ImageMut: Image + for<'a> DesignatorMut<'a> {
     /// Clear this image with color provided.
     fn clear(&mut self, color: Self::Pixel);
 
+    /// Get mutable contiguous row of pixels at `y` for bulk access.
+    /// Returns `None` if the row is out of bounds or the image does not store its rows contiguously.
+    fn row_mut(&mut self, y: i32) -> Option<&mut [Self::Pixel]> {
+        let _ = y;
+        None
+    }
+
+    /// Get iterator over mutable contiguous rows of pixels from top to bottom.
+    /// Yields nothing if the image does not store its rows contiguously.
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut [Self::Pixel]> {
+        std::iter::empty()
+    }
+
     /// Get optional `FastHorizontalWriter` for faster horizontal line drawing.
     fn fast_horizontal_writer(&mut self) -> Option<impl FastHorizontalWriter<Self>> {
         None::<FastHorizontalWriterPlaceholder>
@@ -85,6 +120,15 @@ pub trait ImageMut: Image + for<'a> DesignatorMut<'a> {
     }
 }
 
+/// Image owning its pixels.
+pub trait OwnedImage: ImageMut + Sized {
+    /// Create new image with `width` and `height`, getting each pixel by its position from the `function`.
+    /// Returns `None` if the image type can not have such dimensions.
+    fn from_fn<F>(width: usize, height: usize, function: F) -> Option<Self>
+    where
+        F: FnMut(Vector<i32>) -> Self::Pixel;
+}
+
 struct FastHorizontalWriterPlaceholder;
 
 impl<I> FastHorizontalWriter<I> for FastHorizontalWriterPlaceholder
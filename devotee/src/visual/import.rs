@@ -0,0 +1,145 @@
+#[cfg(feature = "png-import")]
+use std::time::Duration;
+
+use super::canvas::Canvas;
+use super::image::ImageMut;
+use crate::util::vector::Vector;
+
+/// Animated GIF decoding.
+#[cfg(feature = "gif-import")]
+pub mod gif;
+/// Loading and saving of static PNG images.
+#[cfg(feature = "image-io")]
+pub mod io;
+/// Animated PNG decoding.
+#[cfg(feature = "png-import")]
+pub mod png;
+
+/// Image import error enumeration.
+#[derive(Debug)]
+pub enum ImportError {
+    /// GIF decoding error.
+    #[cfg(feature = "gif-import")]
+    GifError(::gif::DecodingError),
+
+    /// PNG decoding error.
+    #[cfg(feature = "png-import")]
+    PngError(::png::DecodingError),
+
+    /// PNG encoding error.
+    #[cfg(feature = "image-io")]
+    PngEncodingError(::png::EncodingError),
+
+    /// File input or output error.
+    #[cfg(feature = "image-io")]
+    IoError(std::io::Error),
+
+    /// Decoded image dimensions differ from the expected ones.
+    #[cfg(feature = "image-io")]
+    DimensionsMismatch,
+
+    /// Decoded image uses unsupported pixel format.
+    UnsupportedFormat,
+}
+
+/// How the frame area is treated before the next frame is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Disposal {
+    Keep,
+    Clear,
+    Restore,
+}
+
+/// Full-size `[r, g, b, a]` buffer frames are composed on.
+struct Screen {
+    width: usize,
+    height: usize,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Screen {
+    fn new(width: usize, height: usize) -> Self {
+        let pixels = vec![[0; 4]; width * height];
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Draw the frame region, calling `blend` with the screen and frame pixels.
+    fn draw<F>(
+        &mut self,
+        origin: (usize, usize),
+        dimensions: (usize, usize),
+        pixel: impl Fn(usize, usize) -> [u8; 4],
+        blend: F,
+    ) where
+        F: Fn([u8; 4], [u8; 4]) -> [u8; 4],
+    {
+        for y in 0..dimensions.1 {
+            for x in 0..dimensions.0 {
+                let (screen_x, screen_y) = (origin.0 + x, origin.1 + y);
+                if screen_x < self.width && screen_y < self.height {
+                    let index = screen_x + screen_y * self.width;
+                    self.pixels[index] = blend(self.pixels[index], pixel(x, y));
+                }
+            }
+        }
+    }
+
+    fn clear(&mut self, origin: (usize, usize), dimensions: (usize, usize)) {
+        self.draw(origin, dimensions, |_, _| [0; 4], |_, clear| clear);
+    }
+
+    fn to_canvas<P, F>(&self, mapper: &mut F) -> Canvas<P>
+    where
+        P: Clone,
+        F: FnMut([u8; 4]) -> P,
+    {
+        let mut canvas = Canvas::with_resolution(mapper([0; 4]), self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let color = self.pixels[x + y * self.width];
+                if let Some(pixel) = canvas.pixel_mut(Vector::new(x as i32, y as i32)) {
+                    *pixel = mapper(color);
+                }
+            }
+        }
+        canvas
+    }
+}
+
+/// Blend `above` pixel over `below` one according to its alpha.
+#[cfg(feature = "png-import")]
+fn over(below: [u8; 4], above: [u8; 4]) -> [u8; 4] {
+    match above[3] {
+        0 => below,
+        255 => above,
+        alpha => {
+            let alpha = alpha as u32;
+            let below_alpha = below[3] as u32 * (255 - alpha) / 255;
+            let total = alpha + below_alpha;
+            let channel = |index: usize| {
+                ((above[index] as u32 * alpha + below[index] as u32 * below_alpha) / total) as u8
+            };
+            [channel(0), channel(1), channel(2), total as u8]
+        }
+    }
+}
+
+/// Helper mapper keeping `[r, g, b, a]` pixels as is.
+pub fn rgba() -> impl FnMut([u8; 4]) -> [u8; 4] {
+    |color| color
+}
+
+/// Helper mapper converting `[r, g, b, a]` pixels into `0xaa_rr_gg_bb` values.
+pub fn argb() -> impl FnMut([u8; 4]) -> u32 {
+    |[r, g, b, a]| u32::from_be_bytes([a, r, g, b])
+}
+
+#[cfg(feature = "png-import")]
+fn delay(numerator: u16, denominator: u16) -> Duration {
+    let denominator = if denominator == 0 { 100 } else { denominator };
+    Duration::from_secs_f64(numerator as f64 / denominator as f64)
+}
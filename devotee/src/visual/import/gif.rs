@@ -0,0 +1,67 @@
+use std::io::Read;
+use std::time::Duration;
+
+use gif::{ColorOutput, DecodeOptions, DecodingError, DisposalMethod, Repeat};
+
+use super::{Disposal, ImportError, Screen};
+use crate::visual::animation::{Animation, Looping};
+
+impl From<DecodingError> for ImportError {
+    fn from(value: DecodingError) -> Self {
+        Self::GifError(value)
+    }
+}
+
+/// Decode animated GIF into the animation of fully composed frames.
+/// The `mapper` converts `[r, g, b, a]` pixels into the desired pixel type, e.g. palette indices.
+pub fn decode<R, P, F>(reader: R, mapper: F) -> Result<Animation<P>, ImportError>
+where
+    R: Read,
+    P: Clone,
+    F: FnMut([u8; 4]) -> P,
+{
+    let mut mapper = mapper;
+    let mut options = DecodeOptions::new();
+    options.set_color_output(ColorOutput::RGBA);
+    let mut decoder = options.read_info(reader)?;
+    let looping = match decoder.repeat() {
+        Repeat::Infinite => Looping::Infinite,
+        Repeat::Finite(count) => Looping::Finite(count as u32 + 1),
+    };
+    let mut screen = Screen::new(decoder.width() as usize, decoder.height() as usize);
+    let mut frames = Vec::new();
+
+    while let Some(frame) = decoder.read_next_frame()? {
+        let origin = (frame.left as usize, frame.top as usize);
+        let dimensions = (frame.width as usize, frame.height as usize);
+        let disposal = match frame.dispose {
+            DisposalMethod::Any | DisposalMethod::Keep => Disposal::Keep,
+            DisposalMethod::Background => Disposal::Clear,
+            DisposalMethod::Previous => Disposal::Restore,
+        };
+        let previous = (disposal == Disposal::Restore).then(|| screen.pixels.clone());
+
+        let buffer = &frame.buffer;
+        let width = dimensions.0;
+        screen.draw(
+            origin,
+            dimensions,
+            |x, y| {
+                let index = (x + y * width) * 4;
+                buffer
+                    .get(index..index + 4)
+                    .map_or([0; 4], |color| [color[0], color[1], color[2], color[3]])
+            },
+            |below, above| if above[3] == 0 { below } else { above },
+        );
+        let delay = Duration::from_millis(frame.delay as u64 * 10);
+        frames.push((screen.to_canvas(&mut mapper), delay));
+
+        match (disposal, previous) {
+            (Disposal::Clear, _) => screen.clear(origin, dimensions),
+            (Disposal::Restore, Some(previous)) => screen.pixels = previous,
+            _ => (),
+        }
+    }
+    Ok(Animation::new(frames).with_looping(looping))
+}
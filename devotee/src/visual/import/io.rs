@@ -0,0 +1,133 @@
+use std::array;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+use png::{BitDepth, ColorType, Encoder, EncodingError};
+
+use super::ImportError;
+use crate::util::vector::Vector;
+use crate::visual::canvas::Canvas;
+use crate::visual::image::Image;
+use crate::visual::sprite::Sprite;
+
+impl From<std::io::Error> for ImportError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl From<EncodingError> for ImportError {
+    fn from(value: EncodingError) -> Self {
+        Self::PngEncodingError(value)
+    }
+}
+
+fn save<P, Q, F>(
+    path: Q,
+    width: usize,
+    height: usize,
+    pixel: impl Fn(usize, usize) -> P,
+    mapper: F,
+) -> Result<(), ImportError>
+where
+    Q: AsRef<Path>,
+    F: FnMut(P) -> [u8; 4],
+{
+    let mut mapper = mapper;
+    let writer = BufWriter::new(File::create(path)?);
+    let mut encoder = Encoder::new(writer, width as u32, height as u32);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    let mut data = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            data.extend_from_slice(&mapper(pixel(x, y)));
+        }
+    }
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(())
+}
+
+impl<P> Canvas<P>
+where
+    P: Clone,
+{
+    /// Load the PNG image from the file at `path`.
+    /// Only the default image of animated PNG files is loaded.
+    /// The `mapper` converts `[r, g, b, a]` pixels into the desired pixel type, e.g. palette indices.
+    pub fn load_png<Q, F>(path: Q, mapper: F) -> Result<Self, ImportError>
+    where
+        Q: AsRef<Path>,
+        F: FnMut([u8; 4]) -> P,
+    {
+        let reader = BufReader::new(File::open(path)?);
+        let animation = super::png::decode(reader, mapper)?;
+        animation
+            .frames()
+            .first()
+            .map(|(canvas, _)| canvas.clone())
+            .ok_or(ImportError::UnsupportedFormat)
+    }
+
+    /// Save the canvas as an 8-bit RGBA PNG image into the file at `path`.
+    /// The `mapper` converts pixels into `[r, g, b, a]` values.
+    pub fn save_png<Q, F>(&self, path: Q, mapper: F) -> Result<(), ImportError>
+    where
+        Q: AsRef<Path>,
+        F: FnMut(P) -> [u8; 4],
+    {
+        let pixel = |x: usize, y: usize| {
+            // SAFETY: the position lies within the canvas bounds.
+            unsafe { self.unsafe_pixel(Vector::new(x as i32, y as i32)).clone() }
+        };
+        save(
+            path,
+            self.width() as usize,
+            self.height() as usize,
+            pixel,
+            mapper,
+        )
+    }
+}
+
+impl<P, const W: usize, const H: usize> Sprite<P, W, H>
+where
+    P: Copy,
+{
+    /// Load the PNG image of exactly `W` by `H` pixels from the file at `path`.
+    /// The `mapper` converts `[r, g, b, a]` pixels into the desired pixel type, e.g. palette indices.
+    pub fn load_png<Q, F>(path: Q, mapper: F) -> Result<Self, ImportError>
+    where
+        Q: AsRef<Path>,
+        F: FnMut([u8; 4]) -> P,
+    {
+        let canvas = Canvas::load_png(path, mapper)?;
+        if canvas.width() as usize != W || canvas.height() as usize != H {
+            return Err(ImportError::DimensionsMismatch);
+        }
+        let data = array::from_fn(|y| {
+            array::from_fn(|x| {
+                // SAFETY: the position lies within the canvas bounds.
+                unsafe { *canvas.unsafe_pixel(Vector::new(x as i32, y as i32)) }
+            })
+        });
+        Ok(Self::with_data(data))
+    }
+
+    /// Save the sprite as an 8-bit RGBA PNG image into the file at `path`.
+    /// The `mapper` converts pixels into `[r, g, b, a]` values.
+    pub fn save_png<Q, F>(&self, path: Q, mapper: F) -> Result<(), ImportError>
+    where
+        Q: AsRef<Path>,
+        F: FnMut(P) -> [u8; 4],
+    {
+        let pixel = |x: usize, y: usize| {
+            // SAFETY: the position lies within the sprite bounds.
+            unsafe { *self.unsafe_pixel(Vector::new(x as i32, y as i32)) }
+        };
+        save(path, W, H, pixel, mapper)
+    }
+}
@@ -0,0 +1,96 @@
+use std::io::Read;
+
+use png::{BlendOp, ColorType, Decoder, DecodingError, DisposeOp, Transformations};
+
+use super::{delay, over, Disposal, ImportError, Screen};
+use crate::visual::animation::{Animation, Looping};
+
+impl From<DecodingError> for ImportError {
+    fn from(value: DecodingError) -> Self {
+        Self::PngError(value)
+    }
+}
+
+/// Decode animated PNG into the animation of fully composed frames.
+/// Static PNG images are decoded as single frame animations.
+/// The `mapper` converts `[r, g, b, a]` pixels into the desired pixel type, e.g. palette indices.
+pub fn decode<R, P, F>(reader: R, mapper: F) -> Result<Animation<P>, ImportError>
+where
+    R: Read,
+    P: Clone,
+    F: FnMut([u8; 4]) -> P,
+{
+    let mut mapper = mapper;
+    let mut decoder = Decoder::new(reader);
+    decoder.set_transformations(Transformations::normalize_to_color8() | Transformations::ALPHA);
+    let mut reader = decoder.read_info()?;
+    let (width, height) = reader.info().size();
+    let animation = reader.info().animation_control;
+    // Without frame control before the image data the default image is not a part of the animation.
+    let skip_default = animation.is_some() && reader.info().frame_control.is_none();
+    let count = animation.map_or(1, |control| control.num_frames as usize) + skip_default as usize;
+    let looping = match animation.map(|control| control.num_plays) {
+        None | Some(0) => Looping::Infinite,
+        Some(plays) => Looping::Finite(plays),
+    };
+
+    let mut screen = Screen::new(width as usize, height as usize);
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let mut frames = Vec::new();
+    for index in 0..count {
+        let output = reader.next_frame(&mut buffer)?;
+        if index == 0 && skip_default {
+            continue;
+        }
+        let samples = match output.color_type {
+            ColorType::Grayscale => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+            ColorType::Indexed => return Err(ImportError::UnsupportedFormat),
+        };
+        let control = reader.info().frame_control;
+        let (origin, dimensions) =
+            control.map_or(((0, 0), (width as usize, height as usize)), |control| {
+                (
+                    (control.x_offset as usize, control.y_offset as usize),
+                    (control.width as usize, control.height as usize),
+                )
+            });
+        let disposal = match control.map(|control| control.dispose_op) {
+            None | Some(DisposeOp::None) => Disposal::Keep,
+            Some(DisposeOp::Background) => Disposal::Clear,
+            // The first frame can not restore anything and clears its area instead.
+            Some(DisposeOp::Previous) if frames.is_empty() => Disposal::Clear,
+            Some(DisposeOp::Previous) => Disposal::Restore,
+        };
+        let previous = (disposal == Disposal::Restore).then(|| screen.pixels.clone());
+
+        let line_size = output.line_size;
+        let pixel = |x: usize, y: usize| {
+            let start = y * line_size + x * samples;
+            let data = &buffer[start..start + samples];
+            match samples {
+                1 => [data[0], data[0], data[0], 255],
+                2 => [data[0], data[0], data[0], data[1]],
+                3 => [data[0], data[1], data[2], 255],
+                _ => [data[0], data[1], data[2], data[3]],
+            }
+        };
+        match control.map(|control| control.blend_op) {
+            Some(BlendOp::Over) => screen.draw(origin, dimensions, pixel, over),
+            _ => screen.draw(origin, dimensions, pixel, |_, above| above),
+        }
+        let delay = control.map_or(delay(0, 0), |control| {
+            delay(control.delay_num, control.delay_den)
+        });
+        frames.push((screen.to_canvas(&mut mapper), delay));
+
+        match (disposal, previous) {
+            (Disposal::Clear, _) => screen.clear(origin, dimensions),
+            (Disposal::Restore, Some(previous)) => screen.pixels = previous,
+            _ => (),
+        }
+    }
+    Ok(Animation::new(frames).with_looping(looping))
+}
@@ -0,0 +1,179 @@
+use std::ops::RangeInclusive;
+
+use backend::RenderSurface;
+
+use super::canvas::Canvas;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{FastHorizontalWriter, Image, ImageMut};
+use crate::util::vector::Vector;
+
+/// Canvas of `u8` palette indices with the palette of `0xrr_gg_bb` colors stored alongside.
+/// As render surface it provides `0xrr_gg_bb` colors resolved through the current palette,
+/// so swapping the palette recolors the whole canvas without touching the indices.
+/// Present it with the `XrgbConverter` from the extras.
+#[derive(Clone, Debug)]
+pub struct IndexedCanvas {
+    indices: Canvas<u8>,
+    palette: Vec<u32>,
+    fallback: u32,
+}
+
+impl IndexedCanvas {
+    /// Create new indexed canvas with given fill `index`, resolution and `palette`.
+    pub fn with_resolution(index: u8, width: usize, height: usize, palette: Vec<u32>) -> Self {
+        Self::new(Canvas::with_resolution(index, width, height), palette)
+    }
+
+    /// Wrap canvas of `indices` with the `palette`.
+    pub fn new(indices: Canvas<u8>, palette: Vec<u32>) -> Self {
+        Self {
+            indices,
+            palette,
+            fallback: 0x00_00_00,
+        }
+    }
+
+    /// Set color used for indices outside of the palette.
+    pub fn with_fallback(self, fallback: u32) -> Self {
+        Self { fallback, ..self }
+    }
+
+    /// Get reference to the canvas of indices.
+    pub fn indices(&self) -> &Canvas<u8> {
+        &self.indices
+    }
+
+    /// Unwrap the canvas of indices.
+    pub fn into_inner(self) -> Canvas<u8> {
+        self.indices
+    }
+
+    /// Get colors of the palette.
+    pub fn palette(&self) -> &[u32] {
+        &self.palette
+    }
+
+    /// Replace the palette, returning the previous one.
+    pub fn set_palette(&mut self, palette: Vec<u32>) -> Vec<u32> {
+        std::mem::replace(&mut self.palette, palette)
+    }
+
+    /// Change single color of the palette.
+    pub fn set_color(&mut self, index: u8, color: u32) {
+        if let Some(entry) = self.palette.get_mut(index as usize) {
+            *entry = color;
+        }
+    }
+
+    /// Resolve color of the `index` through the palette.
+    pub fn color(&self, index: u8) -> u32 {
+        self.palette
+            .get(index as usize)
+            .copied()
+            .unwrap_or(self.fallback)
+    }
+}
+
+impl<'a> DesignatorRef<'a> for IndexedCanvas {
+    type PixelRef = &'a u8;
+}
+
+impl Image for IndexedCanvas {
+    type Pixel = u8;
+
+    fn pixel(&self, position: Vector<i32>) -> Option<&u8> {
+        self.indices.pixel(position)
+    }
+
+    unsafe fn unsafe_pixel(&self, position: Vector<i32>) -> &u8 {
+        self.indices.unsafe_pixel(position)
+    }
+
+    fn width(&self) -> i32 {
+        Image::width(&self.indices)
+    }
+
+    fn height(&self) -> i32 {
+        Image::height(&self.indices)
+    }
+
+    fn row(&self, y: i32) -> Option<&[u8]> {
+        Image::row(&self.indices, y)
+    }
+}
+
+impl<'a> DesignatorMut<'a> for IndexedCanvas {
+    type PixelMut = &'a mut u8;
+}
+
+impl ImageMut for IndexedCanvas {
+    fn pixel_mut(&mut self, position: Vector<i32>) -> Option<&mut u8> {
+        self.indices.pixel_mut(position)
+    }
+
+    unsafe fn unsafe_pixel_mut(&mut self, position: Vector<i32>) -> &mut u8 {
+        self.indices.unsafe_pixel_mut(position)
+    }
+
+    fn clear(&mut self, color: u8) {
+        self.indices.clear(color);
+    }
+
+    fn row_mut(&mut self, y: i32) -> Option<&mut [u8]> {
+        self.indices.row_mut(y)
+    }
+
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut [u8]> {
+        self.indices.rows_mut()
+    }
+
+    fn fast_horizontal_writer(&mut self) -> Option<impl FastHorizontalWriter<Self>> {
+        Some(IndexedFastHorizontalWriter { indexed: self })
+    }
+}
+
+impl RenderSurface for IndexedCanvas {
+    type Data = u32;
+
+    fn width(&self) -> usize {
+        RenderSurface::width(&self.indices)
+    }
+
+    fn height(&self) -> usize {
+        RenderSurface::height(&self.indices)
+    }
+
+    fn data(&self, x: usize, y: usize) -> u32 {
+        self.color(self.indices.data(x, y))
+    }
+
+    fn resize(&mut self, width: usize, height: usize, preserve: bool) -> bool {
+        self.indices.resize(width, height, preserve)
+    }
+}
+
+struct IndexedFastHorizontalWriter<'a> {
+    indexed: &'a mut IndexedCanvas,
+}
+
+impl FastHorizontalWriter<IndexedCanvas> for IndexedFastHorizontalWriter<'_> {
+    fn write_line<F: FnMut(i32, i32, u8) -> u8>(
+        &mut self,
+        x: RangeInclusive<i32>,
+        y: i32,
+        function: &mut F,
+    ) {
+        if let Some(mut writer) = self.indexed.indices.fast_horizontal_writer() {
+            writer.write_line(x, y, function);
+        }
+    }
+}
+
+/// Helper stamper mapper drawing indexed images through the `palette`, e.g. for recolored sprite variants.
+/// Indices outside of the palette leave the original pixels intact.
+pub fn recolor<P>(palette: &[P]) -> impl FnMut(i32, i32, P, i32, i32, u8) -> P + '_
+where
+    P: Clone,
+{
+    move |_, _, original, _, _, index| palette.get(index as usize).cloned().unwrap_or(original)
+}
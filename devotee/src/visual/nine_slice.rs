@@ -0,0 +1,86 @@
+/// Widths of the fixed borders of the nine-slice image.
+///
+/// Corners are kept intact, edges are stretched or tiled along one axis and the center along both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Margins {
+    /// Width of the left border.
+    pub left: i32,
+    /// Height of the top border.
+    pub top: i32,
+    /// Width of the right border.
+    pub right: i32,
+    /// Height of the bottom border.
+    pub bottom: i32,
+}
+
+impl Margins {
+    /// Create new margins.
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Create margins of the same size on each side.
+    pub fn uniform(size: i32) -> Self {
+        Self::new(size, size, size, size)
+    }
+}
+
+/// Mapping of the destination position along single axis to the source one.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Slicer {
+    source: i32,
+    source_borders: (i32, i32),
+    destination: i32,
+    destination_borders: (i32, i32),
+    tile: bool,
+}
+
+impl Slicer {
+    /// Create mapping of the `destination` length onto the `source` one with `borders` at both ends.
+    /// Borders are shrunk proportionally if the destination is too short for them.
+    pub(super) fn new(source: i32, destination: i32, borders: (i32, i32), tile: bool) -> Self {
+        let start = borders.0.clamp(0, source);
+        let end = borders.1.clamp(0, source - start);
+        let destination_borders = if start + end > destination {
+            let shrunk = (destination as i64 * start as i64 / (start + end) as i64) as i32;
+            (shrunk, destination - shrunk)
+        } else {
+            (start, end)
+        };
+        Self {
+            source,
+            source_borders: (start, end),
+            destination,
+            destination_borders,
+            tile,
+        }
+    }
+
+    /// Get source position for the `position` in the destination, if there is any.
+    pub(super) fn map(&self, position: i32) -> Option<i32> {
+        let (start, end) = self.destination_borders;
+        if position < start {
+            return Some(position);
+        }
+        if position >= self.destination - end {
+            return Some(self.source - (self.destination - position));
+        }
+        let source_center = self.source - self.source_borders.0 - self.source_borders.1;
+        if source_center <= 0 {
+            return None;
+        }
+        let position = position - start;
+        let offset = if self.tile {
+            position % source_center
+        } else {
+            let destination_center = self.destination - start - end;
+            (position as i64 * source_center as i64 / destination_center as i64) as i32
+        };
+        Some(self.source_borders.0 + offset)
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+use backend::Converter;
+
+use crate::util::invalidation::{Invalidator, Subscription};
+
+/// Colors of the PICO-8 fantasy console palette in `0xrr_gg_bb` format.
+pub const PICO8: [u32; 16] = [
+    0x00_00_00, 0x1d_2b_53, 0x7e_25_53, 0x00_87_51, 0xab_52_36, 0x5f_57_4f, 0xc2_c3_c7, 0xff_f1_e8,
+    0xff_00_4d, 0xff_a3_00, 0xff_ec_27, 0x00_e4_36, 0x29_ad_ff, 0x83_76_9c, 0xff_77_a8, 0xff_cc_aa,
+];
+
+/// Colors of the original Game Boy green screen from the darkest to the lightest in `0xrr_gg_bb` format.
+pub const GAMEBOY: [u32; 4] = [0x0f_38_0f, 0x30_62_30, 0x8b_ac_0f, 0x9b_bc_0f];
+
+/// Colors of the full 16 color CGA palette in `0xrr_gg_bb` format.
+pub const CGA: [u32; 16] = [
+    0x00_00_00, 0x00_00_aa, 0x00_aa_00, 0x00_aa_aa, 0xaa_00_00, 0xaa_00_aa, 0xaa_55_00, 0xaa_aa_aa,
+    0x55_55_55, 0x55_55_ff, 0x55_ff_55, 0x55_ff_ff, 0xff_55_55, 0xff_55_ff, 0xff_ff_55, 0xff_ff_ff,
+];
+
+/// Colors of the NES picture processing unit indexed by the hardware color values in `0xrr_gg_bb` format.
+pub const NES: [u32; 64] = [
+    0x7c_7c_7c, 0x00_00_fc, 0x00_00_bc, 0x44_28_bc, 0x94_00_84, 0xa8_00_20, 0xa8_10_00, 0x88_14_00,
+    0x50_30_00, 0x00_78_00, 0x00_68_00, 0x00_58_00, 0x00_40_58, 0x00_00_00, 0x00_00_00, 0x00_00_00,
+    0xbc_bc_bc, 0x00_78_f8, 0x00_58_f8, 0x68_44_fc, 0xd8_00_cc, 0xe4_00_58, 0xf8_38_00, 0xe4_5c_10,
+    0xac_7c_00, 0x00_b8_00, 0x00_a8_00, 0x00_a8_44, 0x00_88_88, 0x00_00_00, 0x00_00_00, 0x00_00_00,
+    0xf8_f8_f8, 0x3c_bc_fc, 0x68_88_fc, 0x98_78_f8, 0xf8_78_f8, 0xf8_58_98, 0xf8_78_58, 0xfc_a0_44,
+    0xf8_b8_00, 0xb8_f8_18, 0x58_d8_54, 0x58_f8_98, 0x00_e8_d8, 0x78_78_78, 0x00_00_00, 0x00_00_00,
+    0xfc_fc_fc, 0xa4_e4_fc, 0xb8_b8_f8, 0xd8_b8_f8, 0xf8_b8_f8, 0xf8_a4_c0, 0xf0_d0_b0, 0xfc_e0_a8,
+    0xf8_d8_78, 0xd8_f8_78, 0xb8_f8_b8, 0xb8_f8_d8, 0x00_fc_fc, 0xf8_d8_f8, 0x00_00_00, 0x00_00_00,
+];
+
+/// Palette converter mapping indices into `0xff_rr_gg_bb` colors.
+/// Any palette mutation emits invalidation signal to the subscribed caches.
+#[derive(Clone, Debug)]
+pub struct Palette {
+    colors: Vec<u32>,
+    fallback: u32,
+    invalidator: Invalidator,
+}
+
+impl Palette {
+    /// Create new palette with provided colors.
+    pub fn new(colors: Vec<u32>) -> Self {
+        Self {
+            colors,
+            fallback: 0xff_00_00_00,
+            invalidator: Invalidator::new(),
+        }
+    }
+
+    /// Set color used for indices outside of the palette.
+    pub fn with_fallback(self, fallback: u32) -> Self {
+        Self { fallback, ..self }
+    }
+
+    /// Get colors of the palette.
+    pub fn colors(&self) -> &[u32] {
+        &self.colors
+    }
+
+    /// Get color by its index.
+    pub fn color(&self, index: usize) -> Option<u32> {
+        self.colors.get(index).copied()
+    }
+
+    /// Change single color of the palette.
+    pub fn set_color(&mut self, index: usize, color: u32) {
+        if let Some(entry) = self.colors.get_mut(index) {
+            if *entry != color {
+                *entry = color;
+                self.invalidator.invalidate();
+            }
+        }
+    }
+
+    /// Replace all the colors of the palette.
+    pub fn swap(&mut self, colors: Vec<u32>) -> Vec<u32> {
+        self.invalidator.invalidate();
+        std::mem::replace(&mut self.colors, colors)
+    }
+
+    /// Subscribe to the palette changes.
+    pub fn subscribe(&self) -> Subscription {
+        self.invalidator.subscribe()
+    }
+}
+
+impl Converter for Palette {
+    type Data = u8;
+
+    fn convert(&self, _: usize, _: usize, data: Self::Data) -> u32 {
+        self.colors
+            .get(data as usize)
+            .map_or(self.fallback, |color| 0xff_00_00_00 | color)
+    }
+}
+
+/// Format colors as lines of `rrggbb` hex values.
+pub fn encode_hex(colors: &[u32]) -> String {
+    colors
+        .iter()
+        .map(|color| format!("{:06x}\n", color & 0x00_ff_ff_ff))
+        .collect()
+}
+
+/// Parse lines of `rrggbb` hex values, skipping empty lines and `;` comments.
+pub fn decode_hex(text: &str) -> Option<Vec<u32>> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with(';'))
+        .map(|line| {
+            let line = line.trim_start_matches('#');
+            (line.len() == 6)
+                .then(|| u32::from_str_radix(line, 16).ok())
+                .flatten()
+        })
+        .collect()
+}
+
+/// Load palette colors from the hex text file at `path`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_palette<P: AsRef<Path>>(path: P) -> io::Result<Vec<u32>> {
+    let text = fs::read_to_string(path)?;
+    decode_hex(&text).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Malformed palette"))
+}
+
+/// Store palette colors into the hex text file at `path`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn store_palette<P: AsRef<Path>>(path: P, colors: &[u32]) -> io::Result<()> {
+    fs::write(path, encode_hex(colors))
+}
+
+fn distance(first: u32, second: u32) -> u32 {
+    let channel = |value: u32, shift: u32| ((value >> shift) & 0xff) as i32;
+    [(16, 2), (8, 4), (0, 3)]
+        .into_iter()
+        .map(|(shift, weight)| {
+            let delta = channel(first, shift) - channel(second, shift);
+            (delta * delta * weight) as u32
+        })
+        .sum()
+}
+
+/// Find index of the color in `colors` nearest to the `0xrr_gg_bb` color.
+/// Channels are weighted to roughly match perceived difference.
+/// Returns `None` for empty `colors`.
+pub fn nearest(colors: &[u32], rgb: u32) -> Option<usize> {
+    colors
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, color)| distance(**color, rgb))
+        .map(|(index, _)| index)
+}
+
+/// Nearest color lookup for up to 256 palette colors precomputed for 5 bits per channel.
+#[derive(Clone, Debug)]
+pub struct Quantizer {
+    table: Vec<u8>,
+}
+
+impl Quantizer {
+    /// Precompute nearest indices of the `colors`.
+    /// Colors beyond the first 256 are ignored.
+    pub fn new(colors: &[u32]) -> Self {
+        let colors = &colors[..colors.len().min(256)];
+        let table = (0..1 << 15)
+            .map(|cell: u32| {
+                let channel = |shift: u32| (((cell >> shift) & 0x1f) << 3) | 0x04;
+                let rgb = (channel(10) << 16) | (channel(5) << 8) | channel(0);
+                nearest(colors, rgb).unwrap_or(0) as u8
+            })
+            .collect();
+        Self { table }
+    }
+
+    /// Get index of the palette color nearest to the `0xrr_gg_bb` color.
+    pub fn quantize(&self, rgb: u32) -> u8 {
+        let cell = ((rgb >> 9) & 0x7c00) | ((rgb >> 6) & 0x03e0) | ((rgb >> 3) & 0x001f);
+        self.table[cell as usize]
+    }
+}
@@ -1,11 +1,14 @@
 use std::ops::{Deref, DerefMut};
 
 use crate::util::getter::Getter;
+use crate::util::rect::Rect;
+use crate::util::transform::Transform2;
 use crate::util::vector::Vector;
-use crate::visual::util::AngleIterator;
+use crate::visual::util::{self, AngleIterator};
 
 use super::image::{DesignatorMut, DesignatorRef, PixelMut, PixelRef};
-use super::{Image, ImageMut, Paint, Painter, Scan};
+use super::nine_slice::{Margins, Slicer};
+use super::{paint, stamp, Anchor, Image, ImageMut, JoinStyle, Paint, Painter, Sampling, Scan};
 
 fn scanline_segment_i32(segment: (Vector<i32>, Vector<i32>), scanline: i32) -> Scan<i32> {
     let (from, to) = if segment.0.y() < segment.1.y() {
@@ -14,49 +17,137 @@ fn scanline_segment_i32(segment: (Vector<i32>, Vector<i32>), scanline: i32) -> S
         (segment.1, segment.0)
     };
 
-    let (delta_x, delta_y) = (to - from).split();
-
     if scanline < from.y() || scanline > to.y() {
         return Scan::None;
     }
-    if delta_y == 0 {
+    if from.y() == to.y() {
         return Scan::Inclusive(from.x(), to.x()).sorted();
     }
 
+    // Wide arithmetic keeps products of extreme coordinates from overflowing.
+    let (from_x, from_y) = (from.x() as i128, from.y() as i128);
+    let (to_x, to_y) = (to.x() as i128, to.y() as i128);
+    let (delta_x, delta_y) = (to_x - from_x, to_y - from_y);
+    let y = scanline as i128;
+    let narrow = |value: i128| value.clamp(i32::MIN as i128, i32::MAX as i128) as i32;
+
     let steep = delta_x.abs() < delta_y;
     if steep {
-        let y = scanline;
-        let x = ((delta_x + 1) * (y - from.y())
-            + (delta_x - 1) * (y - to.y())
-            + (from.x() + to.x()) * delta_y)
-            / (delta_y * 2);
+        let x =
+            ((delta_x + 1) * (y - from_y) + (delta_x - 1) * (y - to_y) + (from_x + to_x) * delta_y)
+                / (delta_y * 2);
 
-        Scan::Single(x).sorted()
+        Scan::Single(narrow(x)).sorted()
     } else {
-        let (left, right) = if from.x() < to.x() {
-            (from.x(), to.x())
-        } else {
-            (to.x(), from.x())
-        };
+        let (left, right) = (from_x.min(to_x), from_x.max(to_x));
 
-        let y = scanline;
-        let center_x = (delta_x + 1) * (y - from.y())
-            + (delta_x - 1) * (y - to.y())
-            + (from.x() + to.x()) * delta_y;
+        let center_x =
+            (delta_x + 1) * (y - from_y) + (delta_x - 1) * (y - to_y) + (from_x + to_x) * delta_y;
 
-        let left_x = (delta_x + 1) * (y - from.y() + 1)
-            + (delta_x - 1) * (y - to.y() + 1)
-            + (from.x() + to.x()) * delta_y;
-        let right_x = (delta_x + 1) * (y - from.y() - 1)
-            + (delta_x - 1) * (y - to.y() - 1)
-            + (from.x() + to.x()) * delta_y;
+        let left_x = (delta_x + 1) * (y - from_y + 1)
+            + (delta_x - 1) * (y - to_y + 1)
+            + (from_x + to_x) * delta_y;
+        let right_x = (delta_x + 1) * (y - from_y - 1)
+            + (delta_x - 1) * (y - to_y - 1)
+            + (from_x + to_x) * delta_y;
 
         let first_x = (center_x + left_x) / (4 * delta_y);
         let second_x = (center_x + right_x) / (4 * delta_y);
         if first_x < second_x {
-            Scan::Inclusive(left.max(first_x), right.min(second_x - 1))
+            Scan::Inclusive(narrow(left.max(first_x)), narrow(right.min(second_x - 1)))
+        } else {
+            Scan::Inclusive(narrow(left.max(second_x)), narrow(right.min(first_x - 1)))
+        }
+    }
+}
+
+fn shift(point: Vector<i32>, x: i32, y: i32) -> Vector<i32> {
+    Vector::new(point.x().saturating_add(x), point.y().saturating_add(y))
+}
+
+/// Get the midpoint circle `y` at the `x` column of the octant above the diagonal,
+/// the largest one with `x² + y² - y <= radius²`.
+fn circle_octant_y(radius: i32, x: i32) -> i32 {
+    let value = radius as i64 * radius as i64 - x as i64 * x as i64;
+    let mut y = value.max(0).isqrt() + 1;
+    while y * y - y > value {
+        y -= 1;
+    }
+    y as i32
+}
+
+/// Get the half width of the midpoint filled circle at the non-negative `row` offset from its center.
+fn filled_circle_half_width(radius: i32, row: i32) -> Option<i32> {
+    if row == 0 {
+        return Some(radius);
+    }
+    if row > radius {
+        return None;
+    }
+    let side = Some(circle_octant_y(radius, row)).filter(|&y| row - 1 < y);
+    let (radius, row) = (radius as i64, row as i64);
+    let cap =
+        Some((radius * radius - row * row + row).isqrt() as i32).filter(|&x| (x as i64) < row);
+    side.max(cap)
+}
+
+/// Get the first value in the `[from, to]` range matching the monotonous `predicate`.
+/// Returns `to + 1` if there is no such value.
+fn first_matching<P: Fn(i32) -> bool>(mut from: i32, mut to: i32, predicate: P) -> i32 {
+    let mut result = to.saturating_add(1);
+    while from <= to {
+        let middle = from + (to - from) / 2;
+        if predicate(middle) {
+            result = middle;
+            to = middle - 1;
         } else {
-            Scan::Inclusive(left.max(second_x), right.min(first_x - 1))
+            from = middle + 1;
+        }
+    }
+    result
+}
+
+/// Walk the midpoint ellipse in the quadrant of non-negative offsets,
+/// from the top point to the right one with non-increasing `y`.
+fn ellipse_quadrant<M: FnMut(i32, i32)>(radii: Vector<i32>, mut mapper: M) {
+    let (radius_x, radius_y) = radii.split();
+    if radius_y == 0 {
+        for x in 0..=radius_x {
+            mapper(x, 0);
+        }
+        return;
+    }
+    let (square_x, square_y) = ((radius_x as f64).powi(2), (radius_y as f64).powi(2));
+    let (mut x, mut y) = (0, radius_y);
+    let mut checker_x = 0.0;
+    let mut checker_y = 2.0 * square_x * y as f64;
+
+    let mut decision = square_y - square_x * radius_y as f64 + 0.25 * square_x;
+    while checker_x < checker_y {
+        mapper(x, y);
+        x += 1;
+        checker_x += 2.0 * square_y;
+        if decision < 0.0 {
+            decision += square_y + checker_x;
+        } else {
+            y -= 1;
+            checker_y -= 2.0 * square_x;
+            decision += square_y + checker_x - checker_y;
+        }
+    }
+
+    let mut decision = square_y * (x as f64 + 0.5).powi(2) + square_x * (y as f64 - 1.0).powi(2)
+        - square_x * square_y;
+    while y >= 0 {
+        mapper(x, y);
+        y -= 1;
+        checker_y -= 2.0 * square_x;
+        if decision > 0.0 {
+            decision += square_x - checker_y;
+        } else {
+            x += 1;
+            checker_x += 2.0 * square_y;
+            decision += square_x - checker_y + checker_x;
         }
     }
 }
@@ -68,6 +159,11 @@ where
     for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
     for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
 {
+    /// Shift the `point` by the painter offset, saturating on overflow.
+    fn offset_point(&self, point: Vector<i32>) -> Vector<i32> {
+        shift(point, self.offset.x(), self.offset.y())
+    }
+
     fn map_on_line_offset<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
         &mut self,
         from: Vector<i32>,
@@ -75,8 +171,8 @@ where
         function: &mut F,
         skip: usize,
     ) {
-        let from = from + self.offset;
-        let to = to + self.offset;
+        let from = self.offset_point(from);
+        let to = self.offset_point(to);
         if from.x() == to.x() {
             self.map_vertical_line_raw(from.x(), from.y(), to.y(), function, skip);
             return;
@@ -86,8 +182,17 @@ where
             return;
         }
 
-        let mut iter = from.y()..=to.y();
-        let mut iter_rev = (to.y()..=from.y()).rev();
+        let (low, high) = self.bounds();
+        if high.x() <= low.x() || high.y() <= low.y() {
+            return;
+        }
+
+        let (top, bottom) = (
+            from.y().min(to.y()).max(low.y()),
+            from.y().max(to.y()).min(high.y() - 1),
+        );
+        let mut iter = top..=bottom;
+        let mut iter_rev = (top..=bottom).rev();
 
         let iter_ref: &mut dyn Iterator<Item = i32> = if from.y() < to.y() {
             &mut iter
@@ -97,10 +202,13 @@ where
 
         let rev = from.x() > to.x();
 
-        let mut skip = skip;
+        // Pixels to skip are counted from `from`, which is invisible when clamped.
+        let visible =
+            (low.x()..high.x()).contains(&from.x()) && (low.y()..high.y()).contains(&from.y());
+        let mut skip = if visible { skip } else { 0 };
 
         for y in iter_ref {
-            let scan = scanline_segment_i32((from, to), y);
+            let scan = scanline_segment_i32((from, to), y).clamped(low.x(), high.x() - 1);
             let mut scan_rev = scan.rev().into_iter();
             let mut scan = scan.into_iter();
             let scan: &mut dyn Iterator<Item = i32> = if rev { &mut scan_rev } else { &mut scan };
@@ -121,7 +229,7 @@ where
         vertices: [Vector<i32>; 3],
         function: &mut F,
     ) {
-        let mut vertex = vertices.map(|v| v + self.offset);
+        let mut vertex = vertices.map(|v| self.offset_point(v));
         vertex.sort_by(|a, b| a.y_ref().cmp(b.y_ref()));
         let [a, b, c] = vertex;
 
@@ -137,9 +245,10 @@ where
             return;
         }
 
+        let (low, high) = self.bounds();
         let middle = if b.y() == c.y() { b.y() } else { b.y() - 1 };
 
-        for y in a.y()..=middle {
+        for y in a.y().max(low.y())..=middle.min(high.y() - 1) {
             let left_range = scanline_segment_i32((a, b), y);
             let right_range = scanline_segment_i32((a, c), y);
             let left = left_range
@@ -150,7 +259,7 @@ where
         }
 
         let middle = middle + 1;
-        for y in middle..=c.y() {
+        for y in middle.max(low.y())..=c.y().min(high.y() - 1) {
             let left_range = scanline_segment_i32((a, c), y);
             let right_range = scanline_segment_i32((b, c), y);
             let left = left_range
@@ -189,6 +298,11 @@ where
             },
         );
 
+        let (low, high) = self.bounds();
+        let (top, bottom) = (
+            top.max(low.y().saturating_sub(self.offset.y())),
+            bottom.min((high.y() - 1).saturating_sub(self.offset.y())),
+        );
         for y in top..=bottom {
             let segments = AngleIterator::new(vertices);
 
@@ -227,16 +341,16 @@ where
                     Scan::None => {}
                 }
             }
-            flips.sort_by(|a, b| a.position.cmp(&b.position));
+            flips.sort_by_key(|flip| flip.position);
 
             let mut counter = 0;
             let mut current_left = left;
             for flip in flips {
                 if counter % 2 == 1 || counter / 2 % 2 == 1 {
                     self.map_fast_horizontal_line_raw(
-                        current_left + self.offset.x(),
-                        flip.position + self.offset.x(),
-                        y + self.offset.y(),
+                        current_left.saturating_add(self.offset.x()),
+                        flip.position.saturating_add(self.offset.x()),
+                        y.saturating_add(self.offset.y()),
                         function,
                     );
                 }
@@ -257,111 +371,217 @@ where
         &mut self,
         center: Vector<i32>,
         radius: i32,
+        sector: Option<(f32, f32)>,
         function: &mut F,
     ) {
-        let center = center + self.offset;
-        self.map_fast_horizontal_line_raw(
-            center.x() - radius,
-            center.x() + radius,
-            center.y(),
-            function,
-        );
-
-        let mut x = 0;
-        let mut y = radius;
-        let mut decision = 1 - radius;
-        let mut checker_x = 1;
-        let mut checker_y = -2 * radius;
-
-        while x < y {
-            if decision > 0 {
-                self.map_fast_horizontal_line_raw(
-                    center.x() - x,
-                    center.x() + x,
-                    center.y() + y,
-                    function,
-                );
-                self.map_fast_horizontal_line_raw(
-                    center.x() - x,
-                    center.x() + x,
-                    center.y() - y,
+        let center = self.offset_point(center);
+        if radius < 0 || !self.circle_visible(center, radius) {
+            return;
+        }
+        let (top, bottom) = self.circle_rows(center, radius);
+        for row in top..=bottom {
+            if let Some(half_width) = filled_circle_half_width(radius, row.abs()) {
+                self.map_on_span_raw(
+                    center.x().saturating_sub(half_width),
+                    center.x().saturating_add(half_width),
+                    center.y() + row,
+                    (center, sector),
                     function,
                 );
-                y -= 1;
-                checker_y += 2;
-                decision += checker_y;
+            }
+        }
+    }
+
+    fn map_on_circle_offset<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        center: Vector<i32>,
+        radius: i32,
+        sector: Option<(f32, f32)>,
+        function: &mut F,
+    ) {
+        let center = self.offset_point(center);
+        if radius < 0 || !self.circle_visible(center, radius) {
+            return;
+        }
+        self.map_on_arc_pixel_raw(shift(center, radius, 0), (center, sector), function);
+        self.map_on_arc_pixel_raw(shift(center, -radius, 0), (center, sector), function);
+        self.map_on_arc_pixel_raw(shift(center, 0, radius), (center, sector), function);
+        self.map_on_arc_pixel_raw(shift(center, 0, -radius), (center, sector), function);
+
+        let octant_y = |x| {
+            if x == 0 {
+                radius
             } else {
-                x += 1;
-                checker_x += 2;
-                decision += checker_x;
+                circle_octant_y(radius, x)
+            }
+        };
+        let steps = first_matching(0, radius, |x| x >= octant_y(x) - 2);
 
+        let (top, bottom) = self.circle_rows(center, radius);
+        let (near, far) = if top > 0 {
+            (top, bottom)
+        } else if bottom < 0 {
+            (-bottom, -top)
+        } else {
+            (0, bottom.max(-top))
+        };
+        let sides = (near.max(1), far.min(steps));
+        let caps = (
+            first_matching(1, steps, |x| octant_y(x) <= far),
+            first_matching(1, steps, |x| octant_y(x) < near) - 1,
+        );
+        let (first, second) = if sides.0 <= caps.0 {
+            (sides, caps)
+        } else {
+            (caps, sides)
+        };
+        let second = (second.0.max(first.1 + 1), second.1);
+
+        for x in (first.0..=first.1).chain(second.0..=second.1) {
+            let y = octant_y(x);
+            self.map_on_arc_pixel_raw(shift(center, x, y), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, x, -y), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, -x, y), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, -x, -y), (center, sector), function);
+
+            self.map_on_arc_pixel_raw(shift(center, y, x), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, y, -x), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, -y, x), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, -y, -x), (center, sector), function);
+        }
+
+        if steps == octant_y(steps) - 2 {
+            let x = steps + 1;
+            self.map_on_arc_pixel_raw(shift(center, x, x), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, x, -x), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, -x, x), (center, sector), function);
+            self.map_on_arc_pixel_raw(shift(center, -x, -x), (center, sector), function);
+        }
+    }
+
+    fn map_on_filled_ellipse_offset<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        center: Vector<i32>,
+        radii: Vector<i32>,
+        function: &mut F,
+    ) {
+        let center = self.offset_point(center);
+        if radii.x() < 0 || radii.y() < 0 || !self.ellipse_visible(center, radii) {
+            return;
+        }
+        let mut map_rows = |half_width: i32, y: i32| {
+            let (from_x, to_x) = (
+                center.x().saturating_sub(half_width),
+                center.x().saturating_add(half_width),
+            );
+            self.map_fast_horizontal_line_raw(from_x, to_x, center.y().saturating_add(y), function);
+            if y != 0 {
                 self.map_fast_horizontal_line_raw(
-                    center.x() - y,
-                    center.x() + y,
-                    center.y() + x,
-                    function,
-                );
-                self.map_fast_horizontal_line_raw(
-                    center.x() - y,
-                    center.x() + y,
-                    center.y() - x,
+                    from_x,
+                    to_x,
+                    center.y().saturating_sub(y),
                     function,
                 );
             }
+        };
+
+        let mut row: Option<(i32, i32)> = None;
+        ellipse_quadrant(radii, |x, y| {
+            if let Some((row_x, row_y)) = row {
+                if row_y != y {
+                    map_rows(row_x, row_y);
+                }
+            }
+            row = Some((x, y));
+        });
+        if let Some((row_x, row_y)) = row {
+            map_rows(row_x, row_y);
         }
     }
 
-    fn map_on_circle_offset<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+    fn map_on_ellipse_offset<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
         &mut self,
         center: Vector<i32>,
-        radius: i32,
+        radii: Vector<i32>,
         function: &mut F,
     ) {
-        let center = center + self.offset;
-        self.map_on_pixel_raw(center + (radius, 0), function);
-        self.map_on_pixel_raw(center - (radius, 0), function);
-        self.map_on_pixel_raw(center + (0, radius), function);
-        self.map_on_pixel_raw(center - (0, radius), function);
-
-        let mut x = 0;
-        let mut y = radius;
-        let mut decision = 1 - radius;
-        let mut checker_x = 1;
-        let mut checker_y = -2 * radius;
-
-        let mut mapper = |x, y| {
-            self.map_on_pixel_raw(center + (x, y), function);
-            self.map_on_pixel_raw(center + (x, -y), function);
-            self.map_on_pixel_raw(center + (-x, y), function);
-            self.map_on_pixel_raw(center + (-x, -y), function);
-
-            self.map_on_pixel_raw(center + (y, x), function);
-            self.map_on_pixel_raw(center + (y, -x), function);
-            self.map_on_pixel_raw(center + (-y, x), function);
-            self.map_on_pixel_raw(center + (-y, -x), function);
-        };
-
-        while x < y - 2 {
-            if decision > 0 {
-                y -= 1;
-                checker_y += 2;
-                decision += checker_y;
+        let center = self.offset_point(center);
+        if radii.x() < 0 || radii.y() < 0 || !self.ellipse_visible(center, radii) {
+            return;
+        }
+        ellipse_quadrant(radii, |x, y| {
+            self.map_on_pixel_raw(shift(center, x, y), function);
+            if x != 0 {
+                self.map_on_pixel_raw(shift(center, -x, y), function);
             }
-            x += 1;
-            checker_x += 2;
-            decision += checker_x;
-            mapper(x, y);
+            if y != 0 {
+                self.map_on_pixel_raw(shift(center, x, -y), function);
+                if x != 0 {
+                    self.map_on_pixel_raw(shift(center, -x, -y), function);
+                }
+            }
+        });
+    }
+
+    fn map_on_span_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        from_x: i32,
+        to_x: i32,
+        y: i32,
+        (center, sector): (Vector<i32>, Option<(f32, f32)>),
+        function: &mut F,
+    ) {
+        match sector {
+            Some((start, end)) => self.map_sector_horizontal_line_raw(
+                from_x,
+                to_x,
+                y,
+                (center.map(|v| v as f32), start, end),
+                function,
+            ),
+            None => self.map_fast_horizontal_line_raw(from_x, to_x, y, function),
         }
+    }
 
-        if x == y - 2 {
-            let x = x + 1;
-            self.map_on_pixel_raw(center + (x, x), function);
-            self.map_on_pixel_raw(center + (x, -x), function);
-            self.map_on_pixel_raw(center + (-x, x), function);
-            self.map_on_pixel_raw(center + (-x, -x), function);
+    fn map_on_arc_pixel_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        point: Vector<i32>,
+        (center, sector): (Vector<i32>, Option<(f32, f32)>),
+        function: &mut F,
+    ) {
+        let inside = sector.is_none_or(|(start, end)| {
+            let delta = point - center;
+            util::in_sector((delta.x() as f32, delta.y() as f32), start, end)
+        });
+        if inside {
+            self.map_on_pixel_raw(point, function);
         }
     }
 
+    /// Get the offsets of the first and the last circle rows within the target height.
+    fn circle_rows(&self, center: Vector<i32>, radius: i32) -> (i32, i32) {
+        let (low, high) = self.bounds();
+        let (y, radius) = (center.y() as i64, radius as i64);
+        (
+            (low.y() as i64 - y).max(-radius) as i32,
+            (high.y() as i64 - 1 - y).min(radius) as i32,
+        )
+    }
+
+    fn circle_visible(&self, center: Vector<i32>, radius: i32) -> bool {
+        self.ellipse_visible(center, Vector::new(radius, radius))
+    }
+
+    fn ellipse_visible(&self, center: Vector<i32>, radii: Vector<i32>) -> bool {
+        let (low, high) = self.bounds();
+        let (x, y) = (center.x() as i64, center.y() as i64);
+        let (radius_x, radius_y) = (radii.x() as i64, radii.y() as i64);
+        x + radius_x >= low.x() as i64
+            && y + radius_y >= low.y() as i64
+            && x - radius_x < high.x() as i64
+            && y - radius_y < high.y() as i64
+    }
+
     fn zip_map_images_offset<
         O: Clone,
         F: FnMut(i32, i32, T::Pixel, i32, i32, O) -> T::Pixel,
@@ -374,24 +594,23 @@ where
     ) where
         for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = O>,
     {
-        let at = at + self.offset;
-        let image_start_x = if at.x() < 0 { -at.x() } else { 0 };
-        let image_start_y = if at.y() < 0 { -at.y() } else { 0 };
-
-        let image_end_x = if at.x() + image.width() >= self.target.width() {
-            self.target.width() - at.x()
-        } else {
-            image.width()
-        };
-        let image_end_y = if at.y() + image.height() >= self.target.height() {
-            self.target.height() - at.y()
-        } else {
-            image.height()
+        let at = self.offset_point(at);
+        let (low, high) = self.bounds();
+        let visible = |at: i32, low: i32, high: i32, length: i32| {
+            let start = (low as i64 - at as i64).clamp(0, length as i64) as i32;
+            let end = (high as i64 - at as i64).clamp(0, length as i64) as i32;
+            (start, end)
         };
+        let (image_start_x, mut image_end_x) = visible(at.x(), low.x(), high.x(), image.width());
+        let (image_start_y, mut image_end_y) = visible(at.y(), low.y(), high.y(), image.height());
+        if self.wrap {
+            image_end_x = image_end_x.min(image_start_x.saturating_add(self.target.width()));
+            image_end_y = image_end_y.min(image_start_y.saturating_add(self.target.height()));
+        }
         for x in image_start_x..image_end_x {
             for y in image_start_y..image_end_y {
                 let step = (x, y).into();
-                let pose = at + step;
+                let pose = self.wrapped(at + step);
                 unsafe {
                     let color = Image::unsafe_pixel(image, step);
                     let pixel = function(
@@ -417,11 +636,12 @@ where
     for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = <T as Image>::Pixel>,
 {
     fn pixel(&self, position: Vector<i32>) -> Option<PixelRef<'_, T>> {
-        Image::pixel(self.target, position + self.offset)
+        Image::pixel(self.target, self.wrapped(self.offset_point(position)))
     }
 
     fn pixel_mut(&mut self, position: Vector<i32>) -> Option<PixelMut<'_, T>> {
-        ImageMut::pixel_mut(self.target, position + self.offset)
+        let position = self.wrapped(self.offset_point(position));
+        ImageMut::pixel_mut(self.target, position)
     }
 
     fn mod_pixel<F>(&mut self, position: Vector<i32>, function: F)
@@ -442,21 +662,31 @@ where
         self.map_on_line_offset(from, to, &mut function, 0);
     }
 
-    fn rect_f<F>(&mut self, from: Vector<i32>, dimensions: Vector<i32>, function: F)
+    fn rect_f<R, F>(&mut self, rect: R, function: F)
     where
+        R: Into<Rect<i32>>,
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
     {
+        let rect: Rect<i32> = rect.into();
+        let (from, dimensions) = (rect.origin(), rect.dimensions());
         let mut function = function;
-        let from = from + self.offset;
-        let to = from + dimensions;
+        let from = self.offset_point(from);
+        let to = shift(from, dimensions.x(), dimensions.y());
         self.map_on_filled_rect_raw(from, to, &mut function);
     }
 
-    fn rect_b<F>(&mut self, from: Vector<i32>, dimensions: Vector<i32>, function: F)
+    fn rect_b<R, F>(&mut self, rect: R, function: F)
     where
+        R: Into<Rect<i32>>,
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
     {
-        let (from, to) = (from + self.offset, from + dimensions + self.offset - (1, 1));
+        let rect: Rect<i32> = rect.into();
+        let (from, dimensions) = (rect.origin(), rect.dimensions());
+        if dimensions.x() <= 0 || dimensions.y() <= 0 {
+            return;
+        }
+        let from = self.offset_point(from);
+        let to = shift(from, dimensions.x() - 1, dimensions.y() - 1);
         let mut function = function;
         self.map_horizontal_line_raw(from.x(), to.x(), from.y(), &mut function, 1);
         self.map_horizontal_line_raw(to.x(), from.x(), to.y(), &mut function, 1);
@@ -524,7 +754,7 @@ where
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
     {
         let mut function = function;
-        self.map_on_filled_circle_offset(center, radius, &mut function);
+        self.map_on_filled_circle_offset(center, radius, None, &mut function);
     }
 
     fn circle_b<F>(&mut self, center: Vector<i32>, radius: i32, function: F)
@@ -532,7 +762,59 @@ where
         F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
     {
         let mut function = function;
-        self.map_on_circle_offset(center, radius, &mut function);
+        self.map_on_circle_offset(center, radius, None, &mut function);
+    }
+
+    fn line_thick<F>(&mut self, from: Vector<i32>, to: Vector<i32>, width: i32, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
+    {
+        self.polyline(&[from, to], width, JoinStyle::default(), function);
+    }
+
+    fn polyline<F>(&mut self, points: &[Vector<i32>], width: i32, join: JoinStyle, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
+    {
+        let mut function = function;
+        let offset = self.offset;
+        let points: Vec<_> = points
+            .iter()
+            .map(|&point| shift(point, offset.x(), offset.y()).map(|v| v as f32))
+            .collect();
+        self.map_on_stroke_raw(&points, width as f32, join, &mut function);
+    }
+
+    fn ellipse_f<F>(&mut self, center: Vector<i32>, radii: Vector<i32>, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
+    {
+        let mut function = function;
+        self.map_on_filled_ellipse_offset(center, radii, &mut function);
+    }
+
+    fn ellipse_b<F>(&mut self, center: Vector<i32>, radii: Vector<i32>, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
+    {
+        let mut function = function;
+        self.map_on_ellipse_offset(center, radii, &mut function);
+    }
+
+    fn arc_b<F>(&mut self, center: Vector<i32>, radius: i32, start: f32, end: f32, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
+    {
+        let mut function = function;
+        self.map_on_circle_offset(center, radius, Some((start, end)), &mut function);
+    }
+
+    fn sector_f<F>(&mut self, center: Vector<i32>, radius: i32, start: f32, end: f32, function: F)
+    where
+        F: FnMut(i32, i32, T::Pixel) -> T::Pixel,
+    {
+        let mut function = function;
+        self.map_on_filled_circle_offset(center, radius, Some((start, end)), &mut function);
     }
 }
 
@@ -548,7 +830,7 @@ where
     /// # Safety
     /// - `position + self.offset` must be in the `[0, (width, height))` range.
     pub unsafe fn pixel_unsafe(&self, position: Vector<i32>) -> PixelRef<'_, T> {
-        Image::unsafe_pixel(self.target, position + self.offset)
+        Image::unsafe_pixel(self.target, self.offset_point(position))
     }
 
     /// Get mutable reference to pixel.
@@ -556,7 +838,7 @@ where
     /// # Safety
     /// - `position + self.offset` must be in the `[0, (width, height))` range.
     pub unsafe fn pixel_mut_unsafe(&mut self, position: Vector<i32>) -> PixelMut<'_, T> {
-        ImageMut::unsafe_pixel_mut(self.target, position + self.offset)
+        ImageMut::unsafe_pixel_mut(self.target, self.offset_point(position))
     }
 
     /// Use provided function and given image on this drawable.
@@ -571,6 +853,297 @@ where
         self.zip_map_images_offset(at, image, &mut function)
     }
 
+    /// Use provided function and given image on this drawable, placing the image `anchor` point at `at`.
+    pub fn image_anchored<F, O, U>(
+        &mut self,
+        at: Vector<i32>,
+        anchor: Anchor,
+        image: &U,
+        function: F,
+    ) where
+        U: Image<Pixel = O> + ?Sized,
+        O: Clone,
+        F: FnMut(i32, i32, T::Pixel, i32, i32, O) -> T::Pixel,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = O>,
+    {
+        let at = at - anchor.offset(image.dimensions());
+        self.image(at, image, function)
+    }
+
+    /// Use provided function and given image mapped through the affine `transform` on this drawable.
+    /// The transform maps image coordinates to the offset from `at`, so the image rotates and scales around its top left corner.
+    /// Pixels are sampled at their centers; non-invertible transforms produce no image.
+    pub fn image_transformed<F, O, U>(
+        &mut self,
+        at: Vector<i32>,
+        image: &U,
+        transform: Transform2<f32>,
+        sampling: Sampling,
+        function: F,
+    ) where
+        U: Image<Pixel = O> + ?Sized,
+        O: Clone,
+        F: FnMut(i32, i32, T::Pixel, i32, i32, O) -> T::Pixel,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = O>,
+    {
+        let mut function = function;
+        let Some(inverse) = transform.inverse() else {
+            return;
+        };
+        let (width, height) = (image.width() as f32, image.height() as f32);
+        let corners = [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)]
+            .map(|corner| transform.apply(corner.into()));
+        let (left, top, right, bottom) = corners.iter().fold(
+            (
+                f32::INFINITY,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                f32::NEG_INFINITY,
+            ),
+            |(left, top, right, bottom), corner| {
+                (
+                    left.min(corner.x()),
+                    top.min(corner.y()),
+                    right.max(corner.x()),
+                    bottom.max(corner.y()),
+                )
+            },
+        );
+        if !(left.is_finite() && top.is_finite() && right.is_finite() && bottom.is_finite()) {
+            return;
+        }
+
+        let at = self.offset_point(at);
+        let (low, high) = self.bounds();
+        let start_x = (at.x() as f32 + left).floor().max(low.x() as f32) as i32;
+        let start_y = (at.y() as f32 + top).floor().max(low.y() as f32) as i32;
+        let mut end_x = (at.x() as f32 + right).ceil().min(high.x() as f32) as i32;
+        let mut end_y = (at.y() as f32 + bottom).ceil().min(high.y() as f32) as i32;
+        if self.wrap {
+            end_x = end_x.min(start_x.saturating_add(self.target.width()));
+            end_y = end_y.min(start_y.saturating_add(self.target.height()));
+        }
+
+        for y in start_y..end_y {
+            for x in start_x..end_x {
+                let local = Vector::new((x - at.x()) as f32 + 0.5, (y - at.y()) as f32 + 0.5);
+                let source = match sampling {
+                    Sampling::Nearest => inverse.apply(local).map(|v| v.floor() as i32),
+                };
+                let Some(color) = Image::pixel(image, source) else {
+                    continue;
+                };
+                let color = color.clone();
+                let pose = self.wrapped(Vector::new(x, y));
+                // SAFETY: the position is clipped to the target bounds and wrapped.
+                unsafe {
+                    let pixel = function(
+                        pose.x(),
+                        pose.y(),
+                        self.target.unsafe_pixel(pose).clone(),
+                        source.x(),
+                        source.y(),
+                        color,
+                    );
+                    *self.target.unsafe_pixel_mut(pose) = pixel;
+                }
+            }
+        }
+    }
+
+    /// Use provided function and given nine-slice image stretched to fill rectangle with `dimensions` at `at`.
+    /// Corners keep their size, edges and center are stretched.
+    pub fn nine_slice<F, O, U>(
+        &mut self,
+        at: Vector<i32>,
+        dimensions: Vector<i32>,
+        image: &U,
+        margins: Margins,
+        function: F,
+    ) where
+        U: Image<Pixel = O> + ?Sized,
+        O: Clone,
+        F: FnMut(i32, i32, T::Pixel, i32, i32, O) -> T::Pixel,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = O>,
+    {
+        let mut function = function;
+        self.map_on_nine_slice_offset(at, dimensions, image, margins, false, &mut function);
+    }
+
+    /// Use provided function and given nine-slice image tiled to fill rectangle with `dimensions` at `at`.
+    /// Corners keep their size, edges and center are repeated.
+    pub fn nine_slice_tiled<F, O, U>(
+        &mut self,
+        at: Vector<i32>,
+        dimensions: Vector<i32>,
+        image: &U,
+        margins: Margins,
+        function: F,
+    ) where
+        U: Image<Pixel = O> + ?Sized,
+        O: Clone,
+        F: FnMut(i32, i32, T::Pixel, i32, i32, O) -> T::Pixel,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = O>,
+    {
+        let mut function = function;
+        self.map_on_nine_slice_offset(at, dimensions, image, margins, true, &mut function);
+    }
+
+    fn map_on_nine_slice_offset<F, O, U>(
+        &mut self,
+        at: Vector<i32>,
+        dimensions: Vector<i32>,
+        image: &U,
+        margins: Margins,
+        tile: bool,
+        function: &mut F,
+    ) where
+        U: Image<Pixel = O> + ?Sized,
+        O: Clone,
+        F: FnMut(i32, i32, T::Pixel, i32, i32, O) -> T::Pixel,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = O>,
+    {
+        if dimensions.x() <= 0 || dimensions.y() <= 0 {
+            return;
+        }
+        let horizontal = Slicer::new(
+            image.width(),
+            dimensions.x(),
+            (margins.left, margins.right),
+            tile,
+        );
+        let vertical = Slicer::new(
+            image.height(),
+            dimensions.y(),
+            (margins.top, margins.bottom),
+            tile,
+        );
+
+        let at = self.offset_point(at);
+        let (low, high) = self.bounds();
+        let visible = |at: i32, low: i32, high: i32, length: i32| {
+            let start = (low as i64 - at as i64).clamp(0, length as i64) as i32;
+            let end = (high as i64 - at as i64).clamp(0, length as i64) as i32;
+            (start, end)
+        };
+        let (start_x, mut end_x) = visible(at.x(), low.x(), high.x(), dimensions.x());
+        let (start_y, mut end_y) = visible(at.y(), low.y(), high.y(), dimensions.y());
+        if self.wrap {
+            end_x = end_x.min(start_x.saturating_add(self.target.width()));
+            end_y = end_y.min(start_y.saturating_add(self.target.height()));
+        }
+        for y in start_y..end_y {
+            let Some(source_y) = vertical.map(y) else {
+                continue;
+            };
+            for x in start_x..end_x {
+                let Some(source_x) = horizontal.map(x) else {
+                    continue;
+                };
+                let source = Vector::new(source_x, source_y);
+                let Some(color) = Image::pixel(image, source) else {
+                    continue;
+                };
+                let color = color.clone();
+                let pose = self.wrapped(at + Vector::new(x, y));
+                // SAFETY: the position is clipped to the target bounds and wrapped.
+                unsafe {
+                    let pixel = function(
+                        pose.x(),
+                        pose.y(),
+                        self.target.unsafe_pixel(pose).clone(),
+                        source_x,
+                        source_y,
+                        color,
+                    );
+                    *self.target.unsafe_pixel_mut(pose) = pixel;
+                }
+            }
+        }
+    }
+
+    /// Copy pixels of the `image` onto this drawable.
+    /// Rows exposed by both images are copied in bulk.
+    pub fn blit<U>(&mut self, at: Vector<i32>, image: &U)
+    where
+        U: Image<Pixel = T::Pixel> + ?Sized,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = T::Pixel>,
+    {
+        if self.wrap {
+            self.image(at, image, stamp());
+            return;
+        }
+        let at = self.offset_point(at);
+        let (low, high) = self.bounds();
+        let visible = |at: i32, low: i32, high: i32, length: i32| {
+            let start = (low as i64 - at as i64).clamp(0, length as i64) as i32;
+            let end = (high as i64 - at as i64).clamp(0, length as i64) as i32;
+            (start, end)
+        };
+        let (start_x, end_x) = visible(at.x(), low.x(), high.x(), image.width());
+        let (start_y, end_y) = visible(at.y(), low.y(), high.y(), image.height());
+        for y in start_y..end_y {
+            let target_y = at.y() + y;
+            let source = image.row(y);
+            if let (Some(source), Some(target)) = (source, self.target.row_mut(target_y)) {
+                let target_x = (at.x() + start_x) as usize;
+                target[target_x..target_x + (end_x - start_x) as usize]
+                    .clone_from_slice(&source[start_x as usize..end_x as usize]);
+                continue;
+            }
+            for x in start_x..end_x {
+                // SAFETY: both positions are clipped to the images bounds.
+                unsafe {
+                    let color = image.unsafe_pixel((x, y).into()).clone();
+                    *self.target.unsafe_pixel_mut((at.x() + x, target_y).into()) = color;
+                }
+            }
+        }
+    }
+
+    /// Copy pixels of the `image` enlarged `factor` times onto this drawable.
+    /// Factors below one are treated as one.
+    pub fn blit_scaled<U>(&mut self, at: Vector<i32>, image: &U, factor: i32)
+    where
+        U: Image<Pixel = T::Pixel> + ?Sized,
+        for<'b> <U as DesignatorRef<'b>>::PixelRef: Deref<Target = T::Pixel>,
+    {
+        let view = image
+            .view(Vector::new(0, 0), image.dimensions())
+            .with_scale(factor.max(1));
+        self.blit(at, &view);
+    }
+
+    /// Fill rectangle with the `value`.
+    /// The `dimensions` determine size of the rectangle, zero or negative value produces no rectangle.
+    /// Rows exposed by the target are filled in bulk.
+    pub fn fill_rect(&mut self, from: Vector<i32>, dimensions: Vector<i32>, value: T::Pixel) {
+        if self.wrap {
+            self.rect_f((from, dimensions), paint(value));
+            return;
+        }
+        let from = self.offset_point(from);
+        let to = shift(from, dimensions.x(), dimensions.y());
+        let (low, high) = self.bounds();
+        let (start_x, end_x) = (from.x().max(low.x()), to.x().min(high.x()));
+        let (start_y, end_y) = (from.y().max(low.y()), to.y().min(high.y()));
+        if start_x >= end_x {
+            return;
+        }
+        for y in start_y..end_y {
+            if let Some(row) = self.target.row_mut(y) {
+                row[start_x as usize..end_x as usize].fill(value.clone());
+                continue;
+            }
+            for x in start_x..end_x {
+                // SAFETY: the position is clipped to the target bounds.
+                unsafe {
+                    *self.target.unsafe_pixel_mut((x, y).into()) = value.clone();
+                }
+            }
+        }
+    }
+
     /// Use provided spatial mapper, font and mapper function to draw text.
     pub fn text<M, U, O, F>(
         &mut self,
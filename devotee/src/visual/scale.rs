@@ -0,0 +1,61 @@
+use std::ops::Deref;
+
+use crate::util::vector::Vector;
+
+use super::canvas::Canvas;
+use super::image::{DesignatorRef, OwnedImage};
+use super::Image;
+
+/// Create new canvas with the `source` enlarged `factor` times, repeating each pixel.
+/// Factors below one are treated as one.
+/// Returns `None` if the `source` is empty.
+pub fn scale_nearest<U>(source: &U, factor: i32) -> Option<Canvas<U::Pixel>>
+where
+    U: Image + ?Sized,
+    U::Pixel: Clone,
+    for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = U::Pixel>,
+{
+    let factor = factor.max(1);
+    let dimensions = source.dimensions();
+    resample(source, dimensions * factor, |position| position / factor)
+}
+
+/// Create new canvas with the `source` reduced `factor` times, sampling the center of each block of pixels.
+/// Partial blocks at the right and bottom edges are sampled too.
+/// Factors below one are treated as one.
+/// Returns `None` if the `source` is empty.
+pub fn downscale_by_sampling<U>(source: &U, factor: i32) -> Option<Canvas<U::Pixel>>
+where
+    U: Image + ?Sized,
+    U::Pixel: Clone,
+    for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = U::Pixel>,
+{
+    let factor = factor.max(1);
+    let dimensions = source.dimensions();
+    let reduced = Vector::new(
+        (dimensions.x() + factor - 1) / factor,
+        (dimensions.y() + factor - 1) / factor,
+    );
+    let last = dimensions - Vector::new(1, 1);
+    resample(source, reduced, |position| {
+        (position * factor + Vector::new(factor / 2, factor / 2)).individual_min(last)
+    })
+}
+
+fn resample<U, F>(source: &U, dimensions: Vector<i32>, sample: F) -> Option<Canvas<U::Pixel>>
+where
+    U: Image + ?Sized,
+    U::Pixel: Clone,
+    for<'a> <U as DesignatorRef<'a>>::PixelRef: Deref<Target = U::Pixel>,
+    F: Fn(Vector<i32>) -> Vector<i32>,
+{
+    if source.width() <= 0 || source.height() <= 0 {
+        return None;
+    }
+    Canvas::from_fn(
+        dimensions.x() as usize,
+        dimensions.y() as usize,
+        // SAFETY: sampled positions are within the source dimensions.
+        |position| unsafe { source.unsafe_pixel(sample(position)).clone() },
+    )
+}
@@ -0,0 +1,239 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::util::getter::Getter;
+use crate::util::vector::Vector;
+
+use super::blend::BlendSpace;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{Image, ImageMut, Painter};
+
+/// Signed distance field baked from a bitmap.
+/// Distances are measured in texels, negative inside the shape and positive outside.
+#[derive(Clone, Debug)]
+pub struct DistanceField {
+    width: i32,
+    height: i32,
+    padding: i32,
+    spread: f32,
+    data: Vec<f32>,
+}
+
+impl DistanceField {
+    /// Bake distance field from the `image` using `inside` predicate to classify pixels.
+    /// Distances are clamped to the `spread` value; the field is padded by the spread on each side.
+    pub fn bake<I, F>(image: &I, spread: f32, inside: F) -> Self
+    where
+        I: Image + ?Sized,
+        for<'a> <I as DesignatorRef<'a>>::PixelRef: Deref<Target = I::Pixel>,
+        F: Fn(&I::Pixel) -> bool,
+    {
+        let spread = spread.max(1.0);
+        let padding = spread.ceil() as i32;
+        let (source_width, source_height) = (image.width(), image.height());
+        let mask: Vec<bool> = (0..source_height)
+            .flat_map(|y| (0..source_width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                image
+                    .pixel(Vector::new(x, y))
+                    .is_some_and(|pixel| inside(&pixel))
+            })
+            .collect();
+        let is_inside = |x: i32, y: i32| {
+            x >= 0
+                && y >= 0
+                && x < source_width
+                && y < source_height
+                && mask[(x + y * source_width) as usize]
+        };
+
+        let width = source_width + 2 * padding;
+        let height = source_height + 2 * padding;
+        let mut data = Vec::with_capacity((width * height) as usize);
+        for y in -padding..source_height + padding {
+            for x in -padding..source_width + padding {
+                let state = is_inside(x, y);
+                let mut nearest = spread + 0.5;
+                for dy in -padding..=padding {
+                    for dx in -padding..=padding {
+                        if is_inside(x + dx, y + dy) != state {
+                            let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                            nearest = nearest.min(distance);
+                        }
+                    }
+                }
+                let distance = (nearest - 0.5).min(spread);
+                data.push(if state { -distance } else { distance });
+            }
+        }
+
+        Self {
+            width,
+            height,
+            padding,
+            spread,
+            data,
+        }
+    }
+
+    /// Get width of the source bitmap.
+    pub fn width(&self) -> i32 {
+        self.width - 2 * self.padding
+    }
+
+    /// Get height of the source bitmap.
+    pub fn height(&self) -> i32 {
+        self.height - 2 * self.padding
+    }
+
+    /// Get the spread the field was baked with.
+    pub fn spread(&self) -> f32 {
+        self.spread
+    }
+
+    /// Sample the field with bilinear filtering at the `position` relative to the source bitmap.
+    /// Samples outside of the field are considered to be `spread` away from the shape.
+    pub fn sample(&self, position: Vector<f32>) -> f32 {
+        let x = position.x() + self.padding as f32 - 0.5;
+        let y = position.y() + self.padding as f32 - 0.5;
+        let (left, top) = (x.floor(), y.floor());
+        let (fraction_x, fraction_y) = (x - left, y - top);
+        let (left, top) = (left as i32, top as i32);
+        let texel = |x: i32, y: i32| {
+            if x < 0 || y < 0 || x >= self.width || y >= self.height {
+                self.spread
+            } else {
+                self.data[(x + y * self.width) as usize]
+            }
+        };
+        let upper = texel(left, top) * (1.0 - fraction_x) + texel(left + 1, top) * fraction_x;
+        let lower =
+            texel(left, top + 1) * (1.0 - fraction_x) + texel(left + 1, top + 1) * fraction_x;
+        upper * (1.0 - fraction_y) + lower * fraction_y
+    }
+}
+
+/// Helper coverage function blending `color` over the `0xaa_rr_gg_bb` pixel value with the coverage as opacity.
+pub fn coverage(color: u32, space: BlendSpace) -> impl FnMut(i32, i32, u32, f32) -> u32 {
+    let opacity = (color >> 24) as f32 / 255.0;
+    move |_, _, pixel, coverage| {
+        let opacity = opacity * coverage;
+        space.combine(pixel, color, |destination, source| {
+            destination + (source - destination) * opacity
+        })
+    }
+}
+
+/// Helper coverage function for palette targets.
+/// It replaces pixels covered at least by half with `value`.
+pub fn threshold<P>(value: P) -> impl FnMut(i32, i32, P, f32) -> P
+where
+    P: Clone,
+{
+    move |_, _, pixel, coverage| {
+        if coverage >= 0.5 {
+            value.clone()
+        } else {
+            pixel
+        }
+    }
+}
+
+impl<T> Painter<'_, T, f32>
+where
+    T: ImageMut,
+    T::Pixel: Clone,
+    for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+    for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+{
+    /// Draw the distance field shape scaled by `scale` with its top left corner at `at`.
+    /// The function receives pixel position, its original value and the shape coverage in `(0.0, 1.0]` range.
+    pub fn distance_field<F>(
+        &mut self,
+        at: Vector<f32>,
+        field: &DistanceField,
+        scale: f32,
+        function: F,
+    ) where
+        F: FnMut(i32, i32, T::Pixel, f32) -> T::Pixel,
+    {
+        let mut function = function;
+        self.map_on_distance_field_offset(at, field, scale, &mut function);
+    }
+
+    /// Use provided font of distance fields to draw text scaled by `scale`.
+    /// Lines are broken on newline symbol (`'\n'`), glyphs are advanced by their scaled width.
+    /// Empty lines advance by the height of the previous non-empty line, or the tallest glyph of the text.
+    pub fn distance_field_text<F>(
+        &mut self,
+        at: Vector<f32>,
+        font: &dyn Getter<Index = char, Item = DistanceField>,
+        text: &str,
+        scale: f32,
+        function: F,
+    ) where
+        F: FnMut(i32, i32, T::Pixel, f32) -> T::Pixel,
+    {
+        let mut function = function;
+        let mut cursor = at;
+        let mut line_height = 0.0;
+        let mut last_height = text
+            .chars()
+            .filter_map(|code_point| font.get(&code_point))
+            .map(|glyph| glyph.height() as f32 * scale)
+            .fold(0.0, f32::max);
+        for code_point in text.chars() {
+            if code_point == '\n' {
+                if line_height > 0.0 {
+                    last_height = line_height;
+                }
+                cursor = Vector::new(at.x(), cursor.y() + last_height);
+                line_height = 0.0;
+                continue;
+            }
+            if let Some(glyph) = font.get(&code_point) {
+                self.map_on_distance_field_offset(cursor, glyph, scale, &mut function);
+                *cursor.x_mut() += glyph.width() as f32 * scale;
+                line_height = f32::max(line_height, glyph.height() as f32 * scale);
+            }
+        }
+    }
+
+    fn map_on_distance_field_offset<F: FnMut(i32, i32, T::Pixel, f32) -> T::Pixel>(
+        &mut self,
+        at: Vector<f32>,
+        field: &DistanceField,
+        scale: f32,
+        function: &mut F,
+    ) {
+        if scale <= 0.0 || !scale.is_finite() {
+            return;
+        }
+        let at = at + self.offset;
+        let margin = field.spread;
+        let left = ((at.x() - margin * scale).floor() as i32).max(0);
+        let top = ((at.y() - margin * scale).floor() as i32).max(0);
+        let right =
+            ((at.x() + (field.width() as f32 + margin) * scale).ceil() as i32).min(self.width());
+        let bottom =
+            ((at.y() + (field.height() as f32 + margin) * scale).ceil() as i32).min(self.height());
+
+        for y in top..bottom {
+            for x in left..right {
+                let local = Vector::new(
+                    (x as f32 + 0.5 - at.x()) / scale,
+                    (y as f32 + 0.5 - at.y()) / scale,
+                );
+                let coverage = (0.5 - field.sample(local) * scale).clamp(0.0, 1.0);
+                if coverage > 0.0 {
+                    let position = Vector::new(x, y);
+                    // SAFETY: the position is clamped to the target bounds.
+                    unsafe {
+                        let pixel =
+                            function(x, y, self.target.unsafe_pixel(position).clone(), coverage);
+                        *self.target.unsafe_pixel_mut(position) = pixel;
+                    }
+                }
+            }
+        }
+    }
+}
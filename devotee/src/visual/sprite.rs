@@ -1,6 +1,7 @@
 use backend::RenderSurface;
 
-use super::image::{DesignatorMut, DesignatorRef};
+use super::canvas::Canvas;
+use super::image::{DesignatorMut, DesignatorRef, OwnedImage};
 use super::{Image, ImageMut};
 use crate::util::vector::Vector;
 
@@ -57,6 +58,24 @@ impl<P, const W: usize, const H: usize> Image for Sprite<P, W, H> {
     fn height(&self) -> i32 {
         H as i32
     }
+
+    fn row(&self, y: i32) -> Option<&[P]> {
+        self.data
+            .get(usize::try_from(y).ok()?)
+            .map(|row| row.as_slice())
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[P]> {
+        self.data.iter().map(|row| row.as_slice())
+    }
+
+    fn pixels(&self) -> impl Iterator<Item = (Vector<i32>, &P)> {
+        self.data.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, pixel)| (Vector::new(x as i32, y as i32), pixel))
+        })
+    }
 }
 
 impl<'a, P, const W: usize, const H: usize> DesignatorMut<'a> for Sprite<P, W, H> {
@@ -87,6 +106,54 @@ where
     fn clear(&mut self, color: P) {
         self.data = [[color; W]; H];
     }
+
+    fn row_mut(&mut self, y: i32) -> Option<&mut [P]> {
+        self.data
+            .get_mut(usize::try_from(y).ok()?)
+            .map(|row| row.as_mut_slice())
+    }
+
+    fn rows_mut(&mut self) -> impl Iterator<Item = &mut [P]> {
+        self.data.iter_mut().map(|row| row.as_mut_slice())
+    }
+}
+
+impl<P, const W: usize, const H: usize> OwnedImage for Sprite<P, W, H>
+where
+    P: Copy,
+{
+    /// Create new sprite getting each pixel by its position from the `function`.
+    /// Returns `None` if the dimensions differ from the sprite ones.
+    fn from_fn<F>(width: usize, height: usize, function: F) -> Option<Self>
+    where
+        F: FnMut(Vector<i32>) -> P,
+    {
+        if width != W || height != H {
+            return None;
+        }
+        let mut function = function;
+        let data = std::array::from_fn(|y| {
+            std::array::from_fn(|x| function(Vector::new(x as i32, y as i32)))
+        });
+        Some(Self { data })
+    }
+}
+
+impl<P, const W: usize, const H: usize> TryFrom<Canvas<P>> for Sprite<P, W, H>
+where
+    P: Copy,
+{
+    type Error = Canvas<P>;
+
+    /// Convert the `canvas` into the sprite.
+    /// Returns the `canvas` back if its dimensions differ from the sprite ones.
+    fn try_from(canvas: Canvas<P>) -> Result<Self, Self::Error> {
+        if canvas.dimensions() != Vector::new(W as i32, H as i32) {
+            return Err(canvas);
+        }
+        // SAFETY: the canvas dimensions match the sprite ones.
+        Self::from_fn(W, H, |position| unsafe { *canvas.unsafe_pixel(position) }).ok_or(canvas)
+    }
 }
 
 impl<P, const W: usize, const H: usize> Default for Sprite<P, W, H>
@@ -115,4 +182,8 @@ where
     fn data(&self, x: usize, y: usize) -> P {
         unsafe { *self.unsafe_pixel(Vector::new(x as i32, y as i32)) }
     }
+
+    fn row(&self, y: usize) -> Option<&[P]> {
+        self.data.get(y).map(|row| row.as_slice())
+    }
 }
@@ -0,0 +1,179 @@
+use std::ops::{Deref, DerefMut};
+
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{ImageMut, JoinStyle, Painter};
+use crate::util::vector::Vector;
+
+/// Maximal ratio of the miter length to the stroke width before falling back to the bevel join.
+const MITER_LIMIT: f32 = 4.0;
+
+/// Convex part of the stroke.
+#[derive(Clone, Copy, Debug)]
+enum Shape {
+    /// Convex polygon; triangles repeat the last vertex.
+    Polygon([Vector<f32>; 4]),
+    /// Disc with center and radius.
+    Disc(Vector<f32>, f32),
+}
+
+impl Shape {
+    fn vertical_range(&self) -> (f32, f32) {
+        match self {
+            Shape::Polygon(vertices) => vertices.iter().fold(
+                (f32::INFINITY, f32::NEG_INFINITY),
+                |(top, bottom), vertex| (top.min(vertex.y()), bottom.max(vertex.y())),
+            ),
+            Shape::Disc(center, radius) => (center.y() - radius, center.y() + radius),
+        }
+    }
+
+    /// Get range of pixel centers covered on the `scanline`.
+    /// Bottom and right edges are exclusive, so adjacent shapes do not overlap.
+    fn span(&self, scanline: i32) -> Option<(i32, i32)> {
+        let y = scanline as f32;
+        let (top, bottom) = self.vertical_range();
+        if y < top || y >= bottom {
+            return None;
+        }
+        let (left, right) = match self {
+            Shape::Polygon(vertices) => {
+                let mut range = (f32::INFINITY, f32::NEG_INFINITY);
+                for (index, a) in vertices.iter().enumerate() {
+                    let b = vertices[(index + 1) % vertices.len()];
+                    let (top, bottom) = (a.y().min(b.y()), a.y().max(b.y()));
+                    if y < top || y > bottom {
+                        continue;
+                    }
+                    let xs = if a.y() == b.y() {
+                        (a.x(), b.x())
+                    } else {
+                        let x = a.x() + (y - a.y()) * (b.x() - a.x()) / (b.y() - a.y());
+                        (x, x)
+                    };
+                    range = (range.0.min(xs.0).min(xs.1), range.1.max(xs.0).max(xs.1));
+                }
+                range
+            }
+            Shape::Disc(center, radius) => {
+                let half_width = (radius.powi(2) - (y - center.y()).powi(2)).sqrt();
+                (center.x() - half_width, center.x() + half_width)
+            }
+        };
+        let (from, to) = (left.ceil(), right.ceil() - 1.0);
+        (from <= to).then_some((from as i32, to as i32))
+    }
+}
+
+/// Split the polyline through `points` with given `width` into convex shapes.
+fn shapes(points: &[Vector<f32>], width: f32, join: JoinStyle) -> Vec<Shape> {
+    let half_width = width.max(1.0) / 2.0;
+    let segments: Vec<_> = points
+        .windows(2)
+        .filter_map(|pair| {
+            let delta = pair[1] - pair[0];
+            let length = (delta.x().powi(2) + delta.y().powi(2)).sqrt();
+            (length > 0.0).then(|| {
+                let normal = Vector::new(-delta.y(), delta.x()) / length * half_width;
+                (pair[0], pair[1], normal)
+            })
+        })
+        .collect();
+
+    let mut shapes = Vec::with_capacity(segments.len() * 2);
+    for &(from, to, normal) in &segments {
+        shapes.push(Shape::Polygon([
+            from + normal,
+            to + normal,
+            to - normal,
+            from - normal,
+        ]));
+    }
+    for pair in segments.windows(2) {
+        let ((_, joint, first), (_, _, second)) = (pair[0], pair[1]);
+        let cross: f32 = first.cross_2d(second);
+        if cross == 0.0 && first.dot::<f32, f32>(second) > 0.0 {
+            continue;
+        }
+        if join == JoinStyle::Round {
+            shapes.push(Shape::Disc(joint, half_width));
+            continue;
+        }
+        let sign = if cross > 0.0 { -1.0 } else { 1.0 };
+        let (first, second) = (first * sign, second * sign);
+        let bisector = first + second;
+        let bisector_square = bisector.dot::<f32, f32>(bisector);
+        let ratio = 2.0 * half_width / bisector_square.sqrt();
+        if join == JoinStyle::Miter && bisector_square > 0.0 && ratio <= MITER_LIMIT {
+            let miter = bisector * (2.0 * half_width.powi(2) / bisector_square);
+            shapes.push(Shape::Polygon([
+                joint,
+                joint + first,
+                joint + miter,
+                joint + second,
+            ]));
+        } else {
+            shapes.push(Shape::Polygon([
+                joint,
+                joint + first,
+                joint + second,
+                joint + second,
+            ]));
+        }
+    }
+    shapes
+}
+
+impl<T, C> Painter<'_, T, C>
+where
+    T: ImageMut,
+    T::Pixel: Clone,
+    for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+    for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+{
+    /// Map pixels of the stroked polyline through absolute `points`.
+    /// Pixels covered by several parts of the stroke are mapped once.
+    pub(super) fn map_on_stroke_raw<F: FnMut(i32, i32, T::Pixel) -> T::Pixel>(
+        &mut self,
+        points: &[Vector<f32>],
+        width: f32,
+        join: JoinStyle,
+        function: &mut F,
+    ) {
+        let shapes = shapes(points, width, join);
+        let (top, bottom) = shapes.iter().map(Shape::vertical_range).fold(
+            (f32::INFINITY, f32::NEG_INFINITY),
+            |(top, bottom), (shape_top, shape_bottom)| {
+                (top.min(shape_top), bottom.max(shape_bottom))
+            },
+        );
+        if top > bottom {
+            return;
+        }
+        let (low, high) = self.bounds();
+        let top = (top.ceil() as i32).max(low.y());
+        let bottom = (bottom.ceil() as i32 - 1).min(high.y() - 1);
+
+        let mut spans = Vec::with_capacity(shapes.len());
+        for scanline in top..=bottom {
+            spans.clear();
+            spans.extend(shapes.iter().filter_map(|shape| shape.span(scanline)));
+            spans.sort_unstable();
+            let mut current: Option<(i32, i32)> = None;
+            for &(from, to) in &spans {
+                current = match current {
+                    Some((left, right)) if from <= right.saturating_add(1) => {
+                        Some((left, right.max(to)))
+                    }
+                    Some((left, right)) => {
+                        self.map_fast_horizontal_line_raw(left, right, scanline, function);
+                        Some((from, to))
+                    }
+                    None => Some((from, to)),
+                };
+            }
+            if let Some((left, right)) = current {
+                self.map_fast_horizontal_line_raw(left, right, scanline, function);
+            }
+        }
+    }
+}
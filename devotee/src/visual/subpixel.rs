@@ -1,11 +1,12 @@
 use std::cmp::Ordering;
 use std::ops::{Deref, DerefMut};
 
+use crate::util::rect::Rect;
 use crate::util::vector::Vector;
 use crate::visual::util::AngleIterator;
 
 use super::image::{DesignatorMut, DesignatorRef, PixelMut, PixelRef};
-use super::{Image, ImageMut, Paint, Painter, Scan};
+use super::{Image, ImageMut, JoinStyle, Paint, Painter, Scan};
 
 fn scanline_segment_f32(segment: (Vector<f32>, Vector<f32>), scanline: i32) -> Scan<i32> {
     let (from, to) = if segment.0.y() < segment.1.y() {
@@ -80,8 +81,15 @@ where
         let from_i32 = from.map(round_to_i32);
         let to_i32 = to.map(round_to_i32);
 
-        let mut iter = from_i32.y()..=to_i32.y();
-        let mut iter_rev = (to_i32.y()..=from_i32.y()).rev();
+        let (low, high) = self.bounds();
+        if high.x() <= low.x() || high.y() <= low.y() {
+            return;
+        }
+
+        let top = from_i32.y().min(to_i32.y()).max(low.y());
+        let bottom = from_i32.y().max(to_i32.y()).min(high.y() - 1);
+        let mut iter = top..=bottom;
+        let mut iter_rev = (top..=bottom).rev();
 
         let iter_ref: &mut dyn Iterator<Item = i32> = if from.y() < to.y() {
             &mut iter
@@ -91,10 +99,13 @@ where
 
         let rev = from.x() > to.x();
 
-        let mut skip = skip;
+        // Pixels to skip are counted from `from`, which is invisible when clamped.
+        let visible = (low.x()..high.x()).contains(&from_i32.x())
+            && (low.y()..high.y()).contains(&from_i32.y());
+        let mut skip = if visible { skip } else { 0 };
 
         for y in iter_ref {
-            let scan = scanline_segment_f32((from, to), y);
+            let scan = scanline_segment_f32((from, to), y).clamped(low.x(), high.x() - 1);
             let mut scan_rev = scan.rev().into_iter();
             let mut scan = scan.into_iter();
             let scan: &mut dyn Iterator<Item = i32> = if rev { &mut scan_rev } else { &mut scan };
@@ -140,7 +151,8 @@ where
             b_i32.y() - 1
         };
 
-        for y in a_i32.y()..=middle {
+        let (low, high) = self.bounds();
+        for y in a_i32.y().max(low.y())..=middle.min(high.y() - 1) {
             let left_range = scanline_segment_f32((a, b), y);
             let right_range = scanline_segment_f32((a, c), y);
             let left = left_range
@@ -151,7 +163,7 @@ where
         }
 
         let middle = middle + 1;
-        for y in middle..=c_i32.y() {
+        for y in middle.max(low.y())..=c_i32.y().min(high.y() - 1) {
             let left_range = scanline_segment_f32((a, c), y);
             let right_range = scanline_segment_f32((b, c), y);
             let left = left_range
@@ -192,6 +204,11 @@ where
         let (top, bottom) = (round_to_i32(top), round_to_i32(bottom));
         let left = round_to_i32(left);
         let offset = self.offset.map(round_to_i32);
+        let (low, high) = self.bounds();
+        let (top, bottom) = (
+            top.max(low.y().saturating_sub(offset.y())),
+            bottom.min((high.y() - 1).saturating_sub(offset.y())),
+        );
 
         for y in top..=bottom {
             let segments = AngleIterator::new(vertices);
@@ -231,7 +248,7 @@ where
                     Scan::None => {}
                 }
             }
-            flips.sort_by(|a, b| a.position.cmp(&b.position));
+            flips.sort_by_key(|flip| flip.position);
 
             let mut counter = 0;
             let mut current_left = left;
@@ -257,18 +274,19 @@ where
         }
     }
 
-    fn map_on_filled_subcircle<F: FnMut(i32, i32, P) -> P>(
+    fn map_on_filled_subshape<F: FnMut(i32, i32, P) -> P, W: Fn(f32) -> f32>(
         &mut self,
         center: Vector<f32>,
-        radius: f32,
+        (half_height, half_width): (f32, W),
+        sector: Option<(f32, f32)>,
         function: &mut F,
     ) {
         let center = self.offset + center;
-        let top = round_to_i32(center.y() - radius);
-        let bottom = round_to_i32(center.y() + radius);
+        let (low, high) = self.bounds();
+        let top = round_to_i32(center.y() - half_height).max(low.y());
+        let bottom = round_to_i32(center.y() + half_height).min(high.y() - 1);
 
-        let rs = radius.powi(2);
-        let determine_x = |y: f32| (rs - (y - center.y()).powi(2)).sqrt();
+        let determine_x = |y: f32| half_width(y - center.y());
 
         let mut top_x = determine_x(top as f32 - 0.5);
 
@@ -277,18 +295,20 @@ where
             match (top_x, current_x) {
                 (a, b) if a.is_nan() && b.is_nan() => (),
                 (a, b) if a.is_nan() || b > a => {
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() - b),
                         round_to_i32(center.x() + b),
                         scanline,
+                        (center, sector),
                         function,
                     );
                 }
                 (a, b) if b.is_nan() || a >= b => {
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() - a),
                         round_to_i32(center.x() + a),
                         scanline,
+                        (center, sector),
                         function,
                     );
                 }
@@ -298,18 +318,19 @@ where
         }
     }
 
-    fn map_on_subcircle<F: FnMut(i32, i32, P) -> P>(
+    fn map_on_subshape<F: FnMut(i32, i32, P) -> P, W: Fn(f32) -> f32>(
         &mut self,
         center: Vector<f32>,
-        radius: f32,
+        (half_height, half_width): (f32, W),
+        sector: Option<(f32, f32)>,
         function: &mut F,
     ) {
         let center = self.offset + center;
-        let top = round_to_i32(center.y() - radius);
-        let bottom = round_to_i32(center.y() + radius);
+        let (low, high) = self.bounds();
+        let top = round_to_i32(center.y() - half_height).max(low.y());
+        let bottom = round_to_i32(center.y() + half_height).min(high.y() - 1);
 
-        let rs = radius.powi(2);
-        let determine_x = |y: f32| (rs - (y - center.y()).powi(2)).sqrt();
+        let determine_x = |y: f32| half_width(y - center.y());
 
         let mut top_x = determine_x(top as f32 - 0.5);
 
@@ -318,46 +339,52 @@ where
             match (top_x, current_x) {
                 (a, b) if a.is_nan() && b.is_nan() => (),
                 (a, b) if a.is_nan() => {
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() - b),
                         round_to_i32(center.x() + b),
                         scanline,
+                        (center, sector),
                         function,
                     );
                 }
                 (a, b) if b.is_nan() => {
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() - a),
                         round_to_i32(center.x() + a),
                         scanline,
+                        (center, sector),
                         function,
                     );
                 }
                 (a, b) if a > b => {
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() - a),
                         round_to_i32(center.x() - b),
                         scanline,
+                        (center, sector),
                         function,
                     );
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() + b),
                         round_to_i32(center.x() + a),
                         scanline,
+                        (center, sector),
                         function,
                     );
                 }
                 (a, b) => {
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() - b),
                         round_to_i32(center.x() - a),
                         scanline,
+                        (center, sector),
                         function,
                     );
-                    self.map_fast_horizontal_line_raw(
+                    self.map_on_span_raw(
                         round_to_i32(center.x() + a),
                         round_to_i32(center.x() + b),
                         scanline,
+                        (center, sector),
                         function,
                     );
                 }
@@ -365,6 +392,22 @@ where
             top_x = current_x;
         }
     }
+
+    fn map_on_span_raw<F: FnMut(i32, i32, P) -> P>(
+        &mut self,
+        from_x: i32,
+        to_x: i32,
+        y: i32,
+        (center, sector): (Vector<f32>, Option<(f32, f32)>),
+        function: &mut F,
+    ) {
+        match sector {
+            Some((start, end)) => {
+                self.map_sector_horizontal_line_raw(from_x, to_x, y, (center, start, end), function)
+            }
+            None => self.map_fast_horizontal_line_raw(from_x, to_x, y, function),
+        }
+    }
 }
 
 impl<T, P> Paint<T, f32> for Painter<'_, T, f32>
@@ -375,11 +418,13 @@ where
     for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
 {
     fn pixel(&self, position: Vector<f32>) -> Option<PixelRef<'_, T>> {
-        Image::pixel(self.target, (position + self.offset).map(round_to_i32))
+        let position = self.wrapped((position + self.offset).map(round_to_i32));
+        Image::pixel(self.target, position)
     }
 
     fn pixel_mut(&mut self, position: Vector<f32>) -> Option<PixelMut<'_, T>> {
-        ImageMut::pixel_mut(self.target, (position + self.offset).map(round_to_i32))
+        let position = self.wrapped((position + self.offset).map(round_to_i32));
+        ImageMut::pixel_mut(self.target, position)
     }
 
     fn mod_pixel<F>(&mut self, position: Vector<f32>, function: F)
@@ -404,10 +449,13 @@ where
         self.map_on_subline_offset(from, to, &mut function, 0);
     }
 
-    fn rect_f<F>(&mut self, from: Vector<f32>, dimensions: Vector<f32>, function: F)
+    fn rect_f<R, F>(&mut self, rect: R, function: F)
     where
+        R: Into<Rect<f32>>,
         F: FnMut(i32, i32, P) -> P,
     {
+        let rect: Rect<f32> = rect.into();
+        let (from, dimensions) = (rect.origin(), rect.dimensions());
         let mut function = function;
         let offset = self.offset;
         let from = from + offset;
@@ -416,11 +464,17 @@ where
         self.map_on_filled_rect_raw(from, to, &mut function);
     }
 
-    fn rect_b<F>(&mut self, from: Vector<f32>, dimensions: Vector<f32>, function: F)
+    fn rect_b<R, F>(&mut self, rect: R, function: F)
     where
+        R: Into<Rect<f32>>,
         F: FnMut(i32, i32, P) -> P,
     {
+        let rect: Rect<f32> = rect.into();
+        let (from, dimensions) = (rect.origin(), rect.dimensions());
         let offset = self.offset;
+        if dimensions.x() <= 0.0 || dimensions.y() <= 0.0 {
+            return;
+        }
         let (from, to) = (from + offset, from + dimensions + offset - (1.0, 1.0));
         let (from, to) = (from.map(round_to_i32), to.map(round_to_i32));
         let mut function = function;
@@ -487,7 +541,9 @@ where
         F: FnMut(i32, i32, P) -> P,
     {
         let mut function = function;
-        self.map_on_filled_subcircle(center, radius, &mut function);
+        let square = radius.powi(2);
+        let half_width = |y: f32| (square - y.powi(2)).sqrt();
+        self.map_on_filled_subshape(center, (radius, half_width), None, &mut function);
     }
 
     fn circle_b<F>(&mut self, center: Vector<f32>, radius: f32, function: F)
@@ -495,6 +551,81 @@ where
         F: FnMut(i32, i32, P) -> P,
     {
         let mut function = function;
-        self.map_on_subcircle(center, radius, &mut function);
+        let square = radius.powi(2);
+        let half_width = |y: f32| (square - y.powi(2)).sqrt();
+        self.map_on_subshape(center, (radius, half_width), None, &mut function);
+    }
+
+    fn line_thick<F>(&mut self, from: Vector<f32>, to: Vector<f32>, width: f32, function: F)
+    where
+        F: FnMut(i32, i32, P) -> P,
+    {
+        self.polyline(&[from, to], width, JoinStyle::default(), function);
+    }
+
+    fn polyline<F>(&mut self, points: &[Vector<f32>], width: f32, join: JoinStyle, function: F)
+    where
+        F: FnMut(i32, i32, P) -> P,
+    {
+        let mut function = function;
+        let offset = self.offset;
+        let points: Vec<_> = points.iter().map(|&point| point + offset).collect();
+        self.map_on_stroke_raw(&points, width, join, &mut function);
+    }
+
+    fn ellipse_f<F>(&mut self, center: Vector<f32>, radii: Vector<f32>, function: F)
+    where
+        F: FnMut(i32, i32, P) -> P,
+    {
+        let mut function = function;
+        let (radius_x, radius_y) = radii.split();
+        if radius_x < 0.0 || radius_y < 0.0 {
+            return;
+        }
+        let half_width = |y: f32| radius_x * (1.0 - (y / radius_y).powi(2)).sqrt();
+        self.map_on_filled_subshape(center, (radius_y, half_width), None, &mut function);
+    }
+
+    fn ellipse_b<F>(&mut self, center: Vector<f32>, radii: Vector<f32>, function: F)
+    where
+        F: FnMut(i32, i32, P) -> P,
+    {
+        let mut function = function;
+        let (radius_x, radius_y) = radii.split();
+        if radius_x < 0.0 || radius_y < 0.0 {
+            return;
+        }
+        let half_width = |y: f32| radius_x * (1.0 - (y / radius_y).powi(2)).sqrt();
+        self.map_on_subshape(center, (radius_y, half_width), None, &mut function);
+    }
+
+    fn arc_b<F>(&mut self, center: Vector<f32>, radius: f32, start: f32, end: f32, function: F)
+    where
+        F: FnMut(i32, i32, P) -> P,
+    {
+        let mut function = function;
+        let square = radius.powi(2);
+        let half_width = |y: f32| (square - y.powi(2)).sqrt();
+        self.map_on_subshape(
+            center,
+            (radius, half_width),
+            Some((start, end)),
+            &mut function,
+        );
+    }
+
+    fn sector_f<F>(&mut self, center: Vector<f32>, radius: f32, start: f32, end: f32, function: F)
+    where
+        F: FnMut(i32, i32, P) -> P,
+    {
+        let mut function = function;
+        let square = radius.powi(2);
+        let half_width = |y: f32| (square - y.powi(2)).sqrt();
+        self.map_on_filled_subshape(
+            center,
+            (radius, half_width),
+            Some((start, end)),
+            &mut function,
+        );
     }
 }
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+
+use crate::util::invalidation::Subscription;
+use crate::util::vector::Vector;
+
+use super::canvas::Canvas;
+use super::font::{ink, BitmapFont, TextStyle};
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{Image, ImageMut, PaintTarget, Painter};
+
+/// Maximal amount of canvases kept for reuse.
+const MAX_POOLED: usize = 64;
+
+/// Identifier of the font registered in the `TextCache`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FontId(usize);
+
+#[derive(Clone, Debug)]
+struct Entry<C> {
+    canvas: Canvas<Option<C>>,
+    last_used: u64,
+}
+
+/// Cache of text rasterized with `bool` bitmap fonts into canvases.
+/// Strings drawn with the same font, style and color are laid out once and blitted afterwards.
+///
+/// Entries not drawn for `max_age` ticks are evicted, their canvases are pooled for reuse.
+#[derive(Clone, Debug)]
+pub struct TextCache<C> {
+    fonts: Vec<BitmapFont<bool>>,
+    entries: HashMap<(FontId, TextStyle, C), HashMap<String, Entry<C>>>,
+    pool: Vec<Canvas<Option<C>>>,
+    subscriptions: Vec<Subscription>,
+    tick: u64,
+    max_age: u64,
+}
+
+impl<C> TextCache<C>
+where
+    C: Clone + Eq + Hash,
+{
+    /// Create new empty cache evicting entries not drawn for 60 ticks.
+    pub fn new() -> Self {
+        Self {
+            fonts: Vec::new(),
+            entries: HashMap::new(),
+            pool: Vec::new(),
+            subscriptions: Vec::new(),
+            tick: 0,
+            max_age: 60,
+        }
+    }
+
+    /// Set amount of ticks an entry is kept for without being drawn.
+    pub fn with_max_age(self, max_age: u64) -> Self {
+        Self { max_age, ..self }
+    }
+
+    /// Drop all the entries whenever the subscription source signals, e.g. on palette change.
+    pub fn with_subscription(self, subscription: Subscription) -> Self {
+        let mut subscriptions = self.subscriptions;
+        subscriptions.push(subscription);
+        Self {
+            subscriptions,
+            ..self
+        }
+    }
+
+    /// Register the `font` for drawing with this cache.
+    pub fn add_font(&mut self, font: BitmapFont<bool>) -> FontId {
+        self.fonts.push(font);
+        FontId(self.fonts.len() - 1)
+    }
+
+    /// Get reference to the registered font.
+    pub fn font(&self, font: FontId) -> Option<&BitmapFont<bool>> {
+        self.fonts.get(font.0)
+    }
+
+    /// Get amount of cached strings.
+    pub fn len(&self) -> usize {
+        self.entries.values().map(HashMap::len).sum()
+    }
+
+    /// Check if there are no cached strings.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the `text` rasterized with the `font`, `style` and `color`, rasterizing it if it is not cached.
+    /// Pixels not covered by glyphs are `None`.
+    /// Returns `None` if the font is not registered.
+    pub fn rasterized(
+        &mut self,
+        font: FontId,
+        text: &str,
+        style: TextStyle,
+        color: C,
+    ) -> Option<&Canvas<Option<C>>> {
+        let bitmap = self.fonts.get(font.0)?;
+        let strings = self
+            .entries
+            .entry((font, style, color.clone()))
+            .or_default();
+        if !strings.contains_key(text) {
+            let dimensions = bitmap.measure(text, style);
+            let mut canvas = take_canvas(&mut self.pool, dimensions);
+            canvas.painter::<i32>().text_styled(
+                Vector::new(0, 0),
+                bitmap,
+                text,
+                style,
+                ink(Some(color)),
+            );
+            let entry = Entry {
+                canvas,
+                last_used: self.tick,
+            };
+            strings.insert(text.to_owned(), entry);
+        }
+        let entry = strings.get_mut(text)?;
+        entry.last_used = self.tick;
+        Some(&entry.canvas)
+    }
+
+    /// Draw the `text` with its top left corner at `at`, rasterizing it only if it is not cached.
+    pub fn draw<T>(
+        &mut self,
+        painter: &mut Painter<'_, T, i32>,
+        at: Vector<i32>,
+        font: FontId,
+        text: &str,
+        style: TextStyle,
+        color: C,
+    ) where
+        T: ImageMut<Pixel = C>,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = C>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = C>,
+    {
+        if let Some(canvas) = self.rasterized(font, text, style, color) {
+            painter.image(at, canvas, |_, _, pixel, _, _, value| {
+                value.unwrap_or(pixel)
+            });
+        }
+    }
+
+    /// Register the tick, evicting entries not drawn for too long.
+    /// Drops all the entries if any subscription signalled.
+    pub fn tick(&mut self) {
+        self.tick += 1;
+        let mut stale = false;
+        for subscription in self.subscriptions.iter_mut() {
+            stale |= subscription.acknowledge();
+        }
+        if stale {
+            self.clear();
+            return;
+        }
+        let (tick, max_age) = (self.tick, self.max_age);
+        for strings in self.entries.values_mut() {
+            let expired: Vec<String> = strings
+                .iter()
+                .filter(|(_, entry)| tick - entry.last_used > max_age)
+                .map(|(text, _)| text.clone())
+                .collect();
+            for text in expired {
+                if let Some(entry) = strings.remove(&text) {
+                    self.pool.push(entry.canvas);
+                }
+            }
+        }
+        self.entries.retain(|_, strings| !strings.is_empty());
+        self.pool.truncate(MAX_POOLED);
+    }
+
+    /// Drop all the entries, pooling their canvases.
+    pub fn clear(&mut self) {
+        for (_, strings) in self.entries.drain() {
+            self.pool
+                .extend(strings.into_values().map(|entry| entry.canvas));
+        }
+        self.pool.truncate(MAX_POOLED);
+    }
+}
+
+impl<C> Default for TextCache<C>
+where
+    C: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn take_canvas<C>(pool: &mut Vec<Canvas<Option<C>>>, dimensions: Vector<i32>) -> Canvas<Option<C>>
+where
+    C: Clone,
+{
+    let width = dimensions.x().max(0);
+    let height = dimensions.y().max(0);
+    let pooled = pool
+        .iter()
+        .position(|canvas| canvas.width() == width && canvas.height() == height);
+    match pooled {
+        Some(index) => {
+            let mut canvas = pool.swap_remove(index);
+            for y in 0..height {
+                for x in 0..width {
+                    // SAFETY: the position is within the canvas dimensions.
+                    unsafe { *canvas.unsafe_pixel_mut(Vector::new(x, y)) = None };
+                }
+            }
+            canvas
+        }
+        None => Canvas::with_resolution(None, width as usize, height as usize),
+    }
+}
@@ -0,0 +1,240 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::util::vector::Vector;
+
+use super::canvas::Canvas;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::view::{Flip, Rotation, View};
+use super::{Image, ImageMut, Painter};
+
+/// Single cell of the tile layer referencing the tileset image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Tile {
+    index: usize,
+    flip: Flip,
+    rotation: Rotation,
+}
+
+impl Tile {
+    /// Create new tile showing the tileset image with given `index`.
+    /// Tileset images are indexed row by row, left to right.
+    pub fn new(index: usize) -> Self {
+        Self {
+            index,
+            ..Default::default()
+        }
+    }
+
+    /// Get new tile with desired flip.
+    pub fn with_flip(self, flip: Flip) -> Self {
+        Self { flip, ..self }
+    }
+
+    /// Get new tile with desired rotation.
+    pub fn with_rotation(self, rotation: Rotation) -> Self {
+        Self { rotation, ..self }
+    }
+
+    /// Get index of the tileset image.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the flip.
+    pub fn flip(&self) -> Flip {
+        self.flip
+    }
+
+    /// Get the rotation.
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+}
+
+/// Grid of optional tiles scrolled with the parallax factor relative to the camera.
+#[derive(Clone, Debug)]
+pub struct TileLayer {
+    width: i32,
+    height: i32,
+    tiles: Vec<Option<Tile>>,
+    parallax: Vector<f32>,
+}
+
+impl TileLayer {
+    /// Create new empty layer with dimensions in tiles.
+    pub fn new(width: i32, height: i32) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Self {
+            width,
+            height,
+            tiles: vec![None; (width * height) as usize],
+            parallax: Vector::new(1.0, 1.0),
+        }
+    }
+
+    /// Get new layer with desired parallax factor.
+    /// The factor of one scrolls with the camera, zero keeps the layer in place.
+    pub fn with_parallax(self, parallax: Vector<f32>) -> Self {
+        Self { parallax, ..self }
+    }
+
+    /// Get the parallax factor.
+    pub fn parallax(&self) -> Vector<f32> {
+        self.parallax
+    }
+
+    /// Set the parallax factor.
+    pub fn set_parallax(&mut self, parallax: Vector<f32>) {
+        self.parallax = parallax;
+    }
+
+    /// Get width in tiles.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Get height in tiles.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    fn index(&self, position: Vector<i32>) -> Option<usize> {
+        let (x, y) = position.split();
+        ((0..self.width).contains(&x) && (0..self.height).contains(&y))
+            .then(|| (y * self.width + x) as usize)
+    }
+
+    /// Get tile at `position` in tiles.
+    pub fn tile(&self, position: Vector<i32>) -> Option<Tile> {
+        self.index(position).and_then(|index| self.tiles[index])
+    }
+
+    /// Set or clear tile at `position` in tiles.
+    /// Positions outside of the layer are ignored.
+    pub fn set_tile(&mut self, position: Vector<i32>, tile: Option<Tile>) {
+        if let Some(index) = self.index(position) {
+            self.tiles[index] = tile;
+        }
+    }
+
+    /// Set each cell of the layer to the `tile`.
+    pub fn fill(&mut self, tile: Option<Tile>) {
+        self.tiles.fill(tile);
+    }
+}
+
+/// Layered tile grid drawn from a single tileset image.
+#[derive(Clone, Debug)]
+pub struct Tilemap<P> {
+    tileset: Canvas<P>,
+    tile_size: Vector<i32>,
+    layers: Vec<TileLayer>,
+}
+
+impl<P> Tilemap<P>
+where
+    P: Clone,
+{
+    /// Create new tilemap without layers from the `tileset` split into a grid of `tile_size` images.
+    pub fn new(tileset: Canvas<P>, tile_size: Vector<i32>) -> Self {
+        Self {
+            tileset,
+            tile_size: tile_size.individual_max((1, 1)),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Get new tilemap with the `layer` added on top.
+    pub fn with_layer(mut self, layer: TileLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Add the `layer` on top of existing ones.
+    pub fn push_layer(&mut self, layer: TileLayer) {
+        self.layers.push(layer);
+    }
+
+    /// Get layers from bottom to top.
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    /// Get mutable reference to the layer with given `index`.
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut TileLayer> {
+        self.layers.get_mut(index)
+    }
+
+    /// Get dimensions of a single tile.
+    pub fn tile_size(&self) -> Vector<i32> {
+        self.tile_size
+    }
+
+    /// Get the tileset image.
+    pub fn tileset(&self) -> &Canvas<P> {
+        &self.tileset
+    }
+
+    /// Get view into the tileset image with given `index`.
+    pub fn tile_image(&self, index: usize) -> Option<View<&Canvas<P>>> {
+        let columns = (self.tileset.width() / self.tile_size.x()).max(0) as usize;
+        let rows = (self.tileset.height() / self.tile_size.y()).max(0) as usize;
+        if index >= columns * rows {
+            return None;
+        }
+        let origin = Vector::new(
+            (index % columns) as i32 * self.tile_size.x(),
+            (index / columns) as i32 * self.tile_size.y(),
+        );
+        Some(self.tileset.view(origin, self.tile_size))
+    }
+
+    /// Draw the tilemap area seen by the camera with `origin` and `dimensions` in world pixels
+    /// onto the `painter` with the camera top left corner at zero.
+    /// Only tiles visible through the camera are drawn, layers are scrolled according to their parallax.
+    pub fn render<T, F>(
+        &self,
+        painter: &mut Painter<T, i32>,
+        origin: Vector<i32>,
+        dimensions: Vector<i32>,
+        function: F,
+    ) where
+        T: ImageMut,
+        T::Pixel: Clone,
+        F: FnMut(i32, i32, T::Pixel, i32, i32, P) -> T::Pixel,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = T::Pixel>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = T::Pixel>,
+    {
+        let mut function = function;
+        let size = self.tile_size;
+        for layer in &self.layers {
+            let scroll = Vector::new(
+                (origin.x() as f32 * layer.parallax.x()).round() as i32,
+                (origin.y() as f32 * layer.parallax.y()).round() as i32,
+            );
+            let start = Vector::new(
+                scroll.x().div_euclid(size.x()),
+                scroll.y().div_euclid(size.y()),
+            )
+            .individual_max((0, 0));
+            let end = Vector::new(
+                (scroll.x() + dimensions.x() + size.x() - 1).div_euclid(size.x()),
+                (scroll.y() + dimensions.y() + size.y() - 1).div_euclid(size.y()),
+            )
+            .individual_min((layer.width, layer.height));
+            for y in start.y()..end.y() {
+                for x in start.x()..end.x() {
+                    let Some(tile) = layer.tile(Vector::new(x, y)) else {
+                        continue;
+                    };
+                    let Some(image) = self.tile_image(tile.index) else {
+                        continue;
+                    };
+                    let image = image.with_flip(tile.flip).with_rotation(tile.rotation);
+                    let at = Vector::new(x * size.x(), y * size.y()) - scroll;
+                    painter.image(at, &image, &mut function);
+                }
+            }
+        }
+    }
+}
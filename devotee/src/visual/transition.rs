@@ -0,0 +1,132 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::util::rect::Rect;
+use crate::util::vector::Vector;
+
+use super::dither::Pattern;
+use super::image::{DesignatorMut, DesignatorRef};
+use super::{paint, ImageMut, Paint, Painter};
+
+/// Full-screen transition effect driven by the progress from `0.0` to `1.0`.
+/// At zero progress the image is left intact, at full progress it is completely covered.
+/// Several transitions can be rendered one after another to compose them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Transition<P> {
+    /// Dither the image into the `color`.
+    Fade {
+        /// Color to fade into.
+        color: P,
+        /// Dithering pattern.
+        pattern: Pattern,
+    },
+    /// Close the circle around the `center` covering the image with the `color` outside of it.
+    CircleWipe {
+        /// Color outside of the circle.
+        color: P,
+        /// Center of the circle, center of the target if `None`.
+        center: Option<Vector<i32>>,
+    },
+    /// Enlarge pixels up to `block` size.
+    Pixelate {
+        /// Size of the pixel blocks at full progress.
+        block: i32,
+    },
+    /// Slide in horizontal bars of the `color` from alternating sides.
+    Bars {
+        /// Color of the bars.
+        color: P,
+        /// Amount of bars covering the target height.
+        count: i32,
+    },
+}
+
+impl<P> Transition<P>
+where
+    P: Clone,
+{
+    /// Render the transition at `progress` onto the whole painter target, ignoring the painter offset.
+    pub fn render<T>(&self, painter: &mut Painter<T, i32>, progress: f32)
+    where
+        T: ImageMut<Pixel = P>,
+        for<'a> <T as DesignatorRef<'a>>::PixelRef: Deref<Target = P>,
+        for<'a> <T as DesignatorMut<'a>>::PixelMut: DerefMut<Target = P>,
+    {
+        let progress = progress.clamp(0.0, 1.0);
+        if progress == 0.0 {
+            return;
+        }
+        let offset = painter.offset();
+        painter.set_offset(Vector::<i32>::zero());
+        let dimensions = Vector::new(painter.width(), painter.height());
+        match self {
+            Transition::Fade { color, pattern } => {
+                painter.rect_f((Vector::<i32>::zero(), dimensions), |x, y, pixel| {
+                    if pattern.lit(progress, Vector::new(x, y)) {
+                        color.clone()
+                    } else {
+                        pixel
+                    }
+                });
+            }
+            Transition::CircleWipe { color, center } => {
+                let center = center.unwrap_or(dimensions / 2);
+                let corner = Vector::new(
+                    center.x().max(dimensions.x() - center.x()) as f32,
+                    center.y().max(dimensions.y() - center.y()) as f32,
+                );
+                let radius = (corner.x().powi(2) + corner.y().powi(2)).sqrt() * (1.0 - progress);
+                let radius_square = radius.powi(2);
+                painter.rect_f((Vector::<i32>::zero(), dimensions), |x, y, pixel| {
+                    let (dx, dy) = ((x - center.x()) as f32, (y - center.y()) as f32);
+                    if dx * dx + dy * dy >= radius_square {
+                        color.clone()
+                    } else {
+                        pixel
+                    }
+                });
+            }
+            Transition::Pixelate { block } => {
+                let block = 1 + ((*block - 1).max(0) as f32 * progress).round() as i32;
+                if block > 1 {
+                    for y in (0..dimensions.y()).step_by(block as usize) {
+                        for x in (0..dimensions.x()).step_by(block as usize) {
+                            let sample = Vector::new(
+                                (x + block / 2).min(dimensions.x() - 1),
+                                (y + block / 2).min(dimensions.y() - 1),
+                            );
+                            let Some(value) = painter.pixel(sample).map(|pixel| pixel.clone())
+                            else {
+                                continue;
+                            };
+                            painter.rect_f(
+                                (Vector::new(x, y), Vector::new(block, block)),
+                                paint(value),
+                            );
+                        }
+                    }
+                }
+            }
+            Transition::Bars { color, count } => {
+                let count = (*count).max(1);
+                let width = (dimensions.x() as f32 * progress).ceil() as i32;
+                for bar in 0..count {
+                    let top = dimensions.y() * bar / count;
+                    let bottom = dimensions.y() * (bar + 1) / count;
+                    let left = if bar % 2 == 0 {
+                        0
+                    } else {
+                        dimensions.x() - width
+                    };
+                    painter.rect_f(
+                        Rect::from_corners(
+                            Vector::new(left, top),
+                            Vector::new(left + width, bottom),
+                        ),
+                        paint(color.clone()),
+                    );
+                }
+            }
+        }
+        painter.set_offset(offset);
+    }
+}
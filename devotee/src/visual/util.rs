@@ -1,3 +1,5 @@
+use std::f32::consts::TAU;
+
 #[derive(Clone, Copy, Debug)]
 pub struct AngleIterator<'a, T> {
     index: usize,
@@ -33,3 +35,14 @@ impl<'a, T> Iterator for AngleIterator<'a, T> {
         }
     }
 }
+
+/// Check if the direction of `delta` lies within the sweep from `start` to `end` angle in radians.
+/// The zero `delta` is considered to be in any sector.
+pub fn in_sector(delta: (f32, f32), start: f32, end: f32) -> bool {
+    if delta == (0.0, 0.0) || (end - start).abs() >= TAU {
+        return true;
+    }
+    let sweep = (end - start).rem_euclid(TAU);
+    let angle = delta.1.atan2(delta.0);
+    (angle - start).rem_euclid(TAU) <= sweep
+}
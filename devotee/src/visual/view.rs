@@ -1,35 +1,29 @@
-use std::ops::DerefMut;
+use std::ops::{DerefMut, Range};
 
+use crate::util::rect::Rect;
 use crate::util::vector::Vector;
 
 use super::image::{DesignatorMut, DesignatorRef, PixelMut, PixelRef};
 use super::{FastHorizontalWriter, Image, ImageMut};
 
-#[derive(Clone, Copy, Debug)]
-struct Zone {
-    origin: Vector<i32>,
-    dimensions: Vector<i32>,
-}
-
 /// A view into an `Image`.
 #[derive(Clone, Copy, Debug)]
 pub struct View<T> {
     target: T,
-    zone: Zone,
+    zone: Rect<i32>,
     flip: Flip,
     rotation: Rotation,
     scale: i32,
 }
 
-fn calculate_origin_and_dimensions(
+fn visible_zone(
     original_dimensions: Vector<i32>,
     origin: Vector<i32>,
     dimensions: Vector<i32>,
-) -> (Vector<i32>, Vector<i32>) {
-    let origin_in_bounds = origin.individual_max((0, 0));
-    let end_in_bounds = (origin + dimensions).individual_min(original_dimensions);
-    let dimensions = (end_in_bounds - origin_in_bounds).individual_max((0, 0));
-    (origin_in_bounds, dimensions)
+) -> Rect<i32> {
+    Rect::new(origin, dimensions)
+        .intersection(&Rect::new(Vector::new(0, 0), original_dimensions))
+        .unwrap_or(Rect::new(origin.individual_max((0, 0)), Vector::new(0, 0)))
 }
 
 impl<T> View<T> {
@@ -40,15 +34,31 @@ impl<T> View<T> {
         )
     }
 
+    /// Get range of the target row backing the view row `y` if the view is not deformed.
+    fn row_range(&self, y: i32) -> Option<(i32, Range<usize>)> {
+        let plain = matches!(self.flip, Flip::None)
+            && matches!(self.rotation, Rotation::None)
+            && self.scale == 1;
+        if !plain || y < 0 || y >= self.zone.dimensions().y() {
+            return None;
+        }
+        let start = self.zone.origin().x() as usize;
+        Some((
+            self.zone.origin().y() + y,
+            start..start + self.zone.dimensions().x() as usize,
+        ))
+    }
+
     fn position_if_in_bounds(&self, position: Vector<i32>) -> Option<Vector<i32>> {
         let position = self.deform_position(position);
         if position.x() < 0 || position.y() < 0 {
             return None;
         }
-        if position.x() >= self.zone.dimensions.x() || position.y() >= self.zone.dimensions.y() {
+        if position.x() >= self.zone.dimensions().x() || position.y() >= self.zone.dimensions().y()
+        {
             return None;
         }
-        Some(self.zone.origin + position)
+        Some(self.zone.origin() + position)
     }
 
     /// Get current flip value.
@@ -81,6 +91,11 @@ impl<T> View<T> {
         Self { rotation, ..self }
     }
 
+    /// Get the area of the target seen through this view.
+    pub fn area(&self) -> Rect<i32> {
+        self.zone
+    }
+
     /// Get current scale value.
     pub fn get_scale(&self) -> i32 {
         self.scale
@@ -102,9 +117,7 @@ where
     T: Image + ?Sized,
 {
     pub(super) fn new(target: &'image T, origin: Vector<i32>, dimensions: Vector<i32>) -> Self {
-        let (origin, dimensions) =
-            calculate_origin_and_dimensions(target.dimensions(), origin, dimensions);
-        let zone = Zone { origin, dimensions };
+        let zone = visible_zone(target.dimensions(), origin, dimensions);
         let flip = Flip::None;
         let rotation = Rotation::None;
         let scale = 1;
@@ -124,9 +137,7 @@ where
     T: Image + ?Sized,
 {
     pub(super) fn new(target: &'image mut T, origin: Vector<i32>, dimensions: Vector<i32>) -> Self {
-        let (origin, dimensions) =
-            calculate_origin_and_dimensions(target.dimensions(), origin, dimensions);
-        let zone = Zone { origin, dimensions };
+        let zone = visible_zone(target.dimensions(), origin, dimensions);
         let flip = Flip::None;
         let rotation = Rotation::None;
         let scale = 1;
@@ -160,22 +171,27 @@ where
 
     unsafe fn unsafe_pixel(&self, position: Vector<i32>) -> PixelRef<'_, Self> {
         self.target
-            .unsafe_pixel(self.zone.origin + self.deform_position(position))
+            .unsafe_pixel(self.zone.origin() + self.deform_position(position))
     }
 
     fn width(&self) -> i32 {
         match self.rotation {
-            Rotation::None | Rotation::Half => self.zone.dimensions.x() * self.scale,
-            Rotation::CCW | Rotation::CW => self.zone.dimensions.y() * self.scale,
+            Rotation::None | Rotation::Half => self.zone.dimensions().x() * self.scale,
+            Rotation::CCW | Rotation::CW => self.zone.dimensions().y() * self.scale,
         }
     }
 
     fn height(&self) -> i32 {
         match self.rotation {
-            Rotation::None | Rotation::Half => self.zone.dimensions.y() * self.scale,
-            Rotation::CCW | Rotation::CW => self.zone.dimensions.x() * self.scale,
+            Rotation::None | Rotation::Half => self.zone.dimensions().y() * self.scale,
+            Rotation::CCW | Rotation::CW => self.zone.dimensions().x() * self.scale,
         }
     }
+
+    fn row(&self, y: i32) -> Option<&[Self::Pixel]> {
+        let (y, range) = self.row_range(y)?;
+        self.target.row(y)?.get(range)
+    }
 }
 
 impl<'a, T> DesignatorRef<'a> for View<&mut T>
@@ -197,22 +213,27 @@ where
 
     unsafe fn unsafe_pixel(&self, position: Vector<i32>) -> PixelRef<'_, Self> {
         self.target
-            .unsafe_pixel(self.zone.origin + self.deform_position(position))
+            .unsafe_pixel(self.zone.origin() + self.deform_position(position))
     }
 
     fn width(&self) -> i32 {
         match self.rotation {
-            Rotation::None | Rotation::Half => self.zone.dimensions.x() * self.scale,
-            Rotation::CCW | Rotation::CW => self.zone.dimensions.y() * self.scale,
+            Rotation::None | Rotation::Half => self.zone.dimensions().x() * self.scale,
+            Rotation::CCW | Rotation::CW => self.zone.dimensions().y() * self.scale,
         }
     }
 
     fn height(&self) -> i32 {
         match self.rotation {
-            Rotation::None | Rotation::Half => self.zone.dimensions.y() * self.scale,
-            Rotation::CCW | Rotation::CW => self.zone.dimensions.x() * self.scale,
+            Rotation::None | Rotation::Half => self.zone.dimensions().y() * self.scale,
+            Rotation::CCW | Rotation::CW => self.zone.dimensions().x() * self.scale,
         }
     }
+
+    fn row(&self, y: i32) -> Option<&[Self::Pixel]> {
+        let (y, range) = self.row_range(y)?;
+        self.target.row(y)?.get(range)
+    }
 }
 
 impl<'a, T> DesignatorMut<'a> for View<&mut T>
@@ -234,7 +255,7 @@ where
 
     unsafe fn unsafe_pixel_mut(&mut self, position: Vector<i32>) -> PixelMut<'_, Self> {
         self.target
-            .unsafe_pixel_mut(self.zone.origin + self.deform_position(position))
+            .unsafe_pixel_mut(self.zone.origin() + self.deform_position(position))
     }
 
     fn clear(&mut self, color: Self::Pixel) {
@@ -242,11 +263,11 @@ where
             .target
             .fast_horizontal_writer()
             .map(|mut writer| {
-                for y in 0..self.zone.dimensions.y() {
+                for y in 0..self.zone.dimensions().y() {
                     writer.write_line(
-                        self.zone.origin.x()
-                            ..=(self.zone.origin.x() + self.zone.dimensions.x() - 1),
-                        self.zone.origin.y() + y,
+                        self.zone.origin().x()
+                            ..=(self.zone.origin().x() + self.zone.dimensions().x() - 1),
+                        self.zone.origin().y() + y,
                         &mut |_, _, _| color.clone(),
                     );
                 }
@@ -256,20 +277,26 @@ where
             // We do believe that we are in a proper range.
             // By this time we should have already recalculated origin and dimensions to be in bounds.
             unsafe {
-                for y in 0..self.zone.dimensions.y() {
-                    for x in 0..self.zone.dimensions.x() {
-                        *self.target.unsafe_pixel_mut(self.zone.origin + (x, y)) = color.clone();
+                for y in 0..self.zone.dimensions().y() {
+                    for x in 0..self.zone.dimensions().x() {
+                        *self.target.unsafe_pixel_mut(self.zone.origin() + (x, y)) = color.clone();
                     }
                 }
             }
         }
     }
+
+    fn row_mut(&mut self, y: i32) -> Option<&mut [Self::Pixel]> {
+        let (y, range) = self.row_range(y)?;
+        self.target.row_mut(y)?.get_mut(range)
+    }
 }
 
 /// Flip transform applied to a view.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Flip {
     /// No flip occurs.
+    #[default]
     None,
     /// There is a horizontal flip.
     Horizontal,
@@ -292,20 +319,21 @@ impl From<u8> for Flip {
 }
 
 impl Flip {
-    fn apply(&self, position: Vector<i32>, zone: &Zone) -> Vector<i32> {
+    fn apply(&self, position: Vector<i32>, zone: &Rect<i32>) -> Vector<i32> {
         match self {
             Flip::None => position,
-            Flip::Horizontal => (zone.dimensions.x() - position.x() - 1, position.y()).into(),
-            Flip::Vertical => (position.x(), zone.dimensions.y() - position.y() - 1).into(),
-            Flip::Both => zone.dimensions - position - (1, 1),
+            Flip::Horizontal => (zone.dimensions().x() - position.x() - 1, position.y()).into(),
+            Flip::Vertical => (position.x(), zone.dimensions().y() - position.y() - 1).into(),
+            Flip::Both => zone.dimensions() - position - (1, 1),
         }
     }
 }
 
 /// Rotation transform applied to a view.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum Rotation {
     /// No rotation occurs.
+    #[default]
     None,
     /// Counterclockwise rotation by 90 degrees occurs.
     CCW,
@@ -328,12 +356,12 @@ impl From<u8> for Rotation {
 }
 
 impl Rotation {
-    fn apply(&self, position: Vector<i32>, zone: &Zone) -> Vector<i32> {
+    fn apply(&self, position: Vector<i32>, zone: &Rect<i32>) -> Vector<i32> {
         match self {
             Rotation::None => position,
-            Rotation::CW => (position.y(), zone.dimensions.y() - position.x() - 1).into(),
-            Rotation::Half => zone.dimensions - position - (1, 1),
-            Rotation::CCW => (zone.dimensions.x() - position.y() - 1, position.x()).into(),
+            Rotation::CW => (position.y(), zone.dimensions().y() - position.x() - 1).into(),
+            Rotation::Half => zone.dimensions() - position - (1, 1),
+            Rotation::CCW => (zone.dimensions().x() - position.y() - 1, position.x()).into(),
         }
     }
 }
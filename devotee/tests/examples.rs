@@ -0,0 +1,109 @@
+//! Examples stepped with the headless backend and compared against the golden frames.
+//! Set the `DEVOTEE_BLESS` environment variable to record new golden frames.
+
+use std::time::Duration;
+
+use devotee::app::App;
+use devotee::diagnostics::golden::GoldenFrames;
+use devotee::extras::XrgbConverter;
+use devotee::input::winit_input::{Keyboard, NoInput};
+use devotee::visual::canvas::Canvas;
+use devotee::visual::prelude::*;
+use devotee_backend_headless::{HeadlessBackend, HeadlessMiddleware, HeadlessSurface};
+
+#[allow(dead_code)]
+#[path = "../examples/extended/main.rs"]
+mod extended;
+#[allow(dead_code)]
+#[path = "../examples/gears/main.rs"]
+mod gears;
+#[allow(dead_code)]
+#[path = "../examples/headless/main.rs"]
+mod headless;
+#[allow(dead_code)]
+#[path = "../examples/minimal/main.rs"]
+mod minimal;
+#[cfg(feature = "crossterm-input")]
+#[allow(dead_code)]
+#[path = "../examples/terminal/main.rs"]
+mod terminal;
+
+const FRAMES: usize = 60;
+
+fn update_delay() -> Duration {
+    Duration::from_secs_f32(1.0 / 60.0)
+}
+
+fn check(name: &str, framebuffer: HeadlessSurface) {
+    let (width, height) = framebuffer.dimensions();
+    let mut image = Canvas::with_resolution(0, width, height);
+    for (y, row) in image.rows_mut().enumerate() {
+        for (x, pixel) in row.iter_mut().enumerate() {
+            if let Some(value) = framebuffer.pixel(x, y) {
+                *pixel = value;
+            }
+        }
+    }
+    let golden = GoldenFrames::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden"));
+    if let Err(error) = golden.check(name, &image, &XrgbConverter) {
+        panic!("{name} does not match its golden frame: {error:?}");
+    }
+}
+
+#[test]
+fn minimal() {
+    let framebuffer = HeadlessBackend::new().with_frames(FRAMES).run(
+        App::new(minimal::Minimal),
+        HeadlessMiddleware::new(Canvas::with_resolution(false, 128, 128), NoInput),
+        update_delay(),
+    );
+    check("minimal", framebuffer);
+}
+
+#[test]
+fn extended() {
+    let framebuffer = HeadlessBackend::new().with_frames(FRAMES).run(
+        App::new(extended::Extended::default()),
+        HeadlessMiddleware::new(Canvas::with_resolution(false, 128, 128), Keyboard::new()),
+        update_delay(),
+    );
+    check("extended", framebuffer);
+}
+
+#[test]
+fn gears() {
+    let framebuffer = HeadlessBackend::new().with_frames(FRAMES).run(
+        App::new(gears::Gears::new()),
+        HeadlessMiddleware::new(Canvas::with_resolution(false, 320, 240), Keyboard::new())
+            .with_background_color(0xff000000),
+        update_delay(),
+    );
+    check("gears", framebuffer);
+}
+
+#[test]
+fn headless() {
+    let framebuffer = HeadlessBackend::new().with_frames(30).run(
+        App::new(headless::Headless { frame: 0 }),
+        HeadlessMiddleware::new(Canvas::with_resolution(false, 64, 32), NoInput),
+        update_delay(),
+    );
+    check("headless", framebuffer);
+}
+
+#[cfg(feature = "crossterm-input")]
+#[test]
+fn terminal() {
+    use devotee::input::crossterm_input;
+
+    let framebuffer = HeadlessBackend::new().with_frames(FRAMES).run(
+        App::new(terminal::Terminal::new()),
+        HeadlessMiddleware::new(
+            Canvas::with_resolution(false, 128, 64),
+            crossterm_input::Keyboard::new(),
+        )
+        .with_background_color(0xff000000),
+        Duration::from_secs_f32(1.0 / 30.0),
+    );
+    check("terminal", framebuffer);
+}